@@ -8,7 +8,7 @@
  * Generated by: https://openapi-generator.tech
  */
 
-use super::{configuration, Error};
+use super::{Error, configuration};
 use crate::{apis::ResponseContent, models};
 use reqwest;
 use serde::{Deserialize, Serialize};
@@ -24,10 +24,22 @@ pub enum CheckAddressError {
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`check_addresses`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum CheckAddressesError {
+    Status400(),
+    Status405(),
+    Status413(),
+    Status500(),
+    UnknownValue(serde_json::Value),
+}
+
 /// Converts successful blocklist status results to JSON and returns them, or converts errors into Warp rejections.
 pub async fn check_address(
     configuration: &configuration::Configuration,
     address: &str,
+    chain: Option<models::Chain>,
 ) -> Result<models::BlocklistStatus, Error<CheckAddressError>> {
     let local_var_configuration = configuration;
 
@@ -41,6 +53,10 @@ pub async fn check_address(
     let mut local_var_req_builder =
         local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
 
+    if let Some(ref local_var_str) = chain {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("chain", &local_var_str.to_string())]);
+    }
     if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
         local_var_req_builder =
             local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
@@ -65,3 +81,42 @@ pub async fn check_address(
         Err(Error::ResponseError(local_var_error))
     }
 }
+
+/// Returns the status of each address in the order of the request, or an error if any of the addresses could not be screened.
+pub async fn check_addresses(
+    configuration: &configuration::Configuration,
+    screen_addresses_request: models::ScreenAddressesRequest,
+) -> Result<models::ScreenAddressesResponse, Error<CheckAddressesError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/screen", local_var_configuration.base_path);
+    let mut local_var_req_builder =
+        local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder =
+            local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+    local_var_req_builder = local_var_req_builder.json(&screen_addresses_request);
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<CheckAddressesError> =
+            serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent {
+            status: local_var_status,
+            content: local_var_content,
+            entity: local_var_entity,
+        };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
@@ -0,0 +1,37 @@
+/*
+ * blocklist-openapi-gen
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// Chain : The chain an address belongs to
+/// The chain an address belongs to
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Ord, PartialOrd, Hash, Serialize, Deserialize)]
+pub enum Chain {
+    #[serde(rename = "bitcoin")]
+    Bitcoin,
+    #[serde(rename = "stacks")]
+    Stacks,
+}
+
+impl std::fmt::Display for Chain {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        match self {
+            Self::Bitcoin => write!(f, "bitcoin"),
+            Self::Stacks => write!(f, "stacks"),
+        }
+    }
+}
+
+impl Default for Chain {
+    fn default() -> Chain {
+        Self::Bitcoin
+    }
+}
@@ -1,6 +1,14 @@
 pub mod blocklist_status;
 pub use self::blocklist_status::BlocklistStatus;
+pub mod chain;
+pub use self::chain::Chain;
 pub mod error_response;
 pub use self::error_response::ErrorResponse;
 pub mod risk_severity;
 pub use self::risk_severity::RiskSeverity;
+pub mod screen_addresses_request;
+pub use self::screen_addresses_request::ScreenAddressesRequest;
+pub mod screen_addresses_response;
+pub use self::screen_addresses_response::ScreenAddressesResponse;
+pub mod screened_address;
+pub use self::screened_address::ScreenedAddress;
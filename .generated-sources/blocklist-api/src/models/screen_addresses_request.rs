@@ -0,0 +1,34 @@
+/*
+ * blocklist-openapi-gen
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ScreenAddressesRequest : Request to check the blocklist status of several addresses at once
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScreenAddressesRequest {
+    /// The addresses to screen
+    #[serde(rename = "addresses")]
+    pub addresses: Vec<String>,
+    #[serde(
+        rename = "chain",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub chain: Option<Option<models::Chain>>,
+}
+
+impl ScreenAddressesRequest {
+    /// Request to check the blocklist status of several addresses at once
+    pub fn new(addresses: Vec<String>) -> ScreenAddressesRequest {
+        ScreenAddressesRequest { addresses, chain: None }
+    }
+}
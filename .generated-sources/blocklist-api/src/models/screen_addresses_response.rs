@@ -0,0 +1,27 @@
+/*
+ * blocklist-openapi-gen
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ScreenAddressesResponse : Response to a batch request, with the status of each address in the order of the request
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScreenAddressesResponse {
+    /// The blocklist status of each address
+    #[serde(rename = "results")]
+    pub results: Vec<models::ScreenedAddress>,
+}
+
+impl ScreenAddressesResponse {
+    /// Response to a batch request, with the status of each address in the order of the request
+    pub fn new(results: Vec<models::ScreenedAddress>) -> ScreenAddressesResponse {
+        ScreenAddressesResponse { results }
+    }
+}
@@ -0,0 +1,32 @@
+/*
+ * blocklist-openapi-gen
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// ScreenedAddress : The blocklist status of one of the addresses of a batch request
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ScreenedAddress {
+    /// The screened address
+    #[serde(rename = "address")]
+    pub address: String,
+    #[serde(rename = "status")]
+    pub status: Box<models::BlocklistStatus>,
+}
+
+impl ScreenedAddress {
+    /// The blocklist status of one of the addresses of a batch request
+    pub fn new(address: String, status: models::BlocklistStatus) -> ScreenedAddress {
+        ScreenedAddress {
+            address,
+            status: Box::new(status),
+        }
+    }
+}
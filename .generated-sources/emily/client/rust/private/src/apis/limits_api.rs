@@ -33,6 +33,16 @@ pub enum GetLimitsForAccountError {
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`get_limits_history`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetLimitsHistoryError {
+    Status400(models::ErrorResponse),
+    Status405(models::ErrorResponse),
+    Status500(models::ErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
 /// struct for typed errors of method [`set_limits`]
 #[derive(Debug, Clone, Serialize, Deserialize)]
 #[serde(untagged)]
@@ -132,10 +142,73 @@ pub async fn get_limits_for_account(
     }
 }
 
-/// Note, that `available_to_withdraw` is not settable, but is calculated based on the other fields. Value of `available_to_withdraw` passed to this endpoint will be ignored.
+pub async fn get_limits_history(
+    configuration: &configuration::Configuration,
+    account: Option<&str>,
+    from_timestamp: Option<u64>,
+    to_timestamp: Option<u64>,
+    next_token: Option<&str>,
+    page_size: Option<u32>,
+) -> Result<models::GetLimitsHistoryResponse, Error<GetLimitsHistoryError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/limits/history", local_var_configuration.base_path);
+    let mut local_var_req_builder =
+        local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_str) = account {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("account", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = from_timestamp {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("fromTimestamp", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = to_timestamp {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("toTimestamp", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = next_token {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("nextToken", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = page_size {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("pageSize", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder =
+            local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<GetLimitsHistoryError> =
+            serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent {
+            status: local_var_status,
+            content: local_var_content,
+            entity: local_var_entity,
+        };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
+
+/// Note, that `available_to_withdraw` is not settable, but is calculated based on the other fields. Value of `available_to_withdraw` passed to this endpoint will be ignored.  The new limits may be scheduled to take effect at a future bitcoin block height or timestamp, in which case the returned limits are the ones currently in effect.
 pub async fn set_limits(
     configuration: &configuration::Configuration,
     limits: models::Limits,
+    effective_at_bitcoin_height: Option<u64>,
+    effective_at_timestamp: Option<u64>,
 ) -> Result<models::Limits, Error<SetLimitsError>> {
     let local_var_configuration = configuration;
 
@@ -145,6 +218,14 @@ pub async fn set_limits(
     let mut local_var_req_builder =
         local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
 
+    if let Some(ref local_var_str) = effective_at_bitcoin_height {
+        local_var_req_builder = local_var_req_builder
+            .query(&[("effectiveAtBitcoinHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = effective_at_timestamp {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("effectiveAtTimestamp", &local_var_str.to_string())]);
+    }
     if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
         local_var_req_builder =
             local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
@@ -183,6 +264,8 @@ pub async fn set_limits_for_account(
     configuration: &configuration::Configuration,
     account: &str,
     account_limits: models::AccountLimits,
+    effective_at_bitcoin_height: Option<u64>,
+    effective_at_timestamp: Option<u64>,
 ) -> Result<models::AccountLimits, Error<SetLimitsForAccountError>> {
     let local_var_configuration = configuration;
 
@@ -196,6 +279,14 @@ pub async fn set_limits_for_account(
     let mut local_var_req_builder =
         local_var_client.request(reqwest::Method::POST, local_var_uri_str.as_str());
 
+    if let Some(ref local_var_str) = effective_at_bitcoin_height {
+        local_var_req_builder = local_var_req_builder
+            .query(&[("effectiveAtBitcoinHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = effective_at_timestamp {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("effectiveAtTimestamp", &local_var_str.to_string())]);
+    }
     if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
         local_var_req_builder =
             local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
@@ -95,9 +95,11 @@ pub fn parse_deep_object(prefix: &str, value: &serde_json::Value) -> Vec<(String
 pub mod chainstate_api;
 pub mod cors_api;
 pub mod deposit_api;
+pub mod export_api;
 pub mod health_api;
 pub mod limits_api;
 pub mod new_block_api;
+pub mod stats_api;
 pub mod withdrawal_api;
 
 pub mod configuration;
@@ -0,0 +1,35 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// GetLimitsHistoryResponse : Response to a get limits history request.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetLimitsHistoryResponse {
+    /// The limit history entries, most recent first.
+    #[serde(rename = "entries")]
+    pub entries: Vec<models::LimitHistoryEntry>,
+    /// Next token for the search.
+    #[serde(
+        rename = "nextToken",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub next_token: Option<Option<String>>,
+}
+
+impl GetLimitsHistoryResponse {
+    /// Response to a get limits history request.
+    pub fn new(entries: Vec<models::LimitHistoryEntry>) -> GetLimitsHistoryResponse {
+        GetLimitsHistoryResponse { entries, next_token: None }
+    }
+}
@@ -33,6 +33,16 @@ pub enum GetLimitsForAccountError {
     UnknownValue(serde_json::Value),
 }
 
+/// struct for typed errors of method [`get_limits_history`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum GetLimitsHistoryError {
+    Status400(models::ErrorResponse),
+    Status405(models::ErrorResponse),
+    Status500(models::ErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
 pub async fn get_limits(
     configuration: &configuration::Configuration,
 ) -> Result<models::Limits, Error<GetLimitsError>> {
@@ -109,3 +119,64 @@ pub async fn get_limits_for_account(
         Err(Error::ResponseError(local_var_error))
     }
 }
+
+pub async fn get_limits_history(
+    configuration: &configuration::Configuration,
+    account: Option<&str>,
+    from_timestamp: Option<u64>,
+    to_timestamp: Option<u64>,
+    next_token: Option<&str>,
+    page_size: Option<u32>,
+) -> Result<models::GetLimitsHistoryResponse, Error<GetLimitsHistoryError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/limits/history", local_var_configuration.base_path);
+    let mut local_var_req_builder =
+        local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_str) = account {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("account", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = from_timestamp {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("fromTimestamp", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = to_timestamp {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("toTimestamp", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = next_token {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("nextToken", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = page_size {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("pageSize", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder =
+            local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<GetLimitsHistoryError> =
+            serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent {
+            status: local_var_status,
+            content: local_var_content,
+            entity: local_var_entity,
+        };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
@@ -17,6 +17,14 @@ pub struct Deposit {
     /// Amount of BTC being deposited in satoshis.
     #[serde(rename = "amount")]
     pub amount: u64,
+    /// The height of the bitcoin block that confirmed the deposit transaction. Only known when Emily validates deposits against a bitcoin node.
+    #[serde(
+        rename = "bitcoinBlockHeight",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub bitcoin_block_height: Option<Option<u64>>,
     /// Output index on the bitcoin transaction associated with this specific deposit.
     #[serde(rename = "bitcoinTxOutputIndex")]
     pub bitcoin_tx_output_index: u32,
@@ -79,6 +87,7 @@ impl Deposit {
     ) -> Deposit {
         Deposit {
             amount,
+            bitcoin_block_height: None,
             bitcoin_tx_output_index,
             bitcoin_txid,
             deposit_script,
@@ -0,0 +1,62 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// GetStatsResponse : Response to a get stats request.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetStatsResponse {
+    #[serde(rename = "deposits")]
+    pub deposits: Box<models::DepositStats>,
+    /// Whether the stats are out of sync with the deposits and withdrawals they count. When set, some counters went negative and are reported as zero, and the stats should be rebuilt with `emily-rebuild-stats`.
+    #[serde(rename = "needsRebuild")]
+    pub needs_rebuild: bool,
+    /// The current global peg cap, if there is one.
+    #[serde(
+        rename = "pegCap",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub peg_cap: Option<Option<u64>>,
+    /// The pegged amount as a fraction of the peg cap, if there is one.
+    #[serde(
+        rename = "pegUtilization",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub peg_utilization: Option<Option<f64>>,
+    /// The amount of sBTC pegged in, in satoshis, computed as the confirmed deposit amount minus the confirmed withdrawal amount.
+    #[serde(rename = "peggedAmount")]
+    pub pegged_amount: u64,
+    #[serde(rename = "withdrawals")]
+    pub withdrawals: Box<models::WithdrawalStats>,
+}
+
+impl GetStatsResponse {
+    /// Response to a get stats request.
+    pub fn new(
+        deposits: models::DepositStats,
+        needs_rebuild: bool,
+        pegged_amount: u64,
+        withdrawals: models::WithdrawalStats,
+    ) -> GetStatsResponse {
+        GetStatsResponse {
+            deposits: Box::new(deposits),
+            needs_rebuild,
+            peg_cap: None,
+            peg_utilization: None,
+            pegged_amount,
+            withdrawals: Box::new(withdrawals),
+        }
+    }
+}
@@ -0,0 +1,27 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// GetVolumeResponse : Response to a get volume request.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GetVolumeResponse {
+    /// The volume buckets, oldest first. Buckets without any operations are omitted.
+    #[serde(rename = "buckets")]
+    pub buckets: Vec<models::VolumeBucket>,
+}
+
+impl GetVolumeResponse {
+    /// Response to a get volume request.
+    pub fn new(buckets: Vec<models::VolumeBucket>) -> GetVolumeResponse {
+        GetVolumeResponse { buckets }
+    }
+}
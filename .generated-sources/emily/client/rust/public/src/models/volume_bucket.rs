@@ -0,0 +1,33 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// VolumeBucket : The volume of an operation within a single time bucket.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct VolumeBucket {
+    /// Total amount of the operations created within the bucket in satoshis.
+    #[serde(rename = "amount")]
+    pub amount: u64,
+    /// Number of operations created within the bucket.
+    #[serde(rename = "count")]
+    pub count: u64,
+    /// Unix timestamp, in seconds, at which the bucket starts.
+    #[serde(rename = "startTimestamp")]
+    pub start_timestamp: u64,
+}
+
+impl VolumeBucket {
+    /// The volume of an operation within a single time bucket.
+    pub fn new(amount: u64, count: u64, start_timestamp: u64) -> VolumeBucket {
+        VolumeBucket { amount, count, start_timestamp }
+    }
+}
@@ -0,0 +1,60 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// WithdrawalStats : Aggregated statistics about the withdrawals tracked by Emily.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct WithdrawalStats {
+    /// Number of accepted withdrawals.
+    #[serde(rename = "accepted")]
+    pub accepted: u64,
+    /// Number of confirmed withdrawals.
+    #[serde(rename = "confirmed")]
+    pub confirmed: u64,
+    /// Total amount of confirmed withdrawals in satoshis.
+    #[serde(rename = "confirmedAmount")]
+    pub confirmed_amount: u64,
+    /// Number of failed withdrawals.
+    #[serde(rename = "failed")]
+    pub failed: u64,
+    /// Approximate median time, in seconds, between Emily receiving a withdrawal and the withdrawal being confirmed. This is the upper bound of the histogram bucket that contains the median.
+    #[serde(
+        rename = "medianSecondsToConfirmation",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub median_seconds_to_confirmation: Option<Option<u64>>,
+    /// Number of pending withdrawals.
+    #[serde(rename = "pending")]
+    pub pending: u64,
+}
+
+impl WithdrawalStats {
+    /// Aggregated statistics about the withdrawals tracked by Emily.
+    pub fn new(
+        accepted: u64,
+        confirmed: u64,
+        confirmed_amount: u64,
+        failed: u64,
+        pending: u64,
+    ) -> WithdrawalStats {
+        WithdrawalStats {
+            accepted,
+            confirmed,
+            confirmed_amount,
+            failed,
+            median_seconds_to_confirmation: None,
+            pending,
+        }
+    }
+}
@@ -0,0 +1,194 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use super::{configuration, Error};
+use crate::{apis::ResponseContent, models};
+use reqwest;
+use serde::{Deserialize, Serialize};
+
+/// struct for typed errors of method [`export_deposits`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExportDepositsError {
+    Status400(models::ErrorResponse),
+    Status404(models::ErrorResponse),
+    Status405(models::ErrorResponse),
+    Status500(models::ErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
+/// struct for typed errors of method [`export_withdrawals`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(untagged)]
+pub enum ExportWithdrawalsError {
+    Status400(models::ErrorResponse),
+    Status404(models::ErrorResponse),
+    Status405(models::ErrorResponse),
+    Status500(models::ErrorResponse),
+    UnknownValue(serde_json::Value),
+}
+
+pub async fn export_deposits(
+    configuration: &configuration::Configuration,
+    format: Option<models::ExportFormat>,
+    from_stacks_height: Option<u64>,
+    to_stacks_height: Option<u64>,
+    from_bitcoin_height: Option<u64>,
+    to_bitcoin_height: Option<u64>,
+    next_token: Option<&str>,
+    page_size: Option<u16>,
+) -> Result<String, Error<ExportDepositsError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/export/deposits", local_var_configuration.base_path);
+    let mut local_var_req_builder =
+        local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_str) = format {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("format", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = from_stacks_height {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("fromStacksHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = to_stacks_height {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("toStacksHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = from_bitcoin_height {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("fromBitcoinHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = to_bitcoin_height {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("toBitcoinHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = next_token {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("nextToken", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = page_size {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("pageSize", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder =
+            local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+    if let Some(ref local_var_apikey) = local_var_configuration.api_key {
+        let local_var_key = local_var_apikey.key.clone();
+        let local_var_value = match local_var_apikey.prefix {
+            Some(ref local_var_prefix) => format!("{} {}", local_var_prefix, local_var_key),
+            None => local_var_key,
+        };
+        local_var_req_builder = local_var_req_builder.header("x-api-key", local_var_value);
+    };
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<ExportDepositsError> =
+            serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent {
+            status: local_var_status,
+            content: local_var_content,
+            entity: local_var_entity,
+        };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
+
+pub async fn export_withdrawals(
+    configuration: &configuration::Configuration,
+    format: Option<models::ExportFormat>,
+    from_stacks_height: Option<u64>,
+    to_stacks_height: Option<u64>,
+    from_bitcoin_height: Option<u64>,
+    to_bitcoin_height: Option<u64>,
+    next_token: Option<&str>,
+    page_size: Option<u16>,
+) -> Result<String, Error<ExportWithdrawalsError>> {
+    let local_var_configuration = configuration;
+
+    let local_var_client = &local_var_configuration.client;
+
+    let local_var_uri_str = format!("{}/export/withdrawals", local_var_configuration.base_path);
+    let mut local_var_req_builder =
+        local_var_client.request(reqwest::Method::GET, local_var_uri_str.as_str());
+
+    if let Some(ref local_var_str) = format {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("format", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = from_stacks_height {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("fromStacksHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = to_stacks_height {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("toStacksHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = from_bitcoin_height {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("fromBitcoinHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = to_bitcoin_height {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("toBitcoinHeight", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = next_token {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("nextToken", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_str) = page_size {
+        local_var_req_builder =
+            local_var_req_builder.query(&[("pageSize", &local_var_str.to_string())]);
+    }
+    if let Some(ref local_var_user_agent) = local_var_configuration.user_agent {
+        local_var_req_builder =
+            local_var_req_builder.header(reqwest::header::USER_AGENT, local_var_user_agent.clone());
+    }
+    if let Some(ref local_var_apikey) = local_var_configuration.api_key {
+        let local_var_key = local_var_apikey.key.clone();
+        let local_var_value = match local_var_apikey.prefix {
+            Some(ref local_var_prefix) => format!("{} {}", local_var_prefix, local_var_key),
+            None => local_var_key,
+        };
+        local_var_req_builder = local_var_req_builder.header("x-api-key", local_var_value);
+    };
+
+    let local_var_req = local_var_req_builder.build()?;
+    let local_var_resp = local_var_client.execute(local_var_req).await?;
+
+    let local_var_status = local_var_resp.status();
+    let local_var_content = local_var_resp.text().await?;
+
+    if !local_var_status.is_client_error() && !local_var_status.is_server_error() {
+        serde_json::from_str(&local_var_content).map_err(Error::from)
+    } else {
+        let local_var_entity: Option<ExportWithdrawalsError> =
+            serde_json::from_str(&local_var_content).ok();
+        let local_var_error = ResponseContent {
+            status: local_var_status,
+            content: local_var_content,
+            entity: local_var_entity,
+        };
+        Err(Error::ResponseError(local_var_error))
+    }
+}
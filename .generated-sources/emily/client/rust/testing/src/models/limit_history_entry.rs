@@ -0,0 +1,49 @@
+/*
+ * emily-openapi-spec
+ *
+ * No description provided (generated by Openapi Generator https://github.com/openapitools/openapi-generator)
+ *
+ * The version of the OpenAPI document: 0.1.0
+ *
+ * Generated by: https://openapi-generator.tech
+ */
+
+use crate::models;
+use serde::{Deserialize, Serialize};
+
+/// LimitHistoryEntry : A single entry in the history of an account's limits.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
+pub struct LimitHistoryEntry {
+    /// The account the limits apply to.
+    #[serde(rename = "account")]
+    pub account: String,
+    /// Bitcoin block height at which the limits take effect, if the change was scheduled by bitcoin block height.
+    #[serde(
+        rename = "effectiveAtBitcoinHeight",
+        default,
+        with = "::serde_with::rust::double_option",
+        skip_serializing_if = "Option::is_none"
+    )]
+    pub effective_at_bitcoin_height: Option<Option<u64>>,
+    #[serde(rename = "limits")]
+    pub limits: Box<models::AccountLimits>,
+    /// Unix timestamp, in seconds, at which the limits were set or, for changes scheduled by timestamp, take effect.
+    #[serde(rename = "timestamp")]
+    pub timestamp: u64,
+}
+
+impl LimitHistoryEntry {
+    /// A single entry in the history of an account's limits.
+    pub fn new(
+        account: String,
+        limits: models::AccountLimits,
+        timestamp: u64,
+    ) -> LimitHistoryEntry {
+        LimitHistoryEntry {
+            account,
+            effective_at_bitcoin_height: None,
+            limits: Box::new(limits),
+            timestamp,
+        }
+    }
+}
@@ -12,6 +12,8 @@ pub mod deposit_info;
 pub use self::deposit_info::DepositInfo;
 pub mod deposit_parameters;
 pub use self::deposit_parameters::DepositParameters;
+pub mod deposit_stats;
+pub use self::deposit_stats::DepositStats;
 pub mod deposit_status;
 pub use self::deposit_status::DepositStatus;
 pub mod deposit_update;
@@ -20,18 +22,30 @@ pub mod deposit_with_status;
 pub use self::deposit_with_status::DepositWithStatus;
 pub mod error_response;
 pub use self::error_response::ErrorResponse;
+pub mod export_format;
+pub use self::export_format::ExportFormat;
 pub mod fulfillment;
 pub use self::fulfillment::Fulfillment;
 pub mod get_deposits_for_transaction_response;
 pub use self::get_deposits_for_transaction_response::GetDepositsForTransactionResponse;
 pub mod get_deposits_response;
 pub use self::get_deposits_response::GetDepositsResponse;
+pub mod get_limits_history_response;
+pub use self::get_limits_history_response::GetLimitsHistoryResponse;
+pub mod get_stats_response;
+pub use self::get_stats_response::GetStatsResponse;
+pub mod get_volume_response;
+pub use self::get_volume_response::GetVolumeResponse;
 pub mod get_withdrawals_response;
 pub use self::get_withdrawals_response::GetWithdrawalsResponse;
 pub mod health_data;
 pub use self::health_data::HealthData;
+pub mod limit_history_entry;
+pub use self::limit_history_entry::LimitHistoryEntry;
 pub mod limits;
 pub use self::limits::Limits;
+pub mod stats_operation;
+pub use self::stats_operation::StatsOperation;
 pub mod update_deposits_request_body;
 pub use self::update_deposits_request_body::UpdateDepositsRequestBody;
 pub mod update_deposits_response;
@@ -40,12 +54,16 @@ pub mod update_withdrawals_request_body;
 pub use self::update_withdrawals_request_body::UpdateWithdrawalsRequestBody;
 pub mod update_withdrawals_response;
 pub use self::update_withdrawals_response::UpdateWithdrawalsResponse;
+pub mod volume_bucket;
+pub use self::volume_bucket::VolumeBucket;
 pub mod withdrawal;
 pub use self::withdrawal::Withdrawal;
 pub mod withdrawal_info;
 pub use self::withdrawal_info::WithdrawalInfo;
 pub mod withdrawal_parameters;
 pub use self::withdrawal_parameters::WithdrawalParameters;
+pub mod withdrawal_stats;
+pub use self::withdrawal_stats::WithdrawalStats;
 pub mod withdrawal_status;
 pub use self::withdrawal_status::WithdrawalStatus;
 pub mod withdrawal_update;
@@ -1,13 +1,20 @@
 //! Handlers for the blocklist client API
 
-use crate::client::{risk_client, sanctions};
-use crate::common::error::ErrorResponse;
-use crate::config::{AssessmentMethod, Settings};
-use reqwest::Client;
+use crate::audit::{AuditQuery, AuditResponse};
+use crate::client::{ScreeningContext, policy};
+use crate::common::error::{Error, ErrorResponse};
+use crate::common::{
+    BlocklistStatus, Chain, ScreenAddressesRequest, ScreenAddressesResponse, ScreenQuery,
+    ScreenedAddress,
+};
+use crate::config::{AssessmentMethod, Provider, Settings};
 use std::convert::Infallible;
 use tracing::error;
 use warp::{Rejection, Reply, http::StatusCode};
 
+/// The maximum number of addresses that can be screened by a single batch request
+pub const MAX_BATCH_SIZE: usize = 100;
+
 /// Handles requests to check the blocklist status of a given address.
 /// Converts successful blocklist status results to JSON and returns them,
 /// or converts errors into Warp rejections.
@@ -17,7 +24,8 @@ use warp::{Rejection, Reply, http::StatusCode};
     path = "/screen/{address}",
     tag = "address",
     params(
-    ("address" = String, Path, description = "Address to get risk assessment for")
+    ("address" = String, Path, description = "Address to get risk assessment for"),
+    ("chain" = Option<Chain>, Query, description = "Chain of the address, detected from the address if unset")
     ),
     responses(
     (status = 200, description = "Risk assessment retrieved successfully", body = BlocklistStatus),
@@ -30,24 +38,127 @@ use warp::{Rejection, Reply, http::StatusCode};
 
 pub async fn check_address_handler(
     address: String,
-    client: Client,
+    query: ScreenQuery,
+    context: ScreeningContext,
+    config: Settings,
+) -> impl Reply {
+    let chain = query.chain.unwrap_or_else(|| Chain::detect(&address));
+    let result = screen_address(&context, &config, &address, chain)
+        .await
+        .map(|blocklist_status| warp::reply::json(&blocklist_status));
+
+    match result {
+        Ok(blocklist_status) => blocklist_status.into_response(),
+        Err(error) => error.into_response(),
+    }
+}
+
+/// Handles requests to check the blocklist status of several addresses at once.
+/// Returns the status of each address in the order of the request, or an error if
+/// any of the addresses could not be screened.
+#[utoipa::path(
+    post,
+    operation_id = "checkAddresses",
+    path = "/screen",
+    tag = "address",
+    request_body = ScreenAddressesRequest,
+    responses(
+    (status = 200, description = "Risk assessments retrieved successfully", body = ScreenAddressesResponse),
+    (status = 400, description = "Invalid request body"),
+    (status = 405, description = "Method not allowed"),
+    (status = 413, description = "Request body too large"),
+    (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn check_addresses_handler(
+    request: ScreenAddressesRequest,
+    context: ScreeningContext,
     config: Settings,
 ) -> impl Reply {
     let result = (async {
-        match config.assessment.assessment_method {
-            AssessmentMethod::Sanctions => {
-                sanctions::check_address(&client, &config.risk_analysis, &address).await
-            }
-            AssessmentMethod::RiskAnalysis => {
-                risk_client::check_address(&client, &config.risk_analysis, &address).await
-            }
+        if request.addresses.len() > MAX_BATCH_SIZE {
+            return Err(Error::HttpRequest(
+                StatusCode::BAD_REQUEST,
+                format!("At most {MAX_BATCH_SIZE} addresses can be screened at once"),
+            ));
         }
+        let mut results = Vec::with_capacity(request.addresses.len());
+        for address in request.addresses {
+            let chain = request.chain.unwrap_or_else(|| Chain::detect(&address));
+            let status = screen_address(&context, &config, &address, chain).await?;
+            results.push(ScreenedAddress { address, status });
+        }
+        Ok(ScreenAddressesResponse { results })
     })
     .await
-    .map(|blocklist_status| warp::reply::json(&blocklist_status));
+    .map(|response| warp::reply::json(&response));
 
     match result {
-        Ok(blocklist_status) => blocklist_status.into_response(),
+        Ok(response) => response.into_response(),
+        Err(error) => error.into_response(),
+    }
+}
+
+/// Screen a single address of the given chain with the configured assessment method
+async fn screen_address(
+    context: &ScreeningContext,
+    config: &Settings,
+    address: &str,
+    chain: Chain,
+) -> Result<BlocklistStatus, Error> {
+    match config.assessment.assessment_method {
+        AssessmentMethod::Sanctions => {
+            policy::check_provider(Provider::Sanctions, context, config, address, chain).await
+        }
+        AssessmentMethod::RiskAnalysis => {
+            policy::check_provider(Provider::RiskAnalysis, context, config, address, chain).await
+        }
+        AssessmentMethod::LocalFile => {
+            policy::check_provider(Provider::LocalFile, context, config, address, chain).await
+        }
+        AssessmentMethod::Composite => match &config.policy {
+            Some(policy) => policy::check_address(context, config, policy, address, chain).await,
+            None => Err(Error::HttpRequest(
+                StatusCode::INTERNAL_SERVER_ERROR,
+                "screening policy is not configured".to_string(),
+            )),
+        },
+    }
+}
+
+/// Handles requests to query the audit log of screening decisions.
+/// Returns the most recent decisions matching the query, most recent first.
+#[utoipa::path(
+    get,
+    operation_id = "getAuditLog",
+    path = "/audit",
+    tag = "audit",
+    params(
+    ("address" = Option<String>, Query, description = "Only return decisions about this address"),
+    ("provider" = Option<String>, Query, description = "Only return decisions made by this provider"),
+    ("since" = Option<u64>, Query, description = "Only return decisions made at or after this unix timestamp, in seconds"),
+    ("limit" = Option<usize>, Query, description = "Maximum number of decisions to return, defaults to 100 and is capped at 1000")
+    ),
+    responses(
+    (status = 200, description = "Audit log retrieved successfully", body = AuditResponse),
+    (status = 400, description = "Invalid request query"),
+    (status = 404, description = "Audit log is not enabled"),
+    (status = 405, description = "Method not allowed"),
+    (status = 500, description = "Internal server error")
+    )
+)]
+pub async fn audit_handler(query: AuditQuery, context: ScreeningContext) -> impl Reply {
+    let result = match &context.audit_log {
+        Some(audit_log) => audit_log.query(&query).await,
+        None => Err(Error::HttpRequest(
+            StatusCode::NOT_FOUND,
+            "Audit log is not enabled".to_string(),
+        )),
+    }
+    .map(|entries| warp::reply::json(&AuditResponse { entries }));
+
+    match result {
+        Ok(response) => response.into_response(),
         Err(error) => error.into_response(),
     }
 }
@@ -68,6 +179,16 @@ pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible>
         return Ok(warp::reply::with_status(json, StatusCode::BAD_REQUEST));
     }
 
+    if err.find::<warp::reject::PayloadTooLarge>().is_some() {
+        let json = warp::reply::json(&ErrorResponse {
+            message: "Payload Too Large".to_string(),
+        });
+        return Ok(warp::reply::with_status(
+            json,
+            StatusCode::PAYLOAD_TOO_LARGE,
+        ));
+    }
+
     if err.find::<warp::reject::MethodNotAllowed>().is_some() {
         let json = warp::reply::json(&ErrorResponse {
             message: "Method Not Allowed".to_string(),
@@ -87,3 +208,28 @@ pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible>
         StatusCode::INTERNAL_SERVER_ERROR,
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use warp::Filter;
+
+    #[tokio::test]
+    async fn test_handle_rejection_payload_too_large() {
+        let filter = warp::post()
+            .and(warp::body::content_length_limit(4))
+            .and(warp::body::bytes())
+            .map(|_| warp::reply())
+            .recover(handle_rejection);
+
+        let response = warp::test::request()
+            .method("POST")
+            .body("too large")
+            .reply(&filter)
+            .await;
+
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        let body: serde_json::Value = serde_json::from_slice(response.body()).unwrap();
+        assert_eq!(body["message"], "Payload Too Large");
+    }
+}
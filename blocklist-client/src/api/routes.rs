@@ -1,19 +1,46 @@
 //! Route configuration for the Blocklist client
 
 use super::handlers;
+use crate::client::ScreeningContext;
 use crate::config::SETTINGS;
-use reqwest::Client;
 use warp::Filter;
 
+/// The maximum size, in bytes, of the body of a batch screening request
+const MAX_BODY_SIZE: u64 = 64 * 1024;
+
 /// This function sets up the Warp filters for handling incoming screening requests. It defines a
-/// route for the `/screen/{address}` endpoint, which accepts GET requests
+/// route for the `/screen/{address}` endpoint, which accepts GET requests with an optional `chain`
+/// query parameter, a route for the `/screen` endpoint, which accepts POST requests to screen
+/// several addresses at once, and a route for the `/audit` endpoint, which accepts GET requests
+/// to query the audit log.
 pub fn routes(
-    client: Client,
+    context: ScreeningContext,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
-    warp::path("screen")
+    let screen_context = context.clone();
+    let screen = warp::path("screen")
         .and(warp::path::param::<String>())
         .and(warp::get())
-        .and(warp::any().map(move || client.clone()))
+        .and(warp::query())
+        .and(warp::any().map(move || screen_context.clone()))
+        .and(warp::any().map(move || SETTINGS.clone()))
+        .then(handlers::check_address_handler);
+
+    let batch_context = context.clone();
+    let screen_batch = warp::path("screen")
+        .and(warp::path::end())
+        .and(warp::post())
+        .and(warp::body::content_length_limit(MAX_BODY_SIZE))
+        .and(warp::body::json())
+        .and(warp::any().map(move || batch_context.clone()))
         .and(warp::any().map(move || SETTINGS.clone()))
-        .then(handlers::check_address_handler)
+        .then(handlers::check_addresses_handler);
+
+    let audit = warp::path("audit")
+        .and(warp::path::end())
+        .and(warp::get())
+        .and(warp::query())
+        .and(warp::any().map(move || context.clone()))
+        .then(handlers::audit_handler);
+
+    screen.or(screen_batch).or(audit)
 }
@@ -0,0 +1,336 @@
+//! Append-only audit log of the screening decisions made by the Blocklist client
+//!
+//! Every decision is appended to a file as a single JSON line, so that the log can be inspected
+//! with standard tools and survives restarts. Queries read the file back and filter its entries.
+//! The file is rotated once it reaches a maximum size, which bounds the cost of a query.
+
+use crate::common::error::Error;
+use crate::common::{BlocklistStatus, RiskSeverity};
+use crate::config::AuditConfig;
+use serde::{Deserialize, Serialize};
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufRead, BufReader, ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+use std::time::{SystemTime, UNIX_EPOCH};
+use utoipa::ToSchema;
+
+/// The number of entries returned by a query when no limit is given
+const DEFAULT_QUERY_LIMIT: usize = 100;
+
+/// The maximum number of entries returned by a query
+const MAX_QUERY_LIMIT: usize = 1000;
+
+/// A single screening decision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct AuditEntry {
+    /// Unix timestamp, in seconds, of the decision
+    pub timestamp: u64,
+    /// The screened address
+    pub address: String,
+    /// The provider that made the decision, or `composite` for the combined decision of a
+    /// screening policy
+    pub provider: String,
+    /// The risk severity of the address
+    pub severity: RiskSeverity,
+    /// Whether the address is blocklisted
+    pub is_blocklisted: bool,
+    /// Whether the address was accepted
+    pub accept: bool,
+    /// Reason for the decision
+    pub reason: Option<String>,
+    /// Hex encoded SHA-256 hash of the raw provider response, if the decision is based on one
+    pub response_hash: Option<String>,
+    /// Whether the decision is based on a cached provider response
+    pub cached: bool,
+}
+
+impl AuditEntry {
+    /// Create an entry for a decision made now
+    pub fn new(
+        address: &str,
+        provider: impl ToString,
+        status: &BlocklistStatus,
+        response_hash: Option<String>,
+        cached: bool,
+    ) -> Self {
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|duration| duration.as_secs())
+            .unwrap_or_default();
+        Self {
+            timestamp,
+            address: address.to_string(),
+            provider: provider.to_string(),
+            severity: status.severity.clone(),
+            is_blocklisted: status.is_blocklisted,
+            accept: status.accept,
+            reason: status.reason.clone(),
+            response_hash,
+            cached,
+        }
+    }
+}
+
+/// Query parameters for the audit log
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct AuditQuery {
+    /// Only return decisions about this address
+    pub address: Option<String>,
+    /// Only return decisions made by this provider
+    pub provider: Option<String>,
+    /// Only return decisions made at or after this unix timestamp, in seconds
+    pub since: Option<u64>,
+    /// Maximum number of entries to return, defaults to 100 and is capped at 1000
+    pub limit: Option<usize>,
+}
+
+impl AuditQuery {
+    fn matches(&self, entry: &AuditEntry) -> bool {
+        self.address
+            .as_ref()
+            .is_none_or(|address| *address == entry.address)
+            && self
+                .provider
+                .as_ref()
+                .is_none_or(|provider| *provider == entry.provider)
+            && self.since.is_none_or(|since| entry.timestamp >= since)
+    }
+}
+
+/// Response to an audit log query
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct AuditResponse {
+    /// The matching entries, most recent first
+    pub entries: Vec<AuditEntry>,
+}
+
+/// The open audit log file and its size in bytes
+#[derive(Debug)]
+struct LogFile {
+    file: File,
+    size: u64,
+}
+
+/// Append-only audit log backed by a file
+///
+/// Once the file grows past the configured maximum size it is rotated to a single backup file
+/// with a `.1` suffix, replacing the previous backup, so at most twice the maximum size is kept on
+/// disk and read back by a query. File I/O runs on the blocking thread pool.
+#[derive(Debug)]
+pub struct AuditLog {
+    path: PathBuf,
+    rotated_path: PathBuf,
+    max_size_bytes: u64,
+    file: Arc<Mutex<LogFile>>,
+}
+
+impl AuditLog {
+    /// Open the audit log file in the given config, creating it if it does not exist
+    pub fn open(config: &AuditConfig) -> Result<Self, Error> {
+        let mut rotated_path = config.path.clone().into_os_string();
+        rotated_path.push(".1");
+        Ok(Self {
+            path: config.path.clone(),
+            rotated_path: rotated_path.into(),
+            max_size_bytes: config.max_size_bytes,
+            file: Arc::new(Mutex::new(open_file(&config.path)?)),
+        })
+    }
+
+    /// Append an entry to the audit log
+    pub async fn record(&self, entry: &AuditEntry) -> Result<(), Error> {
+        let mut line =
+            serde_json::to_vec(entry).map_err(|error| Error::Serialization(error.to_string()))?;
+        line.push(b'\n');
+
+        let file = Arc::clone(&self.file);
+        let path = self.path.clone();
+        let rotated_path = self.rotated_path.clone();
+        let max_size_bytes = self.max_size_bytes;
+        spawn_blocking(move || {
+            let mut file = file
+                .lock()
+                .map_err(|_| Error::AuditLog("audit log lock poisoned".to_string()))?;
+            if file.size > 0 && file.size + line.len() as u64 > max_size_bytes {
+                fs::rename(&path, &rotated_path).map_err(|error| {
+                    Error::AuditLog(format!("failed to rotate {}: {error}", path.display()))
+                })?;
+                *file = open_file(&path)?;
+            }
+            file.file
+                .write_all(&line)
+                .and_then(|_| file.file.flush())
+                .map_err(|error| Error::AuditLog(format!("failed to write entry: {error}")))?;
+            file.size += line.len() as u64;
+            Ok(())
+        })
+        .await
+    }
+
+    /// Get the most recent entries matching the query
+    pub async fn query(&self, query: &AuditQuery) -> Result<Vec<AuditEntry>, Error> {
+        let limit = query
+            .limit
+            .unwrap_or(DEFAULT_QUERY_LIMIT)
+            .min(MAX_QUERY_LIMIT);
+        let query = query.clone();
+        let paths = [self.path.clone(), self.rotated_path.clone()];
+        spawn_blocking(move || {
+            let mut entries = Vec::new();
+            for path in &paths {
+                let mut matching = read_entries(path, &query)?;
+                matching.reverse();
+                entries.extend(matching.into_iter().take(limit - entries.len()));
+                if entries.len() == limit {
+                    break;
+                }
+            }
+            Ok(entries)
+        })
+        .await
+    }
+}
+
+/// Open a log file for appending, creating it if it does not exist
+fn open_file(path: &Path) -> Result<LogFile, Error> {
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|error| Error::AuditLog(format!("failed to open {}: {error}", path.display())))?;
+    let size = file
+        .metadata()
+        .map_err(|error| Error::AuditLog(format!("failed to open {}: {error}", path.display())))?
+        .len();
+    Ok(LogFile { file, size })
+}
+
+/// Read the entries of a log file matching the query, oldest first. A missing file has no entries.
+fn read_entries(path: &Path, query: &AuditQuery) -> Result<Vec<AuditEntry>, Error> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(error) if error.kind() == ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(error) => {
+            return Err(Error::AuditLog(format!(
+                "failed to open audit log: {error}"
+            )));
+        }
+    };
+
+    let mut entries = Vec::new();
+    for line in BufReader::new(file).lines() {
+        let line =
+            line.map_err(|error| Error::AuditLog(format!("failed to read entry: {error}")))?;
+        // A partially written last line is skipped rather than failing the whole query.
+        let Ok(entry) = serde_json::from_str::<AuditEntry>(&line) else {
+            continue;
+        };
+        if query.matches(&entry) {
+            entries.push(entry);
+        }
+    }
+    Ok(entries)
+}
+
+/// Run file I/O on the blocking thread pool, so that it does not stall the async runtime
+async fn spawn_blocking<T, F>(f: F) -> Result<T, Error>
+where
+    F: FnOnce() -> Result<T, Error> + Send + 'static,
+    T: Send + 'static,
+{
+    tokio::task::spawn_blocking(f)
+        .await
+        .map_err(|error| Error::AuditLog(format!("audit log task failed: {error}")))?
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn status(is_blocklisted: bool) -> BlocklistStatus {
+        BlocklistStatus {
+            is_blocklisted,
+            severity: if is_blocklisted {
+                RiskSeverity::Severe
+            } else {
+                RiskSeverity::Low
+            },
+            accept: !is_blocklisted,
+            reason: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_record_and_query() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = AuditConfig {
+            path: dir.path().join("audit.log"),
+            max_size_bytes: 1024 * 1024,
+        };
+        let audit_log = AuditLog::open(&config).unwrap();
+
+        let first = AuditEntry::new("first", "sanctions", &status(true), Some("a".into()), false);
+        let second = AuditEntry::new("second", "local_file", &status(false), None, false);
+        let third = AuditEntry::new("first", "sanctions", &status(true), Some("a".into()), true);
+        for entry in [&first, &second, &third] {
+            audit_log.record(entry).await.unwrap();
+        }
+
+        let all = audit_log.query(&AuditQuery::default()).await.unwrap();
+        assert_eq!(all, vec![third.clone(), second.clone(), first.clone()]);
+
+        let query = AuditQuery {
+            address: Some("first".to_string()),
+            limit: Some(1),
+            ..Default::default()
+        };
+        assert_eq!(audit_log.query(&query).await.unwrap(), vec![third]);
+
+        let query = AuditQuery {
+            provider: Some("local_file".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(audit_log.query(&query).await.unwrap(), vec![second]);
+
+        // Entries survive reopening the log.
+        let reopened = AuditLog::open(&config).unwrap();
+        assert_eq!(
+            reopened.query(&AuditQuery::default()).await.unwrap().len(),
+            3
+        );
+    }
+
+    #[tokio::test]
+    async fn test_rotation() {
+        let dir = tempfile::tempdir().unwrap();
+        let entry =
+            |address: &str| AuditEntry::new(address, "sanctions", &status(true), None, false);
+        let entry_size = serde_json::to_vec(&entry("a")).unwrap().len() as u64 + 1;
+        let config = AuditConfig {
+            path: dir.path().join("audit.log"),
+            max_size_bytes: entry_size * 2,
+        };
+        let audit_log = AuditLog::open(&config).unwrap();
+
+        let entries = ["a", "b", "c", "d", "e"].map(entry);
+        for entry in &entries {
+            audit_log.record(entry).await.unwrap();
+        }
+
+        // Only the current file and the last rotated file are kept.
+        assert!(dir.path().join("audit.log.1").exists());
+        let all = audit_log.query(&AuditQuery::default()).await.unwrap();
+        assert_eq!(
+            all,
+            vec![entries[4].clone(), entries[3].clone(), entries[2].clone()]
+        );
+
+        let query = AuditQuery {
+            limit: Some(2),
+            ..Default::default()
+        };
+        let latest = audit_log.query(&query).await.unwrap();
+        assert_eq!(latest, vec![entries[4].clone(), entries[3].clone()]);
+    }
+}
@@ -0,0 +1,135 @@
+//! This module caches the risk assessments returned by the remote providers, so that repeated
+//! screenings of the same address do not call the upstream APIs every time.
+//!
+//! Assessments that blocklist an address (positive verdicts) and assessments that accept it
+//! (negative verdicts) expire after separately configured durations.
+
+use crate::common::RiskAssessment;
+use crate::config::{CacheConfig, Provider};
+use lru::LruCache;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// A cached risk assessment
+#[derive(Debug)]
+struct CachedAssessment {
+    assessment: RiskAssessment,
+    expires_at: Instant,
+}
+
+/// Cache of risk assessments keyed by provider and address
+#[derive(Debug)]
+pub struct AssessmentCache {
+    positive_ttl: Duration,
+    negative_ttl: Duration,
+    entries: Mutex<LruCache<(Provider, String), CachedAssessment>>,
+}
+
+impl AssessmentCache {
+    /// Create an empty cache from the given config
+    pub fn new(config: &CacheConfig) -> Self {
+        let capacity = NonZeroUsize::new(config.max_entries).unwrap_or(NonZeroUsize::MIN);
+        Self {
+            positive_ttl: Duration::from_secs(config.positive_ttl_secs),
+            negative_ttl: Duration::from_secs(config.negative_ttl_secs),
+            entries: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Get the cached assessment of the address by the provider, if it has not expired
+    pub fn get(&self, provider: Provider, address: &str) -> Option<RiskAssessment> {
+        let mut entries = self.entries.lock().ok()?;
+        let key = (provider, address.to_string());
+        match entries.get(&key) {
+            Some(cached) if cached.expires_at > Instant::now() => Some(cached.assessment.clone()),
+            Some(_) => {
+                entries.pop(&key);
+                None
+            }
+            None => None,
+        }
+    }
+
+    /// Cache the assessment of the address by the provider. Assessments whose verdict has a zero
+    /// TTL are not cached.
+    pub fn insert(&self, provider: Provider, address: &str, assessment: RiskAssessment) {
+        let ttl = if assessment.severity.is_severe() {
+            self.positive_ttl
+        } else {
+            self.negative_ttl
+        };
+        if ttl.is_zero() {
+            return;
+        }
+        if let Ok(mut entries) = self.entries.lock() {
+            let cached = CachedAssessment {
+                assessment,
+                expires_at: Instant::now() + ttl,
+            };
+            entries.put((provider, address.to_string()), cached);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::RiskSeverity;
+
+    fn assessment(severity: RiskSeverity) -> RiskAssessment {
+        RiskAssessment {
+            severity,
+            reason: None,
+            response_hash: Some("hash".to_string()),
+        }
+    }
+
+    fn cache(positive_ttl_secs: u64, negative_ttl_secs: u64) -> AssessmentCache {
+        AssessmentCache::new(&CacheConfig {
+            positive_ttl_secs,
+            negative_ttl_secs,
+            max_entries: 2,
+        })
+    }
+
+    #[test]
+    fn test_cache_hit_per_provider() {
+        let cache = cache(60, 60);
+        cache.insert(
+            Provider::Sanctions,
+            "address",
+            assessment(RiskSeverity::Severe),
+        );
+
+        let cached = cache.get(Provider::Sanctions, "address").unwrap();
+        assert_eq!(cached.severity, RiskSeverity::Severe);
+        assert_eq!(cached.response_hash.as_deref(), Some("hash"));
+        assert!(cache.get(Provider::RiskAnalysis, "address").is_none());
+    }
+
+    #[test]
+    fn test_cache_ttl_per_verdict() {
+        let cache = cache(60, 0);
+        cache.insert(
+            Provider::Sanctions,
+            "severe",
+            assessment(RiskSeverity::Severe),
+        );
+        cache.insert(Provider::Sanctions, "low", assessment(RiskSeverity::Low));
+
+        assert!(cache.get(Provider::Sanctions, "severe").is_some());
+        assert!(cache.get(Provider::Sanctions, "low").is_none());
+    }
+
+    #[test]
+    fn test_cache_evicts_least_recently_used() {
+        let cache = cache(60, 60);
+        cache.insert(Provider::Sanctions, "first", assessment(RiskSeverity::Low));
+        cache.insert(Provider::Sanctions, "second", assessment(RiskSeverity::Low));
+        cache.insert(Provider::Sanctions, "third", assessment(RiskSeverity::Low));
+
+        assert!(cache.get(Provider::Sanctions, "first").is_none());
+        assert!(cache.get(Provider::Sanctions, "third").is_some());
+    }
+}
@@ -0,0 +1,508 @@
+//! This module screens addresses against blocklists loaded from local files, without calling any
+//! remote API.
+//!
+//! It provides functionality to:
+//! - Load addresses from OFAC SDN XML and CSV exports, plain address lists and JSON allow/deny files
+//! - Reload the lists whenever one of the files changes
+//! - Check if given address is on one of the lists
+//!
+//! Stacks contract principals are also checked against the lists by the address of their deployer,
+//! so that listing an account covers every contract it deploys.
+//!
+//! The format of each file is determined by its extension: `.xml` and `.csv` files are parsed as
+//! OFAC SDN exports, `.json` files as allow/deny files and any other file as a plain list with one
+//! address per line.
+
+use crate::common::error::Error;
+use crate::common::{BlocklistStatus, Chain, RiskSeverity};
+use crate::config::LocalListsConfig;
+use serde::Deserialize;
+use std::collections::HashSet;
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, RwLock};
+use std::time::{Duration, SystemTime};
+use tracing::{debug, info, warn};
+
+/// The prefix of the OFAC SDN ID type used for digital currency addresses.
+const DIGITAL_CURRENCY_ADDRESS: &str = "Digital Currency Address - ";
+
+/// The reason reported for addresses found on a local blocklist.
+const BLOCKLISTED_REASON: &str = "local blocklist";
+
+/// Contents of a JSON allow/deny file
+#[derive(Debug, Default, Deserialize)]
+struct AllowDenyFile {
+    /// Addresses that are always accepted, even if they are on a deny list
+    #[serde(default)]
+    allow: Vec<String>,
+    /// Addresses that are blocklisted
+    #[serde(default)]
+    deny: Vec<String>,
+}
+
+/// The addresses loaded from all configured files
+#[derive(Debug, Default)]
+struct Lists {
+    /// Addresses that are always accepted
+    allow: HashSet<String>,
+    /// Addresses that are blocklisted
+    deny: HashSet<String>,
+    /// Last modification time of each file when the lists were loaded
+    modified: Vec<Option<SystemTime>>,
+}
+
+/// Blocklist backed by local files
+#[derive(Debug)]
+pub struct LocalBlocklist {
+    paths: Vec<PathBuf>,
+    lists: RwLock<Lists>,
+}
+
+impl LocalBlocklist {
+    /// Load the blocklist from the files in the given config
+    pub fn load(config: &LocalListsConfig) -> Result<Self, Error> {
+        let lists = load_lists(&config.paths)?;
+        info!(
+            "Loaded {} blocklisted and {} allowlisted addresses from local files",
+            lists.deny.len(),
+            lists.allow.len()
+        );
+        Ok(Self {
+            paths: config.paths.clone(),
+            lists: RwLock::new(lists),
+        })
+    }
+
+    /// Screen the provided address for blocklist status
+    /// Marks the address as not accepted if it is on a deny list and not on an allow list
+    pub fn check_address(&self, address: &str, chain: Chain) -> Result<BlocklistStatus, Error> {
+        let candidates = candidate_addresses(address, chain);
+        let lists = self
+            .lists
+            .read()
+            .map_err(|_| Error::LocalBlocklist("blocklist lock poisoned".to_string()))?;
+        let is_blocklisted = !candidates
+            .iter()
+            .any(|address| lists.allow.contains(address))
+            && candidates
+                .iter()
+                .any(|address| lists.deny.contains(address));
+        debug!("Local blocklist status for {chain} address {address}: {is_blocklisted}");
+
+        Ok(BlocklistStatus {
+            is_blocklisted,
+            severity: if is_blocklisted {
+                RiskSeverity::Severe
+            } else {
+                RiskSeverity::Low
+            },
+            accept: !is_blocklisted,
+            reason: is_blocklisted.then(|| BLOCKLISTED_REASON.to_string()),
+        })
+    }
+
+    /// Whether the provided address is on an allow list
+    pub fn is_allowlisted(&self, address: &str, chain: Chain) -> Result<bool, Error> {
+        let candidates = candidate_addresses(address, chain);
+        let lists = self
+            .lists
+            .read()
+            .map_err(|_| Error::LocalBlocklist("blocklist lock poisoned".to_string()))?;
+        Ok(candidates
+            .iter()
+            .any(|address| lists.allow.contains(address)))
+    }
+
+    /// Reload the lists if any of the files changed since they were last loaded. Returns whether
+    /// the lists were reloaded.
+    pub fn reload_if_changed(&self) -> Result<bool, Error> {
+        let modified = modification_times(&self.paths);
+        let changed = self
+            .lists
+            .read()
+            .map_err(|_| Error::LocalBlocklist("blocklist lock poisoned".to_string()))?
+            .modified
+            != modified;
+        if !changed {
+            return Ok(false);
+        }
+
+        let lists = load_lists(&self.paths)?;
+        info!(
+            "Reloaded {} blocklisted and {} allowlisted addresses from local files",
+            lists.deny.len(),
+            lists.allow.len()
+        );
+        *self
+            .lists
+            .write()
+            .map_err(|_| Error::LocalBlocklist("blocklist lock poisoned".to_string()))? = lists;
+        Ok(true)
+    }
+
+    /// Spawn a task that periodically reloads the lists when any of the files changes. A list
+    /// that fails to load is logged and the previously loaded lists stay in use.
+    pub fn spawn_reload_task(self: Arc<Self>, interval: Duration) -> tokio::task::JoinHandle<()> {
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            loop {
+                interval.tick().await;
+                let blocklist = Arc::clone(&self);
+                match tokio::task::spawn_blocking(move || blocklist.reload_if_changed()).await {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(error)) => warn!("Failed to reload local blocklist: {error}"),
+                    Err(error) => warn!("Local blocklist reload task failed: {error}"),
+                }
+            }
+        })
+    }
+}
+
+/// Load the lists from all the given files
+fn load_lists(paths: &[PathBuf]) -> Result<Lists, Error> {
+    let mut lists = Lists {
+        modified: modification_times(paths),
+        ..Default::default()
+    };
+    for path in paths {
+        let contents = std::fs::read_to_string(path).map_err(|error| {
+            Error::LocalBlocklist(format!("failed to read {}: {error}", path.display()))
+        })?;
+        match extension(path).as_deref() {
+            Some("xml") => lists.deny.extend(parse_sdn_xml(&contents)),
+            Some("csv") => lists.deny.extend(parse_sdn_csv(&contents)),
+            Some("json") => {
+                let file: AllowDenyFile = serde_json::from_str(&contents).map_err(|error| {
+                    Error::LocalBlocklist(format!("failed to parse {}: {error}", path.display()))
+                })?;
+                lists
+                    .allow
+                    .extend(file.allow.iter().map(String::as_str).map(normalize_address));
+                lists
+                    .deny
+                    .extend(file.deny.iter().map(String::as_str).map(normalize_address));
+            }
+            _ => lists.deny.extend(parse_plain_list(&contents)),
+        }
+    }
+    Ok(lists)
+}
+
+/// The lowercase extension of the given path
+fn extension(path: &Path) -> Option<String> {
+    path.extension()
+        .and_then(|extension| extension.to_str())
+        .map(str::to_lowercase)
+}
+
+/// The last modification time of each of the given files. Files whose metadata cannot be read
+/// have no modification time, so that they are reloaded once they become readable again.
+fn modification_times(paths: &[PathBuf]) -> Vec<Option<SystemTime>> {
+    paths
+        .iter()
+        .map(|path| {
+            std::fs::metadata(path)
+                .and_then(|meta| meta.modified())
+                .ok()
+        })
+        .collect()
+}
+
+/// The normalized addresses under which the provided address may be listed: the address itself
+/// and, for a Stacks contract principal, the address of its deployer.
+fn candidate_addresses(address: &str, chain: Chain) -> Vec<String> {
+    let address = address.trim();
+    let mut candidates = vec![normalize_address(address)];
+    let account = chain.account_address(address);
+    if account != address {
+        candidates.push(normalize_address(account));
+    }
+    candidates
+}
+
+/// Normalize an address so that lookups do not depend on the casing of case-insensitive
+/// encodings. Hex and bech32 addresses are lowercased, base58 addresses are case-sensitive and
+/// kept as is.
+fn normalize_address(address: &str) -> String {
+    let address = address.trim();
+    let lowercase = address.to_lowercase();
+    let is_case_insensitive = ["0x", "bc1", "tb1", "bcrt1"]
+        .iter()
+        .any(|prefix| lowercase.starts_with(prefix));
+    if is_case_insensitive {
+        lowercase
+    } else {
+        address.to_string()
+    }
+}
+
+/// Parse a plain list with one address per line. Empty lines and lines starting with `#` are
+/// ignored.
+fn parse_plain_list(contents: &str) -> impl Iterator<Item = String> + '_ {
+    contents
+        .lines()
+        .map(str::trim)
+        .filter(|line| !line.is_empty() && !line.starts_with('#'))
+        .map(normalize_address)
+}
+
+/// Parse the digital currency addresses from an OFAC SDN XML export, where they are listed as
+/// `<id>` elements with an `<idType>` of `Digital Currency Address - <currency>` and the address
+/// in `<idNumber>`.
+fn parse_sdn_xml(contents: &str) -> Vec<String> {
+    contents
+        .split("<id>")
+        .skip(1)
+        .filter(|id| {
+            element_text(id, "idType")
+                .is_some_and(|id_type| id_type.starts_with(DIGITAL_CURRENCY_ADDRESS))
+        })
+        .filter_map(|id| element_text(id, "idNumber"))
+        .map(normalize_address)
+        .collect()
+}
+
+/// The trimmed text of the first element with the given name
+fn element_text<'a>(contents: &'a str, name: &str) -> Option<&'a str> {
+    let start = contents.find(&format!("<{name}>"))? + name.len() + 2;
+    let end = contents[start..].find(&format!("</{name}>"))? + start;
+    Some(contents[start..end].trim())
+}
+
+/// Parse the digital currency addresses from an OFAC SDN CSV export, where they are listed in the
+/// remarks as `Digital Currency Address - <currency> <address>;`.
+fn parse_sdn_csv(contents: &str) -> Vec<String> {
+    contents
+        .split(DIGITAL_CURRENCY_ADDRESS)
+        .skip(1)
+        .filter_map(|remark| {
+            let mut words = remark.split(|c: char| c.is_whitespace() || c == ';' || c == '"');
+            // The first word is the currency code.
+            words.next()?;
+            words.find(|word| !word.is_empty())
+        })
+        .map(normalize_address)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Write;
+
+    const SDN_XML: &str = r#"<sdnEntry>
+  <uid>36216</uid>
+  <lastName>SECONDEYE SOLUTION</lastName>
+  <idList>
+    <id>
+      <uid>1</uid>
+      <idType>Passport</idType>
+      <idNumber>AB1234</idNumber>
+    </id>
+    <id>
+      <uid>2</uid>
+      <idType>Digital Currency Address - ETH</idType>
+      <idNumber>0x1DA5821544E25C636C1417BA96ADE4CF6D2F9B5A</idNumber>
+    </id>
+    <id>
+      <uid>3</uid>
+      <idType>Digital Currency Address - XBT</idType>
+      <idNumber>12QtD5BFwRsdNsAZY76UVE1xyCGNTojH9h</idNumber>
+    </id>
+  </idList>
+</sdnEntry>"#;
+
+    const SDN_CSV: &str = r#"36216,"SECONDEYE SOLUTION","-0- ",...,"Digital Currency Address - XBT 12QtD5BFwRsdNsAZY76UVE1xyCGNTojH9h; Digital Currency Address - ETH 0x1da5821544e25c636c1417ba96ade4cf6d2f9b5a; Website www.example.com."
+36217,"OTHER","-0- ",...,"Passport AB1234.""#;
+
+    fn write_file(dir: &tempfile::TempDir, name: &str, contents: &str) -> PathBuf {
+        let path = dir.path().join(name);
+        let mut file = std::fs::File::create(&path).unwrap();
+        file.write_all(contents.as_bytes()).unwrap();
+        path
+    }
+
+    #[test]
+    fn test_parse_sdn_xml() {
+        let addresses = parse_sdn_xml(SDN_XML);
+        assert_eq!(
+            addresses,
+            vec![
+                "0x1da5821544e25c636c1417ba96ade4cf6d2f9b5a".to_string(),
+                "12QtD5BFwRsdNsAZY76UVE1xyCGNTojH9h".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_sdn_csv() {
+        let addresses = parse_sdn_csv(SDN_CSV);
+        assert_eq!(
+            addresses,
+            vec![
+                "12QtD5BFwRsdNsAZY76UVE1xyCGNTojH9h".to_string(),
+                "0x1da5821544e25c636c1417ba96ade4cf6d2f9b5a".to_string(),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_parse_plain_list() {
+        let addresses: Vec<_> =
+            parse_plain_list("# comment\n\n BC1QXY2KGDYGJRSQTZQ2N0YRF2493P83KKFJHX0WLH \n")
+                .collect();
+        assert_eq!(
+            addresses,
+            vec!["bc1qxy2kgdygjrsqtzq2n0yrf2493p83kkfjhx0wlh"]
+        );
+    }
+
+    #[test]
+    fn test_check_address_with_allow_and_deny_lists() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LocalListsConfig {
+            paths: vec![
+                write_file(&dir, "sdn.xml", SDN_XML),
+                write_file(
+                    &dir,
+                    "overrides.json",
+                    r#"{"allow": ["12QtD5BFwRsdNsAZY76UVE1xyCGNTojH9h"], "deny": ["denied"]}"#,
+                ),
+            ],
+            reload_interval_secs: 1,
+        };
+        let blocklist = LocalBlocklist::load(&config).unwrap();
+
+        let status = blocklist
+            .check_address("0x1da5821544e25c636c1417ba96ade4cf6d2f9b5a", Chain::Bitcoin)
+            .unwrap();
+        assert!(status.is_blocklisted);
+        assert_eq!(status.severity, RiskSeverity::Severe);
+        assert_eq!(status.reason, Some(BLOCKLISTED_REASON.to_string()));
+        assert!(!status.accept);
+
+        assert!(
+            blocklist
+                .check_address("denied", Chain::Bitcoin)
+                .unwrap()
+                .is_blocklisted
+        );
+
+        // Allowlisted addresses are accepted even though they are on the SDN list.
+        let status = blocklist
+            .check_address("12QtD5BFwRsdNsAZY76UVE1xyCGNTojH9h", Chain::Bitcoin)
+            .unwrap();
+        assert!(!status.is_blocklisted);
+        assert!(status.accept);
+
+        let status = blocklist.check_address("unknown", Chain::Bitcoin).unwrap();
+        assert!(!status.is_blocklisted);
+        assert_eq!(status.severity, RiskSeverity::Low);
+        assert!(status.reason.is_none());
+    }
+
+    #[test]
+    fn test_check_stacks_principals() {
+        let dir = tempfile::tempdir().unwrap();
+        let config = LocalListsConfig {
+            paths: vec![write_file(
+                &dir,
+                "stacks.json",
+                r#"{"allow": ["SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7.allowed"],
+                    "deny": ["SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7", "SP3FBR2AGK5H9QBDH3EEN6DF8EK8JY7RX8QJ5SVTE.token"]}"#,
+            )],
+            reload_interval_secs: 1,
+        };
+        let blocklist = LocalBlocklist::load(&config).unwrap();
+
+        // Contracts are blocklisted when their deployer is.
+        let deployer = "SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7";
+        let contract = "SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7.contract";
+        assert!(
+            blocklist
+                .check_address(deployer, Chain::Stacks)
+                .unwrap()
+                .is_blocklisted
+        );
+        assert!(
+            blocklist
+                .check_address(contract, Chain::Stacks)
+                .unwrap()
+                .is_blocklisted
+        );
+
+        // An allowlisted contract is accepted even though its deployer is blocklisted.
+        let allowed = "SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7.allowed";
+        assert!(
+            !blocklist
+                .check_address(allowed, Chain::Stacks)
+                .unwrap()
+                .is_blocklisted
+        );
+        assert!(blocklist.is_allowlisted(allowed, Chain::Stacks).unwrap());
+
+        // Blocklisting a contract does not blocklist its deployer, and Stacks principals are
+        // case sensitive.
+        let token = "SP3FBR2AGK5H9QBDH3EEN6DF8EK8JY7RX8QJ5SVTE.token";
+        assert!(
+            blocklist
+                .check_address(token, Chain::Stacks)
+                .unwrap()
+                .is_blocklisted
+        );
+        let token_deployer = "SP3FBR2AGK5H9QBDH3EEN6DF8EK8JY7RX8QJ5SVTE";
+        assert!(
+            !blocklist
+                .check_address(token_deployer, Chain::Stacks)
+                .unwrap()
+                .is_blocklisted
+        );
+        assert!(
+            !blocklist
+                .check_address(&deployer.to_lowercase(), Chain::Stacks)
+                .unwrap()
+                .is_blocklisted
+        );
+    }
+
+    #[test]
+    fn test_reload_if_changed() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = write_file(&dir, "list.txt", "first\n");
+        let config = LocalListsConfig {
+            paths: vec![path.clone()],
+            reload_interval_secs: 1,
+        };
+        let blocklist = LocalBlocklist::load(&config).unwrap();
+        assert!(!blocklist.reload_if_changed().unwrap());
+
+        let file = std::fs::OpenOptions::new()
+            .append(true)
+            .open(&path)
+            .unwrap();
+        (&file).write_all(b"second\n").unwrap();
+        file.set_modified(SystemTime::now() + Duration::from_secs(10))
+            .unwrap();
+
+        assert!(blocklist.reload_if_changed().unwrap());
+        assert!(
+            blocklist
+                .check_address("second", Chain::Bitcoin)
+                .unwrap()
+                .is_blocklisted
+        );
+    }
+
+    #[test]
+    fn test_load_fails_for_missing_file() {
+        let config = LocalListsConfig {
+            paths: vec![PathBuf::from("/does/not/exist.txt")],
+            reload_interval_secs: 1,
+        };
+        assert!(matches!(
+            LocalBlocklist::load(&config),
+            Err(Error::LocalBlocklist(_))
+        ));
+    }
+}
@@ -1,6 +1,48 @@
 //! This module provides clients for interacting with the various blocklist APIs
 
+use crate::audit::AuditLog;
+use crate::common::error::Error;
+use reqwest::{Client, Response};
+use serde::de::DeserializeOwned;
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+
+/// Cache of risk assessments returned by the remote providers
+pub mod cache;
+/// Blocklist loaded from local files
+pub mod local;
+/// Composite screening policy combining several providers
+pub mod policy;
 /// Client for interacting with the Risk API
 pub mod risk_client;
 /// Client for interacting with the Sanctions API
 pub mod sanctions;
+
+/// Shared state used to screen addresses
+#[derive(Clone, Debug)]
+pub struct ScreeningContext {
+    /// HTTP client used to call the remote providers
+    pub client: Client,
+    /// Blocklist loaded from local files, if configured
+    pub local_blocklist: Option<Arc<local::LocalBlocklist>>,
+    /// Cache of remote risk assessments, if configured
+    pub cache: Option<Arc<cache::AssessmentCache>>,
+    /// Audit log of screening decisions, if configured
+    pub audit_log: Option<Arc<AuditLog>>,
+}
+
+/// Read the body of a provider response and parse it as JSON. Returns the parsed body along with
+/// the hex encoded SHA-256 hash of the raw body.
+pub(crate) async fn read_json_response<T: DeserializeOwned>(
+    response: Response,
+) -> Result<(T, String), Error> {
+    let body = response.bytes().await?;
+    let response_hash = hex::encode(Sha256::digest(&body));
+    match serde_json::from_slice::<T>(&body) {
+        Ok(parsed) => Ok((parsed, response_hash)),
+        Err(e) => match e.classify() {
+            serde_json::error::Category::Data => Err(Error::InvalidApiResponse),
+            _ => Err(Error::Serialization(e.to_string())),
+        },
+    }
+}
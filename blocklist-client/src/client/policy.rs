@@ -0,0 +1,291 @@
+//! This module combines the verdicts of several providers into a single blocklist status according
+//! to the configured screening policy.
+//!
+//! It provides functionality to:
+//! - Screen an address with a single provider
+//! - Screen an address with every provider of the policy and combine their verdicts
+//!
+//! An error from any provider fails the whole screening, so that an address is never accepted
+//! without every configured check having run.
+//!
+//! The remote providers only know about accounts, so Stacks contract principals are screened with
+//! them by the address of their deployer.
+
+use crate::audit::AuditEntry;
+use crate::client::{ScreeningContext, risk_client, sanctions};
+use crate::common::error::Error;
+use crate::common::{BlocklistStatus, Chain, RiskAssessment, RiskSeverity};
+use crate::config::{PolicyConfig, PolicyRule, Provider, Settings};
+use tracing::debug;
+
+/// The provider recorded in the audit log for the combined decision of a policy
+const COMPOSITE_PROVIDER: &str = "composite";
+
+/// The verdict of a single provider
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Verdict {
+    /// The provider that screened the address
+    pub provider: Provider,
+    /// The blocklist status reported by the provider
+    pub status: BlocklistStatus,
+}
+
+impl Verdict {
+    /// Whether this verdict counts as a vote to deny the address under the given rule
+    fn denies(&self, rule: PolicyRule) -> bool {
+        match rule {
+            PolicyRule::AnySevere => self.status.severity.is_severe(),
+            PolicyRule::Quorum => !self.status.accept,
+        }
+    }
+
+    /// Human readable summary of the verdict, such as `sanctions: Severe (sanctions)`
+    fn summary(&self) -> String {
+        match &self.status.reason {
+            Some(reason) => format!("{}: {} ({reason})", self.provider, self.status.severity),
+            None => format!("{}: {}", self.provider, self.status.severity),
+        }
+    }
+}
+
+/// Screen the provided address with a single provider and record the decision in the audit log.
+/// Assessments of the remote providers are served from the cache when possible.
+pub async fn check_provider(
+    provider: Provider,
+    context: &ScreeningContext,
+    config: &Settings,
+    address: &str,
+    chain: Chain,
+) -> Result<BlocklistStatus, Error> {
+    let (status, response_hash, cached) = match provider {
+        Provider::Sanctions | Provider::RiskAnalysis => {
+            let account = chain.account_address(address);
+            let (assessment, cached) = assess_address(provider, context, config, account).await?;
+            let response_hash = assessment.response_hash.clone();
+            (BlocklistStatus::from(assessment), response_hash, cached)
+        }
+        Provider::LocalFile => {
+            let status = context
+                .local_blocklist
+                .as_ref()
+                .ok_or_else(|| Error::LocalBlocklist("local blocklist is not loaded".to_string()))?
+                .check_address(address, chain)?;
+            (status, None, false)
+        }
+    };
+
+    record(
+        context,
+        AuditEntry::new(address, provider, &status, response_hash, cached),
+    )
+    .await?;
+    Ok(status)
+}
+
+/// Get the risk assessment of the address by a remote provider, from the cache if possible.
+/// Returns the assessment and whether it was served from the cache.
+async fn assess_address(
+    provider: Provider,
+    context: &ScreeningContext,
+    config: &Settings,
+    address: &str,
+) -> Result<(RiskAssessment, bool), Error> {
+    if let Some(assessment) = context
+        .cache
+        .as_ref()
+        .and_then(|cache| cache.get(provider, address))
+    {
+        debug!("Using cached {provider} assessment for address {address}");
+        return Ok((assessment, true));
+    }
+
+    let client = &context.client;
+    let assessment = match provider {
+        Provider::Sanctions => {
+            sanctions::assess_address(client, &config.risk_analysis, address).await?
+        }
+        Provider::RiskAnalysis => {
+            risk_client::assess_address(client, &config.risk_analysis, address).await?
+        }
+        Provider::LocalFile => {
+            return Err(Error::LocalBlocklist(
+                "local blocklist has no risk assessments".to_string(),
+            ));
+        }
+    };
+    if let Some(cache) = &context.cache {
+        cache.insert(provider, address, assessment.clone());
+    }
+    Ok((assessment, false))
+}
+
+/// Screen the provided address with every provider of the policy and combine their verdicts. The
+/// verdict of each provider and the combined decision are recorded in the audit log.
+pub async fn check_address(
+    context: &ScreeningContext,
+    config: &Settings,
+    policy: &PolicyConfig,
+    address: &str,
+    chain: Chain,
+) -> Result<BlocklistStatus, Error> {
+    let allowlisted = match &context.local_blocklist {
+        Some(local_blocklist) if policy.allow_list_overrides => {
+            local_blocklist.is_allowlisted(address, chain)?
+        }
+        _ => false,
+    };
+
+    let mut verdicts = Vec::with_capacity(policy.providers.len());
+    for &provider in &policy.providers {
+        let status = check_provider(provider, context, config, address, chain).await?;
+        debug!("Provider {provider} screened address {address}: {status:?}");
+        verdicts.push(Verdict { provider, status });
+    }
+
+    let status = combine(policy, &verdicts, allowlisted);
+    record(
+        context,
+        AuditEntry::new(address, COMPOSITE_PROVIDER, &status, None, false),
+    )
+    .await?;
+    Ok(status)
+}
+
+/// Record a decision in the audit log, if it is configured. A decision that cannot be recorded
+/// fails the screening, so that no decision goes unrecorded.
+async fn record(context: &ScreeningContext, entry: AuditEntry) -> Result<(), Error> {
+    match &context.audit_log {
+        Some(audit_log) => audit_log.record(&entry).await,
+        None => Ok(()),
+    }
+}
+
+/// Combine the verdicts of the providers according to the policy. The address is blocklisted if
+/// the rule denies it and it is not overridden by the allow list. The reported severity is the
+/// highest severity among the verdicts, or `Severe` if the address is blocklisted, and the reason
+/// lists every verdict.
+pub fn combine(policy: &PolicyConfig, verdicts: &[Verdict], allowlisted: bool) -> BlocklistStatus {
+    let denials = verdicts
+        .iter()
+        .filter(|verdict| verdict.denies(policy.rule))
+        .count();
+    let denied = match policy.rule {
+        PolicyRule::AnySevere => denials > 0,
+        PolicyRule::Quorum => denials >= policy.quorum.unwrap_or(verdicts.len()),
+    };
+    let is_blocklisted = denied && !allowlisted;
+
+    let severity = if is_blocklisted {
+        RiskSeverity::Severe
+    } else if allowlisted {
+        RiskSeverity::Low
+    } else {
+        verdicts
+            .iter()
+            .map(|verdict| verdict.status.severity.clone())
+            .max()
+            .unwrap_or(RiskSeverity::Low)
+    };
+
+    let mut reasons: Vec<String> = verdicts.iter().map(Verdict::summary).collect();
+    if allowlisted {
+        reasons.push("allow list override".to_string());
+    }
+
+    BlocklistStatus {
+        is_blocklisted,
+        severity,
+        accept: !is_blocklisted,
+        reason: Some(reasons.join("; ")),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn policy(rule: PolicyRule, quorum: Option<usize>) -> PolicyConfig {
+        PolicyConfig {
+            providers: vec![
+                Provider::Sanctions,
+                Provider::RiskAnalysis,
+                Provider::LocalFile,
+            ],
+            rule,
+            quorum,
+            allow_list_overrides: true,
+        }
+    }
+
+    fn verdict(provider: Provider, severity: RiskSeverity) -> Verdict {
+        let is_blocklisted = severity >= RiskSeverity::High;
+        Verdict {
+            provider,
+            status: BlocklistStatus {
+                is_blocklisted,
+                severity,
+                accept: !is_blocklisted,
+                reason: is_blocklisted.then(|| "flagged".to_string()),
+            },
+        }
+    }
+
+    fn verdicts(severities: [RiskSeverity; 3]) -> Vec<Verdict> {
+        let [sanctions, risk_analysis, local_file] = severities;
+        vec![
+            verdict(Provider::Sanctions, sanctions),
+            verdict(Provider::RiskAnalysis, risk_analysis),
+            verdict(Provider::LocalFile, local_file),
+        ]
+    }
+
+    #[test_case(PolicyRule::AnySevere, None, [RiskSeverity::Low, RiskSeverity::Low, RiskSeverity::Low], false; "any severe none")]
+    #[test_case(PolicyRule::AnySevere, None, [RiskSeverity::Low, RiskSeverity::Severe, RiskSeverity::Low], true; "any severe one")]
+    #[test_case(PolicyRule::AnySevere, None, [RiskSeverity::High, RiskSeverity::High, RiskSeverity::Low], false; "any severe high only")]
+    #[test_case(PolicyRule::Quorum, Some(2), [RiskSeverity::Severe, RiskSeverity::Low, RiskSeverity::Low], false; "two of three one")]
+    #[test_case(PolicyRule::Quorum, Some(2), [RiskSeverity::Severe, RiskSeverity::High, RiskSeverity::Low], true; "two of three two")]
+    fn test_combine(
+        rule: PolicyRule,
+        quorum: Option<usize>,
+        severities: [RiskSeverity; 3],
+        expected_blocklisted: bool,
+    ) {
+        let status = combine(&policy(rule, quorum), &verdicts(severities), false);
+        assert_eq!(status.is_blocklisted, expected_blocklisted);
+        assert_eq!(status.accept, !expected_blocklisted);
+    }
+
+    #[test]
+    fn test_combine_allow_list_overrides() {
+        let verdicts = verdicts([
+            RiskSeverity::Severe,
+            RiskSeverity::Severe,
+            RiskSeverity::Low,
+        ]);
+        let status = combine(&policy(PolicyRule::AnySevere, None), &verdicts, true);
+
+        assert!(!status.is_blocklisted);
+        assert!(status.accept);
+        assert_eq!(status.severity, RiskSeverity::Low);
+        assert_eq!(
+            status.reason.as_deref(),
+            Some(
+                "sanctions: Severe (flagged); risk_analysis: Severe (flagged); local_file: Low; allow list override"
+            )
+        );
+    }
+
+    #[test]
+    fn test_combine_reports_highest_severity() {
+        let verdicts = verdicts([RiskSeverity::Low, RiskSeverity::Medium, RiskSeverity::Low]);
+        let status = combine(&policy(PolicyRule::AnySevere, None), &verdicts, false);
+
+        assert!(!status.is_blocklisted);
+        assert_eq!(status.severity, RiskSeverity::Medium);
+        assert_eq!(
+            status.reason.as_deref(),
+            Some("sanctions: Low; risk_analysis: Moderate; local_file: Low")
+        );
+    }
+}
@@ -7,12 +7,12 @@
 //!
 //! The module includes functions to handle API requests, interpret responses, and map them to application-specific errors.
 
+use crate::client::read_json_response;
 use crate::common::error::Error;
 use crate::common::{BlocklistStatus, RiskAssessment};
 use crate::config::RiskAnalysisConfig;
 use reqwest::{Client, Response, StatusCode};
 use serde::Deserialize;
-use std::error::Error as StdError;
 use tracing::debug;
 const API_BASE_PATH: &str = "/api/risk/v2/entities";
 
@@ -65,57 +65,40 @@ async fn get_risk_assessment(
         .await?;
 
     let checked_response = check_api_response(response).await?;
-    let resp_result = checked_response.json::<RiskAssessment>().await;
-
-    match resp_result {
-        Ok(resp) => Ok(resp),
-        Err(e) if e.is_decode() => {
-            // Check if the source of the error is serde_json::Error
-            if let Some(serde_err) = e
-                .source()
-                .and_then(|cause| cause.downcast_ref::<serde_json::Error>())
-            {
-                match serde_err.classify() {
-                    serde_json::error::Category::Data => Err(Error::InvalidApiResponse),
-                    _ => Err(Error::Serialization(serde_err.to_string())),
-                }
-            } else {
-                Err(Error::Network(e))
-            }
-        }
-        Err(e) => Err(Error::Network(e)),
-    }
+    let (mut assessment, response_hash) =
+        read_json_response::<RiskAssessment>(checked_response).await?;
+    assessment.response_hash = Some(response_hash);
+    Ok(assessment)
 }
 
-/// Screen the provided address for blocklist status after registering it
-/// Marks the address as not accepted if it is identified as high risk
-pub async fn check_address(
+/// Register the provided address with the risk provider and get its risk assessment
+pub async fn assess_address(
     client: &Client,
     config: &RiskAnalysisConfig,
     address: &str,
-) -> Result<BlocklistStatus, Error> {
+) -> Result<RiskAssessment, Error> {
     // First, register the address
     let register_response = register_address(client, config, address).await?;
     debug!("Address registered: {}", register_response.address);
 
     // If registration is successful, proceed to check the address
-    let RiskAssessment { severity, reason } = get_risk_assessment(client, config, address).await?;
+    let assessment = get_risk_assessment(client, config, address).await?;
     debug!(
         "Received risk assessment: Severity = {}, Reason = {:?}",
-        severity, reason
+        assessment.severity, assessment.reason
     );
+    Ok(assessment)
+}
 
-    let is_severe = severity.is_severe();
-    let blocklist_status = BlocklistStatus {
-        // `is_blocklisted` is set to true if risk is Severe
-        is_blocklisted: is_severe,
-        severity,
-        // `accept` is set to false if severity is Severe
-        accept: !is_severe,
-        reason,
-    };
-
-    Ok(blocklist_status)
+/// Screen the provided address for blocklist status after registering it
+/// Marks the address as not accepted if it is identified as high risk
+pub async fn check_address(
+    client: &Client,
+    config: &RiskAnalysisConfig,
+    address: &str,
+) -> Result<BlocklistStatus, Error> {
+    let assessment = assess_address(client, config, address).await?;
+    Ok(BlocklistStatus::from(assessment))
 }
 
 /// Evaluates the HTTP response from an API request and translates HTTP status codes into application-specific errors
@@ -5,12 +5,12 @@
 //!
 //! The module includes functions to handle API requests, interpret responses, and map them to application-specific errors.
 
+use crate::client::read_json_response;
 use crate::common::error::Error;
 use crate::common::{BlocklistStatus, RiskAssessment, RiskSeverity};
 use crate::config::RiskAnalysisConfig;
 use reqwest::{Client, Response, StatusCode};
 use serde::Deserialize;
-use std::error::Error as StdError;
 use tracing::debug;
 const API_BASE_PATH: &str = "/api/v1/address";
 
@@ -57,41 +57,38 @@ async fn get_risk_assessment(
         .send()
         .await?;
     let checked_response = check_api_response(response).await?;
-    let resp_result = checked_response.json::<SanctionsResponse>().await;
+    let (resp, response_hash) = read_json_response::<SanctionsResponse>(checked_response).await?;
     // Currently this client can produce only two risks: Low and Severe. If the response contains any
     // identifications (which mean address is under sanctions), the risk is Severe. Otherwise, it is Low.
-    match resp_result {
-        Ok(resp) => {
-            if resp.identifications.is_empty() {
-                Ok(RiskAssessment {
-                    severity: RiskSeverity::Low,
-                    reason: None,
-                })
-            } else {
-                Ok(RiskAssessment {
-                    severity: RiskSeverity::Severe,
-                    reason: Some("sanctions".to_string()),
-                })
-            }
-        }
-        Err(e) if e.is_decode() => {
-            // Check if the source of the error is serde_json::Error
-            if let Some(serde_err) = e
-                .source()
-                .and_then(|cause| cause.downcast_ref::<serde_json::Error>())
-            {
-                match serde_err.classify() {
-                    serde_json::error::Category::Data => Err(Error::InvalidApiResponse),
-                    _ => Err(Error::Serialization(serde_err.to_string())),
-                }
-            } else {
-                Err(Error::Network(e))
-            }
-        }
-        Err(e) => Err(Error::Network(e)),
+    if resp.identifications.is_empty() {
+        Ok(RiskAssessment {
+            severity: RiskSeverity::Low,
+            reason: None,
+            response_hash: Some(response_hash),
+        })
+    } else {
+        Ok(RiskAssessment {
+            severity: RiskSeverity::Severe,
+            reason: Some("sanctions".to_string()),
+            response_hash: Some(response_hash),
+        })
     }
 }
 
+/// Get the risk assessment of the provided address from the sanctions API
+pub async fn assess_address(
+    client: &Client,
+    config: &RiskAnalysisConfig,
+    address: &str,
+) -> Result<RiskAssessment, Error> {
+    let assessment = get_risk_assessment(client, config, address).await?;
+    debug!(
+        "Received risk assessment: Severity = {}, Reason = {:?}",
+        assessment.severity, assessment.reason
+    );
+    Ok(assessment)
+}
+
 /// Screen the provided address for blocklist status
 /// Marks the address as not accepted if it is identified as high risk
 pub async fn check_address(
@@ -99,22 +96,8 @@ pub async fn check_address(
     config: &RiskAnalysisConfig,
     address: &str,
 ) -> Result<BlocklistStatus, Error> {
-    let RiskAssessment { severity, reason } = get_risk_assessment(client, config, address).await?;
-    debug!(
-        "Received risk assessment: Severity = {}, Reason = {:?}",
-        severity, reason
-    );
-    let is_severe = severity.is_severe();
-    let blocklist_status = BlocklistStatus {
-        // `is_blocklisted` is set to true if risk is Severe
-        is_blocklisted: is_severe,
-        severity,
-        // `accept` is set to false if severity is Severe
-        accept: !is_severe,
-        reason,
-    };
-
-    Ok(blocklist_status)
+    let assessment = assess_address(client, config, address).await?;
+    Ok(BlocklistStatus::from(assessment))
 }
 
 /// Evaluates the HTTP response from an API request and translates HTTP status codes into application-specific errors
@@ -189,6 +172,27 @@ mod tests {
         ));
     }
 
+    #[tokio::test]
+    async fn test_get_risk_assessment_records_response_hash() {
+        let body = r#"{"identifications": []}"#;
+        let mut server = Server::new_async().await;
+        let mock = server
+            .mock("GET", format!("{API_BASE_PATH}/{TEST_ADDRESS}").as_str())
+            .with_status(200)
+            .with_body(body)
+            .create();
+
+        let (client, config) = setup_client(&server);
+
+        let result = get_risk_assessment(&client, &config, TEST_ADDRESS).await;
+
+        mock.assert();
+
+        use sha2::Digest as _;
+        let expected_hash = hex::encode(sha2::Sha256::digest(body.as_bytes()));
+        assert_eq!(result.unwrap().response_hash, Some(expected_hash));
+    }
+
     #[tokio::test]
     async fn test_get_risk_assessment_invalid_response() {
         let mut server = Server::new_async().await;
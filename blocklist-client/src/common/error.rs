@@ -55,6 +55,14 @@ pub enum Error {
     /// Request timeout error
     #[error("Request timeout")]
     RequestTimeout,
+
+    /// The local blocklist files could not be loaded
+    #[error("Local blocklist error: {0}")]
+    LocalBlocklist(String),
+
+    /// The audit log could not be read or written
+    #[error("Audit log error: {0}")]
+    AuditLog(String),
 }
 
 /// Error implementation.
@@ -73,6 +81,8 @@ impl Error {
             Error::InternalServer => StatusCode::INTERNAL_SERVER_ERROR,
             Error::ServiceUnavailable => StatusCode::SERVICE_UNAVAILABLE,
             Error::RequestTimeout => StatusCode::REQUEST_TIMEOUT,
+            Error::LocalBlocklist(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::AuditLog(_) => StatusCode::INTERNAL_SERVER_ERROR,
         }
     }
 
@@ -90,6 +100,8 @@ impl Error {
             Error::InternalServer => "Internal server error".to_string(),
             Error::ServiceUnavailable => "Service unavailable".to_string(),
             Error::RequestTimeout => "Request timeout".to_string(),
+            Error::LocalBlocklist(_) => "Local blocklist unavailable".to_string(),
+            Error::AuditLog(_) => "Audit log unavailable".to_string(),
         }
     }
 }
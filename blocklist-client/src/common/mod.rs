@@ -19,8 +19,89 @@ pub struct BlocklistStatus {
     pub reason: Option<String>,
 }
 
-/// Risk severity linked to an address
+/// The chain an address belongs to
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum Chain {
+    /// A bitcoin address
+    Bitcoin,
+    /// A Stacks standard or contract principal
+    Stacks,
+}
+
+impl fmt::Display for Chain {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Chain::Bitcoin => write!(f, "bitcoin"),
+            Chain::Stacks => write!(f, "stacks"),
+        }
+    }
+}
+
+impl Chain {
+    /// Guess the chain of an address screened without a chain hint. Stacks principals start with
+    /// one of the c32 version prefixes `SP`, `SM`, `ST` or `SN`, which no bitcoin address format
+    /// uses.
+    pub fn detect(address: &str) -> Self {
+        let address = address.trim();
+        if ["SP", "SM", "ST", "SN"]
+            .iter()
+            .any(|prefix| address.starts_with(prefix))
+        {
+            Chain::Stacks
+        } else {
+            Chain::Bitcoin
+        }
+    }
+
+    /// The account address behind the given address. For a Stacks contract principal, such as
+    /// `SP000000000000000000002Q6VF78.pox-4`, this is the standard principal of the deployer.
+    /// Every other address is its own account address.
+    pub fn account_address(self, address: &str) -> &str {
+        match self {
+            Chain::Stacks => address
+                .split_once('.')
+                .map_or(address, |(account, _)| account),
+            Chain::Bitcoin => address,
+        }
+    }
+}
+
+/// Query parameters of a screening request
+#[derive(Debug, Clone, Default, Deserialize, ToSchema)]
+pub struct ScreenQuery {
+    /// The chain of the screened address, detected from the address if unset
+    pub chain: Option<Chain>,
+}
+
+/// Request to check the blocklist status of several addresses at once
 #[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ScreenAddressesRequest {
+    /// The addresses to screen
+    pub addresses: Vec<String>,
+    /// The chain of the screened addresses, detected from each address if unset
+    #[serde(default)]
+    pub chain: Option<Chain>,
+}
+
+/// The blocklist status of one of the addresses of a batch request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ScreenedAddress {
+    /// The screened address
+    pub address: String,
+    /// The blocklist status of the address
+    pub status: BlocklistStatus,
+}
+
+/// Response to a batch request, with the status of each address in the order of the request
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize, ToSchema)]
+pub struct ScreenAddressesResponse {
+    /// The blocklist status of each address
+    pub results: Vec<ScreenedAddress>,
+}
+
+/// Risk severity linked to an address, ordered from lowest to highest risk
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize, ToSchema)]
 pub enum RiskSeverity {
     /// Low risk
     Low,
@@ -55,7 +136,7 @@ impl RiskSeverity {
 }
 
 /// Risk information associated with a wallet address
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Debug, Clone)]
 pub struct RiskAssessment {
     /// The evaluated risk severity for the address
     #[serde(rename = "risk")]
@@ -63,4 +144,38 @@ pub struct RiskAssessment {
     /// The reason for the assigned risk severity
     #[serde(rename = "riskReason")]
     pub reason: Option<String>,
+    /// Hex encoded SHA-256 hash of the raw provider response the assessment was parsed from
+    #[serde(skip)]
+    pub response_hash: Option<String>,
+}
+
+impl From<RiskAssessment> for BlocklistStatus {
+    /// Marks the address as not accepted if it is identified as severe risk
+    fn from(assessment: RiskAssessment) -> Self {
+        let is_severe = assessment.severity.is_severe();
+        BlocklistStatus {
+            // `is_blocklisted` is set to true if risk is Severe
+            is_blocklisted: is_severe,
+            severity: assessment.severity,
+            // `accept` is set to false if severity is Severe
+            accept: !is_severe,
+            reason: assessment.reason,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("SP000000000000000000002Q6VF78", Chain::Stacks; "mainnet standard")]
+    #[test_case("SM2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKQVX8X0G", Chain::Stacks; "mainnet multisig")]
+    #[test_case("ST000000000000000000002AMW42H.pox-4", Chain::Stacks; "testnet contract")]
+    #[test_case("SN2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKP6D2ZK9", Chain::Stacks; "testnet multisig")]
+    #[test_case("bc1qar0srrr7xfkvy5l643lydnw9re59gtzzwf5mdq", Chain::Bitcoin; "bech32")]
+    #[test_case("3J98t1WpEZ73CNmQviecrnyiWrnqRhWNLy", Chain::Bitcoin; "base58")]
+    fn test_detect(address: &str, expected: Chain) {
+        assert_eq!(Chain::detect(address), expected);
+    }
 }
@@ -5,6 +5,7 @@ use serde::Deserialize;
 use std::sync::LazyLock;
 
 use clap::Parser;
+use std::fmt;
 use std::path::PathBuf;
 
 /// Struct which represent command line arguments
@@ -24,10 +25,24 @@ static CLI: LazyLock<Cli> = LazyLock::new(Cli::parse);
 pub struct Settings {
     /// Blocklist client's server related config
     pub server: ServerConfig,
-    /// Blocklist client's risk service config
+    /// Blocklist client's risk service config, not needed for the `local_file` assessment method
+    #[serde(default)]
     pub risk_analysis: RiskAnalysisConfig,
     /// Blocklist client's assessment method config
     pub assessment: AssesmentConfig,
+    /// Blocklist client's local blocklist files config, required for the `local_file` assessment
+    /// method
+    #[serde(default)]
+    pub local_lists: Option<LocalListsConfig>,
+    /// Blocklist client's screening policy config, required for the `composite` assessment method
+    #[serde(default)]
+    pub policy: Option<PolicyConfig>,
+    /// Blocklist client's risk assessment cache config. Assessments are not cached if unset.
+    #[serde(default)]
+    pub cache: Option<CacheConfig>,
+    /// Blocklist client's audit log config. Decisions are not recorded if unset.
+    #[serde(default)]
+    pub audit: Option<AuditConfig>,
 }
 
 /// Blocklist client's assessment method config
@@ -54,10 +69,63 @@ pub enum AssessmentMethod {
     Sanctions,
     /// Use risk analysis API
     RiskAnalysis,
+    /// Use blocklists loaded from local files
+    LocalFile,
+    /// Use several providers and combine their verdicts according to the screening policy
+    Composite,
 }
 
-/// Blocklist client's risk API config
+/// A provider that screens addresses as part of a composite screening policy
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq, Hash)]
+#[serde(rename_all = "snake_case")]
+pub enum Provider {
+    /// Sanctions list API
+    Sanctions,
+    /// Risk analysis API
+    RiskAnalysis,
+    /// Blocklists loaded from local files
+    LocalFile,
+}
+
+impl fmt::Display for Provider {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Provider::Sanctions => write!(f, "sanctions"),
+            Provider::RiskAnalysis => write!(f, "risk_analysis"),
+            Provider::LocalFile => write!(f, "local_file"),
+        }
+    }
+}
+
+/// Rule used to combine the verdicts of the providers of a composite screening policy
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum PolicyRule {
+    /// Deny the address if any provider assesses it as severe risk
+    AnySevere,
+    /// Deny the address if at least `quorum` providers do not accept it
+    Quorum,
+}
+
+/// Blocklist client's composite screening policy config
 #[derive(Deserialize, Clone, Debug)]
+pub struct PolicyConfig {
+    /// Providers that screen every address
+    pub providers: Vec<Provider>,
+    /// Rule used to combine the verdicts of the providers
+    pub rule: PolicyRule,
+    /// Number of providers that must not accept an address for it to be denied, required for the
+    /// `quorum` rule
+    #[serde(default)]
+    pub quorum: Option<usize>,
+    /// Whether addresses on the allow list of the local blocklist files are always accepted,
+    /// whatever the verdicts of the providers
+    #[serde(default)]
+    pub allow_list_overrides: bool,
+}
+
+/// Blocklist client's risk API config
+#[derive(Deserialize, Clone, Debug, Default)]
 pub struct RiskAnalysisConfig {
     /// API URL of the Risk service
     pub api_url: String,
@@ -65,6 +133,54 @@ pub struct RiskAnalysisConfig {
     pub api_key: String,
 }
 
+/// Blocklist client's local blocklist files config
+#[derive(Deserialize, Clone, Debug)]
+pub struct LocalListsConfig {
+    /// Paths of the blocklist files. Files ending in `.xml` or `.csv` are parsed as OFAC SDN
+    /// exports, files ending in `.json` as allow/deny files and any other file as a plain list
+    /// with one address per line.
+    pub paths: Vec<PathBuf>,
+    /// Interval, in seconds, at which the files are checked for changes
+    #[serde(default = "default_reload_interval_secs")]
+    pub reload_interval_secs: u64,
+}
+
+fn default_reload_interval_secs() -> u64 {
+    30
+}
+
+/// Blocklist client's risk assessment cache config
+#[derive(Deserialize, Clone, Debug)]
+pub struct CacheConfig {
+    /// Time, in seconds, for which assessments that blocklist an address are cached. Zero
+    /// disables caching of these assessments.
+    pub positive_ttl_secs: u64,
+    /// Time, in seconds, for which assessments that accept an address are cached. Zero disables
+    /// caching of these assessments.
+    pub negative_ttl_secs: u64,
+    /// Maximum number of cached assessments
+    #[serde(default = "default_cache_max_entries")]
+    pub max_entries: usize,
+}
+
+fn default_cache_max_entries() -> usize {
+    10_000
+}
+
+/// Blocklist client's audit log config
+#[derive(Deserialize, Clone, Debug)]
+pub struct AuditConfig {
+    /// Path of the append-only audit log file
+    pub path: PathBuf,
+    /// Size in bytes past which the audit log file is rotated. Only the last rotated file is kept.
+    #[serde(default = "default_audit_max_size_bytes")]
+    pub max_size_bytes: u64,
+}
+
+fn default_audit_max_size_bytes() -> u64 {
+    64 * 1024 * 1024
+}
+
 /// Statically configured settings for the Blocklist client
 pub static SETTINGS: LazyLock<Settings> = LazyLock::new(|| match &CLI.config {
     Some(path) => {
@@ -79,7 +195,10 @@ impl Settings {
     pub fn new() -> Result<Self, ConfigError> {
         let env = Environment::with_prefix("BLOCKLIST_CLIENT")
             .separator("__")
-            .prefix_separator("_");
+            .prefix_separator("_")
+            .list_separator(",")
+            .with_list_parse_key("local_lists.paths")
+            .with_list_parse_key("policy.providers");
 
         let cfg = Config::builder()
             .add_source(File::from_str(
@@ -100,7 +219,10 @@ impl Settings {
     pub fn new_from_path(path: &str) -> Result<Self, ConfigError> {
         let env = Environment::with_prefix("BLOCKLIST_CLIENT")
             .separator("__")
-            .prefix_separator("_");
+            .prefix_separator("_")
+            .list_separator(",")
+            .with_list_parse_key("local_lists.paths")
+            .with_list_parse_key("policy.providers");
 
         let cfg = Config::builder()
             .add_source(File::with_name(path))
@@ -123,6 +245,90 @@ impl Settings {
                 "Port must be between 1 and 65535".to_string(),
             ));
         }
+        if self.uses_remote_api() && self.risk_analysis.api_url.is_empty() {
+            return Err(ConfigError::Message(
+                "Risk analysis API URL cannot be empty".to_string(),
+            ));
+        }
+        if self.uses_local_lists() {
+            let Some(local_lists) = &self.local_lists else {
+                return Err(ConfigError::Message(
+                    "Local lists must be configured to use local blocklist files".to_string(),
+                ));
+            };
+            if local_lists.paths.is_empty() {
+                return Err(ConfigError::Message(
+                    "Local list paths cannot be empty".to_string(),
+                ));
+            }
+            if local_lists.reload_interval_secs == 0 {
+                return Err(ConfigError::Message(
+                    "Local list reload interval must be positive".to_string(),
+                ));
+            }
+        }
+        if self
+            .cache
+            .as_ref()
+            .is_some_and(|cache| cache.max_entries == 0)
+        {
+            return Err(ConfigError::Message(
+                "Cache max entries must be positive".to_string(),
+            ));
+        }
+        if let AssessmentMethod::Composite = self.assessment.assessment_method {
+            let Some(policy) = &self.policy else {
+                return Err(ConfigError::Message(
+                    "Policy must be configured for the composite assessment method".to_string(),
+                ));
+            };
+            if policy.providers.is_empty() {
+                return Err(ConfigError::Message(
+                    "Policy providers cannot be empty".to_string(),
+                ));
+            }
+            if policy.rule == PolicyRule::Quorum
+                && !policy
+                    .quorum
+                    .is_some_and(|quorum| (1..=policy.providers.len()).contains(&quorum))
+            {
+                return Err(ConfigError::Message(
+                    "Policy quorum must be between 1 and the number of providers".to_string(),
+                ));
+            }
+        }
         Ok(())
     }
+    /// The providers used by the configured assessment method
+    pub fn providers(&self) -> Vec<Provider> {
+        match self.assessment.assessment_method {
+            AssessmentMethod::Sanctions => vec![Provider::Sanctions],
+            AssessmentMethod::RiskAnalysis => vec![Provider::RiskAnalysis],
+            AssessmentMethod::LocalFile => vec![Provider::LocalFile],
+            AssessmentMethod::Composite => self
+                .policy
+                .as_ref()
+                .map(|policy| policy.providers.clone())
+                .unwrap_or_default(),
+        }
+    }
+
+    /// Whether the configured assessment method calls the risk service
+    pub fn uses_remote_api(&self) -> bool {
+        self.providers()
+            .iter()
+            .any(|provider| matches!(provider, Provider::Sanctions | Provider::RiskAnalysis))
+    }
+
+    /// Whether the configured assessment method needs the local blocklist files
+    pub fn uses_local_lists(&self) -> bool {
+        let allow_list_overrides = matches!(
+            self.assessment.assessment_method,
+            AssessmentMethod::Composite
+        ) && self
+            .policy
+            .as_ref()
+            .is_some_and(|policy| policy.allow_list_overrides);
+        allow_list_overrides || self.providers().contains(&Provider::LocalFile)
+    }
 }
@@ -2,6 +2,7 @@
 #![deny(missing_docs)]
 
 pub mod api;
+pub mod audit;
 pub mod client;
 pub mod common;
 pub mod config;
@@ -1,9 +1,16 @@
+use crate::audit::AuditLog;
+use crate::client::ScreeningContext;
+use crate::client::cache::AssessmentCache;
+use crate::client::local::LocalBlocklist;
 use crate::config::SETTINGS;
 use reqwest::Client;
+use std::sync::Arc;
+use std::time::Duration;
 use tracing::info;
 use warp::Filter;
 
 mod api;
+mod audit;
 mod client;
 mod common;
 mod config;
@@ -14,7 +21,35 @@ async fn main() {
 
     let client = Client::new();
 
-    let routes = api::routes::routes(client)
+    let local_blocklist = match &SETTINGS.local_lists {
+        Some(local_lists) if SETTINGS.uses_local_lists() => {
+            let local_blocklist = Arc::new(
+                LocalBlocklist::load(local_lists).expect("Failed to load local blocklist"),
+            );
+            Arc::clone(&local_blocklist)
+                .spawn_reload_task(Duration::from_secs(local_lists.reload_interval_secs));
+            Some(local_blocklist)
+        }
+        _ => None,
+    };
+
+    let cache = SETTINGS
+        .cache
+        .as_ref()
+        .map(|cache| Arc::new(AssessmentCache::new(cache)));
+    let audit_log = SETTINGS
+        .audit
+        .as_ref()
+        .map(|audit| Arc::new(AuditLog::open(audit).expect("Failed to open audit log")));
+
+    let context = ScreeningContext {
+        client,
+        local_blocklist,
+        cache,
+        audit_log,
+    };
+
+    let routes = api::routes::routes(context)
         .recover(api::handlers::handle_rejection)
         .with(warp::log("api"));
 
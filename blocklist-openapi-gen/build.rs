@@ -1,4 +1,4 @@
-use blocklist_client::{api, common};
+use blocklist_client::{api, audit, common};
 use std::fs::File;
 use std::io::Write;
 use utoipa::OpenApi;
@@ -9,11 +9,21 @@ fn main() {
 
 #[derive(utoipa::OpenApi)]
 #[openapi(
-    paths(api::handlers::check_address_handler,),
+    paths(
+        api::handlers::check_address_handler,
+        api::handlers::check_addresses_handler,
+        api::handlers::audit_handler,
+    ),
     components(schemas(
         common::BlocklistStatus,
+        common::Chain,
+        common::ScreenAddressesRequest,
+        common::ScreenAddressesResponse,
+        common::ScreenedAddress,
         common::RiskSeverity,
-        common::error::ErrorResponse
+        common::error::ErrorResponse,
+        audit::AuditEntry,
+        audit::AuditResponse
     ))
 )]
 struct ApiDoc;
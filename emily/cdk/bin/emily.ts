@@ -13,4 +13,5 @@ new EmilyStack(app, 'EmilyStack', {
         region: EmilyStackUtils.getAwsRegion()
     },
     deployerAddress: EmilyStackUtils.getDeployerAddress(),
+    bitcoinRpcUrl: EmilyStackUtils.getBitcoinRpcUrl(),
 });
@@ -16,4 +16,10 @@ export interface EmilyStackProps extends cdk.StackProps {
    * The address of the deployer of the sBTC smart contracts.
    */
   readonly deployerAddress: string;
+
+  /**
+   * The RPC endpoint of the bitcoin node used to check pending deposits. The
+   * pending deposits are not checked when this is not set.
+   */
+  readonly bitcoinRpcUrl?: string;
 }
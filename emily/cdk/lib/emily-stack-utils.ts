@@ -56,6 +56,11 @@ export class EmilyStackUtils {
      */
     private static deployerAddress?: string;
 
+    /*
+     * The RPC endpoint of the bitcoin node used to check pending deposits.
+     */
+    private static bitcoinRpcUrl?: string;
+
     /*
      * Returns the current stage name.
      */
@@ -141,6 +146,15 @@ export class EmilyStackUtils {
         return this.deployerAddress;
     }
 
+    /*
+     * Returns the RPC endpoint of the bitcoin node or undefined if none is set.
+     */
+    public static getBitcoinRpcUrl(): string | undefined {
+        // Treat an empty value, as set by an unset CI secret, as no node.
+        this.bitcoinRpcUrl ??= process.env.BITCOIN_RPC_URL || undefined;
+        return this.bitcoinRpcUrl;
+    }
+
     /*
      * Returns true iff the current stack is a development stack / not a production stack.
      */
@@ -2,6 +2,8 @@
 import * as cdk from 'aws-cdk-lib';
 import * as apig from 'aws-cdk-lib/aws-apigateway';
 import * as dynamodb from 'aws-cdk-lib/aws-dynamodb';
+import * as events from 'aws-cdk-lib/aws-events';
+import * as eventsTargets from 'aws-cdk-lib/aws-events-targets';
 import * as iam from 'aws-cdk-lib/aws-iam';
 import * as lambda from 'aws-cdk-lib/aws-lambda';
 import * as route53 from 'aws-cdk-lib/aws-route53';
@@ -77,12 +79,22 @@ export class EmilyStack extends cdk.Stack {
             pointInTimeRecovery,
         );
 
+        const statsTableId: string = 'StatsTable';
+        const statsTableName: string = EmilyStackUtils.getResourceName(statsTableId, props);
+        const statsTable: dynamodb.Table = this.createOrUpdateStatsTable(
+            statsTableId,
+            statsTableName,
+            persistentResourceRemovalPolicy,
+            pointInTimeRecovery,
+        );
+
         if (!EmilyStackUtils.isTablesOnly()) {
             const operationLambda: lambda.Function = this.createOrUpdateOperationLambda(
                 depositTableName,
                 withdrawalTableName,
                 chainstateTableName,
                 limitTableName,
+                statsTableName,
                 persistentResourceRemovalPolicy,
                 props
             );
@@ -98,11 +110,32 @@ export class EmilyStack extends cdk.Stack {
             withdrawalTable.grantReadWriteData(operationLambda);
             chainstateTable.grantReadWriteData(operationLambda);
             limitTable.grantReadWriteData(operationLambda);
+            statsTable.grantReadWriteData(operationLambda);
 
             const emilyApis: apig.SpecRestApi[] = this.createOrUpdateApi(
                 alias,
                 props,
             );
+
+            // The API only checks pending deposits against bitcoin when it runs as
+            // a long lived server, so the lambda deployment checks them on a schedule.
+            if (props.bitcoinRpcUrl !== undefined) {
+                const depositMonitorLambda: lambda.Function = this.createOrUpdateDepositMonitorLambda(
+                    depositTableName,
+                    withdrawalTableName,
+                    chainstateTableName,
+                    limitTableName,
+                    statsTableName,
+                    props.bitcoinRpcUrl,
+                    props
+                );
+
+                depositTable.grantReadWriteData(depositMonitorLambda);
+                withdrawalTable.grantReadWriteData(depositMonitorLambda);
+                chainstateTable.grantReadWriteData(depositMonitorLambda);
+                limitTable.grantReadWriteData(depositMonitorLambda);
+                statsTable.grantReadWriteData(depositMonitorLambda);
+            }
         }
     }
 
@@ -389,11 +422,42 @@ export class EmilyStack extends cdk.Stack {
         });
     }
 
+    /**
+     * Creates or updates a DynamoDB table for aggregated statistics.
+     * @param {string} tableId The id of the table AWS resource.
+     * @param {string} tableName The name of the DynamoDB table.
+     * @returns {dynamodb.Table} The created or updated DynamoDB table.
+     * @post A DynamoDB table is returned without additional configuration.
+     */
+    createOrUpdateStatsTable(
+        tableId: string,
+        tableName: string,
+        removalPolicy: cdk.RemovalPolicy,
+        pointInTimeRecovery: undefined | boolean,
+    ): dynamodb.Table {
+        return new dynamodb.Table(this, tableId, {
+            tableName: tableName,
+            partitionKey: {
+                name: 'Stat',
+                type: dynamodb.AttributeType.STRING,
+            },
+            sortKey: {
+                name: 'Bucket',
+                type: dynamodb.AttributeType.NUMBER,
+            },
+            removalPolicy: removalPolicy,
+            billingMode: dynamodb.BillingMode.PAY_PER_REQUEST, // On-demand provisioning
+            pointInTimeRecovery: pointInTimeRecovery,
+        });
+    }
+
     /**
      * Creates or updates the operation Lambda function.
      * @param {string} depositTableName The name of the deposit DynamoDB table.
      * @param {string} withdrawalTableName The name of the withdrawal DynamoDB table.
      * @param {string} chainstateTableName The name of the chainstate DynamoDB table.
+     * @param {string} limitTableName The name of the limit DynamoDB table.
+     * @param {string} statsTableName The name of the stats DynamoDB table.
      * @param {EmilyStackProps} props The stack properties.
      * @returns {lambda.Function} The created or updated Lambda function.
      * @post Lambda function with environment variables set and permissions for DynamoDB access is returned.
@@ -403,6 +467,7 @@ export class EmilyStack extends cdk.Stack {
         withdrawalTableName: string,
         chainstateTableName: string,
         limitTableName: string,
+        statsTableName: string,
         removalPolicy: cdk.RemovalPolicy,
         props: EmilyStackProps,
     ): lambda.Function {
@@ -426,6 +491,7 @@ export class EmilyStack extends cdk.Stack {
                 WITHDRAWAL_TABLE_NAME: withdrawalTableName,
                 CHAINSTATE_TABLE_NAME: chainstateTableName,
                 LIMIT_TABLE_NAME: limitTableName,
+                STATS_TABLE_NAME: statsTableName,
                 // Declare an environment variable that will be overwritten in local SAM
                 // deployments the AWS stack. SAM can only set environment variables that are
                 // already expected to be present in the lambda.
@@ -433,6 +499,11 @@ export class EmilyStack extends cdk.Stack {
                 IS_MAINNET: props.stageName == Constants.PROD_STAGE_NAME || props.stageName == Constants.PRIVATE_MAINNET_STAGE_NAME ? "true" : "false",
                 VERSION: EmilyStackUtils.getLambdaGitIdentifier(),
                 DEPLOYER_ADDRESS: props.deployerAddress,
+                // The lambda is only reachable through API Gateway, which appends the
+                // address of the caller to the `X-Forwarded-For` header.
+                BEHIND_TRUSTED_PROXY: "true",
+                // Checks new deposits against the bitcoin node when one is configured.
+                ...(props.bitcoinRpcUrl !== undefined ? { BITCOIN_RPC_URL: props.bitcoinRpcUrl } : {}),
             },
             description: `Emily Api Handler. ${EmilyStackUtils.getLambdaGitIdentifier()}`,
             currentVersionOptions: {
@@ -444,6 +515,69 @@ export class EmilyStack extends cdk.Stack {
         return operationLambda;
     }
 
+    /**
+     * Creates or updates the Lambda function that periodically checks pending deposits
+     * against the bitcoin node, along with the schedule that invokes it.
+     * @param {string} depositTableName The name of the deposit DynamoDB table.
+     * @param {string} withdrawalTableName The name of the withdrawal DynamoDB table.
+     * @param {string} chainstateTableName The name of the chainstate DynamoDB table.
+     * @param {string} limitTableName The name of the limit DynamoDB table.
+     * @param {string} statsTableName The name of the stats DynamoDB table.
+     * @param {string} bitcoinRpcUrl The RPC endpoint of the bitcoin node.
+     * @param {EmilyStackProps} props The stack properties.
+     * @returns {lambda.Function} The created or updated Lambda function.
+     * @post Lambda function invoked every minute by an EventBridge rule is returned.
+     */
+    createOrUpdateDepositMonitorLambda(
+        depositTableName: string,
+        withdrawalTableName: string,
+        chainstateTableName: string,
+        limitTableName: string,
+        statsTableName: string,
+        bitcoinRpcUrl: string,
+        props: EmilyStackProps,
+    ): lambda.Function {
+
+        const depositMonitorLambdaId: string = "DepositMonitorLambda";
+        const depositMonitorLambda: lambda.Function = new lambda.Function(this, depositMonitorLambdaId, {
+            functionName: EmilyStackUtils.getResourceName(depositMonitorLambdaId, props),
+            architecture: lambda.Architecture.X86_64,
+            runtime: lambda.Runtime.PROVIDED_AL2023,
+            code: lambda.Code.fromAsset(EmilyStackUtils.getPathFromProjectRoot(
+                props.stageName === Constants.UNIT_TEST_STAGE_NAME
+                    ? "emily/cdk/test/assets/empty-lambda.zip"
+                    : "target/lambda/emily-deposit-monitor/bootstrap.zip"
+            )),
+            // Each invocation checks a bounded page of deposits, one bitcoin RPC call each.
+            timeout: cdk.Duration.seconds(50),
+            handler: "main",
+            environment: {
+                DEPOSIT_TABLE_NAME: depositTableName,
+                WITHDRAWAL_TABLE_NAME: withdrawalTableName,
+                CHAINSTATE_TABLE_NAME: chainstateTableName,
+                LIMIT_TABLE_NAME: limitTableName,
+                STATS_TABLE_NAME: statsTableName,
+                IS_LOCAL: "false",
+                IS_MAINNET: props.stageName == Constants.PROD_STAGE_NAME || props.stageName == Constants.PRIVATE_MAINNET_STAGE_NAME ? "true" : "false",
+                VERSION: EmilyStackUtils.getLambdaGitIdentifier(),
+                DEPLOYER_ADDRESS: props.deployerAddress,
+                BITCOIN_RPC_URL: bitcoinRpcUrl,
+            },
+            description: `Emily Deposit Monitor. ${EmilyStackUtils.getLambdaGitIdentifier()}`,
+            // Only one invocation at a time so that pages are not checked twice.
+            reservedConcurrentExecutions: 1,
+        });
+
+        const depositMonitorScheduleId: string = "DepositMonitorSchedule";
+        new events.Rule(this, depositMonitorScheduleId, {
+            ruleName: EmilyStackUtils.getResourceName(depositMonitorScheduleId, props),
+            schedule: events.Schedule.rate(cdk.Duration.minutes(1)),
+            targets: [new eventsTargets.LambdaFunction(depositMonitorLambda)],
+        });
+
+        return depositMonitorLambda;
+    }
+
     /**
      * Creates or updates the API Gateway to connect with the Lambda function.
      * @param {lambda.Function} operationLambda The Lambda function to connect to the API.
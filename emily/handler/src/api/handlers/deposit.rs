@@ -1,4 +1,6 @@
 //! Handlers for Deposit endpoints.
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use bitcoin::ScriptBuf;
 use bitcoin::opcodes::all::{self as opcodes};
 use sbtc::deposits::ReclaimScriptInputs;
@@ -21,6 +23,7 @@ use crate::api::models::{
     },
     deposit::responses::GetDepositsResponse,
 };
+use crate::bitcoin_node::BitcoinTxStatus;
 use crate::common::error::Error;
 use crate::context::EmilyContext;
 use crate::database::accessors;
@@ -30,6 +33,7 @@ use crate::database::entries::deposit::{
     DepositEntry, DepositEntryKey, DepositEvent, DepositParametersEntry,
     ValidatedUpdateDepositsRequest,
 };
+use crate::metrics::{DepositRejection, Metrics};
 
 /// Get deposit handler.
 #[utoipa::path(
@@ -309,12 +313,15 @@ pub async fn get_deposits_for_reclaim_pubkeys(
         (status = 400, description = "Invalid request body", body = ErrorResponse),
         (status = 404, description = "Address not found", body = ErrorResponse),
         (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 413, description = "Request body too large", body = ErrorResponse),
+        (status = 429, description = "Too many requests", body = ErrorResponse),
         (status = 500, description = "Internal server error", body = ErrorResponse)
     )
 )]
 #[instrument(skip(context))]
 pub async fn create_deposit(
     context: EmilyContext,
+    client_address: Option<String>,
     body: CreateDepositRequestBody,
 ) -> impl warp::reply::Reply {
     tracing::debug!(
@@ -325,8 +332,34 @@ pub async fn create_deposit(
     // Internal handler so `?` can be used correctly while still returning a reply.
     async fn handler(
         context: EmilyContext,
+        client_address: Option<String>,
         body: CreateDepositRequestBody,
     ) -> Result<impl warp::reply::Reply, Error> {
+        // Reject abusive clients and malformed submissions before doing
+        // any database work.
+        let rate_limiter = &context.deposit_rate_limiter;
+        if !rate_limiter.check_ip(client_address.as_deref()) {
+            Metrics::increment_deposit_rejected(DepositRejection::IpRateLimited);
+            return Err(too_many_requests());
+        }
+
+        let (deposit_info, tx) = body
+            .validate_with_transaction(context.settings.is_mainnet)
+            .inspect_err(|_| {
+                Metrics::increment_deposit_rejected(DepositRejection::InvalidSubmission)
+            })?;
+        let recipient = hex::encode(deposit_info.recipient.serialize_to_vec());
+        let bitcoin_tx_inputs: Vec<String> = tx
+            .input
+            .iter()
+            .map(|input| input.previous_output.to_string())
+            .collect();
+
+        if !rate_limiter.check_recipient(&recipient) {
+            Metrics::increment_deposit_rejected(DepositRejection::RecipientRateLimited);
+            return Err(too_many_requests());
+        }
+
         // Set variables.
         let api_state = accessors::get_api_state(&context).await?;
         api_state.error_if_reorganizing()?;
@@ -335,8 +368,6 @@ pub async fn create_deposit(
         let stacks_block_hash = chaintip.key.hash;
         let stacks_block_height = chaintip.key.height;
 
-        let deposit_info = body.validate(context.settings.is_mainnet)?;
-
         // Check if deposit with such txid and outindex already exists.
         let entry = accessors::get_deposit_entry(
             &context,
@@ -356,6 +387,21 @@ pub async fn create_deposit(
             Err(Error::NotFound) => {}
             Err(e) => return Err(e),
         }
+        // Make sure the transaction was actually broadcast.
+        let bitcoin_block_height = match &context.bitcoin_client {
+            Some(client) => match client.get_output_status(&deposit_info.outpoint).await? {
+                BitcoinTxStatus::NotFound => {
+                    Metrics::increment_deposit_rejected(DepositRejection::TransactionNotFound);
+                    return Err(Error::HttpRequest(
+                        StatusCode::BAD_REQUEST,
+                        "deposit transaction not found in the mempool or chain".to_string(),
+                    ));
+                }
+                BitcoinTxStatus::InMempool => None,
+                BitcoinTxStatus::Confirmed { block_height } => Some(block_height),
+            },
+            None => None,
+        };
         let reclaim_pubkeys_hash = extract_reclaim_pubkeys_hash(&deposit_info.reclaim_script);
         if reclaim_pubkeys_hash.is_none() {
             tracing::warn!(
@@ -370,7 +416,7 @@ pub async fn create_deposit(
                 bitcoin_txid: body.bitcoin_txid,
                 bitcoin_tx_output_index: body.bitcoin_tx_output_index,
             },
-            recipient: hex::encode(deposit_info.recipient.serialize_to_vec()),
+            recipient,
             parameters: DepositParametersEntry {
                 max_fee: deposit_info.max_fee,
                 lock_time: deposit_info.lock_time.to_consensus_u32(),
@@ -388,6 +434,12 @@ pub async fn create_deposit(
             reclaim_script: body.reclaim_script,
             deposit_script: body.deposit_script,
             reclaim_pubkeys_hash,
+            bitcoin_tx_inputs,
+            bitcoin_block_height,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|duration| duration.as_secs()),
             ..Default::default()
         };
         // Validate deposit entry.
@@ -399,11 +451,26 @@ pub async fn create_deposit(
         Ok(with_status(json(&response), StatusCode::CREATED))
     }
     // Handle and respond.
-    handler(context, body)
+    handler(context, client_address, body)
         .await
         .map_or_else(Reply::into_response, Reply::into_response)
 }
 
+/// Rejection of a create deposit request whose body exceeds the size
+/// limit, so that it is counted as a rejected deposit submission.
+#[derive(Debug)]
+pub struct DepositBodyTooLarge;
+
+impl warp::reject::Reject for DepositBodyTooLarge {}
+
+/// The error returned when a deposit submission exceeds a rate limit.
+fn too_many_requests() -> Error {
+    Error::HttpRequest(
+        StatusCode::TOO_MANY_REQUESTS,
+        "too many deposit submissions, try again later".to_string(),
+    )
+}
+
 /// Update deposits handler.
 #[utoipa::path(
     put,
@@ -0,0 +1,198 @@
+//! Handlers for export endpoints.
+use futures::{StreamExt as _, TryStreamExt as _, stream};
+use serde::Serialize;
+use tracing::instrument;
+use warp::http::header::{CONTENT_TYPE, HeaderValue};
+use warp::hyper::Body;
+use warp::reply::{Reply, Response};
+
+use crate::api::models::export::{
+    CsvRecord, DepositExportRecord, ExportFormat, ExportQuery, WithdrawalExportRecord, csv_line,
+};
+use crate::common::error::Error;
+use crate::context::EmilyContext;
+use crate::database::accessors;
+
+/// The header holding the token to get the next page of an export with.
+pub const NEXT_TOKEN_HEADER: &str = "x-next-token";
+
+/// The number of entries fetched concurrently while exporting a page.
+const CONCURRENT_ENTRY_FETCHES: usize = 16;
+
+/// Export deposits handler.
+#[utoipa::path(
+    get,
+    operation_id = "exportDeposits",
+    path = "/export/deposits",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "the format of the export. Defaults to csv."),
+        ("fromStacksHeight" = Option<u64>, Query, description = "minimum stacks block height of the last modification."),
+        ("toStacksHeight" = Option<u64>, Query, description = "maximum stacks block height of the last modification."),
+        ("fromBitcoinHeight" = Option<u64>, Query, description = "minimum bitcoin block height of the last modification."),
+        ("toBitcoinHeight" = Option<u64>, Query, description = "maximum bitcoin block height of the last modification."),
+        ("nextToken" = Option<String>, Query, description = "the next token value from the previous return of this api call."),
+        ("pageSize" = Option<u16>, Query, description = "the maximum number of records in the page. Defaults to 500 and is capped at 1000.")
+    ),
+    tag = "export",
+    responses(
+        (status = 200, description = "Deposits exported successfully", content_type = "text/csv", body = String,
+            headers(("x-next-token" = String, description = "the next token to get the next page with, if there are more records."))),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 404, description = "Bitcoin block not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("ApiGatewayKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn export_deposits(context: EmilyContext, query: ExportQuery) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(context: EmilyContext, query: ExportQuery) -> Result<Response, Error> {
+        let (minimum_height, maximum_height) = stacks_height_range(&context, &query).await?;
+        let (entries, next_token) = accessors::get_deposit_entries_modified_in_height_range(
+            &context,
+            minimum_height,
+            maximum_height,
+            query.next_token.clone(),
+            query.page_size(),
+        )
+        .await?;
+        let records = stream::iter(entries)
+            .map(|info| {
+                let context = &context;
+                async move {
+                    let entry =
+                        accessors::get_deposit_entry(context, &info.primary_index_key).await?;
+                    DepositExportRecord::try_from(entry)
+                }
+            })
+            .buffered(CONCURRENT_ENTRY_FETCHES)
+            .try_collect::<Vec<_>>()
+            .await?;
+        export_response(query.format, records, next_token)
+    }
+    // Handle and respond.
+    handler(context, query)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Export withdrawals handler.
+#[utoipa::path(
+    get,
+    operation_id = "exportWithdrawals",
+    path = "/export/withdrawals",
+    params(
+        ("format" = Option<ExportFormat>, Query, description = "the format of the export. Defaults to csv."),
+        ("fromStacksHeight" = Option<u64>, Query, description = "minimum stacks block height of the last modification."),
+        ("toStacksHeight" = Option<u64>, Query, description = "maximum stacks block height of the last modification."),
+        ("fromBitcoinHeight" = Option<u64>, Query, description = "minimum bitcoin block height of the last modification."),
+        ("toBitcoinHeight" = Option<u64>, Query, description = "maximum bitcoin block height of the last modification."),
+        ("nextToken" = Option<String>, Query, description = "the next token value from the previous return of this api call."),
+        ("pageSize" = Option<u16>, Query, description = "the maximum number of records in the page. Defaults to 500 and is capped at 1000.")
+    ),
+    tag = "export",
+    responses(
+        (status = 200, description = "Withdrawals exported successfully", content_type = "text/csv", body = String,
+            headers(("x-next-token" = String, description = "the next token to get the next page with, if there are more records."))),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 404, description = "Bitcoin block not found", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+    security(("ApiGatewayKey" = []))
+)]
+#[instrument(skip(context))]
+pub async fn export_withdrawals(
+    context: EmilyContext,
+    query: ExportQuery,
+) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(context: EmilyContext, query: ExportQuery) -> Result<Response, Error> {
+        let (minimum_height, maximum_height) = stacks_height_range(&context, &query).await?;
+        let (entries, next_token) = accessors::get_withdrawal_entries_modified_in_height_range(
+            &context,
+            minimum_height,
+            maximum_height,
+            query.next_token.clone(),
+            query.page_size(),
+        )
+        .await?;
+        let records = stream::iter(entries)
+            .map(|info| {
+                let context = &context;
+                async move {
+                    let request_id = info.primary_index_key.request_id;
+                    let entry = accessors::get_withdrawal_entry(context, &request_id).await?;
+                    WithdrawalExportRecord::try_from(entry)
+                }
+            })
+            .buffered(CONCURRENT_ENTRY_FETCHES)
+            .try_collect::<Vec<_>>()
+            .await?;
+        export_response(query.format, records, next_token)
+    }
+    // Handle and respond.
+    handler(context, query)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Validates the query and resolves its height range into an inclusive
+/// range of stacks block heights.
+async fn stacks_height_range(
+    context: &EmilyContext,
+    query: &ExportQuery,
+) -> Result<(u64, u64), Error> {
+    query.validate()?;
+    if query.from_bitcoin_height.is_some() || query.to_bitcoin_height.is_some() {
+        return accessors::get_stacks_height_range_for_bitcoin_heights(
+            context,
+            query.from_bitcoin_height,
+            query.to_bitcoin_height,
+        )
+        .await;
+    }
+    Ok((
+        query.from_stacks_height.unwrap_or(0),
+        query.to_stacks_height.unwrap_or(u64::MAX),
+    ))
+}
+
+/// Makes a response with one line per record in the given format, and
+/// the next token in the `x-next-token` header if there are more records.
+fn export_response<R>(
+    format: ExportFormat,
+    records: Vec<R>,
+    next_token: Option<String>,
+) -> Result<Response, Error>
+where
+    R: CsvRecord + Serialize,
+{
+    let mut body = String::new();
+    if format == ExportFormat::Csv {
+        body.push_str(&csv_line(R::HEADER));
+    }
+    for record in records {
+        match format {
+            ExportFormat::Csv => body.push_str(&csv_line(record.csv_fields())),
+            ExportFormat::Ndjson => {
+                body.push_str(&serde_json::to_string(&record)?);
+                body.push('\n');
+            }
+        }
+    }
+
+    let mut response = Response::new(Body::from(body));
+    let headers = response.headers_mut();
+    headers.insert(
+        CONTENT_TYPE,
+        HeaderValue::from_static(format.content_type()),
+    );
+    if let Some(next_token) = next_token {
+        // Tokens are URL safe base64, so they are always valid header values.
+        let value = HeaderValue::from_str(&next_token).map_err(|_| Error::InternalServer)?;
+        headers.insert(NEXT_TOKEN_HEADER, value);
+    }
+    Ok(response)
+}
@@ -144,9 +144,14 @@ pub async fn execute_reorg_handler(
         for attempt in 0..ENTRY_UPDATE_RETRIES {
             let mut entry =
                 accessors::get_deposit_entry(context, &deposit.primary_index_key).await?;
+            let previous_status = entry.status.clone();
             entry.reorganize_around(&request.canonical_tip)?;
             match accessors::set_deposit_entry(context, &mut entry).await {
-                Ok(_) => break,
+                Ok(_) => {
+                    accessors::record_deposit_status_change(context, &previous_status, &entry)
+                        .await;
+                    break;
+                }
                 Err(Error::VersionConflict(error)) => {
                     warn!(
                         %error,
@@ -186,9 +191,14 @@ pub async fn execute_reorg_handler(
         for attempt in 0..ENTRY_UPDATE_RETRIES {
             let request_id = withdrawal.primary_index_key.request_id;
             let mut entry = accessors::get_withdrawal_entry(context, &request_id).await?;
+            let previous_status = entry.status.clone();
             entry.reorganize_around(&request.canonical_tip)?;
             match accessors::set_withdrawal_entry(context, &mut entry).await {
-                Ok(_) => break,
+                Ok(_) => {
+                    accessors::record_withdrawal_status_change(context, &previous_status, &entry)
+                        .await;
+                    break;
+                }
                 Err(Error::VersionConflict(error)) => {
                     warn!(
                         %error,
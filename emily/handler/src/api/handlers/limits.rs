@@ -2,7 +2,10 @@
 use std::time::SystemTime;
 
 use crate::{
-    api::models::limits::{AccountLimits, Limits},
+    api::models::limits::{
+        AccountLimits, GetLimitsHistoryQuery, GetLimitsHistoryResponse, LimitHistoryEntry, Limits,
+        SetLimitsQuery,
+    },
     common::error::Error,
     context::EmilyContext,
     database::{
@@ -39,13 +42,76 @@ pub async fn get_limits(context: EmilyContext) -> impl warp::reply::Reply {
         .map_or_else(Reply::into_response, Reply::into_response)
 }
 
+/// Get limits history handler.
+#[utoipa::path(
+    get,
+    operation_id = "getLimitsHistory",
+    path = "/limits/history",
+    params(
+        ("account" = Option<String>, Query, description = "the account to get the limit history for. Defaults to the global limits."),
+        ("fromTimestamp" = Option<u64>, Query, description = "only include entries with a unix timestamp, in seconds, on or after this one."),
+        ("toTimestamp" = Option<u64>, Query, description = "only include entries with a unix timestamp, in seconds, on or before this one."),
+        ("nextToken" = Option<String>, Query, description = "the next token value from the previous return of this api call."),
+        ("pageSize" = Option<u16>, Query, description = "the maximum number of items in the response list.")
+    ),
+    tag = "limits",
+    responses(
+        (status = 200, description = "Limits history retrieved successfully", body = GetLimitsHistoryResponse),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+)]
+#[instrument(skip(context))]
+pub async fn get_limits_history(
+    context: EmilyContext,
+    query: GetLimitsHistoryQuery,
+) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        context: EmilyContext,
+        query: GetLimitsHistoryQuery,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        let account = query
+            .account
+            .unwrap_or_else(|| GLOBAL_CAP_ACCOUNT.to_string());
+        let (entries, next_token) = accessors::get_limit_history_for_account(
+            &context,
+            &account,
+            query.from_timestamp.unwrap_or(0),
+            query.to_timestamp.unwrap_or(u64::MAX),
+            query.next_token,
+            query.page_size,
+        )
+        .await?;
+        // Convert data into resource types.
+        let entries: Vec<LimitHistoryEntry> = entries.into_iter().map(Into::into).collect();
+        // Create response.
+        let response = GetLimitsHistoryResponse { next_token, entries };
+        // Respond.
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+    // Handle and respond.
+    handler(context, query)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
 /// Set limits handler.
 /// Note, that `available_to_withdraw` is not settable, but is calculated based on the other fields.
 /// Value of `available_to_withdraw` passed to this endpoint will be ignored.
+///
+/// The new limits may be scheduled to take effect at a future bitcoin block
+/// height or timestamp, in which case the returned limits are the ones
+/// currently in effect.
 #[utoipa::path(
     post,
     operation_id = "setLimits",
     path = "/limits",
+    params(
+        ("effectiveAtBitcoinHeight" = Option<u64>, Query, description = "the bitcoin block height at which the new limits take effect."),
+        ("effectiveAtTimestamp" = Option<u64>, Query, description = "the unix timestamp, in seconds, at which the new limits take effect.")
+    ),
     tag = "limits",
     request_body = Limits,
     responses(
@@ -58,20 +124,28 @@ pub async fn get_limits(context: EmilyContext) -> impl warp::reply::Reply {
     security(("ApiGatewayKey" = []))
 )]
 #[instrument(skip(context))]
-pub async fn set_limits(context: EmilyContext, limits: Limits) -> impl warp::reply::Reply {
+pub async fn set_limits(
+    context: EmilyContext,
+    schedule: SetLimitsQuery,
+    limits: Limits,
+) -> impl warp::reply::Reply {
     // Internal handler so `?` can be used correctly while still returning a reply.
     async fn handler(
         context: EmilyContext,
+        schedule: SetLimitsQuery,
         limits: Limits,
     ) -> Result<impl warp::reply::Reply, Error> {
         // Validate the withdrawal limit configuration.
         limits.validate()?;
+        let (now, bitcoin_height) = accessors::limits_effective_point(&context).await?;
+        schedule.validate(now, bitcoin_height)?;
         // Set the global limits.
         accessors::set_limit_for_account(
             &context,
-            &LimitEntry::from_account_limit(
+            &LimitEntry::from_scheduled_account_limit(
                 GLOBAL_CAP_ACCOUNT.to_string(),
                 SystemTime::now(),
+                &schedule,
                 &AccountLimits {
                     peg_cap: limits.peg_cap,
                     per_deposit_minimum: limits.per_deposit_minimum,
@@ -88,7 +162,12 @@ pub async fn set_limits(context: EmilyContext, limits: Limits) -> impl warp::rep
             .account_caps
             .into_iter()
             .map(|(account, account_limits)| {
-                LimitEntry::from_account_limit(account, SystemTime::now(), &account_limits)
+                LimitEntry::from_scheduled_account_limit(
+                    account,
+                    SystemTime::now(),
+                    &schedule,
+                    &account_limits,
+                )
             })
             .collect::<Vec<LimitEntry>>();
         // Put each entry into the table.
@@ -101,7 +180,7 @@ pub async fn set_limits(context: EmilyContext, limits: Limits) -> impl warp::rep
         Ok(with_status(json(&global_limits), StatusCode::CREATED))
     }
     // Handle and respond.
-    handler(context, limits)
+    handler(context, schedule, limits)
         .await
         .map_or_else(Reply::into_response, Reply::into_response)
 }
@@ -153,6 +232,8 @@ pub async fn get_limits_for_account(
     path = "/limits/{account}",
     params(
         ("account" = String, Path, description = "The account for which to set the limits."),
+        ("effectiveAtBitcoinHeight" = Option<u64>, Query, description = "the bitcoin block height at which the new limits take effect."),
+        ("effectiveAtTimestamp" = Option<u64>, Query, description = "the unix timestamp, in seconds, at which the new limits take effect."),
     ),
     tag = "limits",
     request_body = AccountLimits,
@@ -169,24 +250,32 @@ pub async fn get_limits_for_account(
 pub async fn set_limits_for_account(
     context: EmilyContext,
     account: String,
+    schedule: SetLimitsQuery,
     body: crate::api::models::limits::AccountLimits,
 ) -> impl warp::reply::Reply {
     // Internal handler so `?` can be used correctly while still returning a reply.
     async fn handler(
         context: EmilyContext,
         account: String,
+        schedule: SetLimitsQuery,
         account_limit: crate::api::models::limits::AccountLimits,
     ) -> Result<impl warp::reply::Reply, Error> {
+        let (now, bitcoin_height) = accessors::limits_effective_point(&context).await?;
+        schedule.validate(now, bitcoin_height)?;
         // Create the limit entry.
-        let limit_entry =
-            LimitEntry::from_account_limit(account, SystemTime::now(), &account_limit);
+        let limit_entry = LimitEntry::from_scheduled_account_limit(
+            account,
+            SystemTime::now(),
+            &schedule,
+            &account_limit,
+        );
         // Put entry into the table.
         accessors::set_limit_for_account(&context, &limit_entry).await?;
         // Respond.
         Ok(with_status(json(&account_limit), StatusCode::OK))
     }
     // Handle and respond.
-    handler(context, account, body)
+    handler(context, account, schedule, body)
         .await
         .map_or_else(Reply::into_response, Reply::into_response)
 }
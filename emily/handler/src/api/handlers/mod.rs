@@ -1,6 +1,7 @@
 //! Handlers for the emily API
 
 use crate::common::error::ErrorResponse;
+use crate::metrics::{DepositRejection, Metrics};
 
 use std::convert::Infallible;
 use tracing::error;
@@ -10,6 +11,8 @@ use warp::{Rejection, Reply, http::StatusCode};
 pub mod chainstate;
 /// Deposit handlers.
 pub mod deposit;
+/// Export handlers.
+pub mod export;
 /// Health handlers.
 pub mod health;
 /// Internal handlers.
@@ -18,6 +21,8 @@ pub mod internal;
 pub mod limits;
 /// New block handlers.
 pub mod new_block;
+/// Stats handlers.
+pub mod stats;
 /// Testing handlers.
 #[cfg(feature = "testing")]
 pub mod testing;
@@ -40,6 +45,34 @@ pub async fn handle_rejection(err: Rejection) -> Result<impl Reply, Infallible>
         return Ok(warp::reply::with_status(json, StatusCode::BAD_REQUEST));
     }
 
+    if err.find::<deposit::DepositBodyTooLarge>().is_some() {
+        Metrics::increment_deposit_rejected(DepositRejection::BodyTooLarge);
+        let json = warp::reply::json(&ErrorResponse {
+            message: "Payload Too Large: deposit request body exceeds the size limit".to_string(),
+        });
+        return Ok(warp::reply::with_status(
+            json,
+            StatusCode::PAYLOAD_TOO_LARGE,
+        ));
+    }
+
+    if let Some(e) = err.find::<warp::reject::PayloadTooLarge>() {
+        let json = warp::reply::json(&ErrorResponse {
+            message: format!("Payload Too Large: {e}"),
+        });
+        return Ok(warp::reply::with_status(
+            json,
+            StatusCode::PAYLOAD_TOO_LARGE,
+        ));
+    }
+
+    if let Some(e) = err.find::<warp::reject::LengthRequired>() {
+        let json = warp::reply::json(&ErrorResponse {
+            message: format!("Length Required: {e}"),
+        });
+        return Ok(warp::reply::with_status(json, StatusCode::LENGTH_REQUIRED));
+    }
+
     if let Some(e) = err.find::<warp::reject::MethodNotAllowed>() {
         let json = warp::reply::json(&ErrorResponse {
             message: format!("Method Not Allowed: {e:?}"),
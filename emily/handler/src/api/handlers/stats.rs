@@ -0,0 +1,177 @@
+//! Handlers for stats endpoints.
+use std::collections::BTreeMap;
+
+use crate::{
+    api::models::stats::{
+        DepositStats, GetStatsResponse, GetVolumeQuery, GetVolumeResponse, StatsOperation,
+        VolumeBucket, WithdrawalStats,
+    },
+    common::error::Error,
+    context::EmilyContext,
+    database::{
+        accessors,
+        entries::stats::{
+            OperationKind, StatusCountsEntry, VOLUME_BUCKET_SECONDS, median_confirmation_time,
+        },
+    },
+};
+use tracing::instrument;
+use warp::http::StatusCode;
+use warp::reply::{Reply, json, with_status};
+
+/// Get aggregated statistics handler.
+#[utoipa::path(
+    get,
+    operation_id = "getStats",
+    path = "/stats",
+    tag = "stats",
+    responses(
+        (status = 200, description = "Stats retrieved successfully", body = GetStatsResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+)]
+#[instrument(skip(context))]
+pub async fn get_stats(context: EmilyContext) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(context: EmilyContext) -> Result<impl warp::reply::Reply, Error> {
+        let deposit_counts = accessors::get_status_counts(&context, OperationKind::Deposit).await?;
+        let withdrawal_counts =
+            accessors::get_status_counts(&context, OperationKind::Withdrawal).await?;
+        let deposit_histogram =
+            accessors::get_confirmation_time_histogram(&context, OperationKind::Deposit).await?;
+        let withdrawal_histogram =
+            accessors::get_confirmation_time_histogram(&context, OperationKind::Withdrawal).await?;
+        let peg_cap = accessors::get_limits(&context).await?.peg_cap;
+
+        let needs_rebuild =
+            deposit_counts.has_negative_counter() || withdrawal_counts.has_negative_counter();
+        if needs_rebuild {
+            tracing::error!(
+                ?deposit_counts,
+                ?withdrawal_counts,
+                "negative stats counters; the stats need to be rebuilt"
+            );
+        }
+
+        let deposits = DepositStats {
+            pending: count(deposit_counts.pending),
+            accepted: count(deposit_counts.accepted),
+            confirmed: count(deposit_counts.confirmed),
+            failed: count(deposit_counts.failed),
+            rbf: count(deposit_counts.rbf),
+            confirmed_amount: count(deposit_counts.confirmed_amount),
+            median_seconds_to_confirmation: median_confirmation_time(&deposit_histogram),
+        };
+        let withdrawals = WithdrawalStats {
+            pending: count(withdrawal_counts.pending),
+            accepted: count(withdrawal_counts.accepted),
+            confirmed: count(withdrawal_counts.confirmed),
+            failed: count(withdrawal_counts.failed),
+            confirmed_amount: count(withdrawal_counts.confirmed_amount),
+            median_seconds_to_confirmation: median_confirmation_time(&withdrawal_histogram),
+        };
+        let pegged_amount = pegged_amount(&deposit_counts, &withdrawal_counts);
+        let peg_utilization = peg_cap
+            .filter(|cap| *cap > 0)
+            .map(|cap| pegged_amount as f64 / cap as f64);
+
+        let response = GetStatsResponse {
+            deposits,
+            withdrawals,
+            pegged_amount,
+            peg_cap,
+            peg_utilization,
+            needs_rebuild,
+        };
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+    // Handle and respond.
+    handler(context)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Get volume over time handler.
+#[utoipa::path(
+    get,
+    operation_id = "getVolume",
+    path = "/stats/volume",
+    params(
+        ("operation" = Option<StatsOperation>, Query, description = "the operation to get the volume of. Defaults to deposits."),
+        ("fromTimestamp" = Option<u64>, Query, description = "only include buckets starting on or after this unix timestamp, in seconds."),
+        ("toTimestamp" = Option<u64>, Query, description = "only include buckets starting on or before this unix timestamp, in seconds."),
+        ("bucketSeconds" = Option<u64>, Query, description = "the width of the returned buckets in seconds. Must be a multiple of 3600.")
+    ),
+    tag = "stats",
+    responses(
+        (status = 200, description = "Volume retrieved successfully", body = GetVolumeResponse),
+        (status = 400, description = "Invalid request body", body = ErrorResponse),
+        (status = 405, description = "Method not allowed", body = ErrorResponse),
+        (status = 500, description = "Internal server error", body = ErrorResponse)
+    ),
+)]
+#[instrument(skip(context))]
+pub async fn get_volume(context: EmilyContext, query: GetVolumeQuery) -> impl warp::reply::Reply {
+    // Internal handler so `?` can be used correctly while still returning a reply.
+    async fn handler(
+        context: EmilyContext,
+        query: GetVolumeQuery,
+    ) -> Result<impl warp::reply::Reply, Error> {
+        let bucket_seconds = query.bucket_seconds.unwrap_or(VOLUME_BUCKET_SECONDS);
+        if bucket_seconds == 0 || bucket_seconds % VOLUME_BUCKET_SECONDS != 0 {
+            return Err(Error::HttpRequest(
+                StatusCode::BAD_REQUEST,
+                format!("bucket seconds must be a multiple of {VOLUME_BUCKET_SECONDS}"),
+            ));
+        }
+        let kind = match query.operation {
+            StatsOperation::Deposit => OperationKind::Deposit,
+            StatsOperation::Withdrawal => OperationKind::Withdrawal,
+        };
+        let entries = accessors::get_volume_buckets(
+            &context,
+            kind,
+            query.from_timestamp.unwrap_or(0),
+            query.to_timestamp.unwrap_or(u64::MAX),
+        )
+        .await?;
+
+        // Merge the hourly buckets into buckets of the requested width.
+        let mut buckets: BTreeMap<u64, VolumeBucket> = BTreeMap::new();
+        for entry in entries {
+            let start_timestamp = entry.key.bucket - entry.key.bucket % bucket_seconds;
+            let bucket = buckets.entry(start_timestamp).or_insert(VolumeBucket {
+                start_timestamp,
+                ..Default::default()
+            });
+            bucket.count += entry.count;
+            bucket.amount += entry.amount;
+        }
+
+        let response = GetVolumeResponse {
+            buckets: buckets.into_values().collect(),
+        };
+        Ok(with_status(json(&response), StatusCode::OK))
+    }
+    // Handle and respond.
+    handler(context, query)
+        .await
+        .map_or_else(Reply::into_response, Reply::into_response)
+}
+
+/// Converts a stats counter into a count. A negative counter means that the
+/// stats are out of sync and is flagged by `needs_rebuild`, so it's reported
+/// as zero here.
+fn count(counter: i64) -> u64 {
+    u64::try_from(counter).unwrap_or_default()
+}
+
+/// The amount of sBTC currently pegged in, according to the counters.
+fn pegged_amount(deposits: &StatusCountsEntry, withdrawals: &StatusCountsEntry) -> u64 {
+    count(
+        deposits
+            .confirmed_amount
+            .saturating_sub(withdrawals.confirmed_amount),
+    )
+}
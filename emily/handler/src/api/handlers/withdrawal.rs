@@ -1,4 +1,6 @@
 //! Handlers for withdrawal endpoints.
+use std::time::{SystemTime, UNIX_EPOCH};
+
 use tracing::{debug, instrument};
 use warp::reply::{Reply, json, with_status};
 
@@ -284,6 +286,10 @@ pub async fn create_withdrawal(
             last_update_block_hash: stacks_block_hash,
             last_update_height: stacks_block_height,
             txid,
+            created_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .ok()
+                .map(|duration| duration.as_secs()),
             ..Default::default()
         };
         // Validate withdrawal entry.
@@ -59,6 +59,11 @@ pub struct Deposit {
     /// Transaction ID of the transaction that replaced this one via RBF.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub replaced_by_tx: Option<String>,
+    /// The height of the bitcoin block that confirmed the deposit
+    /// transaction. Only known when Emily validates deposits against a
+    /// bitcoin node.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitcoin_block_height: Option<u64>,
 }
 
 /// Deposit parameters.
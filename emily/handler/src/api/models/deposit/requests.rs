@@ -72,6 +72,16 @@ impl CreateDepositRequestBody {
     /// Validates that the deposit request is valid.
     /// This includes validating the request fields and if their content matches the transaction
     pub fn validate(&self, is_mainnet: bool) -> Result<DepositInfo, Error> {
+        self.validate_with_transaction(is_mainnet)
+            .map(|(deposit_info, _)| deposit_info)
+    }
+
+    /// Validates that the deposit request is valid, returning the decoded
+    /// transaction along with the deposit info.
+    pub fn validate_with_transaction(
+        &self,
+        is_mainnet: bool,
+    ) -> Result<(DepositInfo, Transaction), Error> {
         let deposit_req = CreateDepositRequest {
             outpoint: OutPoint {
                 txid: parse_with_custom_error(
@@ -107,9 +117,10 @@ impl CreateDepositRequestBody {
                 )
             })?;
 
-        deposit_req
+        let deposit_info = deposit_req
             .validate_tx(&tx, is_mainnet)
-            .map_err(|e| Error::HttpRequest(StatusCode::BAD_REQUEST, e.to_string()))
+            .map_err(|e| Error::HttpRequest(StatusCode::BAD_REQUEST, e.to_string()))?;
+        Ok((deposit_info, tx))
     }
 }
 
@@ -0,0 +1,353 @@
+//! Request and response structures for export api calls.
+
+use serde::{Deserialize, Serialize};
+use utoipa::ToSchema;
+
+use crate::api::models::common::{DepositStatus, Fulfillment, WithdrawalStatus};
+use crate::api::models::deposit::Deposit;
+use crate::api::models::withdrawal::Withdrawal;
+use crate::common::error::ValidationError;
+
+/// The format of an export.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum ExportFormat {
+    /// Comma separated values with a header row. The status history is
+    /// encoded as a JSON array in the last column.
+    #[default]
+    Csv,
+    /// One JSON object per line.
+    Ndjson,
+}
+
+impl ExportFormat {
+    /// The content type of an export in this format.
+    pub fn content_type(&self) -> &'static str {
+        match self {
+            ExportFormat::Csv => "text/csv",
+            ExportFormat::Ndjson => "application/x-ndjson",
+        }
+    }
+}
+
+/// The number of records in an export page when no page size is given.
+pub const DEFAULT_EXPORT_PAGE_SIZE: u16 = 500;
+
+/// The maximum number of records in an export page. This keeps a page well
+/// within the response size and time limits of the API gateway.
+pub const MAX_EXPORT_PAGE_SIZE: u16 = 1000;
+
+/// Query structure for exporting deposits or withdrawals modified within a
+/// height range. The range may be given either in stacks block heights or
+/// in bitcoin block heights, but not both. Both ends of the range are
+/// inclusive and open ended if omitted.
+///
+/// The export is paged. When there are more records, the response carries
+/// a next token to pass in the query for the next page.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct ExportQuery {
+    /// The format of the export. Defaults to CSV.
+    #[serde(default)]
+    pub format: ExportFormat,
+    /// Minimum stacks block height of the last modification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_stacks_height: Option<u64>,
+    /// Maximum stacks block height of the last modification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_stacks_height: Option<u64>,
+    /// Minimum bitcoin block height of the last modification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_bitcoin_height: Option<u64>,
+    /// Maximum bitcoin block height of the last modification.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_bitcoin_height: Option<u64>,
+    /// Next token for the search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_token: Option<String>,
+    /// Maximum number of records in the page. Defaults to 500 and is capped
+    /// at 1000.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u16>,
+}
+
+impl ExportQuery {
+    /// Validates that the height range is given in a single kind of height.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        let has_stacks_range = self.from_stacks_height.is_some() || self.to_stacks_height.is_some();
+        let has_bitcoin_range =
+            self.from_bitcoin_height.is_some() || self.to_bitcoin_height.is_some();
+        if has_stacks_range && has_bitcoin_range {
+            return Err(ValidationError::AmbiguousExportRange);
+        }
+        Ok(())
+    }
+
+    /// The number of records to export in the page.
+    pub fn page_size(&self) -> u16 {
+        self.page_size
+            .unwrap_or(DEFAULT_EXPORT_PAGE_SIZE)
+            .clamp(1, MAX_EXPORT_PAGE_SIZE)
+    }
+}
+
+/// A single event in the status history of a deposit.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositHistoryEvent {
+    /// The status of the deposit after the event.
+    pub status: DepositStatus,
+    /// The status message.
+    pub message: String,
+    /// Stacks block height at the time of the event.
+    pub stacks_block_height: u64,
+    /// Stacks block hash associated with the height of the event.
+    pub stacks_block_hash: String,
+}
+
+/// A single event in the status history of a withdrawal.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalHistoryEvent {
+    /// The status of the withdrawal after the event.
+    pub status: WithdrawalStatus,
+    /// The status message.
+    pub message: String,
+    /// Stacks block height at the time of the event.
+    pub stacks_block_height: u64,
+    /// Stacks block hash associated with the height of the event.
+    pub stacks_block_hash: String,
+}
+
+/// An exported deposit.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositExportRecord {
+    /// The deposit.
+    #[serde(flatten)]
+    pub deposit: Deposit,
+    /// The status history of the deposit, oldest first.
+    pub history: Vec<DepositHistoryEvent>,
+}
+
+/// An exported withdrawal.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalExportRecord {
+    /// The withdrawal.
+    #[serde(flatten)]
+    pub withdrawal: Withdrawal,
+    /// The status history of the withdrawal, oldest first.
+    pub history: Vec<WithdrawalHistoryEvent>,
+}
+
+/// A record that can be exported as a row of a CSV file.
+pub trait CsvRecord {
+    /// The names of the columns.
+    const HEADER: &'static [&'static str];
+    /// The fields of the record, in the order of the columns.
+    fn csv_fields(&self) -> Vec<String>;
+}
+
+/// The names of the columns holding fulfillment data.
+const FULFILLMENT_COLUMNS: [&str; 6] = [
+    "fulfillmentBitcoinTxid",
+    "fulfillmentBitcoinTxIndex",
+    "fulfillmentStacksTxid",
+    "fulfillmentBitcoinBlockHash",
+    "fulfillmentBitcoinBlockHeight",
+    "fulfillmentBtcFee",
+];
+
+impl CsvRecord for DepositExportRecord {
+    const HEADER: &'static [&'static str] = &[
+        "bitcoinTxid",
+        "bitcoinTxOutputIndex",
+        "recipient",
+        "amount",
+        "maxFee",
+        "lockTime",
+        "status",
+        "statusMessage",
+        "lastUpdateHeight",
+        "lastUpdateBlockHash",
+        "replacedByTx",
+        "bitcoinBlockHeight",
+        FULFILLMENT_COLUMNS[0],
+        FULFILLMENT_COLUMNS[1],
+        FULFILLMENT_COLUMNS[2],
+        FULFILLMENT_COLUMNS[3],
+        FULFILLMENT_COLUMNS[4],
+        FULFILLMENT_COLUMNS[5],
+        "history",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        let deposit = &self.deposit;
+        let mut fields = vec![
+            deposit.bitcoin_txid.clone(),
+            deposit.bitcoin_tx_output_index.to_string(),
+            deposit.recipient.clone(),
+            deposit.amount.to_string(),
+            deposit.parameters.max_fee.to_string(),
+            deposit.parameters.lock_time.to_string(),
+            json_string(&deposit.status),
+            deposit.status_message.clone(),
+            deposit.last_update_height.to_string(),
+            deposit.last_update_block_hash.clone(),
+            deposit.replaced_by_tx.clone().unwrap_or_default(),
+            optional_field(deposit.bitcoin_block_height),
+        ];
+        fields.extend(fulfillment_fields(deposit.fulfillment.as_ref()));
+        fields.push(serde_json::to_string(&self.history).unwrap_or_default());
+        fields
+    }
+}
+
+impl CsvRecord for WithdrawalExportRecord {
+    const HEADER: &'static [&'static str] = &[
+        "requestId",
+        "stacksBlockHash",
+        "stacksBlockHeight",
+        "recipient",
+        "sender",
+        "amount",
+        "maxFee",
+        "txid",
+        "status",
+        "statusMessage",
+        "lastUpdateHeight",
+        "lastUpdateBlockHash",
+        FULFILLMENT_COLUMNS[0],
+        FULFILLMENT_COLUMNS[1],
+        FULFILLMENT_COLUMNS[2],
+        FULFILLMENT_COLUMNS[3],
+        FULFILLMENT_COLUMNS[4],
+        FULFILLMENT_COLUMNS[5],
+        "history",
+    ];
+
+    fn csv_fields(&self) -> Vec<String> {
+        let withdrawal = &self.withdrawal;
+        let mut fields = vec![
+            withdrawal.request_id.to_string(),
+            withdrawal.stacks_block_hash.clone(),
+            withdrawal.stacks_block_height.to_string(),
+            withdrawal.recipient.clone(),
+            withdrawal.sender.clone(),
+            withdrawal.amount.to_string(),
+            withdrawal.parameters.max_fee.to_string(),
+            withdrawal.txid.clone(),
+            json_string(&withdrawal.status),
+            withdrawal.status_message.clone(),
+            withdrawal.last_update_height.to_string(),
+            withdrawal.last_update_block_hash.clone(),
+        ];
+        fields.extend(fulfillment_fields(withdrawal.fulfillment.as_ref()));
+        fields.push(serde_json::to_string(&self.history).unwrap_or_default());
+        fields
+    }
+}
+
+/// Formats the given fields as a single CSV line, including the trailing
+/// newline.
+pub fn csv_line<I, S>(fields: I) -> String
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    let mut line = fields
+        .into_iter()
+        .map(|field| escape_csv_field(field.as_ref()))
+        .collect::<Vec<_>>()
+        .join(",");
+    line.push('\n');
+    line
+}
+
+/// Quotes a CSV field if it contains a separator, quote or line break.
+fn escape_csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+/// The CSV fields of an optional fulfillment, empty if there is none.
+fn fulfillment_fields(fulfillment: Option<&Fulfillment>) -> Vec<String> {
+    match fulfillment {
+        Some(fulfillment) => vec![
+            fulfillment.bitcoin_txid.clone(),
+            fulfillment.bitcoin_tx_index.to_string(),
+            fulfillment.stacks_txid.clone(),
+            fulfillment.bitcoin_block_hash.clone(),
+            fulfillment.bitcoin_block_height.to_string(),
+            fulfillment.btc_fee.to_string(),
+        ],
+        None => vec![String::new(); FULFILLMENT_COLUMNS.len()],
+    }
+}
+
+/// The CSV field of an optional value, empty if there is none.
+fn optional_field(value: Option<u64>) -> String {
+    value.map(|value| value.to_string()).unwrap_or_default()
+}
+
+/// The JSON string representation of a value, such as a status.
+fn json_string<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value)
+        .ok()
+        .and_then(|value| value.as_str().map(str::to_string))
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(&["a", "b"], "a,b\n"; "plain")]
+    #[test_case(&["a,b", "c"], "\"a,b\",c\n"; "separator")]
+    #[test_case(&["say \"hi\""], "\"say \"\"hi\"\"\"\n"; "quote")]
+    #[test_case(&["", ""], ",\n"; "empty")]
+    fn csv_line_escapes_fields(fields: &[&str], expected: &str) {
+        assert_eq!(csv_line(fields), expected);
+    }
+
+    #[test_case(None, DEFAULT_EXPORT_PAGE_SIZE; "default")]
+    #[test_case(Some(0), 1; "zero")]
+    #[test_case(Some(20), 20; "within bounds")]
+    #[test_case(Some(u16::MAX), MAX_EXPORT_PAGE_SIZE; "capped")]
+    fn export_page_size_is_bounded(page_size: Option<u16>, expected: u16) {
+        let query = ExportQuery {
+            page_size,
+            ..Default::default()
+        };
+        assert_eq!(query.page_size(), expected);
+    }
+
+    #[test]
+    fn deposit_record_fields_match_header() {
+        let record = DepositExportRecord {
+            deposit: Deposit {
+                status: DepositStatus::Confirmed,
+                fulfillment: Some(Fulfillment::default()),
+                ..Default::default()
+            },
+            history: vec![DepositHistoryEvent::default()],
+        };
+        let fields = record.csv_fields();
+        assert_eq!(fields.len(), DepositExportRecord::HEADER.len());
+        assert_eq!(fields[6], "confirmed");
+    }
+
+    #[test]
+    fn withdrawal_record_fields_match_header() {
+        let record = WithdrawalExportRecord::default();
+        let fields = record.csv_fields();
+        assert_eq!(fields.len(), WithdrawalExportRecord::HEADER.len());
+        assert_eq!(fields[8], "pending");
+        assert_eq!(fields.last().unwrap(), "[]");
+    }
+}
@@ -84,3 +84,126 @@ pub struct AccountLimits {
     /// Maximum total sBTC that can be withdrawn within the rolling withdrawal window.
     pub rolling_withdrawal_cap: Option<u64>,
 }
+
+/// Query structure for setting limits that take effect in the future.
+///
+/// At most one of the fields may be set. When neither is set the limits
+/// take effect immediately.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct SetLimitsQuery {
+    /// Bitcoin block height at which the new limits take effect.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_at_bitcoin_height: Option<u64>,
+    /// Unix timestamp, in seconds, at which the new limits take effect.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub effective_at_timestamp: Option<u64>,
+}
+
+impl SetLimitsQuery {
+    /// Validates that the schedule is unambiguous and not in the past,
+    /// given the current unix timestamp and bitcoin block height. A
+    /// bitcoin block height can only be checked when the current height is
+    /// known.
+    pub fn validate(&self, now: u64, bitcoin_height: Option<u64>) -> Result<(), ValidationError> {
+        match (
+            self.effective_at_bitcoin_height,
+            self.effective_at_timestamp,
+        ) {
+            (Some(_), Some(_)) => Err(ValidationError::AmbiguousLimitSchedule),
+            (None, Some(timestamp)) if timestamp < now => Err(ValidationError::PastLimitSchedule),
+            (Some(height), None) if bitcoin_height.is_some_and(|current| height < current) => {
+                Err(ValidationError::PastLimitSchedule)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Query structure for getting the history of an account's limits.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLimitsHistoryQuery {
+    /// The account to get the history for. Defaults to the global limits.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub account: Option<String>,
+    /// Only include entries with a timestamp on or after this unix
+    /// timestamp, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_timestamp: Option<u64>,
+    /// Only include entries with a timestamp on or before this unix
+    /// timestamp, in seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_timestamp: Option<u64>,
+    /// Next token for the search.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub next_token: Option<String>,
+    /// Maximum number of results to show.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub page_size: Option<u16>,
+}
+
+/// A single entry in the history of an account's limits.
+#[derive(
+    Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct LimitHistoryEntry {
+    /// The account the limits apply to.
+    pub account: String,
+    /// Unix timestamp, in seconds, at which the limits were set or, for
+    /// changes scheduled by timestamp, take effect.
+    pub timestamp: u64,
+    /// Bitcoin block height at which the limits take effect, if the
+    /// change was scheduled by bitcoin block height.
+    pub effective_at_bitcoin_height: Option<u64>,
+    /// The limits.
+    pub limits: AccountLimits,
+}
+
+/// Response to a get limits history request.
+#[derive(
+    Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetLimitsHistoryResponse {
+    /// Next token for the search.
+    pub next_token: Option<String>,
+    /// The limit history entries, most recent first.
+    pub entries: Vec<LimitHistoryEntry>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case(None, None, Ok(()); "immediate")]
+    #[test_case(None, Some(100), Ok(()); "now")]
+    #[test_case(None, Some(101), Ok(()); "future timestamp")]
+    #[test_case(None, Some(99), Err(ValidationError::PastLimitSchedule); "past timestamp")]
+    #[test_case(Some(10), None, Ok(()); "current height")]
+    #[test_case(Some(11), None, Ok(()); "future height")]
+    #[test_case(Some(9), None, Err(ValidationError::PastLimitSchedule); "past height")]
+    #[test_case(Some(11), Some(101), Err(ValidationError::AmbiguousLimitSchedule); "ambiguous")]
+    fn set_limits_query_validation(
+        effective_at_bitcoin_height: Option<u64>,
+        effective_at_timestamp: Option<u64>,
+        expected: Result<(), ValidationError>,
+    ) {
+        let query = SetLimitsQuery {
+            effective_at_bitcoin_height,
+            effective_at_timestamp,
+        };
+        assert_eq!(query.validate(100, Some(10)), expected);
+    }
+
+    #[test]
+    fn set_limits_query_accepts_any_height_when_the_height_is_unknown() {
+        let query = SetLimitsQuery {
+            effective_at_bitcoin_height: Some(1),
+            effective_at_timestamp: None,
+        };
+        assert_eq!(query.validate(100, None), Ok(()));
+    }
+}
@@ -6,11 +6,15 @@ pub mod chainstate;
 pub mod common;
 /// Api structures for deposits.
 pub mod deposit;
+/// Api structures for exports.
+pub mod export;
 /// Api structures for health endpoint.
 pub mod health;
 /// Api structures for limits.
 pub mod limits;
 /// Api structures for new block events.
 pub mod new_block;
+/// Api structures for stats.
+pub mod stats;
 /// Api structures for withdrawals.
 pub mod withdrawal;
@@ -0,0 +1,129 @@
+//! Request structures for stats api calls.
+
+use serde::{Deserialize, Serialize};
+use utoipa::{ToResponse, ToSchema};
+
+/// Aggregated statistics about the deposits tracked by Emily.
+#[derive(
+    Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct DepositStats {
+    /// Number of pending deposits.
+    pub pending: u64,
+    /// Number of accepted deposits.
+    pub accepted: u64,
+    /// Number of confirmed deposits.
+    pub confirmed: u64,
+    /// Number of failed deposits.
+    pub failed: u64,
+    /// Number of deposits replaced by fee.
+    pub rbf: u64,
+    /// Total amount of confirmed deposits in satoshis.
+    pub confirmed_amount: u64,
+    /// Approximate median time, in seconds, between Emily receiving a
+    /// deposit and the deposit being confirmed. This is the upper bound
+    /// of the histogram bucket that contains the median.
+    pub median_seconds_to_confirmation: Option<u64>,
+}
+
+/// Aggregated statistics about the withdrawals tracked by Emily.
+#[derive(
+    Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct WithdrawalStats {
+    /// Number of pending withdrawals.
+    pub pending: u64,
+    /// Number of accepted withdrawals.
+    pub accepted: u64,
+    /// Number of confirmed withdrawals.
+    pub confirmed: u64,
+    /// Number of failed withdrawals.
+    pub failed: u64,
+    /// Total amount of confirmed withdrawals in satoshis.
+    pub confirmed_amount: u64,
+    /// Approximate median time, in seconds, between Emily receiving a
+    /// withdrawal and the withdrawal being confirmed. This is the upper
+    /// bound of the histogram bucket that contains the median.
+    pub median_seconds_to_confirmation: Option<u64>,
+}
+
+/// Response to a get stats request.
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize, ToSchema, ToResponse)]
+#[serde(rename_all = "camelCase")]
+pub struct GetStatsResponse {
+    /// Deposit statistics.
+    pub deposits: DepositStats,
+    /// Withdrawal statistics.
+    pub withdrawals: WithdrawalStats,
+    /// The amount of sBTC pegged in, in satoshis, computed as the confirmed
+    /// deposit amount minus the confirmed withdrawal amount.
+    pub pegged_amount: u64,
+    /// The current global peg cap, if there is one.
+    pub peg_cap: Option<u64>,
+    /// The pegged amount as a fraction of the peg cap, if there is one.
+    pub peg_utilization: Option<f64>,
+    /// Whether the stats are out of sync with the deposits and withdrawals
+    /// they count. When set, some counters went negative and are reported
+    /// as zero, and the stats should be rebuilt with `emily-rebuild-stats`.
+    pub needs_rebuild: bool,
+}
+
+/// The sBTC operation to get volume statistics for.
+#[derive(Clone, Copy, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsOperation {
+    /// Deposits.
+    #[default]
+    Deposit,
+    /// Withdrawals.
+    Withdrawal,
+}
+
+/// Query structure for getting the volume of an operation over time.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeQuery {
+    /// The operation to get the volume of. Defaults to deposits.
+    #[serde(default)]
+    pub operation: StatsOperation,
+    /// Only include buckets starting on or after this unix timestamp, in
+    /// seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub from_timestamp: Option<u64>,
+    /// Only include buckets starting on or before this unix timestamp, in
+    /// seconds.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub to_timestamp: Option<u64>,
+    /// The width of the returned buckets in seconds. Must be a multiple of
+    /// one hour, which is also the default.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bucket_seconds: Option<u64>,
+}
+
+/// The volume of an operation within a single time bucket.
+#[derive(
+    Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct VolumeBucket {
+    /// Unix timestamp, in seconds, at which the bucket starts.
+    pub start_timestamp: u64,
+    /// Number of operations created within the bucket.
+    pub count: u64,
+    /// Total amount of the operations created within the bucket in
+    /// satoshis.
+    pub amount: u64,
+}
+
+/// Response to a get volume request.
+#[derive(
+    Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize, ToSchema, ToResponse,
+)]
+#[serde(rename_all = "camelCase")]
+pub struct GetVolumeResponse {
+    /// The volume buckets, oldest first. Buckets without any operations
+    /// are omitted.
+    pub buckets: Vec<VolumeBucket>,
+}
@@ -1,4 +1,6 @@
 //! Route definitions for the deposit endpoint.
+use std::net::SocketAddr;
+
 use warp::Filter;
 
 use crate::context::EmilyContext;
@@ -82,14 +84,62 @@ fn get_deposits_for_reclaim_pubkeys(
 fn create_deposit(
     context: EmilyContext,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    let limits = &context.settings.deposit_submission_limits;
+    let max_body_bytes = limits.max_body_bytes;
+    let behind_trusted_proxy = limits.behind_trusted_proxy;
     warp::any()
         .map(move || context.clone())
         .and(warp::path!("deposit"))
         .and(warp::post())
+        .and(client_address(behind_trusted_proxy))
+        .and(deposit_body_limit(max_body_bytes))
         .and(warp::body::json())
         .then(handlers::deposit::create_deposit)
 }
 
+/// Limits the size of a create deposit request body, tagging oversized
+/// requests so that they are counted as rejected deposit submissions.
+fn deposit_body_limit(
+    max_body_bytes: u64,
+) -> impl Filter<Extract = (), Error = warp::Rejection> + Clone {
+    warp::body::content_length_limit(max_body_bytes).or_else(|rejection: warp::Rejection| async {
+        if rejection.find::<warp::reject::PayloadTooLarge>().is_some() {
+            return Err(warp::reject::custom(handlers::deposit::DepositBodyTooLarge));
+        }
+        Err(rejection)
+    })
+}
+
+/// Extracts the address of the client making the request.
+///
+/// Behind API Gateway the remote address is the gateway itself, which
+/// appends the address of the caller to the end of the `X-Forwarded-For`
+/// header. Earlier entries are supplied by the caller and cannot be
+/// trusted, so only the last entry is used. The header is only used when
+/// Emily is configured to run behind such a trusted proxy, since without
+/// one the caller controls the whole header.
+fn client_address(
+    behind_trusted_proxy: bool,
+) -> impl Filter<Extract = (Option<String>,), Error = warp::Rejection> + Clone {
+    warp::header::optional::<String>("x-forwarded-for")
+        .and(warp::addr::remote())
+        .map(
+            move |forwarded_for: Option<String>, remote: Option<SocketAddr>| {
+                forwarded_for
+                    .filter(|_| behind_trusted_proxy)
+                    .and_then(|header| {
+                        header
+                            .rsplit(',')
+                            .next()
+                            .map(str::trim)
+                            .filter(|address| !address.is_empty())
+                            .map(str::to_string)
+                    })
+                    .or_else(|| remote.map(|address| address.ip().to_string()))
+            },
+        )
+}
+
 /// Update deposits from signer endpoint.
 fn update_deposits_signer(
     context: EmilyContext,
@@ -115,3 +165,31 @@ fn update_deposits_sidecar(
 }
 
 // TODO(387): Add route unit tests.
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const REMOTE: &str = "10.0.0.1:4000";
+
+    async fn extract_client_address(behind_trusted_proxy: bool) -> Option<String> {
+        warp::test::request()
+            .remote_addr(REMOTE.parse().unwrap())
+            .header("x-forwarded-for", "1.1.1.1, 2.2.2.2")
+            .filter(&client_address(behind_trusted_proxy))
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn client_address_uses_forwarded_for_behind_trusted_proxy() {
+        let address = extract_client_address(true).await;
+        assert_eq!(address.as_deref(), Some("2.2.2.2"));
+    }
+
+    #[tokio::test]
+    async fn client_address_ignores_forwarded_for_without_trusted_proxy() {
+        let address = extract_client_address(false).await;
+        assert_eq!(address.as_deref(), Some("10.0.0.1"));
+    }
+}
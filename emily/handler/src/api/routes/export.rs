@@ -0,0 +1,38 @@
+//! Route definitions for the export endpoint.
+
+use warp::Filter;
+
+use crate::context::EmilyContext;
+
+use super::handlers;
+
+/// Export routes.
+pub fn routes(
+    context: EmilyContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    export_deposits(context.clone()).or(export_withdrawals(context))
+}
+
+/// Export deposits endpoint.
+fn export_deposits(
+    context: EmilyContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::any()
+        .map(move || context.clone())
+        .and(warp::path!("export" / "deposits"))
+        .and(warp::get())
+        .and(warp::query())
+        .then(handlers::export::export_deposits)
+}
+
+/// Export withdrawals endpoint.
+fn export_withdrawals(
+    context: EmilyContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::any()
+        .map(move || context.clone())
+        .and(warp::path!("export" / "withdrawals"))
+        .and(warp::get())
+        .and(warp::query())
+        .then(handlers::export::export_withdrawals)
+}
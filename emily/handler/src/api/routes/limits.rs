@@ -11,6 +11,9 @@ pub fn routes(
     context: EmilyContext,
 ) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
     get_limits(context.clone())
+        // The history route must come before the account route so that
+        // `history` isn't interpreted as an account.
+        .or(get_limits_history(context.clone()))
         .or(set_limits(context.clone()))
         .or(set_limits_for_account(context.clone()))
         .or(get_limits_for_account(context))
@@ -27,6 +30,18 @@ fn get_limits(
         .then(handlers::limits::get_limits)
 }
 
+/// Get limits history endpoint.
+fn get_limits_history(
+    context: EmilyContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::any()
+        .map(move || context.clone())
+        .and(warp::path!("limits" / "history"))
+        .and(warp::get())
+        .and(warp::query())
+        .then(handlers::limits::get_limits_history)
+}
+
 /// Set limits endpoint.
 fn set_limits(
     context: EmilyContext,
@@ -35,6 +50,7 @@ fn set_limits(
         .map(move || context.clone())
         .and(warp::path!("limits"))
         .and(warp::post())
+        .and(warp::query())
         .and(warp::body::json())
         .then(handlers::limits::set_limits)
 }
@@ -47,6 +63,7 @@ fn set_limits_for_account(
         .map(move || context.clone())
         .and(warp::path!("limits" / String))
         .and(warp::post())
+        .and(warp::query())
         .and(warp::body::json())
         .then(handlers::limits::set_limits_for_account)
 }
@@ -10,12 +10,16 @@ use warp::Filter;
 mod chainstate;
 /// Deposit routes.
 mod deposit;
+/// Export routes.
+mod export;
 /// Health routes.
 mod health;
 /// Limit routes.
 mod limits;
 /// NewBlock routes.
 mod new_block;
+/// Stats routes.
+mod stats;
 /// Testing routes.
 #[cfg(feature = "testing")]
 mod testing;
@@ -55,6 +59,10 @@ pub fn routes(
         .boxed()
         .or(limits::routes(context.clone()))
         .boxed()
+        .or(stats::routes(context.clone()))
+        .boxed()
+        .or(export::routes(context.clone()))
+        .boxed()
         .or(testing::routes(context))
         .boxed()
         .or(verbose_not_found_route())
@@ -78,7 +86,11 @@ pub fn routes(
         .boxed()
         .or(withdrawal::routes(context.clone()))
         .boxed()
-        .or(limits::routes(context))
+        .or(limits::routes(context.clone()))
+        .boxed()
+        .or(stats::routes(context.clone()))
+        .boxed()
+        .or(export::routes(context))
         .boxed()
         // Convert reply to tuple to that more routes can be added to the returned filter.
         .map(|reply| (reply,))
@@ -0,0 +1,37 @@
+//! Route definitions for the stats endpoint.
+
+use warp::Filter;
+
+use crate::context::EmilyContext;
+
+use super::handlers;
+
+/// Stats routes.
+pub fn routes(
+    context: EmilyContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    get_stats(context.clone()).or(get_volume(context))
+}
+
+/// Get stats endpoint.
+fn get_stats(
+    context: EmilyContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::any()
+        .map(move || context.clone())
+        .and(warp::path!("stats"))
+        .and(warp::get())
+        .then(handlers::stats::get_stats)
+}
+
+/// Get volume endpoint.
+fn get_volume(
+    context: EmilyContext,
+) -> impl Filter<Extract = impl warp::Reply, Error = warp::Rejection> + Clone {
+    warp::any()
+        .map(move || context.clone())
+        .and(warp::path!("stats" / "volume"))
+        .and(warp::get())
+        .and(warp::query())
+        .then(handlers::stats::get_volume)
+}
@@ -0,0 +1,48 @@
+//! Emily deposit monitor lambda entrypoint.
+//!
+//! The lambda is invoked on a schedule and checks a page of pending
+//! deposits against the bitcoin node on every invocation, marking double
+//! spent deposits as failed. Warm invocations carry on from the page where
+//! the previous invocation stopped, so that every pending deposit is
+//! eventually checked even when there are more than fit in one page.
+
+use std::sync::Arc;
+
+use emily_handler::bitcoin_node;
+use emily_handler::context::EmilyContext;
+use emily_handler::logging;
+use tokio::sync::Mutex;
+use tracing::info;
+use warp_lambda::lambda_http::lambda_runtime::{self, LambdaEvent, service_fn};
+
+#[tokio::main]
+async fn main() -> Result<(), lambda_runtime::Error> {
+    // Setup logging.
+    logging::setup_logging("info,emily_handler=debug", false);
+
+    // Setup context.
+    let context: EmilyContext = EmilyContext::from_env()
+        .await
+        .unwrap_or_else(|e| panic!("{e}"));
+    info!(lambdaContext = ?context);
+    let client = context
+        .bitcoin_client
+        .clone()
+        .expect("BITCOIN_RPC_URL must be set for the deposit monitor");
+
+    // The token of the next page of pending deposits to check.
+    let next_token: Arc<Mutex<Option<String>>> = Arc::default();
+
+    lambda_runtime::run(service_fn(|_: LambdaEvent<serde_json::Value>| {
+        let context = context.clone();
+        let client = client.clone();
+        let next_token = Arc::clone(&next_token);
+        async move {
+            let mut next_token = next_token.lock().await;
+            let token = next_token.take();
+            *next_token = bitcoin_node::check_pending_deposits(&context, &client, token).await?;
+            Ok::<_, emily_handler::common::error::Error>(())
+        }
+    }))
+    .await
+}
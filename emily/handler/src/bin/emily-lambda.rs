@@ -25,6 +25,7 @@ async fn main() {
         .allow_any_origin()
         .allow_methods(vec!["GET", "POST", "OPTIONS"])
         .allow_headers(vec!["content-type", "x-api-key"])
+        .expose_headers(vec![api::handlers::export::NEXT_TOKEN_HEADER])
         .build();
 
     // Setup service filters.
@@ -0,0 +1,29 @@
+//! Emily stats rebuild command.
+//!
+//! Recounts the status counters and volume buckets in the stats table from
+//! the deposit and withdrawal tables. Run it once after the stats table is
+//! first deployed to backfill the existing operations, and again whenever
+//! `GET /stats` reports that the stats need to be rebuilt. It reads the same
+//! environment as the Emily lambda and should be run while Emily isn't
+//! receiving updates.
+
+use emily_handler::context::EmilyContext;
+use emily_handler::database::accessors;
+use emily_handler::logging;
+use tracing::info;
+
+#[tokio::main]
+async fn main() -> Result<(), emily_handler::common::error::Error> {
+    // Setup logging.
+    logging::setup_logging("info,emily_handler=debug", false);
+
+    // Setup context.
+    let context: EmilyContext = EmilyContext::from_env()
+        .await
+        .unwrap_or_else(|e| panic!("{e}"));
+    info!(lambdaContext = ?context);
+
+    accessors::rebuild_stats(&context).await?;
+    info!("stats rebuilt successfully");
+    Ok(())
+}
@@ -1,7 +1,10 @@
 //! Emily Warp Service Binary.
 
+use std::time::Duration;
+
 use clap::Args;
 use clap::Parser;
+use emily_handler::bitcoin_node;
 use emily_handler::context::EmilyContext;
 use tracing::info;
 use warp::Filter as _;
@@ -38,6 +41,16 @@ pub struct GeneralArgs {
     /// DynamoDB endpoint.
     #[arg(long, default_value = "http://localhost:8000")]
     pub dynamodb_endpoint: String,
+    /// Bitcoin node RPC endpoint, including credentials, used to validate
+    /// deposits. Deposits are not checked against a bitcoin node if unset.
+    /// The node must be running with `txindex=1`, otherwise pending
+    /// deposits are not checked for double spends.
+    #[arg(long)]
+    pub bitcoin_rpc_url: Option<String>,
+    /// How often, in seconds, pending deposits are checked against the
+    /// bitcoin node for double spends.
+    #[arg(long, default_value = "60")]
+    pub bitcoin_monitor_interval_seconds: u64,
 }
 
 /// Server related arguments.
@@ -62,6 +75,8 @@ async fn main() {
                 pretty_logs,
                 log_directives,
                 dynamodb_endpoint,
+                bitcoin_rpc_url,
+                bitcoin_monitor_interval_seconds,
             },
     } = Cli::parse();
 
@@ -70,16 +85,32 @@ async fn main() {
 
     // Setup context.
     // TODO(389 + 358): Handle config pickup in a way that will only fail for the relevant call.
-    let context: EmilyContext = EmilyContext::local_instance(&dynamodb_endpoint)
+    let mut context: EmilyContext = EmilyContext::local_instance(&dynamodb_endpoint)
         .await
         .unwrap();
+    if let Some(bitcoin_rpc_url) = bitcoin_rpc_url {
+        context = context
+            .with_bitcoin_rpc_url(&bitcoin_rpc_url)
+            .expect("Failed to set up the bitcoin RPC client");
+    }
     info!(lambdaContext = ?context);
 
+    // Watch pending deposits for double spends.
+    if let Some(client) = context.bitcoin_client.clone() {
+        let interval = Duration::from_secs(bitcoin_monitor_interval_seconds);
+        tokio::spawn(bitcoin_node::monitor_pending_deposits(
+            context.clone(),
+            client,
+            interval,
+        ));
+    }
+
     // Create CORS configuration
     let cors = warp::cors()
         .allow_any_origin()
         .allow_methods(vec!["GET", "POST", "OPTIONS"])
         .allow_headers(vec!["content-type", "x-api-key"])
+        .expose_headers(vec![api::handlers::export::NEXT_TOKEN_HEADER])
         .build();
 
     let routes = api::routes::routes(context)
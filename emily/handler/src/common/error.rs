@@ -64,6 +64,24 @@ pub enum ValidationError {
     )]
     InvalidReplacedByTxStatus(DepositStatus, String, u32),
 
+    /// A limit change was scheduled by both bitcoin block height and
+    /// timestamp.
+    #[error(
+        "ambiguous limit schedule: only one of effective_at_bitcoin_height and effective_at_timestamp may be provided"
+    )]
+    AmbiguousLimitSchedule,
+
+    /// A limit change was scheduled for a bitcoin block height or
+    /// timestamp that has already passed.
+    #[error("limit schedule is in the past: limit changes can only be scheduled for the future")]
+    PastLimitSchedule,
+
+    /// Both a stacks and a bitcoin height range were given for an export.
+    #[error(
+        "ambiguous export range: only one of a stacks height range and a bitcoin height range may be provided"
+    )]
+    AmbiguousExportRange,
+
     /// The deposit has status RBF but is missing the replaced_by_tx field.
     #[error("missing replaced_by_tx for RBF deposit with txid: {0}, vout: {1}")]
     DepositMissingReplacementTx(String, u32),
@@ -140,6 +158,21 @@ pub enum Error {
     #[error("Mismatch when updating withdrawal request; existing: {0}; update: {1}")]
     WithdrawalRequestIdMismatch(WithdrawalEntryKey, u64),
 
+    /// This happens when the configured bitcoin RPC endpoint could not be
+    /// parsed as a URL.
+    #[error("Invalid bitcoin RPC URL: {0}")]
+    InvalidBitcoinRpcUrl(String),
+
+    /// This happens when a call to the bitcoin node fails or returns an
+    /// unexpected response.
+    #[error("Bitcoin RPC call {0} failed: {1}")]
+    BitcoinRpc(&'static str, String),
+
+    /// This happens when the configured bitcoin node does not maintain a
+    /// transaction index, which is needed to find confirmed transactions.
+    #[error("Bitcoin node is not running with txindex=1")]
+    BitcoinTxindexUnavailable,
+
     /// This means that the stacks address in the environment for the
     /// signers multisig address is invalid.
     #[error("Could not parse a stacks address from a string")]
@@ -249,6 +282,9 @@ impl Error {
             Error::Reorganizing(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::VersionConflict(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::Deserialization(_) => StatusCode::BAD_REQUEST,
+            Error::InvalidBitcoinRpcUrl(_) => StatusCode::INTERNAL_SERVER_ERROR,
+            Error::BitcoinRpc(_, _) => StatusCode::BAD_GATEWAY,
+            Error::BitcoinTxindexUnavailable => StatusCode::INTERNAL_SERVER_ERROR,
             Error::InvalidStacksAddress(_) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::InvalidDepositEntry(_, _) => StatusCode::INTERNAL_SERVER_ERROR,
             Error::DepositOutputMismatch(_, _) => StatusCode::INTERNAL_SERVER_ERROR,
@@ -2,6 +2,8 @@
 
 /// Api errors.
 pub mod error;
+/// Rate limiting for public endpoints.
+pub mod rate_limit;
 
 /// 6 block confirmations are considered as industry standard for considering that this block
 /// will not be reorged. See https://en.bitcoin.it/wiki/Confirmation
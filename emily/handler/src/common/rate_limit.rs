@@ -0,0 +1,207 @@
+//! In-memory rate limiting for public Emily endpoints.
+//!
+//! The limiter state lives in the memory of a single server or lambda
+//! instance, so the limits are enforced per instance rather than
+//! globally. That is good enough to take the edge off of abusive clients
+//! without adding a database round trip to every request.
+
+use std::collections::HashMap;
+use std::collections::VecDeque;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
+
+/// The maximum number of keys tracked by a limiter. When it is reached,
+/// expired keys are pruned and, if that is not enough, the keys with the
+/// oldest requests are evicted, which resets their budget.
+const MAX_TRACKED_KEYS: usize = 10_000;
+
+/// A sliding window rate limiter keyed by an arbitrary string.
+#[derive(Debug, Clone)]
+pub struct RateLimiter {
+    /// The maximum number of requests allowed per key within `window`.
+    max_requests: u32,
+    /// The length of the sliding window.
+    window: Duration,
+    /// The maximum number of keys tracked at once.
+    max_tracked_keys: usize,
+    /// The times of the accepted requests for each key, oldest first.
+    state: Arc<Mutex<HashMap<String, VecDeque<Instant>>>>,
+}
+
+impl RateLimiter {
+    /// Create a new rate limiter allowing `max_requests` per key within
+    /// each `window`.
+    pub fn new(max_requests: u32, window: Duration) -> Self {
+        Self {
+            max_requests,
+            window,
+            max_tracked_keys: MAX_TRACKED_KEYS,
+            state: Arc::new(Mutex::new(HashMap::new())),
+        }
+    }
+
+    /// Record a request for the given key, returning whether it is within
+    /// the limit. Requests that exceed the limit are not recorded.
+    pub fn check(&self, key: &str) -> bool {
+        self.check_at(key, Instant::now())
+    }
+
+    fn check_at(&self, key: &str, now: Instant) -> bool {
+        // A poisoned lock only means another thread panicked while holding
+        // it; the timestamps are still usable.
+        let mut state = self.state.lock().unwrap_or_else(|err| err.into_inner());
+
+        if state.len() >= self.max_tracked_keys && !state.contains_key(key) {
+            self.prune(&mut state, now);
+        }
+
+        let times = state.entry(key.to_string()).or_default();
+        while times
+            .front()
+            .is_some_and(|first| now.duration_since(*first) >= self.window)
+        {
+            times.pop_front();
+        }
+
+        if times.len() >= self.max_requests as usize {
+            return false;
+        }
+        times.push_back(now);
+        true
+    }
+
+    /// Make room for new keys. Keys without requests in the window are
+    /// removed first. If the state is still over 90% of its capacity, the
+    /// keys whose last request is the oldest are evicted until it is not,
+    /// so that pruning does not run again on every new key.
+    fn prune(&self, state: &mut HashMap<String, VecDeque<Instant>>, now: Instant) {
+        state.retain(|_, times| {
+            times
+                .back()
+                .is_some_and(|last| now.duration_since(*last) < self.window)
+        });
+
+        let target = self.max_tracked_keys - self.max_tracked_keys / 10;
+        if state.len() <= target {
+            return;
+        }
+        let mut last_requests: Vec<(Instant, String)> = state
+            .iter()
+            .filter_map(|(key, times)| times.back().map(|last| (*last, key.clone())))
+            .collect();
+        last_requests.sort_unstable();
+        let excess = state.len() - target;
+        for (_, key) in last_requests.into_iter().take(excess) {
+            state.remove(&key);
+        }
+    }
+}
+
+/// Rate limiters applied to the public create deposit endpoint.
+#[derive(Debug, Clone, Default)]
+pub struct DepositRateLimiter {
+    /// Limiter keyed by the address of the submitting client.
+    pub per_ip: Option<RateLimiter>,
+    /// Limiter keyed by the stacks recipient of the deposit.
+    pub per_recipient: Option<RateLimiter>,
+}
+
+impl DepositRateLimiter {
+    /// Record a submission from the given client address, returning
+    /// whether it is within the limit. Submissions without a known client
+    /// address are always allowed.
+    pub fn check_ip(&self, client_address: Option<&str>) -> bool {
+        match (&self.per_ip, client_address) {
+            (Some(limiter), Some(address)) => limiter.check(address),
+            _ => true,
+        }
+    }
+
+    /// Record a submission for the given recipient, returning whether it
+    /// is within the limit.
+    pub fn check_recipient(&self, recipient: &str) -> bool {
+        self.per_recipient
+            .as_ref()
+            .is_none_or(|limiter| limiter.check(recipient))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limiter_rejects_requests_over_the_limit() {
+        let limiter = RateLimiter::new(2, Duration::from_secs(60));
+        let now = Instant::now();
+
+        assert!(limiter.check_at("a", now));
+        assert!(limiter.check_at("a", now));
+        assert!(!limiter.check_at("a", now));
+        // Other keys have their own budget.
+        assert!(limiter.check_at("b", now));
+    }
+
+    #[test]
+    fn limiter_allows_requests_after_the_window_passes() {
+        let window = Duration::from_secs(60);
+        let limiter = RateLimiter::new(1, window);
+        let now = Instant::now();
+
+        assert!(limiter.check_at("a", now));
+        assert!(!limiter.check_at("a", now + window / 2));
+        assert!(limiter.check_at("a", now + window));
+    }
+
+    #[test]
+    fn limiter_evicts_the_oldest_keys_when_full() {
+        let window = Duration::from_secs(60);
+        let limiter = RateLimiter {
+            max_tracked_keys: 20,
+            ..RateLimiter::new(1, window)
+        };
+        let now = Instant::now();
+
+        for i in 0..20 {
+            let at = now + Duration::from_millis(i);
+            assert!(limiter.check_at(&i.to_string(), at));
+        }
+        // All keys are live, so a new key evicts the oldest ones instead
+        // of growing the state.
+        let later = now + Duration::from_secs(1);
+        assert!(limiter.check_at("new", later));
+        let state = limiter.state.lock().unwrap();
+        assert_eq!(state.len(), 19);
+        assert!(!state.contains_key("0"));
+        assert!(!state.contains_key("1"));
+        assert!(state.contains_key("2"));
+        assert!(state.contains_key("new"));
+        drop(state);
+
+        // The live keys that were kept are still limited.
+        assert!(!limiter.check_at("19", later));
+    }
+
+    #[test]
+    fn deposit_limiter_without_limits_allows_everything() {
+        let limiter = DepositRateLimiter::default();
+        for _ in 0..100 {
+            assert!(limiter.check_ip(Some("127.0.0.1")));
+            assert!(limiter.check_recipient("recipient"));
+        }
+    }
+
+    #[test]
+    fn deposit_limiter_ignores_unknown_client_addresses() {
+        let limiter = DepositRateLimiter {
+            per_ip: Some(RateLimiter::new(1, Duration::from_secs(60))),
+            per_recipient: None,
+        };
+        assert!(limiter.check_ip(Some("127.0.0.1")));
+        assert!(!limiter.check_ip(Some("127.0.0.1")));
+        assert!(limiter.check_ip(None));
+        assert!(limiter.check_ip(None));
+    }
+}
@@ -1,13 +1,15 @@
 //! Accessors.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::time::{SystemTime, UNIX_EPOCH};
 
 use aws_sdk_dynamodb::types::AttributeValue;
 use aws_sdk_dynamodb::types::error::ConditionalCheckFailedException;
+use serde::{Deserialize, Serialize};
 use serde_dynamo::Item;
 use strum::IntoEnumIterator as _;
 
-use tracing::{debug, warn};
+use tracing::{debug, info, warn};
 
 use crate::api::models::limits::{AccountLimits, Limits};
 use crate::common::error::{Error, Inconsistency};
@@ -25,6 +27,11 @@ use super::entries::deposit::{
 use super::entries::limits::{
     GLOBAL_CAP_ACCOUNT, LimitEntry, LimitEntryKey, LimitTablePrimaryIndex,
 };
+use super::entries::stats::{
+    CONFIRMED_AMOUNT_ATTRIBUTE, CONFIRMED_STATUS_ATTRIBUTE, OperationKind, StatsBucketEntry,
+    StatsBucketIndex, StatsEntryKey, StatusCountsEntry, StatusCountsIndex,
+    deposit_status_attribute, recount, withdrawal_status_attribute,
+};
 use super::entries::withdrawal::{
     ValidatedWithdrawalUpdate, WithdrawalInfoByRecipientEntry, WithdrawalInfoBySenderEntry,
     WithdrawalTableByRecipientSecondaryIndex, WithdrawalTableBySenderSecondaryIndex,
@@ -40,6 +47,7 @@ use super::entries::{
         DepositEntry, DepositEntryKey, DepositInfoEntry, DepositTablePrimaryIndex,
         DepositTableSecondaryIndex, DepositUpdatePackage,
     },
+    detokenize, tokenize,
     withdrawal::{
         WithdrawalEntry, WithdrawalInfoEntry, WithdrawalTablePrimaryIndex,
         WithdrawalTableSecondaryIndex, WithdrawalUpdatePackage,
@@ -53,7 +61,16 @@ use super::entries::{
 
 /// Add deposit entry.
 pub async fn add_deposit_entry(context: &EmilyContext, entry: &DepositEntry) -> Result<(), Error> {
-    put_entry::<DepositTablePrimaryIndex>(context, entry).await
+    put_entry::<DepositTablePrimaryIndex>(context, entry).await?;
+    record_operation_created(
+        context,
+        OperationKind::Deposit,
+        deposit_status_attribute(&entry.status),
+        entry.amount,
+        entry.created_at,
+    )
+    .await;
+    Ok(())
 }
 
 /// Sets / updates an existing deposit entry.
@@ -160,6 +177,28 @@ pub async fn get_all_deposit_entries_modified_from_height_with_status(
     .await
 }
 
+/// Gets a page of deposit entries last modified within the inclusive stacks
+/// block height range `[minimum_height, maximum_height]`, across all
+/// statuses.
+pub async fn get_deposit_entries_modified_in_height_range(
+    context: &EmilyContext,
+    minimum_height: u64,
+    maximum_height: u64,
+    maybe_next_token: Option<String>,
+    page_size: u16,
+) -> Result<(Vec<DepositInfoEntry>, Option<String>), Error> {
+    let statuses: Vec<DepositStatus> = DepositStatus::iter().collect();
+    query_page_with_partition_keys_and_sort_key_between::<DepositTableSecondaryIndex>(
+        context,
+        &statuses,
+        &minimum_height,
+        &maximum_height,
+        maybe_next_token,
+        page_size,
+    )
+    .await
+}
+
 /// Get deposit entries for a given transaction.
 pub async fn get_deposit_entries_for_transaction(
     context: &EmilyContext,
@@ -249,7 +288,7 @@ pub async fn update_deposit(
     // Get simplified status enum.
     let status: DepositStatus = (&update.event.status).into();
     // Build the update.
-    context
+    let entry = context
         .dynamodb_client
         .update_item()
         .table_name(&context.settings.deposit_table_name)
@@ -281,7 +320,37 @@ pub async fn update_deposit(
         .ok_or(Error::MissingAttributesDeposit(update.key.clone()))
         .and_then(|attributes| {
             serde_dynamo::from_item::<Item, DepositEntry>(attributes.into()).map_err(Error::from)
-        })
+        })?;
+    // Keep the stats in line with the new status. This is a separate write
+    // that can fail on its own; see `rebuild_stats`.
+    if let Some(previous_event) = entry.history.iter().rev().nth(1) {
+        record_deposit_status_change(context, &(&previous_event.status).into(), &entry).await;
+    }
+    Ok(entry)
+}
+
+/// Sets the height of the bitcoin block that confirmed a deposit
+/// transaction without altering the deposit's history or version.
+pub async fn set_deposit_bitcoin_block_height(
+    context: &EmilyContext,
+    key: &DepositEntryKey,
+    bitcoin_block_height: u64,
+) -> Result<(), Error> {
+    let key_item: Item = serde_dynamo::to_item(key)?;
+    context
+        .dynamodb_client
+        .update_item()
+        .table_name(&context.settings.deposit_table_name)
+        .set_key(Some(key_item.into()))
+        .update_expression("SET BitcoinBlockHeight = :height")
+        .condition_expression("attribute_exists(Version)")
+        .expression_attribute_values(
+            ":height",
+            serde_dynamo::to_attribute_value(bitcoin_block_height)?,
+        )
+        .send()
+        .await?;
+    Ok(())
 }
 
 // Withdrawal ------------------------------------------------------------------
@@ -291,7 +360,16 @@ pub async fn add_withdrawal_entry(
     context: &EmilyContext,
     entry: &WithdrawalEntry,
 ) -> Result<(), Error> {
-    put_entry::<WithdrawalTablePrimaryIndex>(context, entry).await
+    put_entry::<WithdrawalTablePrimaryIndex>(context, entry).await?;
+    record_operation_created(
+        context,
+        OperationKind::Withdrawal,
+        withdrawal_status_attribute(&entry.status),
+        entry.amount,
+        entry.created_at,
+    )
+    .await;
+    Ok(())
 }
 
 /// Sets / updates an existing withdrawal entry.
@@ -418,6 +496,28 @@ pub async fn get_all_withdrawal_entries_modified_from_height_with_status(
     .await
 }
 
+/// Gets a page of withdrawal entries last modified within the inclusive
+/// stacks block height range `[minimum_height, maximum_height]`, across all
+/// statuses.
+pub async fn get_withdrawal_entries_modified_in_height_range(
+    context: &EmilyContext,
+    minimum_height: u64,
+    maximum_height: u64,
+    maybe_next_token: Option<String>,
+    page_size: u16,
+) -> Result<(Vec<WithdrawalInfoEntry>, Option<String>), Error> {
+    let statuses: Vec<WithdrawalStatus> = WithdrawalStatus::iter().collect();
+    query_page_with_partition_keys_and_sort_key_between::<WithdrawalTableSecondaryIndex>(
+        context,
+        &statuses,
+        &minimum_height,
+        &maximum_height,
+        maybe_next_token,
+        page_size,
+    )
+    .await
+}
+
 /// Pulls in a withdrawal entry and then updates it, retrying the specified number
 /// of times when there's a version conflict.
 ///
@@ -492,7 +592,7 @@ pub async fn update_withdrawal(
     // Get simplified status enum.
     let status: WithdrawalStatus = (&update.event.status).into();
     // Execute the update.
-    context
+    let entry = context
         .dynamodb_client
         .update_item()
         .table_name(&context.settings.withdrawal_table_name)
@@ -524,7 +624,13 @@ pub async fn update_withdrawal(
         .ok_or(Error::MissingAttributesWithdrawal(update.key.clone()))
         .and_then(|attributes| {
             serde_dynamo::from_item::<Item, WithdrawalEntry>(attributes.into()).map_err(Error::from)
-        })
+        })?;
+    // Keep the stats in line with the new status. This is a separate write
+    // that can fail on its own; see `rebuild_stats`.
+    if let Some(previous_event) = entry.history.iter().rev().nth(1) {
+        record_withdrawal_status_change(context, &(&previous_event.status).into(), &entry).await;
+    }
+    Ok(entry)
 }
 
 // Chainstate ------------------------------------------------------------------
@@ -714,6 +820,36 @@ pub async fn set_api_state(context: &EmilyContext, api_state: &ApiStateEntry) ->
     put_entry_with_version::<SpecialApiStateIndex>(context, &mut api_state.clone()).await
 }
 
+/// Gets the inclusive range of stacks block heights anchored to the
+/// bitcoin blocks within the inclusive range `[minimum_bitcoin_height,
+/// maximum_bitcoin_height]`. The range is open ended on the side of a
+/// missing bound, and at the top if the bitcoin block after
+/// `maximum_bitcoin_height` hasn't been seen yet.
+pub async fn get_stacks_height_range_for_bitcoin_heights(
+    context: &EmilyContext,
+    minimum_bitcoin_height: Option<u64>,
+    maximum_bitcoin_height: Option<u64>,
+) -> Result<(u64, u64), Error> {
+    let minimum_height = match minimum_bitcoin_height {
+        Some(bitcoin_height) => {
+            get_oldest_stacks_block_for_bitcoin_block(context, bitcoin_height).await?
+        }
+        None => 0,
+    };
+    let maximum_height = match maximum_bitcoin_height {
+        Some(bitcoin_height) => {
+            let next_bitcoin_height = bitcoin_height.saturating_add(1);
+            match get_oldest_stacks_block_for_bitcoin_block(context, next_bitcoin_height).await {
+                Ok(height) => height.saturating_sub(1),
+                Err(Error::NotFound) => u64::MAX,
+                Err(error) => return Err(error),
+            }
+        }
+        None => u64::MAX,
+    };
+    Ok((minimum_height, maximum_height))
+}
+
 // Limits ----------------------------------------------------------------------
 
 /// Returns height of oldest stacks block anchored to given bitcoin block
@@ -823,11 +959,18 @@ pub async fn get_limits(context: &EmilyContext) -> Result<Limits, Error> {
         per_withdrawal_cap: default_global_cap.per_withdrawal_cap,
         rolling_withdrawal_blocks: default_global_cap.rolling_withdrawal_blocks,
         rolling_withdrawal_cap: default_global_cap.rolling_withdrawal_cap,
+        effective_at_bitcoin_height: None,
     };
 
+    // Scheduled limit changes are ignored until they take effect.
+    let (now, bitcoin_height) = limits_effective_point(context).await?;
+
     // Aggregate all the latest entries by account.
     let mut limit_by_account: HashMap<String, LimitEntry> = HashMap::new();
-    for entry in all_entries.iter() {
+    for entry in all_entries
+        .iter()
+        .filter(|entry| entry.is_effective(now, bitcoin_height))
+    {
         let account = &entry.key.account;
         if account == GLOBAL_CAP_ACCOUNT {
             // If the account is the global cap account and either we haven't encountered
@@ -888,20 +1031,56 @@ pub async fn get_limit_for_account(
     context: &EmilyContext,
     account: &String,
 ) -> Result<LimitEntry, Error> {
-    // Make the query.
-    let (mut entries, _) = query_with_partition_key::<LimitTablePrimaryIndex>(
-        context,
-        account,
-        None,
-        // Only get the most recent entry. The internals of this query uses
-        // scan_index_forward = false.
-        Some(1),
+    let (now, bitcoin_height) = limits_effective_point(context).await?;
+    // Get every entry that could already be in effect. There should only be
+    // a handful of entries per account.
+    let entries = query_all_with_partition_and_sort_key::<LimitTablePrimaryIndex>(
+        context, account, &now, "<=", None,
     )
     .await?;
-    // The limit is set to 1 so there should always only be one entry returned,
-    // but for the sake of redundancy also get the most recent entry.
-    entries.sort_by_key(|entry| entry.key.timestamp);
-    entries.pop().ok_or(Error::NotFound)
+    // Get the most recent entry that is in effect.
+    entries
+        .into_iter()
+        .filter(|entry| entry.is_effective(now, bitcoin_height))
+        .max_by_key(|entry| entry.key.timestamp)
+        .ok_or(Error::NotFound)
+}
+
+/// Get the history of the limits for a specific account with timestamps
+/// within the inclusive range `[from_timestamp, to_timestamp]`, most
+/// recent first.
+pub async fn get_limit_history_for_account(
+    context: &EmilyContext,
+    account: &String,
+    from_timestamp: u64,
+    to_timestamp: u64,
+    maybe_next_token: Option<String>,
+    maybe_page_size: Option<u16>,
+) -> Result<(Vec<LimitEntry>, Option<String>), Error> {
+    LimitTablePrimaryIndex::query_with_partition_key_and_sort_key_between(
+        &context.dynamodb_client,
+        &context.settings,
+        account,
+        &from_timestamp,
+        &to_timestamp,
+        maybe_next_token,
+        maybe_page_size,
+    )
+    .await
+}
+
+/// Gets the current unix timestamp and bitcoin block height used to decide
+/// which scheduled limit changes are in effect. The bitcoin block height is
+/// `None` if it is not known, in which case limit changes scheduled by
+/// bitcoin block height are not in effect.
+pub async fn limits_effective_point(context: &EmilyContext) -> Result<(u64, Option<u64>), Error> {
+    let now = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        // It's impossible for this to fail.
+        .expect("Error making timestamp when getting limits.")
+        .as_secs();
+    let bitcoin_height = get_api_state(context).await?.chaintip().bitcoin_height;
+    Ok((now, bitcoin_height))
 }
 
 /// Set the limit for a specific account.
@@ -912,6 +1091,261 @@ pub async fn set_limit_for_account(
     put_entry::<LimitTablePrimaryIndex>(context, limit).await
 }
 
+// Stats -----------------------------------------------------------------------
+
+/// Records a deposit status change in the stats table. Call this after
+/// successfully writing a deposit entry whose status was `previous_status`
+/// before the write.
+pub async fn record_deposit_status_change(
+    context: &EmilyContext,
+    previous_status: &DepositStatus,
+    entry: &DepositEntry,
+) {
+    record_status_change(
+        context,
+        OperationKind::Deposit,
+        deposit_status_attribute(previous_status),
+        deposit_status_attribute(&entry.status),
+        entry.amount,
+        entry.created_at,
+    )
+    .await;
+}
+
+/// Records a withdrawal status change in the stats table. Call this after
+/// successfully writing a withdrawal entry whose status was
+/// `previous_status` before the write.
+pub async fn record_withdrawal_status_change(
+    context: &EmilyContext,
+    previous_status: &WithdrawalStatus,
+    entry: &WithdrawalEntry,
+) {
+    record_status_change(
+        context,
+        OperationKind::Withdrawal,
+        withdrawal_status_attribute(previous_status),
+        withdrawal_status_attribute(&entry.status),
+        entry.amount,
+        entry.created_at,
+    )
+    .await;
+}
+
+/// Gets the status counters of the given operation.
+pub async fn get_status_counts(
+    context: &EmilyContext,
+    kind: OperationKind,
+) -> Result<StatusCountsEntry, Error> {
+    let key = StatsEntryKey::counts(kind);
+    match get_entry::<StatusCountsIndex>(context, &key).await {
+        // Nothing has been counted yet.
+        Err(Error::NotFound) => Ok(StatusCountsEntry { key, ..Default::default() }),
+        result => result,
+    }
+}
+
+/// Gets the volume buckets of the given operation that start within the
+/// inclusive range `[from_timestamp, to_timestamp]`, most recent first.
+pub async fn get_volume_buckets(
+    context: &EmilyContext,
+    kind: OperationKind,
+    from_timestamp: u64,
+    to_timestamp: u64,
+) -> Result<Vec<StatsBucketEntry>, Error> {
+    query_all_with_partition_key_and_sort_key_between::<StatsBucketIndex>(
+        context,
+        &kind.volume_stat().to_string(),
+        &from_timestamp,
+        &to_timestamp,
+    )
+    .await
+}
+
+/// Gets the time-to-confirmation histogram of the given operation.
+pub async fn get_confirmation_time_histogram(
+    context: &EmilyContext,
+    kind: OperationKind,
+) -> Result<Vec<StatsBucketEntry>, Error> {
+    query_all_with_partition_and_sort_key::<StatsBucketIndex>(
+        context,
+        &kind.confirmation_time_stat().to_string(),
+        &0,
+        ">=",
+        None,
+    )
+    .await
+}
+
+/// Rebuilds the status counters and the volume buckets in the stats table
+/// from the deposit and withdrawal tables.
+///
+/// The stats are written after, and separately from, the deposit and
+/// withdrawal entries they count, so they miss every operation written
+/// before the stats table existed and drift whenever a stats write fails.
+/// This recounts them from scratch. The time-to-confirmation histograms are
+/// kept as they are, since the tables don't record when an operation was
+/// confirmed.
+///
+/// Operations written while the stats are being rebuilt may be missed, so
+/// this should be run while Emily isn't receiving updates. Rebuilding is
+/// idempotent and can be repeated.
+pub async fn rebuild_stats(context: &EmilyContext) -> Result<(), Error> {
+    let deposits =
+        DepositTablePrimaryIndex::get_all_entries(&context.dynamodb_client, &context.settings)
+            .await?;
+    let (counts, volume) = recount(
+        OperationKind::Deposit,
+        deposits.iter().map(|entry| {
+            let status = deposit_status_attribute(&entry.status);
+            (status, entry.amount, entry.created_at)
+        }),
+    );
+    replace_stats(context, OperationKind::Deposit, counts, volume).await?;
+
+    let withdrawals =
+        WithdrawalTablePrimaryIndex::get_all_entries(&context.dynamodb_client, &context.settings)
+            .await?;
+    let (counts, volume) = recount(
+        OperationKind::Withdrawal,
+        withdrawals.iter().map(|entry| {
+            let status = withdrawal_status_attribute(&entry.status);
+            (status, entry.amount, entry.created_at)
+        }),
+    );
+    replace_stats(context, OperationKind::Withdrawal, counts, volume).await
+}
+
+/// Replaces the status counters and every volume bucket of the given
+/// operation in the stats table.
+async fn replace_stats(
+    context: &EmilyContext,
+    kind: OperationKind,
+    counts: StatusCountsEntry,
+    volume: Vec<StatsBucketEntry>,
+) -> Result<(), Error> {
+    put_entry::<StatusCountsIndex>(context, &counts).await?;
+    let buckets: HashSet<&StatsEntryKey> = volume.iter().map(|entry| &entry.key).collect();
+    let stale = get_volume_buckets(context, kind, 0, u64::MAX)
+        .await?
+        .into_iter()
+        .filter(|entry| !buckets.contains(&entry.key));
+    for entry in stale {
+        delete_entry::<StatsBucketIndex>(context, &entry.key).await?;
+    }
+    for entry in &volume {
+        put_entry::<StatsBucketIndex>(context, entry).await?;
+    }
+    info!(
+        ?kind,
+        ?counts,
+        volume_buckets = volume.len(),
+        "rebuilt the stats of an operation"
+    );
+    Ok(())
+}
+
+/// Records a newly created operation in the stats table. Like status
+/// changes, this is best effort; see [`record_status_change`].
+async fn record_operation_created(
+    context: &EmilyContext,
+    kind: OperationKind,
+    status: &'static str,
+    amount: u64,
+    created_at: Option<u64>,
+) {
+    let created_at = created_at.unwrap_or_else(now_timestamp);
+    let counts = StatsEntryKey::counts(kind);
+    let volume = StatsEntryKey::volume(kind, created_at);
+    let result = async {
+        increment_stats(context, &counts, &[(status, 1)]).await?;
+        increment_stats(context, &volume, &[("Count", 1), ("Amount", amount as i64)]).await
+    }
+    .await;
+    if let Err(error) = result {
+        warn!(%error, ?kind, "failed to record a new operation in the stats table");
+    }
+}
+
+/// Moves an operation between status counters and, when it has just been
+/// confirmed, records its time-to-confirmation.
+///
+/// The stats are best effort: the operation itself has already been
+/// written, so failing to update them only logs a warning, and the stats
+/// then need to be rebuilt with [`rebuild_stats`].
+async fn record_status_change(
+    context: &EmilyContext,
+    kind: OperationKind,
+    previous_status: &'static str,
+    status: &'static str,
+    amount: u64,
+    created_at: Option<u64>,
+) {
+    if previous_status == status {
+        return;
+    }
+    let mut increments = vec![(previous_status, -1), (status, 1)];
+    if previous_status == CONFIRMED_STATUS_ATTRIBUTE {
+        increments.push((CONFIRMED_AMOUNT_ATTRIBUTE, -(amount as i64)));
+    } else if status == CONFIRMED_STATUS_ATTRIBUTE {
+        increments.push((CONFIRMED_AMOUNT_ATTRIBUTE, amount as i64));
+    }
+
+    let result = async {
+        increment_stats(context, &StatsEntryKey::counts(kind), &increments).await?;
+        if status == CONFIRMED_STATUS_ATTRIBUTE
+            && let Some(created_at) = created_at
+        {
+            let seconds = now_timestamp().saturating_sub(created_at);
+            let bucket = StatsEntryKey::confirmation_time(kind, seconds);
+            increment_stats(context, &bucket, &[("Count", 1)]).await?;
+        }
+        Ok::<_, Error>(())
+    }
+    .await;
+    if let Err(error) = result {
+        warn!(%error, ?kind, previous_status, status, "failed to record a status change in the stats table");
+    }
+}
+
+/// Atomically adds the given increments to the attributes of a stats
+/// entry, creating the entry and attributes if they don't exist.
+async fn increment_stats(
+    context: &EmilyContext,
+    key: &StatsEntryKey,
+    increments: &[(&str, i64)],
+) -> Result<(), Error> {
+    let key_item: Item = serde_dynamo::to_item(key)?;
+    let mut request = context
+        .dynamodb_client
+        .update_item()
+        .table_name(&context.settings.stats_table_name)
+        .set_key(Some(key_item.into()));
+    let mut additions = Vec::with_capacity(increments.len());
+    for (index, (attribute, increment)) in increments.iter().enumerate() {
+        additions.push(format!("#attr{index} :inc{index}"));
+        request = request
+            .expression_attribute_names(format!("#attr{index}"), *attribute)
+            .expression_attribute_values(
+                format!(":inc{index}"),
+                AttributeValue::N(increment.to_string()),
+            );
+    }
+    request
+        .update_expression(format!("ADD {}", additions.join(", ")))
+        .send()
+        .await?;
+    Ok(())
+}
+
+/// The current unix timestamp in seconds.
+fn now_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        // It's impossible for this to fail.
+        .expect("Error making timestamp.")
+        .as_secs()
+}
+
 // Testing ---------------------------------------------------------------------
 
 /// Wipes all the tables.
@@ -922,6 +1356,7 @@ pub async fn wipe_all_tables(context: &EmilyContext) -> Result<(), Error> {
     wipe_withdrawal_table(context).await?;
     wipe_chainstate_table(context).await?;
     wipe_limit_table(context).await?;
+    wipe_stats_table(context).await?;
     Ok(())
 }
 
@@ -950,6 +1385,13 @@ async fn wipe_limit_table(context: &EmilyContext) -> Result<(), Error> {
     wipe::<LimitTablePrimaryIndex>(context).await
 }
 
+/// Wipes the stats table. Every stats entry can be read as a bucket entry,
+/// so wiping through the bucket index removes the counters as well.
+#[cfg(feature = "testing")]
+async fn wipe_stats_table(context: &EmilyContext) -> Result<(), Error> {
+    wipe::<StatsBucketIndex>(context).await
+}
+
 // Generics --------------------------------------------------------------------
 
 async fn get_entry<T: TableIndexTrait>(
@@ -1039,6 +1481,97 @@ async fn query_all_with_partition_and_sort_key<T: TableIndexTrait>(
     Ok(items)
 }
 
+async fn query_all_with_partition_key_and_sort_key_between<T: TableIndexTrait>(
+    context: &EmilyContext,
+    partition_key: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey,
+    from: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::SortKey,
+    to: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::SortKey,
+) -> Result<Vec<<T as TableIndexTrait>::Entry>, Error> {
+    // item aggregator.
+    let mut items: Vec<<T as TableIndexTrait>::Entry> = Vec::new();
+    // Next token.
+    let mut next_token: Option<String> = None;
+    // Loop over all items.
+    loop {
+        let mut new_items: Vec<<T as TableIndexTrait>::Entry>;
+        (new_items, next_token) =
+            <T as TableIndexTrait>::query_with_partition_key_and_sort_key_between(
+                &context.dynamodb_client,
+                &context.settings,
+                partition_key,
+                from,
+                to,
+                next_token,
+                None,
+            )
+            .await?;
+        // add new items.
+        items.append(&mut new_items);
+        if next_token.is_none() {
+            // If there are no more entries then end the loop.
+            break;
+        }
+    }
+    // Return the items.
+    Ok(items)
+}
+
+/// Position of a paged query over several partitions: the index of the
+/// partition to resume from and the token to resume that partition with.
+#[derive(Serialize, Deserialize)]
+struct MultiPartitionToken {
+    partition: usize,
+    next_token: Option<String>,
+}
+
+/// Queries at most `page_size` entries whose sort key is within the
+/// inclusive range `[from, to]`, going through the partitions in order.
+/// Returns a token to get the next page with if there are more entries.
+async fn query_page_with_partition_keys_and_sort_key_between<T: TableIndexTrait>(
+    context: &EmilyContext,
+    partition_keys: &[<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey],
+    from: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::SortKey,
+    to: &<<<T as TableIndexTrait>::Entry as EntryTrait>::Key as KeyTrait>::SortKey,
+    maybe_next_token: Option<String>,
+    page_size: u16,
+) -> Result<(Vec<<T as TableIndexTrait>::Entry>, Option<String>), Error> {
+    let MultiPartitionToken { mut partition, mut next_token } = match maybe_next_token {
+        Some(token) => detokenize(token)?,
+        None => MultiPartitionToken { partition: 0, next_token: None },
+    };
+    // item aggregator.
+    let mut items: Vec<<T as TableIndexTrait>::Entry> = Vec::new();
+    while let Some(partition_key) = partition_keys.get(partition) {
+        let remaining = page_size.saturating_sub(items.len() as u16);
+        if remaining == 0 {
+            break;
+        }
+        let mut new_items: Vec<<T as TableIndexTrait>::Entry>;
+        (new_items, next_token) =
+            <T as TableIndexTrait>::query_with_partition_key_and_sort_key_between(
+                &context.dynamodb_client,
+                &context.settings,
+                partition_key,
+                from,
+                to,
+                next_token,
+                Some(remaining),
+            )
+            .await?;
+        // add new items.
+        items.append(&mut new_items);
+        if next_token.is_none() {
+            // If there are no more entries in this partition move on to the next one.
+            partition += 1;
+        }
+    }
+    // Return the items and where to resume from, if anything is left.
+    let next_token = (partition < partition_keys.len())
+        .then(|| tokenize(MultiPartitionToken { partition, next_token }))
+        .transpose()?;
+    Ok((items, next_token))
+}
+
 #[cfg(feature = "testing")]
 async fn wipe<T: TableIndexTrait>(context: &EmilyContext) -> Result<(), Error> {
     <T as TableIndexTrait>::wipe(&context.dynamodb_client, &context.settings).await
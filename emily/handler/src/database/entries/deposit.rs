@@ -11,6 +11,7 @@ use crate::{
         chainstate::Chainstate,
         common::{DepositStatus, Fulfillment},
         deposit::{Deposit, DepositInfo, DepositParameters},
+        export::{DepositExportRecord, DepositHistoryEvent},
     },
     common::error::{Error, Inconsistency, ValidationError},
 };
@@ -77,6 +78,18 @@ pub struct DepositEntry {
     /// Transaction ID of transaction which replaced this transaction during an RBF.
     #[serde(skip_serializing_if = "Option::is_none")]
     pub replaced_by_tx: Option<String>,
+    /// The outpoints spent by the deposit transaction, formatted as
+    /// `txid:vout`. Used to detect double spends of the deposit.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub bitcoin_tx_inputs: Vec<String>,
+    /// The height of the bitcoin block that confirmed the deposit
+    /// transaction, if Emily has observed its confirmation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub bitcoin_block_height: Option<u64>,
+    /// Unix timestamp, in seconds, at which Emily first received the
+    /// deposit. Absent for deposits created before this was recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
 }
 
 /// Implements versioned entry trait for the deposit entry.
@@ -277,6 +290,27 @@ impl TryFrom<DepositEntry> for Deposit {
             deposit_script: deposit_entry.deposit_script,
             fulfillment,
             replaced_by_tx,
+            bitcoin_block_height: deposit_entry.bitcoin_block_height,
+        })
+    }
+}
+
+impl TryFrom<DepositEntry> for DepositExportRecord {
+    type Error = Error;
+    fn try_from(deposit_entry: DepositEntry) -> Result<Self, Self::Error> {
+        let history = deposit_entry
+            .history
+            .iter()
+            .map(|event| DepositHistoryEvent {
+                status: (&event.status).into(),
+                message: event.message.clone(),
+                stacks_block_height: event.stacks_block_height,
+                stacks_block_hash: event.stacks_block_hash.clone(),
+            })
+            .collect();
+        Ok(DepositExportRecord {
+            deposit: deposit_entry.try_into()?,
+            history,
         })
     }
 }
@@ -753,6 +787,9 @@ mod tests {
             history: vec![pending, accepted.clone()],
             reclaim_pubkeys_hash: None,
             replaced_by_tx: None,
+            bitcoin_tx_inputs: Vec::new(),
+            bitcoin_block_height: None,
+            created_at: None,
         };
 
         let update = ValidatedDepositUpdate {
@@ -794,6 +831,9 @@ mod tests {
             history: vec![pending.clone()],
             reclaim_pubkeys_hash: None,
             replaced_by_tx: None,
+            bitcoin_tx_inputs: Vec::new(),
+            bitcoin_block_height: None,
+            created_at: None,
         };
 
         let update = ValidatedDepositUpdate {
@@ -853,6 +893,9 @@ mod tests {
             history: vec![pending.clone(), accepted.clone(), confirmed.clone()],
             reclaim_pubkeys_hash: Some(hex::encode([1u8; 32])),
             replaced_by_tx: None,
+            bitcoin_tx_inputs: Vec::new(),
+            bitcoin_block_height: None,
+            created_at: None,
         };
 
         // Ensure the deposit is valid.
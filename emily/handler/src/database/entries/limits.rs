@@ -1,10 +1,13 @@
 //! Entries into the limit table.
 
-use std::{hash::Hash, time::SystemTime};
+use std::{
+    hash::Hash,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
 
 use serde::{Deserialize, Serialize};
 
-use crate::api::models::limits::AccountLimits;
+use crate::api::models::limits::{AccountLimits, LimitHistoryEntry, SetLimitsQuery};
 
 use super::{EntryTrait, KeyTrait, PrimaryIndex, PrimaryIndexTrait};
 
@@ -42,6 +45,9 @@ pub struct LimitEntry {
     pub rolling_withdrawal_blocks: Option<u64>,
     /// Maximum total sBTC that can be withdrawn within the rolling withdrawal window.
     pub rolling_withdrawal_cap: Option<u64>,
+    /// Bitcoin block height at which this limit takes effect. If none then
+    /// the limit takes effect at the entry timestamp.
+    pub effective_at_bitcoin_height: Option<u64>,
 }
 
 /// Convert from entry to its corresponding limit.
@@ -58,6 +64,18 @@ impl From<LimitEntry> for AccountLimits {
     }
 }
 
+/// Convert from entry to its corresponding history entry.
+impl From<LimitEntry> for LimitHistoryEntry {
+    fn from(limit_entry: LimitEntry) -> Self {
+        LimitHistoryEntry {
+            account: limit_entry.key.account.clone(),
+            timestamp: limit_entry.key.timestamp,
+            effective_at_bitcoin_height: limit_entry.effective_at_bitcoin_height,
+            limits: limit_entry.into(),
+        }
+    }
+}
+
 impl LimitEntry {
     /// Create a new limit entry from an account limit and the chosen time.
     pub fn from_account_limit(
@@ -80,8 +98,37 @@ impl LimitEntry {
             per_withdrawal_cap: account_limit.per_withdrawal_cap,
             rolling_withdrawal_blocks: account_limit.rolling_withdrawal_blocks,
             rolling_withdrawal_cap: account_limit.rolling_withdrawal_cap,
+            effective_at_bitcoin_height: None,
         }
     }
+    /// Create a new limit entry that takes effect according to the given
+    /// schedule, or at the chosen time if the schedule is empty.
+    pub fn from_scheduled_account_limit(
+        account: String,
+        now: SystemTime,
+        schedule: &SetLimitsQuery,
+        account_limit: &AccountLimits,
+    ) -> Self {
+        let effective_at = schedule
+            .effective_at_timestamp
+            .map(|timestamp| UNIX_EPOCH + Duration::from_secs(timestamp))
+            .unwrap_or(now);
+        LimitEntry {
+            effective_at_bitcoin_height: schedule.effective_at_bitcoin_height,
+            ..Self::from_account_limit(account, effective_at, account_limit)
+        }
+    }
+    /// Returns true if the limit is in effect at the given time and
+    /// bitcoin block height.
+    pub fn is_effective(&self, now: u64, bitcoin_height: Option<u64>) -> bool {
+        let height_reached = match self.effective_at_bitcoin_height {
+            Some(effective_height) => {
+                bitcoin_height.is_some_and(|height| height >= effective_height)
+            }
+            None => true,
+        };
+        self.key.timestamp <= now && height_reached
+    }
     /// Returns true if the limit entry has no limits set.
     pub fn is_empty(&self) -> bool {
         self.peg_cap.is_none()
@@ -123,3 +170,52 @@ impl PrimaryIndexTrait for LimitTablePrimaryIndexInner {
         &settings.limit_table_name
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn entry(timestamp: u64, effective_at_bitcoin_height: Option<u64>) -> LimitEntry {
+        LimitEntry {
+            key: LimitEntryKey {
+                account: GLOBAL_CAP_ACCOUNT.to_string(),
+                timestamp,
+            },
+            effective_at_bitcoin_height,
+            ..Default::default()
+        }
+    }
+
+    #[test_case(entry(10, None), 10, None, true; "immediate")]
+    #[test_case(entry(11, None), 10, None, false; "future-timestamp")]
+    #[test_case(entry(10, Some(100)), 10, None, false; "unknown-bitcoin-height")]
+    #[test_case(entry(10, Some(100)), 10, Some(99), false; "bitcoin-height-not-reached")]
+    #[test_case(entry(10, Some(100)), 10, Some(100), true; "bitcoin-height-reached")]
+    fn limit_entry_is_effective(
+        entry: LimitEntry,
+        now: u64,
+        bitcoin_height: Option<u64>,
+        expected: bool,
+    ) {
+        assert_eq!(entry.is_effective(now, bitcoin_height), expected);
+    }
+
+    #[test]
+    fn scheduled_limit_entry_uses_effective_timestamp() {
+        let now = UNIX_EPOCH + Duration::from_secs(10);
+        let schedule = SetLimitsQuery {
+            effective_at_timestamp: Some(20),
+            effective_at_bitcoin_height: None,
+        };
+        let entry = LimitEntry::from_scheduled_account_limit(
+            GLOBAL_CAP_ACCOUNT.to_string(),
+            now,
+            &schedule,
+            &AccountLimits::default(),
+        );
+        assert_eq!(entry.key.timestamp, 20);
+        assert!(!entry.is_effective(10, None));
+        assert!(entry.is_effective(20, None));
+    }
+}
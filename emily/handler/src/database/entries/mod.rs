@@ -10,6 +10,7 @@
 //! - **Submodules**:
 //!   - `chainstate`: Handles entries related to the chain state table.
 //!   - `deposit`: Manages entries for the deposit table.
+//!   - `stats`: Manages entries for the stats table.
 //!   - `withdrawal`: Manages entries for the withdrawal table.
 //!
 //! - **Structures**:
@@ -65,6 +66,8 @@ pub mod chainstate;
 pub mod deposit;
 /// Limits table entries.
 pub mod limits;
+/// Stats table entries.
+pub mod stats;
 /// Withdrawal table entries.
 pub mod withdrawal;
 
@@ -357,6 +360,54 @@ pub(crate) trait TableIndexTrait {
         Ok((entries, next_token))
     }
 
+    /// Generic table query for all attributes with a given primary key and
+    /// a sort key within the inclusive range `[from, to]`.
+    async fn query_with_partition_key_and_sort_key_between(
+        dynamodb_client: &aws_sdk_dynamodb::Client,
+        settings: &Settings,
+        partition_key: &<<Self::Entry as EntryTrait>::Key as KeyTrait>::PartitionKey,
+        from: &<<Self::Entry as EntryTrait>::Key as KeyTrait>::SortKey,
+        to: &<<Self::Entry as EntryTrait>::Key as KeyTrait>::SortKey,
+        maybe_next_token: Option<String>,
+        maybe_page_size: Option<u16>,
+    ) -> Result<(Vec<Self::Entry>, Option<String>), Error> {
+        // Convert inputs into the types needed for querying.
+        let exclusive_start_key =
+            maybe_exclusive_start_key_from_next_token::<Self::SearchToken>(maybe_next_token)?;
+
+        // Query the database.
+        let query_output = dynamodb_client
+            .query()
+            .table_name(Self::table_name(settings))
+            .set_index_name(Self::INDEX_NAME_IF_GSI.map(|s| s.to_string()))
+            .set_exclusive_start_key(exclusive_start_key)
+            .set_limit(maybe_page_size.map(|u| u as i32))
+            .key_condition_expression("#pk = :pk AND #sk BETWEEN :from AND :to")
+            .expression_attribute_names(
+                "#pk",
+                <<Self::Entry as EntryTrait>::Key as KeyTrait>::PARTITION_KEY_NAME,
+            )
+            .expression_attribute_names(
+                "#sk",
+                <<Self::Entry as EntryTrait>::Key as KeyTrait>::SORT_KEY_NAME,
+            )
+            .expression_attribute_values(":pk", serde_dynamo::to_attribute_value(partition_key)?)
+            .expression_attribute_values(":from", serde_dynamo::to_attribute_value(from)?)
+            .expression_attribute_values(":to", serde_dynamo::to_attribute_value(to)?)
+            .scan_index_forward(false)
+            .send()
+            .await
+            .map_err(Box::new)?;
+        // Convert data into output format.
+        let entries: Vec<Self::Entry> =
+            serde_dynamo::from_items(query_output.items.unwrap_or_default())?;
+        let next_token = maybe_next_token_from_last_evaluated_key::<Self::SearchToken>(
+            query_output.last_evaluated_key,
+        )?;
+        // Return.
+        Ok((entries, next_token))
+    }
+
     /// Generic put table entry.
     async fn put_entry(
         dynamodb_client: &aws_sdk_dynamodb::Client,
@@ -601,7 +652,7 @@ where
 }
 
 /// Turns an optional key into a token.
-fn tokenize<T>(key: T) -> Result<String, Error>
+pub(crate) fn tokenize<T>(key: T) -> Result<String, Error>
 where
     T: Serialize,
 {
@@ -629,7 +680,7 @@ where
 }
 
 /// Turns an optional token into a key.
-fn detokenize<T>(token: String) -> Result<T, Error>
+pub(crate) fn detokenize<T>(token: String) -> Result<T, Error>
 where
     T: for<'de> Deserialize<'de>,
 {
@@ -0,0 +1,393 @@
+//! Entries into the stats table.
+//!
+//! The stats table holds counters that are incremented alongside the writes
+//! to the deposit and withdrawal tables so that aggregated statistics can be
+//! served without scanning either table.
+
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::api::models::common::{DepositStatus, WithdrawalStatus};
+
+use super::{EntryTrait, KeyTrait, PrimaryIndex, PrimaryIndexTrait};
+
+// Stats entry key -------------------------------------------------------------
+
+/// The width of a volume bucket in seconds.
+pub const VOLUME_BUCKET_SECONDS: u64 = 3600;
+
+/// The upper bounds, in seconds, of the buckets of the time-to-confirmation
+/// histogram. Durations above the last bound go into a final `u64::MAX`
+/// bucket.
+pub const CONFIRMATION_TIME_BUCKETS: [u64; 14] = [
+    60, 300, 600, 900, 1200, 1800, 2700, 3600, 5400, 7200, 10800, 21600, 43200, 86400,
+];
+
+/// The sBTC operation a statistic is about.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum OperationKind {
+    /// Deposits.
+    Deposit,
+    /// Withdrawals.
+    Withdrawal,
+}
+
+impl OperationKind {
+    /// The stat name of the status counters for this operation.
+    fn counts_stat(&self) -> &'static str {
+        match self {
+            OperationKind::Deposit => "DepositCounts",
+            OperationKind::Withdrawal => "WithdrawalCounts",
+        }
+    }
+    /// The stat name of the volume buckets for this operation.
+    pub fn volume_stat(&self) -> &'static str {
+        match self {
+            OperationKind::Deposit => "DepositVolume",
+            OperationKind::Withdrawal => "WithdrawalVolume",
+        }
+    }
+    /// The stat name of the time-to-confirmation histogram for this
+    /// operation.
+    pub fn confirmation_time_stat(&self) -> &'static str {
+        match self {
+            OperationKind::Deposit => "DepositConfirmationTime",
+            OperationKind::Withdrawal => "WithdrawalConfirmationTime",
+        }
+    }
+}
+
+/// Stats table entry key. This is the primary index key.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StatsEntryKey {
+    /// The name of the statistic.
+    pub stat: String,
+    /// The bucket of the statistic. Zero for statistics without buckets.
+    pub bucket: u64,
+}
+
+impl StatsEntryKey {
+    /// The key of the status counters for the given operation.
+    pub fn counts(kind: OperationKind) -> Self {
+        StatsEntryKey {
+            stat: kind.counts_stat().to_string(),
+            bucket: 0,
+        }
+    }
+    /// The key of the volume bucket containing the given unix timestamp.
+    pub fn volume(kind: OperationKind, timestamp: u64) -> Self {
+        StatsEntryKey {
+            stat: kind.volume_stat().to_string(),
+            bucket: timestamp - timestamp % VOLUME_BUCKET_SECONDS,
+        }
+    }
+    /// The key of the time-to-confirmation histogram bucket for the given
+    /// duration in seconds.
+    pub fn confirmation_time(kind: OperationKind, seconds: u64) -> Self {
+        let bucket = CONFIRMATION_TIME_BUCKETS
+            .into_iter()
+            .find(|bound| seconds <= *bound)
+            .unwrap_or(u64::MAX);
+        StatsEntryKey {
+            stat: kind.confirmation_time_stat().to_string(),
+            bucket,
+        }
+    }
+}
+
+/// Implements the key trait for the stats entry key.
+impl KeyTrait for StatsEntryKey {
+    /// The type of the partition key.
+    type PartitionKey = String;
+    /// the type of the sort key.
+    type SortKey = u64;
+    /// The table field name of the partition key.
+    const PARTITION_KEY_NAME: &'static str = "Stat";
+    /// The table field name of the sort key.
+    const SORT_KEY_NAME: &'static str = "Bucket";
+}
+
+// Status counts entry ---------------------------------------------------------
+
+/// The attribute holding the total amount of confirmed operations.
+pub const CONFIRMED_AMOUNT_ATTRIBUTE: &str = "ConfirmedAmount";
+
+/// The attribute counting confirmed operations.
+pub const CONFIRMED_STATUS_ATTRIBUTE: &str = "Confirmed";
+
+/// Stats table entry counting the operations in each status.
+///
+/// Counters are signed so that moving an operation out of a status it was
+/// never counted in shows up as a negative counter, which means that the
+/// stats are out of sync with the deposit and withdrawal tables and need to
+/// be rebuilt.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StatusCountsEntry {
+    /// Stats entry key.
+    #[serde(flatten)]
+    pub key: StatsEntryKey,
+    /// Number of pending operations.
+    #[serde(default)]
+    pub pending: i64,
+    /// Number of accepted operations.
+    #[serde(default)]
+    pub accepted: i64,
+    /// Number of confirmed operations.
+    #[serde(default)]
+    pub confirmed: i64,
+    /// Number of failed operations.
+    #[serde(default)]
+    pub failed: i64,
+    /// Number of operations replaced by fee. Always zero for withdrawals.
+    #[serde(default)]
+    pub rbf: i64,
+    /// Total amount of confirmed operations in satoshis.
+    #[serde(default)]
+    pub confirmed_amount: i64,
+}
+
+impl StatusCountsEntry {
+    /// Returns true if any of the counters is negative.
+    pub fn has_negative_counter(&self) -> bool {
+        [
+            self.pending,
+            self.accepted,
+            self.confirmed,
+            self.failed,
+            self.rbf,
+            self.confirmed_amount,
+        ]
+        .into_iter()
+        .any(|counter| counter < 0)
+    }
+}
+
+/// Returns the status counts attribute that counts deposits with the given
+/// status.
+pub fn deposit_status_attribute(status: &DepositStatus) -> &'static str {
+    match status {
+        DepositStatus::Pending => "Pending",
+        DepositStatus::Accepted => "Accepted",
+        DepositStatus::Confirmed => CONFIRMED_STATUS_ATTRIBUTE,
+        DepositStatus::Failed => "Failed",
+        DepositStatus::Rbf => "Rbf",
+    }
+}
+
+/// Returns the status counts attribute that counts withdrawals with the
+/// given status.
+pub fn withdrawal_status_attribute(status: &WithdrawalStatus) -> &'static str {
+    match status {
+        WithdrawalStatus::Pending => "Pending",
+        WithdrawalStatus::Accepted => "Accepted",
+        WithdrawalStatus::Confirmed => CONFIRMED_STATUS_ATTRIBUTE,
+        WithdrawalStatus::Failed => "Failed",
+    }
+}
+
+/// Implements the entry trait for the status counts entry.
+impl EntryTrait for StatusCountsEntry {
+    /// The type of the key for this entry type.
+    type Key = StatsEntryKey;
+    /// Extract the key from the status counts entry.
+    fn key(&self) -> Self::Key {
+        self.key.clone()
+    }
+}
+
+/// Primary index struct.
+pub struct StatusCountsIndexInner;
+/// Status counts primary index type.
+pub type StatusCountsIndex = PrimaryIndex<StatusCountsIndexInner>;
+/// Definition of Primary index trait.
+impl PrimaryIndexTrait for StatusCountsIndexInner {
+    type Entry = StatusCountsEntry;
+    fn table_name(settings: &crate::context::Settings) -> &str {
+        &settings.stats_table_name
+    }
+}
+
+// Bucket entry ----------------------------------------------------------------
+
+/// Stats table entry for a single bucket of a bucketed statistic, such as
+/// the volume over time or the time-to-confirmation histogram.
+#[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+pub struct StatsBucketEntry {
+    /// Stats entry key.
+    #[serde(flatten)]
+    pub key: StatsEntryKey,
+    /// Number of operations in the bucket.
+    #[serde(default)]
+    pub count: u64,
+    /// Total amount of the operations in the bucket in satoshis. Not
+    /// tracked for the time-to-confirmation histogram.
+    #[serde(default)]
+    pub amount: u64,
+}
+
+/// Implements the entry trait for the stats bucket entry.
+impl EntryTrait for StatsBucketEntry {
+    /// The type of the key for this entry type.
+    type Key = StatsEntryKey;
+    /// Extract the key from the stats bucket entry.
+    fn key(&self) -> Self::Key {
+        self.key.clone()
+    }
+}
+
+/// Primary index struct.
+pub struct StatsBucketIndexInner;
+/// Stats bucket primary index type.
+pub type StatsBucketIndex = PrimaryIndex<StatsBucketIndexInner>;
+/// Definition of Primary index trait.
+impl PrimaryIndexTrait for StatsBucketIndexInner {
+    type Entry = StatsBucketEntry;
+    fn table_name(settings: &crate::context::Settings) -> &str {
+        &settings.stats_table_name
+    }
+}
+
+/// Counts the given operations, each given as its status counts attribute,
+/// amount and creation timestamp, into the status counters and the volume
+/// buckets of the given operation kind. Operations without a creation
+/// timestamp are counted but aren't in any volume bucket.
+pub fn recount<I>(kind: OperationKind, operations: I) -> (StatusCountsEntry, Vec<StatsBucketEntry>)
+where
+    I: IntoIterator<Item = (&'static str, u64, Option<u64>)>,
+{
+    let mut counts = StatusCountsEntry {
+        key: StatsEntryKey::counts(kind),
+        ..Default::default()
+    };
+    let mut volume: BTreeMap<u64, StatsBucketEntry> = BTreeMap::new();
+    for (status, amount, created_at) in operations {
+        let counter = match status {
+            "Pending" => &mut counts.pending,
+            "Accepted" => &mut counts.accepted,
+            CONFIRMED_STATUS_ATTRIBUTE => &mut counts.confirmed,
+            "Failed" => &mut counts.failed,
+            "Rbf" => &mut counts.rbf,
+            _ => continue,
+        };
+        *counter += 1;
+        if status == CONFIRMED_STATUS_ATTRIBUTE {
+            counts.confirmed_amount += amount as i64;
+        }
+        if let Some(created_at) = created_at {
+            let key = StatsEntryKey::volume(kind, created_at);
+            let bucket = volume
+                .entry(key.bucket)
+                .or_insert(StatsBucketEntry { key, ..Default::default() });
+            bucket.count += 1;
+            bucket.amount += amount;
+        }
+    }
+    (counts, volume.into_values().collect())
+}
+
+/// Returns the upper bound, in seconds, of the histogram bucket containing
+/// the median time-to-confirmation, or `None` if the histogram is empty.
+pub fn median_confirmation_time(histogram: &[StatsBucketEntry]) -> Option<u64> {
+    let mut buckets: Vec<&StatsBucketEntry> = histogram.iter().collect();
+    buckets.sort_by_key(|entry| entry.key.bucket);
+
+    let total: u64 = buckets.iter().map(|entry| entry.count).sum();
+    if total == 0 {
+        return None;
+    }
+
+    let mut seen = 0;
+    for entry in buckets {
+        seen += entry.count;
+        if seen * 2 >= total {
+            return Some(entry.key.bucket);
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn histogram_bucket(bucket: u64, count: u64) -> StatsBucketEntry {
+        StatsBucketEntry {
+            key: StatsEntryKey {
+                stat: OperationKind::Deposit.confirmation_time_stat().to_string(),
+                bucket,
+            },
+            count,
+            amount: 0,
+        }
+    }
+
+    #[test_case(0, 60; "zero")]
+    #[test_case(60, 60; "on-bound")]
+    #[test_case(61, 300; "above-bound")]
+    #[test_case(86401, u64::MAX; "overflow")]
+    fn confirmation_time_bucket(seconds: u64, expected: u64) {
+        let key = StatsEntryKey::confirmation_time(OperationKind::Deposit, seconds);
+        assert_eq!(key.bucket, expected);
+    }
+
+    #[test]
+    fn volume_bucket_is_start_of_hour() {
+        let key = StatsEntryKey::volume(OperationKind::Withdrawal, 7_199);
+        assert_eq!(key.bucket, 3_600);
+        assert_eq!(key.stat, "WithdrawalVolume");
+    }
+
+    #[test]
+    fn recount_counts_statuses_and_volume() {
+        let operations = [
+            ("Pending", 10, Some(3_600)),
+            (CONFIRMED_STATUS_ATTRIBUTE, 20, Some(3_700)),
+            (CONFIRMED_STATUS_ATTRIBUTE, 30, Some(7_200)),
+            ("Failed", 40, None),
+        ];
+        let (counts, volume) = recount(OperationKind::Deposit, operations);
+
+        assert_eq!(counts.key, StatsEntryKey::counts(OperationKind::Deposit));
+        assert_eq!(counts.pending, 1);
+        assert_eq!(counts.confirmed, 2);
+        assert_eq!(counts.failed, 1);
+        assert_eq!(counts.confirmed_amount, 50);
+        assert!(!counts.has_negative_counter());
+
+        let volume: Vec<(u64, u64, u64)> = volume
+            .into_iter()
+            .map(|entry| (entry.key.bucket, entry.count, entry.amount))
+            .collect();
+        assert_eq!(volume, vec![(3_600, 2, 30), (7_200, 1, 30)]);
+    }
+
+    #[test]
+    fn negative_counter_is_detected() {
+        let counts = StatusCountsEntry {
+            accepted: -1,
+            ..Default::default()
+        };
+        assert!(counts.has_negative_counter());
+    }
+
+    #[test]
+    fn median_of_empty_histogram() {
+        assert_eq!(median_confirmation_time(&[]), None);
+        assert_eq!(median_confirmation_time(&[histogram_bucket(60, 0)]), None);
+    }
+
+    #[test]
+    fn median_of_histogram() {
+        let histogram = vec![
+            histogram_bucket(3600, 4),
+            histogram_bucket(60, 1),
+            histogram_bucket(600, 3),
+        ];
+        assert_eq!(median_confirmation_time(&histogram), Some(600));
+    }
+}
@@ -6,6 +6,7 @@ use crate::{
     api::models::{
         chainstate::Chainstate,
         common::WithdrawalStatus,
+        export::{WithdrawalExportRecord, WithdrawalHistoryEvent},
         withdrawal::{Withdrawal, WithdrawalInfo, WithdrawalParameters},
     },
     common::error::{Error, Inconsistency, ValidationError},
@@ -69,6 +70,10 @@ pub struct WithdrawalEntry {
     pub txid: String,
     /// History of this withdrawal transaction.
     pub history: Vec<WithdrawalEvent>,
+    /// Unix timestamp, in seconds, at which Emily first received the
+    /// withdrawal. Absent for withdrawals created before this was recorded.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub created_at: Option<u64>,
 }
 
 /// Implements versioned entry trait for the withdrawal entry.
@@ -213,6 +218,26 @@ impl TryFrom<WithdrawalEntry> for Withdrawal {
     }
 }
 
+impl TryFrom<WithdrawalEntry> for WithdrawalExportRecord {
+    type Error = Error;
+    fn try_from(withdrawal_entry: WithdrawalEntry) -> Result<Self, Self::Error> {
+        let history = withdrawal_entry
+            .history
+            .iter()
+            .map(|event| WithdrawalHistoryEvent {
+                status: (&event.status).into(),
+                message: event.message.clone(),
+                stacks_block_height: event.stacks_block_height,
+                stacks_block_hash: event.stacks_block_hash.clone(),
+            })
+            .collect();
+        Ok(WithdrawalExportRecord {
+            withdrawal: withdrawal_entry.try_into()?,
+            history,
+        })
+    }
+}
+
 /// Withdrawal parameters entry.
 #[derive(Clone, Default, Debug, Eq, PartialEq, Hash, Serialize, Deserialize)]
 #[serde(rename_all = "PascalCase")]
@@ -742,6 +767,7 @@ mod tests {
             last_update_block_hash: "hash".to_string(),
             history: vec![pending, failed.clone()],
             txid: "txid".to_string(),
+            created_at: None,
         };
 
         let withdrawal_update = ValidatedWithdrawalUpdate { request_id: 1, event: failed };
@@ -786,6 +812,7 @@ mod tests {
             last_update_block_hash: "hash".to_string(),
             history: vec![pending.clone()],
             txid: "txid".to_string(),
+            created_at: None,
         };
 
         let withdrawal_update = ValidatedWithdrawalUpdate { request_id: 1, event: failed };
@@ -847,6 +874,7 @@ mod tests {
             last_update_block_hash: "hash6".to_string(),
             history: vec![pending.clone(), accepted.clone(), confirmed.clone()],
             txid: "txid".to_string(),
+            created_at: None,
         };
 
         // Ensure the withdrawal is valid.
@@ -2,7 +2,9 @@
 #![deny(missing_docs)]
 
 pub mod api;
+pub mod bitcoin_node;
 pub mod common;
 pub mod context;
 pub mod database;
 pub mod logging;
+pub mod metrics;
@@ -0,0 +1,45 @@
+//! Metrics recorded by the Emily API.
+//!
+//! No exporter is installed by this crate, so these are no-ops unless the
+//! binary running the handler installs a global recorder.
+
+/// All metrics captured in this crate.
+#[derive(strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum Metrics {
+    /// The total number of create deposit submissions that were rejected
+    /// before being written to the database. We use a label to
+    /// distinguish between the different rejection reasons.
+    DepositSubmissionsRejectedTotal,
+}
+
+impl From<Metrics> for metrics::KeyName {
+    fn from(value: Metrics) -> Self {
+        metrics::KeyName::from_const_str(value.into())
+    }
+}
+
+/// The reason a create deposit submission was rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, strum::IntoStaticStr)]
+#[strum(serialize_all = "snake_case")]
+pub enum DepositRejection {
+    /// The client address exceeded its rate limit.
+    IpRateLimited,
+    /// The deposit recipient exceeded its rate limit.
+    RecipientRateLimited,
+    /// The request body exceeded the configured size limit.
+    BodyTooLarge,
+    /// The submission failed the pre-validation stage.
+    InvalidSubmission,
+    /// The deposit transaction was not found by the bitcoin node.
+    TransactionNotFound,
+}
+
+impl Metrics {
+    /// Increment the counter of rejected create deposit submissions.
+    pub fn increment_deposit_rejected(reason: DepositRejection) {
+        let reason: &'static str = reason.into();
+        metrics::counter!(Metrics::DepositSubmissionsRejectedTotal, "reason" => reason)
+            .increment(1);
+    }
+}
@@ -180,6 +180,7 @@ async fn create_and_get_deposit_happy_path() {
         recipient,
         status: testing_emily_client::models::DepositStatus::Pending,
         status_message: INITIAL_DEPOSIT_STATUS_MESSAGE.into(),
+        bitcoin_block_height: None,
         replaced_by_tx: None,
     };
 
@@ -308,6 +309,7 @@ async fn get_deposits_for_transaction() {
             recipient: recipient.clone(),
             status: testing_emily_client::models::DepositStatus::Pending,
             status_message: INITIAL_DEPOSIT_STATUS_MESSAGE.into(),
+            bitcoin_block_height: None,
             replaced_by_tx: None,
         };
         expected_deposits.push(expected_deposit);
@@ -776,6 +778,7 @@ async fn update_deposits() {
                 recipient: recipient.clone(),
                 status: update_status,
                 status_message: update_status_message.into(),
+                bitcoin_block_height: None,
                 replaced_by_tx: None,
             };
             expected_deposits.push(expected_deposit);
@@ -147,6 +147,8 @@ async fn adding_and_then_updating_single_accout_limit_works() {
             &configuration,
             account_name,
             limit_to_set.clone(),
+            None,
+            None,
         )
         .await
         .expect("Failed to set limit for an account during test.");
@@ -333,12 +335,14 @@ async fn test_updating_account_limits_via_global_limit_works() {
             &configuration,
             account_name,
             limit_to_set.clone(),
+            None,
+            None,
         )
         .await
         .expect("Failed to set limit for an account during test.");
     }
     let global_limits_returned_on_set =
-        apis::limits_api::set_limits(&configuration, global_limits_to_set.clone())
+        apis::limits_api::set_limits(&configuration, global_limits_to_set.clone(), None, None)
             .await
             .expect("Failed to set global limits during test.");
 
@@ -391,9 +395,16 @@ async fn test_incomplete_rolling_withdrawal_limit_config_returns_error(
     };
 
     // Act.
-    let result: StandardError = apis::limits_api::set_limits(&configuration, limits.clone())
+    let result: StandardError = apis::limits_api::set_limits(
+        &configuration,
+        limits.clone(),
+        None,
+        None,
+    )
     .await
-    .expect_err("Expected an error to be returned when setting incomplete withdrawal limit configuration.")
+    .expect_err(
+        "Expected an error to be returned when setting incomplete withdrawal limit configuration.",
+    )
     .into();
 
     // Assert.
@@ -427,7 +438,7 @@ async fn test_complete_rolling_withdrawal_limit_config_works(
         let _ = batch_set_chainstates(&configuration, chainstates).await;
     }
 
-    let result = apis::limits_api::set_limits(&configuration, limits.clone()).await;
+    let result = apis::limits_api::set_limits(&configuration, limits.clone(), None, None).await;
     assert!(result.is_ok());
 
     let global_limits = apis::limits_api::get_limits(&configuration).await;
@@ -458,7 +469,7 @@ async fn test_available_to_withdraw_no_chainstate_in_db_at_target_height() {
         .collect();
     let _ = batch_set_chainstates(&configuration, chainstates).await;
 
-    let result = apis::limits_api::set_limits(&configuration, limits.clone()).await;
+    let result = apis::limits_api::set_limits(&configuration, limits.clone(), None, None).await;
     assert!(result.is_ok());
 
     // Create chainstates
@@ -513,7 +524,7 @@ async fn test_available_to_withdraw_success() {
         .map(|height| new_test_chainstate(height, height, 0))
         .collect();
     let _ = batch_set_chainstates(&configuration, chainstates).await;
-    let result = apis::limits_api::set_limits(&configuration, limits.clone()).await;
+    let result = apis::limits_api::set_limits(&configuration, limits.clone(), None, None).await;
     assert!(result.is_ok());
 
     // Create chainstates
@@ -568,3 +579,79 @@ async fn test_available_to_withdraw_success() {
         .expect("failed to get limits during a valid api call");
     assert_eq!(limits.available_to_withdraw, Some(Some(7000)))
 }
+
+#[tokio::test]
+async fn setting_limits_in_the_past_returns_error() {
+    let configuration = clean_setup().await;
+
+    // Arrange.
+    let limits = Limits {
+        available_to_withdraw: Some(None),
+        peg_cap: Some(Some(100)),
+        per_deposit_minimum: Some(None),
+        per_deposit_cap: Some(None),
+        per_withdrawal_cap: Some(None),
+        rolling_withdrawal_blocks: Some(None),
+        rolling_withdrawal_cap: Some(None),
+        account_caps: HashMap::new(),
+    };
+
+    // Act.
+    let result: StandardError =
+        apis::limits_api::set_limits(&configuration, limits.clone(), None, Some(1))
+            .await
+            .expect_err("Expected an error to be returned when scheduling limits in the past.")
+            .into();
+
+    // Assert.
+    assert_eq!(result.status_code, 400);
+}
+
+#[tokio::test]
+async fn scheduled_limits_are_in_the_history_but_not_in_effect() {
+    let configuration = clean_setup().await;
+
+    // Arrange.
+    let account_name = "test_account";
+    let account_limits = AccountLimits {
+        peg_cap: Some(Some(100)),
+        per_deposit_minimum: Some(None),
+        per_deposit_cap: Some(None),
+        per_withdrawal_cap: Some(None),
+        rolling_withdrawal_blocks: Some(None),
+        rolling_withdrawal_cap: Some(None),
+    };
+    let effective_at_timestamp = u64::MAX / 2;
+
+    // Act.
+    apis::limits_api::set_limits_for_account(
+        &configuration,
+        account_name,
+        account_limits.clone(),
+        None,
+        Some(effective_at_timestamp),
+    )
+    .await
+    .expect("Failed to schedule limits for an account during test.");
+
+    let history = apis::limits_api::get_limits_history(
+        &configuration,
+        Some(account_name),
+        None,
+        None,
+        None,
+        None,
+    )
+    .await
+    .expect("Failed to get the limits history during test.");
+    let in_effect = apis::limits_api::get_limits_for_account(&configuration, account_name).await;
+
+    // Assert.
+    assert_eq!(history.entries.len(), 1);
+    assert_eq!(history.entries[0].timestamp, effective_at_timestamp);
+    assert_eq!(*history.entries[0].limits, account_limits);
+    let error: StandardError = in_effect
+        .expect_err("Expected scheduled limits not to be in effect yet.")
+        .into();
+    assert_eq!(error.status_code, 404);
+}
@@ -36,11 +36,18 @@ use super::CorsSupport;
         // api::handlers::testing::wipe_databases, // The admin endpoint cannot perform testing operations.
         // Limits endpoints.
         api::handlers::limits::get_limits,
+        api::handlers::limits::get_limits_history,
         api::handlers::limits::set_limits,
         api::handlers::limits::get_limits_for_account,
         api::handlers::limits::set_limits_for_account,
         // New block endpoints.
         api::handlers::new_block::new_block,
+        // Stats endpoints.
+        api::handlers::stats::get_stats,
+        api::handlers::stats::get_volume,
+        // Export endpoints.
+        api::handlers::export::export_deposits,
+        api::handlers::export::export_withdrawals,
     ),
     // Components to be included in the OpenAPI specification.
     components(schemas(
@@ -76,6 +83,17 @@ use super::CorsSupport;
         // Limits models
         api::models::limits::Limits,
         api::models::limits::AccountLimits,
+        api::models::limits::LimitHistoryEntry,
+        api::models::limits::GetLimitsHistoryResponse,
+        // Stats models
+        api::models::stats::GetStatsResponse,
+        api::models::stats::DepositStats,
+        api::models::stats::WithdrawalStats,
+        api::models::stats::StatsOperation,
+        api::models::stats::VolumeBucket,
+        api::models::stats::GetVolumeResponse,
+        // Export models
+        api::models::export::ExportFormat,
         // New Block models.
         api::models::new_block::NewBlockEventRaw,
         // Errors.
@@ -32,7 +32,11 @@ use super::CorsSupport;
         api::handlers::chainstate::get_chainstate_at_height,
         // Limits endpoints.
         api::handlers::limits::get_limits,
+        api::handlers::limits::get_limits_history,
         api::handlers::limits::get_limits_for_account,
+        // Stats endpoints.
+        api::handlers::stats::get_stats,
+        api::handlers::stats::get_volume,
     ),
     // Components to be included in the OpenAPI specification.
     components(schemas(
@@ -67,6 +71,15 @@ use super::CorsSupport;
         // Limits models
         api::models::limits::Limits,
         api::models::limits::AccountLimits,
+        api::models::limits::LimitHistoryEntry,
+        api::models::limits::GetLimitsHistoryResponse,
+        // Stats models
+        api::models::stats::GetStatsResponse,
+        api::models::stats::DepositStats,
+        api::models::stats::WithdrawalStats,
+        api::models::stats::StatsOperation,
+        api::models::stats::VolumeBucket,
+        api::models::stats::GetVolumeResponse,
         // Errors.
         common::error::ErrorResponse,
     ))
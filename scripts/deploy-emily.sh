@@ -20,13 +20,14 @@ fi
 # Build emily.
 {
     cd "${EMILY_HANDLER_PATH}" || exit
-    echo "Building emily-lambda..."
+    echo "Building emily-lambda and emily-deposit-monitor..."
     cargo lambda build \
         --bin emily-lambda \
+        --bin emily-deposit-monitor \
         --release \
         --output-format zip \
         --x86-64
-    echo "Done building emily lambda."
+    echo "Done building emily lambdas."
 }
 
 {
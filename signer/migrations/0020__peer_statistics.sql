@@ -0,0 +1,24 @@
+-- Stores statistics about how reliably each signer has taken part in
+-- coordinating and signing, as observed by this signer.
+CREATE TABLE peer_statistics (
+    -- The public key of the peer
+    public_key BYTEA PRIMARY KEY,
+    -- The number of bitcoin tenures that the peer started coordinating.
+    tenures_coordinated BIGINT NOT NULL DEFAULT 0,
+    -- The number of bitcoin tenures that the peer was due to coordinate but
+    -- did not, so that a backup coordinator took over.
+    tenures_missed BIGINT NOT NULL DEFAULT 0,
+    -- The number of WSTS signing rounds that the peer contributed a
+    -- signature share to.
+    rounds_completed BIGINT NOT NULL DEFAULT 0,
+    -- The number of WSTS signing rounds that timed out without the peer
+    -- sending its nonce.
+    nonce_timeouts BIGINT NOT NULL DEFAULT 0,
+    -- The number of WSTS signing rounds that timed out after the peer sent
+    -- its nonce but without it sending its signature share.
+    signature_share_timeouts BIGINT NOT NULL DEFAULT 0,
+    -- The number of messages from the peer that were rejected as invalid.
+    invalid_messages BIGINT NOT NULL DEFAULT 0,
+    -- Timestamp of the last update to this peer's statistics.
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
@@ -0,0 +1,15 @@
+-- Stores the approvals of proposals to change the signing set, as sent by
+-- the signers over the P2P network. Each signer approves at most one
+-- proposal at a time.
+CREATE TABLE signer_set_approvals (
+    -- The public key of the signer approving the proposal.
+    approver_public_key BYTEA PRIMARY KEY,
+    -- The public keys of the proposed signing set, in ascending order.
+    signer_set_public_keys BYTEA[] NOT NULL,
+    -- The proposed number of signatures required.
+    signatures_required INTEGER NOT NULL,
+    -- The bitcoin chain tip of the signer when it sent the approval.
+    bitcoin_chain_tip BYTEA NOT NULL,
+    -- Timestamp of the last update to this signer's approval.
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
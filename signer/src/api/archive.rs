@@ -0,0 +1,302 @@
+//! This module contains the archive of the raw bodies of the
+//! `POST /new_block` webhooks, which allows for replaying them later on.
+//!
+//! The bodies are written to segment files named `new-block-<n>.gz` in the
+//! archive directory, where `n` increases with every new segment. Each
+//! body is appended to the current segment as its own gzip member, which
+//! holds the length of the body as a big-endian u32 followed by the body
+//! itself. Since every member is complete on its own, a segment that was
+//! cut short, say because the signer stopped in the middle of a write, can
+//! still be read up to its last complete body. A new segment is started
+//! whenever the archive is opened, so that no body is appended after such
+//! a truncated one.
+
+use std::fs::File;
+use std::fs::OpenOptions;
+use std::io::BufReader;
+use std::io::ErrorKind;
+use std::io::Read as _;
+use std::io::Write as _;
+use std::path::Path;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+
+use flate2::Compression;
+use flate2::read::MultiGzDecoder;
+use flate2::write::GzEncoder;
+
+use crate::config::EventArchiveConfig;
+use crate::error::Error;
+
+/// The prefix of the file names of the archive segments.
+const SEGMENT_PREFIX: &str = "new-block-";
+
+/// The extension of the file names of the archive segments.
+const SEGMENT_EXTENSION: &str = ".gz";
+
+/// An archive of the raw bodies of the `POST /new_block` webhooks,
+/// compressed and rotated across segment files.
+#[derive(Debug)]
+pub struct EventArchive {
+    /// The directory of the archive and its rotation settings.
+    config: EventArchiveConfig,
+    /// The sequence number of the segment that bodies are appended to.
+    current: Mutex<u64>,
+}
+
+impl EventArchive {
+    /// Open the archive in the configured directory, creating the
+    /// directory if it does not exist. Bodies are appended to a new
+    /// segment following the latest existing one.
+    pub fn new(config: EventArchiveConfig) -> Result<Self, Error> {
+        std::fs::create_dir_all(&config.path)
+            .map_err(|err| Error::EventArchive(err, config.path.clone()))?;
+
+        let current = list_segments(&config.path)?
+            .last()
+            .map(|(sequence, _)| sequence + 1)
+            .unwrap_or_default();
+
+        Ok(Self {
+            config,
+            current: Mutex::new(current),
+        })
+    }
+
+    /// Append the given webhook body to the archive. A new segment is
+    /// started if the current one has reached its maximum size, and the
+    /// oldest segments are removed whenever a segment is created.
+    pub fn record(&self, body: &str) -> Result<(), Error> {
+        let mut current = self.current.lock().unwrap_or_else(PoisonError::into_inner);
+
+        let mut path = segment_path(&self.config.path, *current);
+        let size = std::fs::metadata(&path).map(|meta| meta.len()).unwrap_or(0);
+        let rotate = size >= self.config.max_segment_size;
+        if rotate {
+            *current += 1;
+            path = segment_path(&self.config.path, *current);
+        }
+        let created = rotate || size == 0;
+
+        let member = encode_member(body).map_err(|err| Error::EventArchive(err, path.clone()))?;
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&path)
+            .and_then(|mut file| file.write_all(&member))
+            .map_err(|err| Error::EventArchive(err, path.clone()))?;
+
+        if created {
+            self.prune()?;
+        }
+        Ok(())
+    }
+
+    /// Remove the oldest segments so that at most the configured number
+    /// of segments remain.
+    fn prune(&self) -> Result<(), Error> {
+        let segments = list_segments(&self.config.path)?;
+        let excess = segments
+            .len()
+            .saturating_sub(self.config.max_segments as usize);
+
+        for (_, path) in segments.into_iter().take(excess) {
+            tracing::debug!(path = %path.display(), "removing old new block archive segment");
+            std::fs::remove_file(&path).map_err(|err| Error::EventArchive(err, path))?;
+        }
+        Ok(())
+    }
+}
+
+/// Compress the given body into a gzip member holding the length of the
+/// body followed by the body itself.
+fn encode_member(body: &str) -> std::io::Result<Vec<u8>> {
+    let length = u32::try_from(body.len())
+        .map_err(|_| std::io::Error::new(ErrorKind::InvalidInput, "body is too large"))?;
+
+    let mut encoder = GzEncoder::new(Vec::new(), Compression::default());
+    encoder.write_all(&length.to_be_bytes())?;
+    encoder.write_all(body.as_bytes())?;
+    encoder.finish()
+}
+
+/// The path of the segment with the given sequence number.
+fn segment_path(dir: &Path, sequence: u64) -> PathBuf {
+    dir.join(format!("{SEGMENT_PREFIX}{sequence:010}{SEGMENT_EXTENSION}"))
+}
+
+/// List the segments in the given directory along with their sequence
+/// numbers, oldest first.
+fn list_segments(dir: &Path) -> Result<Vec<(u64, PathBuf)>, Error> {
+    let entries =
+        std::fs::read_dir(dir).map_err(|err| Error::EventArchive(err, dir.to_path_buf()))?;
+
+    let mut segments = Vec::new();
+    for entry in entries {
+        let path = entry
+            .map_err(|err| Error::EventArchive(err, dir.to_path_buf()))?
+            .path();
+        let sequence = path
+            .file_name()
+            .and_then(|name| name.to_str())
+            .and_then(|name| name.strip_prefix(SEGMENT_PREFIX))
+            .and_then(|name| name.strip_suffix(SEGMENT_EXTENSION))
+            .and_then(|sequence| sequence.parse::<u64>().ok());
+
+        if let Some(sequence) = sequence {
+            segments.push((sequence, path));
+        }
+    }
+
+    segments.sort_unstable();
+    Ok(segments)
+}
+
+/// Return the paths of the archived segments at the given path, oldest
+/// first. The path may either be an archive directory or a single
+/// segment file.
+pub fn archive_segments(path: &Path) -> Result<Vec<PathBuf>, Error> {
+    if path.is_file() {
+        return Ok(vec![path.to_path_buf()]);
+    }
+
+    let segments = list_segments(path)?;
+    Ok(segments.into_iter().map(|(_, path)| path).collect())
+}
+
+/// A reader of the webhook bodies archived in a segment, in the order in
+/// which they were received.
+pub struct SegmentReader {
+    /// The path of the segment being read.
+    path: PathBuf,
+    /// The decompressed contents of the segment.
+    decoder: MultiGzDecoder<BufReader<File>>,
+}
+
+impl SegmentReader {
+    /// Open the segment at the given path.
+    pub fn open(path: &Path) -> Result<Self, Error> {
+        let file = File::open(path).map_err(|err| Error::EventArchive(err, path.to_path_buf()))?;
+
+        Ok(Self {
+            path: path.to_path_buf(),
+            decoder: MultiGzDecoder::new(BufReader::new(file)),
+        })
+    }
+
+    /// Read the next body from the segment. Returns `None` at the end of
+    /// the segment, or if the remainder of the segment is truncated.
+    fn read_body(&mut self) -> std::io::Result<Option<String>> {
+        let mut length = [0; 4];
+        if let Err(error) = self.decoder.read_exact(&mut length) {
+            return Self::end_of_segment(error);
+        }
+
+        let mut body = vec![0; u32::from_be_bytes(length) as usize];
+        if let Err(error) = self.decoder.read_exact(&mut body) {
+            return Self::end_of_segment(error);
+        }
+
+        String::from_utf8(body)
+            .map(Some)
+            .map_err(|err| std::io::Error::new(ErrorKind::InvalidData, err))
+    }
+
+    /// Treat a read that ran out of data as the end of the segment.
+    fn end_of_segment(error: std::io::Error) -> std::io::Result<Option<String>> {
+        match error.kind() {
+            ErrorKind::UnexpectedEof => Ok(None),
+            _ => Err(error),
+        }
+    }
+}
+
+impl Iterator for SegmentReader {
+    type Item = Result<String, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.read_body()
+            .map_err(|err| Error::EventArchive(err, self.path.clone()))
+            .transpose()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(path: &Path, max_segment_size: u64, max_segments: u16) -> EventArchiveConfig {
+        EventArchiveConfig {
+            path: path.to_path_buf(),
+            max_segment_size,
+            max_segments,
+        }
+    }
+
+    fn read_all(path: &Path) -> Vec<String> {
+        archive_segments(path)
+            .unwrap()
+            .iter()
+            .flat_map(|segment| SegmentReader::open(segment).unwrap())
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn archived_bodies_are_read_back_in_order() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = EventArchive::new(config(dir.path(), 1024 * 1024, 4)).unwrap();
+
+        let bodies = ["{\"block_height\": 1}", "", "{\n  \"block_height\": 2\n}"];
+        for body in bodies {
+            archive.record(body).unwrap();
+        }
+
+        assert_eq!(archive_segments(dir.path()).unwrap().len(), 1);
+        assert_eq!(read_all(dir.path()), bodies);
+    }
+
+    #[test]
+    fn segments_are_rotated_and_pruned() {
+        let dir = tempfile::tempdir().unwrap();
+        // Every body is larger than the maximum segment size, so each one
+        // goes into its own segment.
+        let archive = EventArchive::new(config(dir.path(), 1, 3)).unwrap();
+
+        let bodies: Vec<String> = (0..5)
+            .map(|n| format!("{{\"block_height\": {n}}}"))
+            .collect();
+        for body in &bodies {
+            archive.record(body).unwrap();
+        }
+
+        let segments = archive_segments(dir.path()).unwrap();
+        assert_eq!(segments.len(), 3);
+        assert_eq!(read_all(dir.path()), bodies[2..]);
+
+        // Reopening the archive starts a segment after the latest one.
+        let archive = EventArchive::new(config(dir.path(), 1024 * 1024, 3)).unwrap();
+        archive.record("{\"block_height\": 5}").unwrap();
+        let segments = archive_segments(dir.path()).unwrap();
+        assert_eq!(segments.len(), 3);
+        assert_eq!(segments.last(), Some(&segment_path(dir.path(), 5)));
+    }
+
+    #[test]
+    fn truncated_segments_are_read_up_to_the_last_complete_body() {
+        let dir = tempfile::tempdir().unwrap();
+        let archive = EventArchive::new(config(dir.path(), 1024 * 1024, 4)).unwrap();
+        archive.record("{\"block_height\": 1}").unwrap();
+        archive.record("{\"block_height\": 2}").unwrap();
+
+        // Cut the last body in half, as if the signer stopped in the
+        // middle of writing it.
+        let path = segment_path(dir.path(), 0);
+        let contents = std::fs::read(&path).unwrap();
+        let last = encode_member("{\"block_height\": 2}").unwrap();
+        std::fs::write(&path, &contents[..contents.len() - last.len() / 2]).unwrap();
+
+        assert_eq!(read_all(dir.path()), ["{\"block_height\": 1}"]);
+    }
+}
@@ -0,0 +1,200 @@
+//! This module contains the backfill of the sBTC registry events of the
+//! stacks blocks that are missing from the database, say because the
+//! database was restored from an older backup or the signer was pointed at
+//! a different stacks node.
+//!
+//! The raw stacks blocks returned by the stacks node do not include the
+//! transaction receipts, so the events cannot be recovered from the blocks
+//! themselves. Instead, the missing blocks are found by walking the stacks
+//! blockchain back from its tip, and the `POST /new_block` webhook bodies
+//! of those blocks are replayed from the event archive through
+//! [`new_block_handler`].
+
+use std::collections::HashSet;
+use std::path::Path;
+
+use axum::extract::State;
+use stacks_common::types::chainstate::StacksBlockId;
+
+use crate::context::Context;
+use crate::error::Error;
+use crate::stacks::api::StacksInteract as _;
+use crate::stacks::api::TenureBlockHeaders;
+use crate::stacks::api::fetch_unknown_ancestors;
+use crate::storage::DbRead as _;
+use crate::storage::DbWrite as _;
+
+use super::ApiState;
+use super::SegmentReader;
+use super::archive_segments;
+use super::new_block::NewBlockIdentifier;
+use super::new_block::new_block_handler;
+
+/// The outcome of a backfill.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct BackfillSummary {
+    /// The number of stacks blocks that were missing from the database.
+    pub missing_blocks: usize,
+    /// The number of missing blocks whose archived webhook body was
+    /// replayed.
+    pub replayed: usize,
+    /// The missing blocks without an archived webhook body. Their headers
+    /// are written to the database, but any sBTC events in them are still
+    /// missing.
+    pub unarchived: Vec<StacksBlockId>,
+}
+
+/// Backfill the events of the stacks blocks that are missing from the
+/// database from the webhook bodies archived at the given path, which may
+/// either be an archive directory or a single archive segment.
+///
+/// The stacks blocks are walked back from the tip of the stacks node with
+/// [`fetch_unknown_ancestors`], the same way that the block observer
+/// does. The archived bodies of the missing blocks are then replayed, in
+/// the order in which they were received, and the headers of the missing
+/// blocks are written last. So if the backfill fails part way through it
+/// can simply be run again.
+///
+/// The block observer writes the headers of new stacks blocks without
+/// their events, so the backfill should be run before the signer is
+/// started on a restored database.
+pub async fn backfill_new_block_events<C>(ctx: &C, archive: &Path) -> Result<BackfillSummary, Error>
+where
+    C: Context + 'static,
+{
+    let stacks = ctx.get_stacks_client();
+    let db = ctx.get_storage_mut();
+
+    let tenure_info = stacks.get_tenure_info().await?;
+    let tenures = fetch_unknown_ancestors(&stacks, &db, tenure_info.tip_block_id).await?;
+
+    // The tenure of the tip is always returned, even if some of its
+    // blocks are already known.
+    let mut missing = Vec::new();
+    for block in tenures.into_iter().flat_map(TenureBlockHeaders::into_iter) {
+        if !db.stacks_block_exists(block.block_hash.into()).await? {
+            missing.push(block);
+        }
+    }
+
+    let mut unreplayed: HashSet<StacksBlockId> = missing
+        .iter()
+        .map(|block| block.block_hash.into())
+        .collect();
+    tracing::info!(missing_blocks = %missing.len(), "backfilling stacks blocks");
+
+    for segment in archive_segments(archive)? {
+        for body in SegmentReader::open(&segment)? {
+            let body = body?;
+            let Ok(block) = serde_json::from_str::<NewBlockIdentifier>(&body) else {
+                continue;
+            };
+            // Several stacks nodes may have pushed the same block, and
+            // one push is all we need.
+            if !unreplayed.remove(&block.index_block_hash) {
+                continue;
+            }
+
+            // The archive is not enabled, so that replayed bodies are not
+            // archived a second time.
+            let state = State(ApiState {
+                ctx: ctx.clone(),
+                archive: None,
+                event_sources: None,
+            });
+            let status = new_block_handler(state, body).await;
+            if !status.is_success() {
+                return Err(Error::NewBlockBackfill(block.index_block_hash, status));
+            }
+        }
+    }
+
+    let unarchived: Vec<StacksBlockId> = missing
+        .iter()
+        .map(|block| block.block_hash.into())
+        .filter(|block_id| unreplayed.contains(block_id))
+        .collect();
+    if !unarchived.is_empty() {
+        tracing::warn!(
+            blocks = ?unarchived,
+            "some missing stacks blocks are not in the archive, so their events are still missing"
+        );
+    }
+
+    let summary = BackfillSummary {
+        missing_blocks: missing.len(),
+        replayed: missing.len() - unarchived.len(),
+        unarchived,
+    };
+    db.write_stacks_block_headers(missing).await?;
+
+    Ok(summary)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use rand::SeedableRng as _;
+
+    use crate::api::EventArchive;
+    use crate::config::EventArchiveConfig;
+    use crate::testing::block_observer::TestHarness;
+    use crate::testing::context::*;
+
+    const COMPLETED_DEPOSIT_WEBHOOK: &str =
+        include_str!("../../tests/fixtures/completed-deposit-event.json");
+
+    /// The completed deposit webhook body, as if it was pushed for the
+    /// given block.
+    fn completed_deposit_webhook(block_id: StacksBlockId) -> String {
+        let mut body: serde_json::Value = serde_json::from_str(COMPLETED_DEPOSIT_WEBHOOK).unwrap();
+        body["index_block_hash"] = format!("0x{}", block_id.to_hex()).into();
+        body.to_string()
+    }
+
+    #[tokio::test]
+    async fn missing_blocks_are_backfilled_from_the_archive() {
+        let mut rng = rand::rngs::StdRng::seed_from_u64(51);
+        let test_harness = TestHarness::generate(&mut rng, 3, 2..3);
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_stacks_client(test_harness.clone())
+            .with_emily_client(test_harness.clone())
+            .with_bitcoin_client(test_harness.clone())
+            .build();
+
+        // Only the tip was pushed to the signer before its database was
+        // lost.
+        let (tip, _, _) = test_harness.stacks_blocks().last().unwrap();
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventArchiveConfig {
+            path: dir.path().to_path_buf(),
+            max_segment_size: 1024 * 1024,
+            max_segments: 2,
+        };
+        let archive = EventArchive::new(config).unwrap();
+        archive.record(&completed_deposit_webhook(*tip)).unwrap();
+        archive.record(&completed_deposit_webhook(*tip)).unwrap();
+
+        let summary = backfill_new_block_events(&ctx, dir.path()).await.unwrap();
+
+        // The test harness puts every tenure on the nakamoto start height,
+        // so only the blocks of the tenure of the tip are walked.
+        assert_eq!(summary.missing_blocks, 2);
+        assert_eq!(summary.replayed, 1);
+        assert_eq!(summary.unarchived.len(), 1);
+        assert!(!summary.unarchived.contains(tip));
+
+        let db = ctx.inner_storage();
+        let store = db.lock().await;
+        assert!(!store.completed_deposit_events.is_empty());
+        assert!(store.stacks_blocks.contains_key(&(*tip).into()));
+        drop(store);
+
+        // Everything is in the database now, so there is nothing left to
+        // backfill.
+        let summary = backfill_new_block_events(&ctx, dir.path()).await.unwrap();
+        assert_eq!(summary, BackfillSummary::default());
+    }
+}
@@ -0,0 +1,226 @@
+//! This module keeps track of the stacks nodes pushing events to the event
+//! observer, for when the signer is fed by several independent nodes.
+//!
+//! Every node pushes every block, so the events of a block are handled for
+//! the first push of the block that succeeds, and later pushes of the same
+//! block are acknowledged without handling them again. The latest block
+//! pushed by each node is tracked so that nodes disagreeing about the
+//! stacks chain tip are reported, and the configured nodes that stop
+//! pushing blocks are reported as stale.
+
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::net::IpAddr;
+use std::num::NonZeroUsize;
+use std::sync::Mutex;
+use std::sync::PoisonError;
+use std::time::Duration;
+use std::time::Instant;
+
+use lru::LruCache;
+use stacks_common::types::chainstate::StacksBlockId;
+
+use crate::config::EventObserverConfig;
+use crate::metrics::Metrics;
+
+/// The number of recently handled blocks that are remembered in order to
+/// deduplicate the pushes of several nodes.
+const HANDLED_BLOCKS_CAPACITY: NonZeroUsize = NonZeroUsize::new(1024).expect("1024 is non zero");
+
+/// The latest block pushed by a stacks node.
+#[derive(Debug, Clone, Copy)]
+struct SourceTip {
+    /// The ID of the block.
+    block_id: StacksBlockId,
+    /// The height of the block.
+    block_height: u64,
+    /// When the block was pushed.
+    pushed_at: Instant,
+}
+
+/// The mutable state of the event sources.
+#[derive(Debug)]
+struct EventSourcesState {
+    /// The blocks whose events were recently handled.
+    handled_blocks: LruCache<StacksBlockId, ()>,
+    /// The latest block pushed by each node.
+    tips: HashMap<IpAddr, SourceTip>,
+    /// The configured nodes that are currently reported as stale.
+    stale: HashSet<IpAddr>,
+}
+
+/// The stacks nodes pushing events to the event observer.
+#[derive(Debug)]
+pub struct EventSources {
+    /// The addresses of the nodes that are expected to push events.
+    sources: Vec<IpAddr>,
+    /// The time after which a node that has not pushed any block is
+    /// reported as stale.
+    stale_after: Duration,
+    /// When the event observer started, which stands in for the time of
+    /// the last push of the nodes that have not pushed anything yet.
+    started_at: Instant,
+    /// The mutable state.
+    state: Mutex<EventSourcesState>,
+}
+
+impl EventSources {
+    /// Create a new tracker for the event sources of the given event
+    /// observer config, or `None` if no event sources are configured.
+    pub fn new(config: &EventObserverConfig) -> Option<Self> {
+        if config.sources.is_empty() {
+            return None;
+        }
+
+        let state = EventSourcesState {
+            handled_blocks: LruCache::new(HANDLED_BLOCKS_CAPACITY),
+            tips: HashMap::new(),
+            stale: HashSet::new(),
+        };
+
+        Some(Self {
+            sources: config.sources.clone(),
+            stale_after: config.source_stale_after,
+            started_at: Instant::now(),
+            state: Mutex::new(state),
+        })
+    }
+
+    /// Record that the given node pushed the given block, reporting any
+    /// disagreement with the other nodes about the stacks chain tip.
+    pub fn observe(&self, source: IpAddr, block_id: StacksBlockId, block_height: u64) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        for (other, tip) in state.tips.iter() {
+            if *other != source && tip.block_height == block_height && tip.block_id != block_id {
+                tracing::warn!(
+                    %source,
+                    %block_id,
+                    %block_height,
+                    other_source = %other,
+                    other_block_id = %tip.block_id,
+                    "stacks nodes disagree about the stacks chain tip"
+                );
+                Metrics::increment_stacks_event_source_tip_disagreements();
+            }
+        }
+
+        let tip = SourceTip {
+            block_id,
+            block_height,
+            pushed_at: Instant::now(),
+        };
+        state.tips.insert(source, tip);
+
+        if state.stale.remove(&source) {
+            tracing::info!(%source, "stacks node resumed pushing events");
+            Metrics::set_stacks_event_source_stale(source, false);
+        }
+    }
+
+    /// Whether the events of the given block were already handled for the
+    /// push of another node.
+    pub fn is_handled(&self, block_id: &StacksBlockId) -> bool {
+        let state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.handled_blocks.contains(block_id)
+    }
+
+    /// Record that the events of the given block were handled, so that
+    /// they are not handled again for the pushes of the other nodes.
+    pub fn mark_handled(&self, block_id: StacksBlockId) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+        state.handled_blocks.put(block_id, ());
+    }
+
+    /// Report the configured nodes that have not pushed a block for too
+    /// long as stale.
+    pub fn check_stale(&self) {
+        self.check_stale_at(Instant::now());
+    }
+
+    /// Report the configured nodes that have not pushed a block for too
+    /// long, as of the given time, as stale.
+    fn check_stale_at(&self, now: Instant) {
+        let mut state = self.state.lock().unwrap_or_else(PoisonError::into_inner);
+
+        for source in &self.sources {
+            let last_push = state
+                .tips
+                .get(source)
+                .map_or(self.started_at, |tip| tip.pushed_at);
+            let silence = now.saturating_duration_since(last_push);
+
+            if silence >= self.stale_after && state.stale.insert(*source) {
+                tracing::error!(
+                    %source,
+                    silence_secs = silence.as_secs(),
+                    "stacks node stopped pushing events"
+                );
+                Metrics::set_stacks_event_source_stale(*source, true);
+            }
+        }
+    }
+
+    /// Whether the given node is currently reported as stale.
+    #[cfg(test)]
+    fn is_stale(&self, source: &IpAddr) -> bool {
+        let state = self.state.lock().unwrap();
+        state.stale.contains(source)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn config(sources: &[IpAddr]) -> EventObserverConfig {
+        EventObserverConfig {
+            bind: "127.0.0.1:8801".parse().unwrap(),
+            archive: None,
+            sources: sources.to_vec(),
+            source_stale_after: Duration::from_secs(60),
+        }
+    }
+
+    fn ip(last_octet: u8) -> IpAddr {
+        IpAddr::from([10, 0, 0, last_octet])
+    }
+
+    #[test]
+    fn no_sources_configured() {
+        assert!(EventSources::new(&config(&[])).is_none());
+    }
+
+    #[test]
+    fn blocks_are_handled_once() {
+        let sources = EventSources::new(&config(&[ip(1), ip(2)])).unwrap();
+        let block_id = StacksBlockId([1; 32]);
+
+        // The block is only handled once the handling of one of its
+        // pushes succeeded.
+        assert!(!sources.is_handled(&block_id));
+        sources.mark_handled(block_id);
+
+        assert!(sources.is_handled(&block_id));
+        assert!(!sources.is_handled(&StacksBlockId([2; 32])));
+    }
+
+    #[test]
+    fn silent_sources_are_stale() {
+        let sources = EventSources::new(&config(&[ip(1), ip(2)])).unwrap();
+        sources.observe(ip(1), StacksBlockId([1; 32]), 10);
+
+        sources.check_stale_at(sources.started_at + Duration::from_secs(30));
+        assert!(!sources.is_stale(&ip(1)));
+        assert!(!sources.is_stale(&ip(2)));
+
+        // The second source never pushed anything, while the first one
+        // pushed a block after the event observer started.
+        sources.check_stale_at(sources.started_at + Duration::from_secs(60));
+        assert!(!sources.is_stale(&ip(1)));
+        assert!(sources.is_stale(&ip(2)));
+
+        sources.observe(ip(2), StacksBlockId([1; 32]), 10);
+        assert!(!sources.is_stale(&ip(2)));
+    }
+}
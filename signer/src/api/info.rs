@@ -8,6 +8,8 @@ use crate::{
     bitcoin::BitcoinInteract,
     config::Settings,
     context::Context,
+    keys::PublicKey,
+    network::version::{PROTOCOL_VERSION, ProtocolVersions},
     stacks::api::StacksInteract,
     storage::{
         DbRead,
@@ -22,6 +24,7 @@ pub struct InfoResponse {
     pub bitcoin: BitcoinInfo,
     pub stacks: StacksInfo,
     pub dkg: DkgInfo,
+    pub p2p: P2PInfo,
     pub config: Option<ConfigInfo>,
     pub build_info: BuildInfo,
     pub timestamp: String,
@@ -82,6 +85,30 @@ pub struct DkgInfo {
     pub contract_aggregate_key: Option<String>,
 }
 
+#[derive(Debug, Serialize)]
+pub struct P2PInfo {
+    /// The signer protocol versions supported by this signer.
+    pub protocol_versions: ProtocolVersions,
+    /// The highest signer protocol version supported by all connected
+    /// peers that have identified themselves.
+    pub protocol_version: u32,
+    /// Whether any connected peer supports a different range of signer
+    /// protocol versions than this signer, as happens during a rolling
+    /// upgrade.
+    pub version_skew: bool,
+    pub peers: Vec<PeerVersionInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PeerVersionInfo {
+    pub peer_id: String,
+    pub public_key: Option<PublicKey>,
+    pub agent_version: Option<String>,
+    pub protocol_versions: Option<ProtocolVersions>,
+    /// The signer protocol version negotiated with the peer.
+    pub protocol_version: Option<u32>,
+}
+
 impl Default for InfoResponse {
     fn default() -> Self {
         // This is a build-time constant and varies per build environment,
@@ -101,6 +128,12 @@ impl Default for InfoResponse {
                 current_aggregate_key: None,
                 contract_aggregate_key: None,
             },
+            p2p: P2PInfo {
+                protocol_versions: ProtocolVersions::SUPPORTED,
+                protocol_version: PROTOCOL_VERSION,
+                version_skew: false,
+                peers: Vec::new(),
+            },
             config: None,
             build_info: BuildInfo {
                 rust_version: crate::RUSTC_VERSION,
@@ -135,6 +168,7 @@ pub async fn build_info<C: Context>(ctx: &C) -> InfoResponse {
     let mut response = InfoResponse::default();
 
     response.populate_config_info(config);
+    response.populate_p2p_info(ctx);
     response.populate_local_chain_info(&storage, ctx).await;
     response.populate_bitcoin_node_info(&bitcoin_client).await;
     response.populate_stacks_node_info(&stacks_client).await;
@@ -167,6 +201,29 @@ impl InfoResponse {
         });
     }
 
+    /// Populates the signer protocol versions of the connected peers.
+    fn populate_p2p_info<C: Context>(&mut self, ctx: &C) {
+        let connected_peers = ctx.state().connected_peers();
+        let signer_set = ctx.state().current_signer_set();
+
+        self.p2p.protocol_version = connected_peers.protocol_version();
+        self.p2p.peers = connected_peers
+            .get_peers()
+            .into_iter()
+            .map(|(peer_id, peer)| PeerVersionInfo {
+                peer_id: peer_id.to_string(),
+                public_key: signer_set.get_pubkey_for_peer(&peer_id),
+                agent_version: peer.agent_version,
+                protocol_versions: peer.protocol_versions,
+                protocol_version: peer.protocol_version,
+            })
+            .collect();
+        self.p2p.version_skew = self.p2p.peers.iter().any(|peer| {
+            peer.protocol_versions
+                .is_some_and(|versions| versions != ProtocolVersions::SUPPORTED)
+        });
+    }
+
     /// Populates the local Bitcoin and Stacks chain tip information.
     async fn populate_local_chain_info<C: Context, R: DbRead>(&mut self, storage: &R, ctx: &C) {
         let bitcoin_tip = ctx.state().bitcoin_chain_tip();
@@ -325,9 +382,11 @@ mod tests {
     use blockstack_lib::net::api::{getinfo::RPCPeerInfoData, gettenureinfo::RPCGetTenureInfo};
     use clarity::types::chainstate::StacksAddress;
     use fake::{Fake as _, Faker};
+    use libp2p::{Multiaddr, swarm::ConnectionId};
 
     use crate::{
         api::ApiState,
+        context::{ConnectionDirection, PeerConnection},
         error::Error,
         storage::{
             DbWrite as _,
@@ -375,7 +434,11 @@ mod tests {
             })
             .await;
 
-        let state = State(ApiState { ctx: context });
+        let state = State(ApiState {
+            ctx: context,
+            archive: None,
+            event_sources: None,
+        });
         let result = info_handler(state).await;
 
         // Assert bitcoin info
@@ -391,6 +454,12 @@ mod tests {
         // Assert config info
         assert!(result.config.is_some());
 
+        // Assert P2P info
+        assert_eq!(result.p2p.protocol_versions, ProtocolVersions::SUPPORTED);
+        assert_eq!(result.p2p.protocol_version, PROTOCOL_VERSION);
+        assert!(!result.p2p.version_skew);
+        assert!(result.p2p.peers.is_empty());
+
         // Assert DKG info
         assert!(result.dkg.contract_aggregate_key.is_none());
         assert!(result.dkg.current_aggregate_key.is_none());
@@ -460,7 +529,11 @@ mod tests {
         };
         storage.write_stacks_block(&stacks_block).await.unwrap();
 
-        let state = State(ApiState { ctx: context.clone() });
+        let state = State(ApiState {
+            ctx: context.clone(),
+            archive: None,
+            event_sources: None,
+        });
         let result = info_handler(state).await;
 
         // Assert local bitcoin tip
@@ -531,7 +604,11 @@ mod tests {
             })
             .await;
 
-        let state = State(ApiState { ctx: context.clone() });
+        let state = State(ApiState {
+            ctx: context.clone(),
+            archive: None,
+            event_sources: None,
+        });
         let result = info_handler(state).await;
 
         let Some(bitcoin_node_tip) = result.bitcoin.node_tip else {
@@ -606,7 +683,11 @@ mod tests {
             })
             .await;
 
-        let state = State(ApiState { ctx: context.clone() });
+        let state = State(ApiState {
+            ctx: context.clone(),
+            archive: None,
+            event_sources: None,
+        });
         let result = info_handler(state).await;
 
         let Some(stacks_node_tip) = result.stacks.node_tip else {
@@ -686,7 +767,11 @@ mod tests {
             })
             .await;
 
-        let state = State(ApiState { ctx: context.clone() });
+        let state = State(ApiState {
+            ctx: context.clone(),
+            archive: None,
+            event_sources: None,
+        });
         let result = info_handler(state).await;
 
         let Some(config) = result.config else {
@@ -730,4 +815,51 @@ mod tests {
         );
         assert_eq!(config.dkg_target_rounds, settings.dkg_target_rounds.get());
     }
+
+    #[test]
+    fn test_p2p_info() {
+        let context = TestContext::default_mocked();
+        let connected_peers = context.state().connected_peers();
+
+        let public_key: PublicKey = Faker.fake();
+        let peer_id = public_key.into();
+        context.state().current_signer_set().add_signer(public_key);
+        connected_peers.add_connection(
+            peer_id,
+            PeerConnection {
+                connection_id: ConnectionId::new_unchecked(1),
+                address: Multiaddr::empty(),
+                direction: ConnectionDirection::Inbound,
+                inferred_security: "noise",
+            },
+        );
+
+        let mut result = InfoResponse::default();
+        result.populate_p2p_info(&context);
+        assert!(!result.p2p.version_skew);
+        assert_eq!(result.p2p.peers.len(), 1);
+        assert_eq!(result.p2p.peers[0].public_key, Some(public_key));
+        assert!(result.p2p.peers[0].protocol_version.is_none());
+
+        // The peer is still running the previous version of the protocol.
+        let versions = ProtocolVersions {
+            min: PROTOCOL_VERSION - 1,
+            max: PROTOCOL_VERSION - 1,
+        };
+        connected_peers.set_protocol_versions(
+            &peer_id,
+            "sbtc-signer/0.0.1".to_string(),
+            versions,
+            PROTOCOL_VERSION - 1,
+        );
+
+        let mut result = InfoResponse::default();
+        result.populate_p2p_info(&context);
+        assert!(result.p2p.version_skew);
+        assert_eq!(result.p2p.protocol_version, PROTOCOL_VERSION - 1);
+        let peer = &result.p2p.peers[0];
+        assert_eq!(peer.agent_version.as_deref(), Some("sbtc-signer/0.0.1"));
+        assert_eq!(peer.protocol_versions, Some(versions));
+        assert_eq!(peer.protocol_version, Some(PROTOCOL_VERSION - 1));
+    }
 }
@@ -1,13 +1,28 @@
 //! This module contains functions and structs for the Signer API.
 //!
 
+use std::sync::Arc;
+
+mod archive;
+mod backfill;
+mod event_sources;
 mod info;
 mod new_block;
+mod p2p;
+mod peers;
 mod router;
+mod signer_set;
 mod status;
 
+pub use archive::EventArchive;
+pub use archive::SegmentReader;
+pub use archive::archive_segments;
+pub use backfill::BackfillSummary;
+pub use backfill::backfill_new_block_events;
+pub use event_sources::EventSources;
 pub use info::build_info;
 pub use new_block::new_block_handler;
+pub use new_block::new_block_source_handler;
 pub use router::get_router;
 
 /// A struct with state data necessary for runtime operation.
@@ -15,6 +30,12 @@ pub use router::get_router;
 pub struct ApiState<C> {
     /// For writing to the database.
     pub ctx: C,
+    /// For archiving the raw bodies of the `POST /new_block` webhooks, if
+    /// enabled.
+    pub archive: Option<Arc<EventArchive>>,
+    /// For deduplicating and monitoring the events pushed by several
+    /// stacks nodes, if any are configured.
+    pub event_sources: Option<Arc<EventSources>>,
 }
 
 /// The name of the sbtc registry smart contract.
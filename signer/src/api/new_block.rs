@@ -2,13 +2,18 @@
 //! which is for processing new block webhooks from a stacks node.
 //!
 
+use axum::extract::ConnectInfo;
 use axum::extract::State;
+use axum::http::Extensions;
 use axum::http::StatusCode;
 use clarity::vm::representations::ContractName;
 use clarity::vm::types::QualifiedContractIdentifier;
 use clarity::vm::types::StandardPrincipalData;
 use sbtc::events::RegistryEvent;
 use sbtc::events::TxInfo;
+use serde::Deserialize;
+use stacks_common::types::chainstate::StacksBlockId;
+use std::net::SocketAddr;
 use std::sync::OnceLock;
 
 use crate::context::Context;
@@ -47,6 +52,67 @@ static SBTC_REGISTRY_IDENTIFIER: OnceLock<QualifiedContractIdentifier> = OnceLoc
 /// will be fine since it is twice as high as required.
 pub const EVENT_OBSERVER_BODY_LIMIT: usize = 8 * 1024 * 1024;
 
+/// The fields of a `POST /new_block` webhook body that identify the
+/// block, which is all that is needed to deduplicate the pushes of several
+/// stacks nodes.
+#[derive(Debug, Deserialize)]
+pub(super) struct NewBlockIdentifier {
+    /// The ID of the block.
+    #[serde(deserialize_with = "sbtc::webhooks::deserialize_hex")]
+    pub index_block_hash: StacksBlockId,
+    /// The height of the block.
+    pub block_height: u64,
+}
+
+/// A handler of `POST /new_block` webhook events that may be pushed by
+/// several stacks nodes.
+///
+/// When event sources are configured, the block is recorded as pushed by
+/// the node that sent the request, and the request is acknowledged without
+/// being handled if the events of the block were already handled for the
+/// push of another node. Otherwise the request is handled by
+/// [`new_block_handler`]. Pushes of the same block that arrive at the same
+/// time may both be handled, which is fine since writing the events is
+/// idempotent.
+pub async fn new_block_source_handler(
+    state: State<ApiState<impl Context>>,
+    extensions: Extensions,
+    body: String,
+) -> StatusCode {
+    let Some(sources) = state.event_sources.clone() else {
+        return new_block_handler(state, body).await;
+    };
+
+    // If the body is not even a block, then the new block handler is the
+    // one to complain about it.
+    let Ok(block) = serde_json::from_str::<NewBlockIdentifier>(&body) else {
+        return new_block_handler(state, body).await;
+    };
+
+    let source = extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if let Some(source) = source {
+        sources.observe(source, block.index_block_hash, block.block_height);
+    }
+
+    if sources.is_handled(&block.index_block_hash) {
+        tracing::debug!(
+            source = ?source,
+            block_id = %block.index_block_hash,
+            "skipping a new block event that was already handled"
+        );
+        Metrics::increment_stacks_events_deduplicated();
+        return StatusCode::OK;
+    }
+
+    let status = new_block_handler(state, body).await;
+    if status.is_success() {
+        sources.mark_handled(block.index_block_hash);
+    }
+    status
+}
+
 /// A handler of `POST /new_block` webhook events.
 ///
 /// # Notes
@@ -79,6 +145,18 @@ pub async fn new_block_handler(state: State<ApiState<impl Context>>, body: Strin
 
     let api = state.0;
 
+    // The body is archived before anything else, so that payloads that we
+    // fail to handle can be replayed later on. Failing to archive it does
+    // not prevent us from handling it though.
+    if let Some(archive) = api.archive.clone() {
+        let body = body.clone();
+        match tokio::task::spawn_blocking(move || archive.record(&body)).await {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => tracing::warn!(%error, "could not archive the new block event"),
+            Err(error) => tracing::warn!(%error, "the new block event archive task failed"),
+        }
+    }
+
     let registry_address = SBTC_REGISTRY_IDENTIFIER.get_or_init(|| {
         // Although the following line can panic, our unit tests hit this
         // code path so if tests pass then this will work in production.
@@ -307,11 +385,17 @@ mod tests {
     use rand::rngs::OsRng;
     use sbtc::events::KeyRotationEvent;
     use secp256k1::SECP256K1;
-    use stacks_common::types::chainstate::StacksBlockId;
+    use std::sync::Arc;
     use test_case::test_case;
     use tower::ServiceExt as _;
 
+    use crate::api::EventArchive;
+    use crate::api::EventSources;
+    use crate::api::SegmentReader;
+    use crate::api::archive_segments;
     use crate::api::get_router;
+    use crate::config::EventArchiveConfig;
+    use crate::config::EventObserverConfig;
     use crate::storage::memory::Store;
     use crate::storage::model::DepositRequest;
     use crate::storage::model::StacksPrincipal;
@@ -356,7 +440,11 @@ mod tests {
             .with_mocked_clients()
             .build();
 
-        let api = ApiState { ctx: ctx.clone() };
+        let api = ApiState {
+            ctx: ctx.clone(),
+            archive: None,
+            event_sources: None,
+        };
 
         let db = ctx.inner_storage();
 
@@ -372,6 +460,102 @@ mod tests {
         assert!(!table_is_empty(db.lock().await));
     }
 
+    #[tokio::test]
+    async fn test_events_are_archived() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventArchiveConfig {
+            path: dir.path().to_path_buf(),
+            max_segment_size: 1024 * 1024,
+            max_segments: 2,
+        };
+        let archive = Arc::new(EventArchive::new(config).unwrap());
+        let api = ApiState {
+            ctx: ctx.clone(),
+            archive: Some(archive),
+            event_sources: None,
+        };
+
+        let res = new_block_handler(State(api), COMPLETED_DEPOSIT_WEBHOOK.to_string()).await;
+        assert_eq!(res, StatusCode::OK);
+
+        // The archived body is exactly what the stacks node sent us.
+        let bodies = archive_segments(dir.path())
+            .unwrap()
+            .iter()
+            .flat_map(|segment| SegmentReader::open(segment).unwrap())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(bodies, [COMPLETED_DEPOSIT_WEBHOOK]);
+    }
+
+    #[tokio::test]
+    async fn test_events_from_several_sources_are_handled_once() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        // Only the handled pushes are archived, which tells us how many
+        // times the block was handled.
+        let dir = tempfile::tempdir().unwrap();
+        let config = EventArchiveConfig {
+            path: dir.path().to_path_buf(),
+            max_segment_size: 1024 * 1024,
+            max_segments: 2,
+        };
+        let archive = Arc::new(EventArchive::new(config).unwrap());
+
+        let sources: Vec<SocketAddr> = vec![
+            "10.0.0.1:20443".parse().unwrap(),
+            "10.0.0.2:20443".parse().unwrap(),
+        ];
+        let config = EventObserverConfig {
+            bind: "127.0.0.1:8801".parse().unwrap(),
+            archive: None,
+            sources: sources.iter().map(SocketAddr::ip).collect(),
+            source_stale_after: std::time::Duration::from_secs(600),
+        };
+        let state = ApiState {
+            ctx: ctx.clone(),
+            archive: Some(archive),
+            event_sources: EventSources::new(&config).map(Arc::new),
+        };
+        let app = get_router().with_state(state);
+
+        for source in sources {
+            let mut request = Request::builder()
+                .uri("/new_block")
+                .method(Method::POST)
+                .body(Body::from(ROTATE_KEYS_WEBHOOK))
+                .unwrap();
+            request.extensions_mut().insert(ConnectInfo(source));
+
+            let response = app.clone().oneshot(request).await.unwrap();
+            assert_eq!(response.status(), StatusCode::OK);
+        }
+
+        let bodies = archive_segments(dir.path())
+            .unwrap()
+            .iter()
+            .flat_map(|segment| SegmentReader::open(segment).unwrap())
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+        assert_eq!(bodies, [ROTATE_KEYS_WEBHOOK]);
+        assert_eq!(
+            ctx.inner_storage()
+                .lock()
+                .await
+                .rotate_keys_transactions
+                .len(),
+            1
+        );
+    }
+
     #[test_case(COMPLETED_DEPOSIT_WEBHOOK, |db| !db.completed_deposit_events.contains_key(&OutPoint::null()); "completed-deposit")]
     #[test_case(WITHDRAWAL_CREATE_WEBHOOK, |db| !db.withdrawal_requests.contains_key(&(1, StacksBlockId::from_hex("75b02b9884ec41c05f2cfa6e20823328321518dd0b027e7b609b63d4d1ea7c78").unwrap().into())); "withdrawal-create")]
     #[test_case(WITHDRAWAL_ACCEPT_WEBHOOK, |db| !db.withdrawal_accept_events.contains_key(&1); "withdrawal-accept")]
@@ -387,7 +571,11 @@ mod tests {
             .with_mocked_clients()
             .build();
 
-        let api = ApiState { ctx: ctx.clone() };
+        let api = ApiState {
+            ctx: ctx.clone(),
+            archive: None,
+            event_sources: None,
+        };
 
         let db = ctx.inner_storage();
 
@@ -687,7 +875,11 @@ mod tests {
             .with_mocked_clients()
             .build();
 
-        let state = ApiState { ctx: ctx.clone() };
+        let state = ApiState {
+            ctx: ctx.clone(),
+            archive: None,
+            event_sources: None,
+        };
         let app = get_router().with_state(state);
 
         let db = ctx.inner_storage();
@@ -721,7 +913,11 @@ mod tests {
             .with_mocked_clients()
             .build();
 
-        let state = State(ApiState { ctx: ctx.clone() });
+        let state = State(ApiState {
+            ctx: ctx.clone(),
+            archive: None,
+            event_sources: None,
+        });
         let body = ROTATE_KEYS_AND_INVALID_EVENT_WEBHOOK.to_string();
 
         let db = ctx.inner_storage();
@@ -0,0 +1,227 @@
+//! Handler for the `/p2p` endpoint, which reports the state of this
+//! signer's connections to the other signers in the p2p network.
+
+use std::collections::HashMap;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Serialize;
+
+use crate::context::ConnectionDirection;
+use crate::context::Context;
+use crate::keys::PublicKey;
+use crate::storage::DbRead as _;
+
+use super::ApiState;
+
+#[derive(Debug, Serialize)]
+pub struct P2PResponse {
+    /// The peers that this signer is currently connected to.
+    pub connected_peers: Vec<ConnectedPeerInfo>,
+    /// The signers in the signing set that this signer is not connected
+    /// to.
+    pub unreachable_signers: Vec<UnreachableSignerInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConnectedPeerInfo {
+    pub peer_id: String,
+    /// The public key of the peer, if it is in the signing set.
+    pub public_key: Option<PublicKey>,
+    pub connections: Vec<ConnectionInfo>,
+    /// The round-trip time of the last successful ping, in milliseconds.
+    pub ping_rtt_ms: Option<u64>,
+    /// Whether the peer is in this signer's gossipsub mesh.
+    pub in_gossipsub_mesh: bool,
+    /// When this signer last received a message from the peer, if ever.
+    pub last_message_received_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct ConnectionInfo {
+    pub address: String,
+    pub direction: ConnectionDirection,
+    /// The security protocol of the connection, as inferred from its
+    /// address.
+    pub inferred_security: &'static str,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UnreachableSignerInfo {
+    pub public_key: PublicKey,
+    pub peer_id: String,
+    /// The address that this signer last dialed the signer on, if ever.
+    pub last_known_address: Option<String>,
+    /// When this signer last dialed the signer, if ever.
+    pub last_dialed_at: Option<String>,
+}
+
+/// A handler returning the peers that this signer is connected to, along
+/// with the signers in the signing set that it cannot reach.
+pub async fn p2p_handler<C: Context>(
+    state: State<ApiState<C>>,
+) -> Result<Json<P2PResponse>, StatusCode> {
+    let signer_set = state.ctx.state().current_signer_set();
+    let own_public_key = state.ctx.config().signer.public_key();
+
+    let p2p_peers = state
+        .ctx
+        .get_storage()
+        .get_p2p_peers()
+        .await
+        .map_err(|error| {
+            tracing::error!(%error, "could not fetch the p2p peers");
+            StatusCode::INTERNAL_SERVER_ERROR
+        })?;
+    let p2p_peers: HashMap<PublicKey, _> = p2p_peers
+        .into_iter()
+        .map(|peer| (peer.public_key, peer))
+        .collect();
+
+    let connected = state.ctx.state().connected_peers().get_peers();
+
+    let connected_peers = connected
+        .iter()
+        .map(|(peer_id, peer)| ConnectedPeerInfo {
+            peer_id: peer_id.to_string(),
+            public_key: signer_set.get_pubkey_for_peer(peer_id),
+            connections: peer
+                .connections
+                .iter()
+                .map(|connection| ConnectionInfo {
+                    address: connection.address.to_string(),
+                    direction: connection.direction,
+                    inferred_security: connection.inferred_security,
+                })
+                .collect(),
+            ping_rtt_ms: peer.ping_rtt.map(|rtt| rtt.as_millis() as u64),
+            in_gossipsub_mesh: peer.in_gossipsub_mesh,
+            last_message_received_at: peer.last_message_received_at.map(|at| at.to_string()),
+        })
+        .collect();
+
+    let mut unreachable_signers: Vec<UnreachableSignerInfo> = signer_set
+        .get_signers()
+        .into_iter()
+        .filter(|signer| signer.public_key() != &own_public_key)
+        .filter(|signer| !connected.contains_key(signer.peer_id()))
+        .map(|signer| {
+            let p2p_peer = p2p_peers.get(signer.public_key());
+            UnreachableSignerInfo {
+                public_key: *signer.public_key(),
+                peer_id: signer.peer_id().to_string(),
+                last_known_address: p2p_peer.map(|peer| peer.address.to_string()),
+                last_dialed_at: p2p_peer.map(|peer| peer.last_dialed_at.to_string()),
+            }
+        })
+        .collect();
+    unreachable_signers.sort_by_key(|signer| signer.public_key);
+
+    Ok(Json(P2PResponse {
+        connected_peers,
+        unreachable_signers,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+    use std::time::Duration;
+
+    use fake::Fake as _;
+    use fake::Faker;
+    use libp2p::Multiaddr;
+    use libp2p::PeerId;
+    use libp2p::swarm::ConnectionId;
+
+    use crate::context::PeerConnection;
+    use crate::storage::DbWrite as _;
+    use crate::testing::context::*;
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_p2p_handler() {
+        let rng = &mut get_rng();
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+
+        let own_public_key = ctx.config().signer.public_key();
+        let connected_signer: PublicKey = Faker.fake_with_rng(rng);
+        let dialed_signer: PublicKey = Faker.fake_with_rng(rng);
+        let unknown_signer: PublicKey = Faker.fake_with_rng(rng);
+        ctx.state().update_current_signer_set(BTreeSet::from([
+            own_public_key,
+            connected_signer,
+            dialed_signer,
+            unknown_signer,
+        ]));
+
+        // We are connected to one of the signers, and have dialed another
+        // one in the past.
+        let peer_id = PeerId::from(connected_signer);
+        let address: Multiaddr = "/ip4/10.0.0.1/tcp/4122".parse().unwrap();
+        let connected_peers = ctx.state().connected_peers();
+        connected_peers.add_connection(
+            peer_id,
+            PeerConnection {
+                connection_id: ConnectionId::new_unchecked(1),
+                address: address.clone(),
+                direction: ConnectionDirection::Outbound,
+                inferred_security: "noise",
+            },
+        );
+        connected_peers.set_ping_rtt(&peer_id, Duration::from_millis(42));
+        connected_peers.set_gossipsub_mesh(&BTreeSet::from([peer_id]));
+
+        let dialed_address: Multiaddr = "/ip4/10.0.0.2/tcp/4122".parse().unwrap();
+        ctx.get_storage_mut()
+            .update_peer_connection(
+                &dialed_signer,
+                &dialed_signer.into(),
+                dialed_address.clone(),
+            )
+            .await
+            .unwrap();
+
+        let state = State(ApiState {
+            ctx: ctx.clone(),
+            archive: None,
+            event_sources: None,
+        });
+        let Json(response) = p2p_handler(state).await.unwrap();
+
+        assert_eq!(response.connected_peers.len(), 1);
+        let peer = &response.connected_peers[0];
+        assert_eq!(peer.peer_id, peer_id.to_string());
+        assert_eq!(peer.public_key, Some(connected_signer));
+        assert_eq!(peer.connections.len(), 1);
+        assert_eq!(peer.connections[0].address, address.to_string());
+        assert_eq!(peer.connections[0].direction, ConnectionDirection::Outbound);
+        assert_eq!(peer.connections[0].inferred_security, "noise");
+        assert_eq!(peer.ping_rtt_ms, Some(42));
+        assert!(peer.in_gossipsub_mesh);
+        assert!(peer.last_message_received_at.is_none());
+
+        // This signer is never reported as unreachable.
+        assert_eq!(response.unreachable_signers.len(), 2);
+        let unreachable = |key: &PublicKey| {
+            response
+                .unreachable_signers
+                .iter()
+                .find(|signer| &signer.public_key == key)
+                .unwrap()
+        };
+        let dialed = unreachable(&dialed_signer);
+        assert_eq!(dialed.last_known_address, Some(dialed_address.to_string()));
+        assert!(dialed.last_dialed_at.is_some());
+
+        let unknown = unreachable(&unknown_signer);
+        assert!(unknown.last_known_address.is_none());
+        assert!(unknown.last_dialed_at.is_none());
+    }
+}
@@ -0,0 +1,174 @@
+//! Handler for the `/peers` endpoint, which reports how reliably each
+//! signer has taken part in coordinating and signing.
+
+use std::collections::BTreeMap;
+
+use axum::Json;
+use axum::extract::State;
+use axum::http::StatusCode;
+use serde::Serialize;
+
+use crate::context::Context;
+use crate::keys::PublicKey;
+use crate::storage::DbRead as _;
+use crate::storage::model::PeerStatistics;
+
+use super::ApiState;
+
+#[derive(Debug, Serialize)]
+pub struct PeersResponse {
+    pub peers: Vec<PeerInfo>,
+}
+
+#[derive(Debug, Serialize)]
+pub struct PeerInfo {
+    pub public_key: PublicKey,
+    /// Whether the peer is in the signing set of this signer.
+    pub is_signer: bool,
+    pub tenures_coordinated: u64,
+    pub tenures_missed: u64,
+    pub rounds_completed: u64,
+    pub nonce_timeouts: u64,
+    pub signature_share_timeouts: u64,
+    pub invalid_messages: u64,
+    /// When the statistics of the peer were last updated, if ever.
+    pub updated_at: Option<String>,
+    /// When this signer last dialed the peer, if ever.
+    pub last_dialed_at: Option<String>,
+}
+
+impl PeerInfo {
+    fn new(stats: PeerStatistics, is_signer: bool, updated: bool) -> Self {
+        Self {
+            public_key: stats.public_key,
+            is_signer,
+            tenures_coordinated: stats.tenures_coordinated,
+            tenures_missed: stats.tenures_missed,
+            rounds_completed: stats.rounds_completed,
+            nonce_timeouts: stats.nonce_timeouts,
+            signature_share_timeouts: stats.signature_share_timeouts,
+            invalid_messages: stats.invalid_messages,
+            updated_at: updated.then(|| stats.updated_at.to_string()),
+            last_dialed_at: None,
+        }
+    }
+}
+
+/// A handler returning the statistics of the signers in the signing set,
+/// along with those of any other peer that has recorded statistics.
+pub async fn peers_handler<C: Context>(
+    state: State<ApiState<C>>,
+) -> Result<Json<PeersResponse>, StatusCode> {
+    let storage = state.ctx.get_storage();
+    let signer_set = state.ctx.state().current_signer_set();
+
+    let statistics = storage.get_peer_statistics().await.map_err(|error| {
+        tracing::error!(%error, "could not fetch the peer statistics");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+    let p2p_peers = storage.get_p2p_peers().await.map_err(|error| {
+        tracing::error!(%error, "could not fetch the p2p peers");
+        StatusCode::INTERNAL_SERVER_ERROR
+    })?;
+
+    let mut peers: BTreeMap<PublicKey, PeerInfo> = signer_set
+        .get_signers()
+        .into_iter()
+        .map(|signer| *signer.public_key())
+        .map(|key| (key, PeerInfo::new(PeerStatistics::new(key), true, false)))
+        .collect();
+
+    for stats in statistics {
+        let is_signer = signer_set.is_signer(&stats.public_key);
+        peers.insert(stats.public_key, PeerInfo::new(stats, is_signer, true));
+    }
+
+    for peer in p2p_peers {
+        if let Some(info) = peers.get_mut(&peer.public_key) {
+            info.last_dialed_at = Some(peer.last_dialed_at.to_string());
+        }
+    }
+
+    Ok(Json(PeersResponse {
+        peers: peers.into_values().collect(),
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::storage::DbWrite as _;
+    use crate::storage::model::PeerEvent;
+    use crate::testing::context::*;
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn test_peers_handler() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        let storage = ctx.get_storage_mut();
+        let mut rng = get_rng();
+
+        // The signing set is the current one, which need not be the
+        // bootstrap signing set from the config.
+        let signer_set: BTreeSet<PublicKey> =
+            (0..3).map(|_| Faker.fake_with_rng(&mut rng)).collect();
+        ctx.state().update_current_signer_set(signer_set.clone());
+        let signer = *signer_set.first().unwrap();
+        let outsider: PublicKey = Faker.fake_with_rng(&mut rng);
+
+        storage
+            .record_peer_event(&signer, PeerEvent::RoundCompleted, 1)
+            .await
+            .unwrap();
+        storage
+            .record_peer_event(&signer, PeerEvent::NonceTimeout, 1)
+            .await
+            .unwrap();
+        storage
+            .record_peer_event(&outsider, PeerEvent::InvalidMessage, 1)
+            .await
+            .unwrap();
+
+        let state = State(ApiState {
+            ctx: ctx.clone(),
+            archive: None,
+            event_sources: None,
+        });
+        let Json(response) = peers_handler(state).await.unwrap();
+
+        // Every signer is listed, even without any statistics, along with
+        // the outsider.
+        assert_eq!(response.peers.len(), signer_set.len() + 1);
+
+        let info = |key: &PublicKey| {
+            response
+                .peers
+                .iter()
+                .find(|peer| &peer.public_key == key)
+                .unwrap()
+        };
+        let signer_info = info(&signer);
+        assert!(signer_info.is_signer);
+        assert_eq!(signer_info.rounds_completed, 1);
+        assert_eq!(signer_info.nonce_timeouts, 1);
+        assert!(signer_info.updated_at.is_some());
+
+        let outsider_info = info(&outsider);
+        assert!(!outsider_info.is_signer);
+        assert_eq!(outsider_info.invalid_messages, 1);
+
+        let idle_signer = signer_set.iter().nth(1).unwrap();
+        let idle_info = info(idle_signer);
+        assert_eq!(idle_info.rounds_completed, 0);
+        assert!(idle_info.updated_at.is_none());
+    }
+}
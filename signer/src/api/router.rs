@@ -11,7 +11,7 @@ use crate::context::Context;
 
 use axum::http::StatusCode;
 
-use super::{ApiState, info, new_block, status};
+use super::{ApiState, info, new_block, p2p, peers, signer_set, status};
 
 async fn new_attachment_handler() -> StatusCode {
     StatusCode::OK
@@ -22,9 +22,16 @@ pub fn get_router<C: Context + 'static>() -> Router<ApiState<C>> {
     Router::new()
         .route("/", get(status::status_handler))
         .route("/info", get(info::info_handler))
+        .route("/peers", get(peers::peers_handler))
+        .route("/p2p", get(p2p::p2p_handler))
+        .route(
+            "/signer-set-proposal",
+            post(signer_set::propose_signer_set_handler)
+                .delete(signer_set::withdraw_signer_set_handler),
+        )
         .route(
             "/new_block",
-            post(new_block::new_block_handler)
+            post(new_block::new_block_source_handler)
                 .layer(DefaultBodyLimit::max(new_block::EVENT_OBSERVER_BODY_LIMIT)),
         )
         // TODO: remove this once https://github.com/stacks-network/stacks-core/issues/5558
@@ -50,7 +57,11 @@ mod tests {
     async fn test_new_attachment() {
         let context = TestContext::default_mocked();
 
-        let state = ApiState { ctx: context.clone() };
+        let state = ApiState {
+            ctx: context.clone(),
+            archive: None,
+            event_sources: None,
+        };
         let app: Router = get_router().with_state(state);
 
         let request = Request::builder()
@@ -0,0 +1,237 @@
+//! Handlers for the `/signer-set-proposal` endpoint, through which the
+//! operator of this signer approves a change of the signer set, or
+//! withdraws the approval.
+//!
+//! Only requests from the loopback interface are accepted, since the
+//! approval is made on behalf of this signer.
+
+use std::collections::BTreeSet;
+use std::net::SocketAddr;
+
+use axum::Json;
+use axum::extract::ConnectInfo;
+use axum::extract::State;
+use axum::http::Extensions;
+use axum::http::StatusCode;
+use serde::Deserialize;
+
+use crate::context::Context;
+use crate::keys::PublicKey;
+use crate::message::SignerSetProposal;
+use crate::storage::DbWrite as _;
+use crate::storage::model;
+
+use super::ApiState;
+
+/// The body of a `POST /signer-set-proposal` request.
+#[derive(Debug, Deserialize)]
+pub struct SignerSetProposalRequest {
+    /// The public keys of the proposed signer set.
+    pub signer_set: BTreeSet<PublicKey>,
+    /// The proposed number of signatures required.
+    pub signatures_required: u16,
+}
+
+/// A handler approving the given signer set change proposal on behalf of
+/// this signer, replacing any proposal that it approved before. The
+/// request decider sends the proposal to the other signers on every new
+/// bitcoin block.
+pub async fn propose_signer_set_handler<C: Context>(
+    state: State<ApiState<C>>,
+    extensions: Extensions,
+    Json(request): Json<SignerSetProposalRequest>,
+) -> StatusCode {
+    if !is_from_loopback(&extensions) {
+        return StatusCode::FORBIDDEN;
+    }
+
+    let proposal = SignerSetProposal {
+        signer_set: request.signer_set,
+        signatures_required: request.signatures_required,
+    };
+    if !proposal.is_valid() {
+        return StatusCode::BAD_REQUEST;
+    }
+
+    tracing::info!(
+        signatures_required = %proposal.signatures_required,
+        num_signers = %proposal.signer_set.len(),
+        "operator approved a signer set change"
+    );
+    write_approval(&state.ctx, proposal).await
+}
+
+/// A handler withdrawing the signer set change proposal that this signer
+/// approved, if any, by approving the signer set of the registry contract
+/// instead, which is never approved as a change.
+pub async fn withdraw_signer_set_handler<C: Context>(
+    state: State<ApiState<C>>,
+    extensions: Extensions,
+) -> StatusCode {
+    if !is_from_loopback(&extensions) {
+        return StatusCode::FORBIDDEN;
+    }
+
+    let Some(registry_signer_info) = state.ctx.state().registry_signer_set_info() else {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    };
+
+    tracing::info!("operator withdrew the approval of a signer set change");
+    write_approval(&state.ctx, SignerSetProposal::from(&registry_signer_info)).await
+}
+
+/// Return whether the request was made from the loopback interface.
+fn is_from_loopback(extensions: &Extensions) -> bool {
+    extensions
+        .get::<ConnectInfo<SocketAddr>>()
+        .is_some_and(|ConnectInfo(addr)| addr.ip().is_loopback())
+}
+
+/// Write the approval of the given proposal by this signer on the current
+/// bitcoin chain tip.
+async fn write_approval(ctx: &impl Context, proposal: SignerSetProposal) -> StatusCode {
+    let Some(chain_tip) = ctx.state().bitcoin_chain_tip() else {
+        return StatusCode::SERVICE_UNAVAILABLE;
+    };
+
+    let approval = model::SignerSetApproval {
+        approver_public_key: ctx.config().signer.public_key(),
+        signer_set_public_keys: proposal.signer_set.into_iter().collect(),
+        signatures_required: proposal.signatures_required,
+        bitcoin_chain_tip: chain_tip.block_hash,
+    };
+
+    match ctx
+        .get_storage_mut()
+        .write_signer_set_approval(&approval)
+        .await
+    {
+        Ok(()) => StatusCode::OK,
+        Err(error) => {
+            tracing::error!(%error, "could not write the signer set approval");
+            StatusCode::INTERNAL_SERVER_ERROR
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use axum::Router;
+    use axum::body::Body;
+    use axum::http::Method;
+    use axum::http::Request;
+    use fake::Fake as _;
+    use fake::Faker;
+    use tower::ServiceExt as _;
+
+    use crate::api::get_router;
+    use crate::stacks::api::SignerSetInfo;
+    use crate::storage::DbRead as _;
+    use crate::testing::context::*;
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    fn request(method: Method, source: SocketAddr, body: String) -> Request<Body> {
+        let mut request = Request::builder()
+            .uri("/signer-set-proposal")
+            .method(method)
+            .header("content-type", "application/json")
+            .body(Body::from(body))
+            .unwrap();
+        request.extensions_mut().insert(ConnectInfo(source));
+        request
+    }
+
+    #[tokio::test]
+    async fn operator_approves_and_withdraws_signer_set_changes() {
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        let mut rng = get_rng();
+
+        let chain_tip: model::BitcoinBlockRef = Faker.fake_with_rng(&mut rng);
+        ctx.state().set_bitcoin_chain_tip(chain_tip);
+        let registry_signer_info = SignerSetInfo {
+            aggregate_key: Faker.fake_with_rng(&mut rng),
+            signer_set: (0..3).map(|_| Faker.fake_with_rng(&mut rng)).collect(),
+            signatures_required: 2,
+        };
+        ctx.state()
+            .update_registry_signer_set_info(registry_signer_info.clone());
+
+        let state = ApiState {
+            ctx: ctx.clone(),
+            archive: None,
+            event_sources: None,
+        };
+        let app: Router = get_router().with_state(state);
+        let loopback: SocketAddr = "127.0.0.1:40000".parse().unwrap();
+        let remote: SocketAddr = "10.0.0.1:40000".parse().unwrap();
+
+        let signer_set: BTreeSet<PublicKey> =
+            (0..4).map(|_| Faker.fake_with_rng(&mut rng)).collect();
+        let body = |signatures_required: u16| {
+            serde_json::json!({
+                "signer_set": signer_set,
+                "signatures_required": signatures_required,
+            })
+            .to_string()
+        };
+
+        // Only the operator may approve a change.
+        let response = app
+            .clone()
+            .oneshot(request(Method::POST, remote, body(3)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::FORBIDDEN);
+
+        // The proposal must be valid.
+        let response = app
+            .clone()
+            .oneshot(request(Method::POST, loopback, body(5)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+
+        let db = ctx.get_storage();
+        assert!(db.get_signer_set_approvals().await.unwrap().is_empty());
+
+        let response = app
+            .clone()
+            .oneshot(request(Method::POST, loopback, body(3)))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let approvals = db.get_signer_set_approvals().await.unwrap();
+        assert_eq!(approvals.len(), 1);
+        let approval = approvals[0].clone();
+        assert_eq!(
+            approval.approver_public_key,
+            ctx.config().signer.public_key()
+        );
+        assert_eq!(approval.bitcoin_chain_tip, chain_tip.block_hash);
+        let expected = SignerSetProposal {
+            signer_set: signer_set.clone(),
+            signatures_required: 3,
+        };
+        assert_eq!(SignerSetProposal::from(approval), expected);
+
+        // Withdrawing the approval replaces it with the approval of the
+        // registry signer set.
+        let response = app
+            .clone()
+            .oneshot(request(Method::DELETE, loopback, String::new()))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let approvals = db.get_signer_set_approvals().await.unwrap();
+        assert_eq!(approvals.len(), 1);
+        let expected = SignerSetProposal::from(&registry_signer_info);
+        assert_eq!(SignerSetProposal::from(approvals[0].clone()), expected);
+    }
+}
@@ -0,0 +1,197 @@
+//! CLI tool for replaying the `POST /new_block` webhook bodies archived by
+//! the event observer of the sBTC signer, either into a signer database or
+//! into an Emily instance, and for backfilling the events of the stacks
+//! blocks that are missing from a signer database.
+
+use std::path::PathBuf;
+use std::time::Duration;
+
+use axum::extract::State;
+use clap::Parser;
+use clap::Subcommand;
+use signer::api::ApiState;
+use signer::api::SegmentReader;
+use signer::api::archive_segments;
+use signer::api::backfill_new_block_events;
+use signer::api::new_block_handler;
+use signer::bitcoin::rpc::BitcoinCoreClient;
+use signer::config::Settings;
+use signer::context::SignerContext;
+use signer::emily_client::EmilyClient;
+use signer::stacks::api::StacksClient;
+use signer::storage::postgres::PgStore;
+use signer::util::ApiFallbackClient;
+use url::Url;
+
+type BitcoinClient = ApiFallbackClient<BitcoinCoreClient>;
+type StacksNodeClient = ApiFallbackClient<StacksClient>;
+type EmilyApiClient = ApiFallbackClient<EmilyClient>;
+
+/// Command line arguments for the replay tool.
+#[derive(Debug, Parser)]
+#[clap(name = "sBTC Signer Event Replay")]
+struct ReplayArgs {
+    /// Path to the archive directory of the event observer, or to a single
+    /// archive segment. The bodies are replayed in the order in which they
+    /// were received.
+    #[clap(short = 'a', long)]
+    archive: PathBuf,
+
+    #[clap(subcommand)]
+    target: ReplayTarget,
+}
+
+#[derive(Debug, Subcommand)]
+enum ReplayTarget {
+    /// Replay the bodies into the database of a signer, through the same
+    /// handler as its event observer.
+    Signer {
+        /// Optional path to the configuration file of the signer. If not
+        /// provided, it is expected that all parameters are provided via
+        /// environment variables.
+        #[clap(short = 'c', long, required = false)]
+        config: Option<PathBuf>,
+
+        /// If this flag is set, any pending migrations are applied to the
+        /// database before replaying the bodies.
+        #[clap(long)]
+        migrate_db: bool,
+    },
+    /// Backfill the events of the stacks blocks that are missing from the
+    /// database of a signer, by walking the stacks blocks back from the tip
+    /// of its stacks node and replaying the archived bodies of the missing
+    /// ones. This should be run before the signer is started on a restored
+    /// database.
+    Backfill {
+        /// Optional path to the configuration file of the signer. If not
+        /// provided, it is expected that all parameters are provided via
+        /// environment variables.
+        #[clap(short = 'c', long, required = false)]
+        config: Option<PathBuf>,
+
+        /// If this flag is set, any pending migrations are applied to the
+        /// database before backfilling the events.
+        #[clap(long)]
+        migrate_db: bool,
+    },
+    /// Replay the bodies into an Emily instance through its
+    /// `POST /new_block` endpoint.
+    Emily {
+        /// The endpoint of the Emily API. The API key, if any, is given as
+        /// the username of the URL, like in the signer configuration.
+        #[clap(short = 'e', long)]
+        endpoint: Url,
+    },
+}
+
+#[tokio::main]
+async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    let args = ReplayArgs::parse();
+    signer::logging::setup_logging("info,signer=debug", true);
+
+    let segments = || -> Result<Vec<PathBuf>, signer::error::Error> {
+        let segments = archive_segments(&args.archive)?;
+        tracing::info!(
+            segments = segments.len(),
+            "replaying archived new block events"
+        );
+        Ok(segments)
+    };
+
+    let replayed = match args.target {
+        ReplayTarget::Signer { config, migrate_db } => {
+            replay_into_signer(&segments()?, config, migrate_db).await?
+        }
+        ReplayTarget::Emily { endpoint } => replay_into_emily(&segments()?, &endpoint).await?,
+        ReplayTarget::Backfill { config, migrate_db } => {
+            let ctx = signer_context(config, migrate_db).await?;
+            let summary = backfill_new_block_events(&ctx, &args.archive).await?;
+            tracing::info!(
+                missing_blocks = %summary.missing_blocks,
+                unarchived = %summary.unarchived.len(),
+                "finished backfilling missing stacks blocks"
+            );
+            summary.replayed
+        }
+    };
+
+    tracing::info!(%replayed, "finished replaying archived new block events");
+    Ok(())
+}
+
+/// Replay the archived bodies into the database of the signer with the
+/// given configuration.
+async fn replay_into_signer(
+    segments: &[PathBuf],
+    config: Option<PathBuf>,
+    migrate_db: bool,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let ctx = signer_context(config, migrate_db).await?;
+
+    let mut replayed = 0;
+    for segment in segments {
+        tracing::info!(segment = %segment.display(), "replaying archive segment");
+        for body in SegmentReader::open(segment)? {
+            // The archive is not enabled, so that replayed bodies are not
+            // archived a second time.
+            let state = State(ApiState {
+                ctx: ctx.clone(),
+                archive: None,
+                event_sources: None,
+            });
+            let status = new_block_handler(state, body?).await;
+            if !status.is_success() {
+                return Err(format!("the new block handler returned {status}").into());
+            }
+            replayed += 1;
+        }
+    }
+
+    Ok(replayed)
+}
+
+/// The context of the signer with the given configuration, connected to
+/// its database.
+async fn signer_context(
+    config: Option<PathBuf>,
+    migrate_db: bool,
+) -> Result<
+    SignerContext<PgStore, BitcoinClient, StacksNodeClient, EmilyApiClient>,
+    Box<dyn std::error::Error>,
+> {
+    let settings = Settings::new(config)?;
+
+    let db = PgStore::connect(settings.signer.db_endpoint.as_str()).await?;
+    if migrate_db {
+        db.apply_migrations().await?;
+    }
+
+    Ok(SignerContext::init(settings, db)?)
+}
+
+/// Replay the archived bodies into the Emily instance at the given
+/// endpoint.
+async fn replay_into_emily(
+    segments: &[PathBuf],
+    endpoint: &Url,
+) -> Result<usize, Box<dyn std::error::Error>> {
+    let client = EmilyClient::try_new(endpoint, Duration::from_secs(10), None)?;
+    let config = client.config();
+    let url = format!("{}/new_block", config.base_path);
+
+    let mut replayed = 0;
+    for segment in segments {
+        tracing::info!(segment = %segment.display(), "replaying archive segment");
+        for body in SegmentReader::open(segment)? {
+            // Emily takes the raw body of the webhook as a JSON string.
+            let mut request = config.client.post(&url).json(&body?);
+            if let Some(api_key) = &config.api_key {
+                request = request.header("x-api-key", &api_key.key);
+            }
+            request.send().await?.error_for_status()?;
+            replayed += 1;
+        }
+    }
+
+    Ok(replayed)
+}
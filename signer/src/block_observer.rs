@@ -46,6 +46,8 @@ use crate::storage::TransactionHandle as _;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockRef;
 use crate::storage::model::EncryptedDkgShares;
+use crate::transaction_coordinator::approved_signer_set_proposal;
+use crate::transaction_coordinator::current_signer_set;
 use crate::util::FutureExt as _;
 use bitcoin::Amount;
 use bitcoin::BlockHash;
@@ -526,6 +528,26 @@ impl<C: Context, B> BlockObserver<C, B> {
         Ok(chain_tip)
     }
 
+    /// Set the `SignerState` object with the signer set change proposal
+    /// that enough of the current signers approved, if any, and allow the
+    /// signers of the current signer set and of the approved proposal to
+    /// connect to us over the p2p network.
+    async fn set_approved_signer_set(&self) -> Result<(), Error> {
+        let approved = approved_signer_set_proposal(&self.context).await?;
+
+        let state = self.context.state();
+        let current_signers = current_signer_set(&self.context).signer_set;
+        let approved_signers = approved.iter().flat_map(|proposal| &proposal.signer_set);
+        for public_key in current_signers.iter().chain(approved_signers) {
+            if !state.current_signer_set().is_signer(public_key) {
+                state.current_signer_set().add_signer(*public_key);
+            }
+        }
+
+        state.set_approved_signer_set(approved);
+        Ok(())
+    }
+
     /// Update the `SignerState` object with data that is unlikely to
     /// change until the arrival of the next bitcoin block.
     ///
@@ -536,6 +558,7 @@ impl<C: Context, B> BlockObserver<C, B> {
     /// * The current signer set.
     /// * The current aggregate key.
     /// * The current bitcoin chain tip.
+    /// * The approved signer set change proposal.
     async fn update_signer_state(&self, chain_tip: BlockHash) -> Result<BitcoinBlockRef, Error> {
         tracing::info!("loading sbtc limits from Emily");
         self.update_sbtc_limits(chain_tip).await?;
@@ -544,7 +567,12 @@ impl<C: Context, B> BlockObserver<C, B> {
         self.set_signer_set_info().await?;
 
         tracing::info!("updating the signer state with the current bitcoin chain tip");
-        self.set_bitcoin_chain_tip(chain_tip).await
+        let chain_tip = self.set_bitcoin_chain_tip(chain_tip).await?;
+
+        tracing::info!("updating the signer state with the approved signer set change");
+        self.set_approved_signer_set().await?;
+
+        Ok(chain_tip)
     }
 
     /// Checks if the latest dkg share is pending and is no longer valid
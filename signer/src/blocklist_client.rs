@@ -4,15 +4,30 @@
 //! which are used to check addresses against a blocklist service. The module's responsibilities
 //! include querying the blocklist API and interpreting the responses to determine if a given
 //! address is blocklisted, along with its associated risk severity.
+//!
+//! Both bitcoin addresses and Stacks principals are screened, each request
+//! telling the blocklist service which chain the addresses belong to.
+//!
+//! Calls to the blocklist service go through a circuit breaker, so that
+//! during an outage the signer stops waiting on a service that keeps
+//! failing and instead fails fast until the service recovers.
 
 use blocklist_api::apis::Error as ClientError;
-use blocklist_api::apis::address_api::{CheckAddressError, check_address};
+use blocklist_api::apis::address_api::{
+    CheckAddressError, CheckAddressesError, check_address, check_addresses,
+};
 use blocklist_api::apis::configuration::Configuration;
+pub use blocklist_api::models::Chain;
+use blocklist_api::models::ScreenAddressesRequest;
 use std::future::Future;
+use std::sync::Arc;
+use std::sync::Mutex;
 use std::time::Duration;
+use std::time::Instant;
 
 use crate::config::BlocklistClientConfig;
 use crate::error::Error;
+use crate::metrics::Metrics;
 
 /// Blocklist client error variants.
 #[derive(Debug, thiserror::Error)]
@@ -20,13 +35,130 @@ pub enum BlocklistClientError {
     /// An error occurred while checking an address
     #[error("error checking an address: {0}")]
     CheckAddress(ClientError<CheckAddressError>),
+    /// An error occurred while checking a batch of addresses
+    #[error("error checking a batch of addresses: {0}")]
+    CheckAddresses(ClientError<CheckAddressesError>),
+    /// The blocklist service returned a different number of results than
+    /// the number of addresses in the batch
+    #[error("expected {expected} results from the blocklist service, got {actual}")]
+    UnexpectedBatchSize {
+        /// The number of addresses in the batch
+        expected: usize,
+        /// The number of results returned
+        actual: usize,
+    },
+    /// The circuit breaker is open after repeated failures, so the
+    /// blocklist service was not called
+    #[error("the blocklist client circuit breaker is open")]
+    CircuitOpen,
 }
 
+/// The maximum number of addresses sent to the blocklist service in a
+/// single batch request. This matches the limit of the blocklist client.
+const MAX_BATCH_SIZE: usize = 100;
+
 /// A trait for checking if an address is blocklisted.
 pub trait BlocklistChecker {
-    /// Checks if the given address is blocklisted.
+    /// Checks if the given address of the given chain is blocklisted.
     /// Returns `true` if the address is blocklisted, otherwise `false`.
-    fn can_accept(&self, address: &str) -> impl Future<Output = Result<bool, Error>> + Send;
+    fn can_accept(
+        &self,
+        address: &str,
+        chain: Chain,
+    ) -> impl Future<Output = Result<bool, Error>> + Send;
+
+    /// Checks whether each of the given addresses of the given chain can
+    /// be accepted, using as few requests to the blocklist service as
+    /// possible. Returns one result per address, in the order of the input.
+    fn can_accept_many(
+        &self,
+        addresses: &[String],
+        chain: Chain,
+    ) -> impl Future<Output = Result<Vec<bool>, Error>> + Send;
+}
+
+/// A circuit breaker that opens after a number of consecutive failed
+/// calls to the blocklist service. While it is open calls fail without
+/// reaching the service. Once the cooldown has elapsed the breaker is
+/// half-open: a single probe call is let through while every other call
+/// keeps failing fast. The breaker closes if the probe succeeds and opens
+/// again for another cooldown if it fails.
+#[derive(Debug)]
+struct CircuitBreaker {
+    threshold: u32,
+    cooldown: Duration,
+    state: Mutex<CircuitState>,
+}
+
+#[derive(Debug, Default)]
+struct CircuitState {
+    /// The number of consecutive failed calls.
+    consecutive_failures: u32,
+    /// When the breaker was last opened, if it is open.
+    opened_at: Option<Instant>,
+    /// When the probe call of a half-open breaker was let through, if
+    /// there is one in flight. A probe that never reports back, because
+    /// its call was dropped, is replaced by a new one after a cooldown.
+    probe_started_at: Option<Instant>,
+}
+
+impl CircuitBreaker {
+    fn new(threshold: u32, cooldown: Duration) -> Self {
+        Self {
+            threshold,
+            cooldown,
+            state: Mutex::new(CircuitState::default()),
+        }
+    }
+
+    /// Whether a call to the blocklist service may be made. When the
+    /// breaker is half-open this lets the caller through as the probe.
+    fn allows_call(&self) -> bool {
+        let mut state = self.state.lock().expect("BUG: Failed to acquire lock");
+        let Some(opened_at) = state.opened_at else {
+            return true;
+        };
+        if opened_at.elapsed() < self.cooldown {
+            return false;
+        }
+        let probe_in_flight = state
+            .probe_started_at
+            .is_some_and(|started_at| started_at.elapsed() < self.cooldown);
+        if probe_in_flight {
+            return false;
+        }
+        state.probe_started_at = Some(Instant::now());
+        true
+    }
+
+    fn record_success(&self) {
+        let mut state = self.state.lock().expect("BUG: Failed to acquire lock");
+        state.consecutive_failures = 0;
+        state.probe_started_at = None;
+        if state.opened_at.take().is_some() {
+            tracing::info!("blocklist client recovered, closing the circuit breaker");
+            Metrics::set_blocklist_circuit_breaker_open(false);
+        }
+    }
+
+    fn record_failure(&self) {
+        Metrics::increment_blocklist_client_failures("error");
+        let mut state = self.state.lock().expect("BUG: Failed to acquire lock");
+        state.consecutive_failures = state.consecutive_failures.saturating_add(1);
+        state.probe_started_at = None;
+        if state.consecutive_failures < self.threshold {
+            return;
+        }
+        if state.opened_at.is_none() {
+            tracing::warn!(
+                failures = state.consecutive_failures,
+                "blocklist client keeps failing, opening the circuit breaker"
+            );
+            Metrics::set_blocklist_circuit_breaker_open(true);
+        }
+        // A failed probe opens the breaker again for another cooldown.
+        state.opened_at = Some(Instant::now());
+    }
 }
 
 /// A client for interacting with the blocklist service.
@@ -34,18 +166,25 @@ pub trait BlocklistChecker {
 pub struct BlocklistClient {
     config: Configuration,
     retry_delay: Duration,
+    circuit_breaker: Arc<CircuitBreaker>,
 }
 
 impl BlocklistChecker for BlocklistClient {
-    async fn can_accept(&self, address: &str) -> Result<bool, Error> {
-        let response = self.check_address(address).await;
-        if let Err(error) = response {
-            tracing::error!(%error, "blocklist client error, sleeping and retrying once");
-            tokio::time::sleep(self.retry_delay).await;
-            self.check_address(address).await
-        } else {
-            response
+    async fn can_accept(&self, address: &str, chain: Chain) -> Result<bool, Error> {
+        self.call(|| self.check_address(address, chain)).await
+    }
+
+    async fn can_accept_many(
+        &self,
+        addresses: &[String],
+        chain: Chain,
+    ) -> Result<Vec<bool>, Error> {
+        let mut results = Vec::with_capacity(addresses.len());
+        for chunk in addresses.chunks(MAX_BATCH_SIZE) {
+            let chunk_results = self.call(|| self.check_addresses(chunk, chain)).await?;
+            results.extend(chunk_results);
         }
+        Ok(results)
     }
 }
 
@@ -68,6 +207,10 @@ impl BlocklistClient {
         BlocklistClient {
             config,
             retry_delay: client_config.retry_delay,
+            circuit_breaker: Arc::new(CircuitBreaker::new(
+                client_config.circuit_breaker_threshold,
+                client_config.circuit_breaker_cooldown,
+            )),
         }
     }
 
@@ -82,22 +225,82 @@ impl BlocklistClient {
         BlocklistClient {
             config,
             retry_delay: Duration::ZERO,
+            // The circuit breaker never opens, so that tests can make any
+            // number of failing calls.
+            circuit_breaker: Arc::new(CircuitBreaker::new(u32::MAX, Duration::ZERO)),
+        }
+    }
+
+    /// Make a call to the blocklist service through the circuit breaker,
+    /// retrying once after a failure.
+    async fn call<T, F, Fut>(&self, call: F) -> Result<T, Error>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, Error>>,
+    {
+        if !self.circuit_breaker.allows_call() {
+            Metrics::increment_blocklist_client_failures("circuit-open");
+            return Err(Error::BlocklistClient(BlocklistClientError::CircuitOpen));
         }
+
+        let mut response = call().await;
+        if let Err(error) = &response {
+            tracing::error!(%error, "blocklist client error, sleeping and retrying once");
+            tokio::time::sleep(self.retry_delay).await;
+            response = call().await;
+        }
+
+        match &response {
+            Ok(_) => self.circuit_breaker.record_success(),
+            Err(_) => self.circuit_breaker.record_failure(),
+        }
+        response
     }
 
-    async fn check_address(&self, address: &str) -> Result<bool, Error> {
+    async fn check_address(&self, address: &str, chain: Chain) -> Result<bool, Error> {
         // Call the generated function from blocklist-api
-        check_address(&self.config, address)
+        check_address(&self.config, address, Some(chain))
             .await
             .map_err(BlocklistClientError::CheckAddress)
             .map_err(Error::BlocklistClient)
             .map(|resp| resp.accept)
     }
+
+    async fn check_addresses(
+        &self,
+        addresses: &[String],
+        chain: Chain,
+    ) -> Result<Vec<bool>, Error> {
+        let request = ScreenAddressesRequest {
+            chain: Some(Some(chain)),
+            ..ScreenAddressesRequest::new(addresses.to_vec())
+        };
+        let response = check_addresses(&self.config, request)
+            .await
+            .map_err(BlocklistClientError::CheckAddresses)
+            .map_err(Error::BlocklistClient)?;
+
+        if response.results.len() != addresses.len() {
+            return Err(Error::BlocklistClient(
+                BlocklistClientError::UnexpectedBatchSize {
+                    expected: addresses.len(),
+                    actual: response.results.len(),
+                },
+            ));
+        }
+
+        Ok(response
+            .results
+            .into_iter()
+            .map(|result| result.status.accept)
+            .collect())
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use crate::config::BlocklistClientConfig;
+    use crate::config::BlocklistOutagePolicy;
 
     use super::*;
     use mockito::{Server, ServerGuard};
@@ -135,14 +338,17 @@ mod tests {
         .to_string();
 
         let mock = guard
-            .mock("GET", format!("{SCREEN_PATH}/{ADDRESS}").as_str())
+            .mock(
+                "GET",
+                format!("{SCREEN_PATH}/{ADDRESS}?chain=bitcoin").as_str(),
+            )
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(&mock_json)
             .create_async()
             .await;
 
-        let can_accept = ctx.client.can_accept(ADDRESS).await;
+        let can_accept = ctx.client.can_accept(ADDRESS, Chain::Bitcoin).await;
         assert!(can_accept.is_ok());
         assert!(!can_accept.unwrap());
 
@@ -162,37 +368,234 @@ mod tests {
         .to_string();
 
         let mock = guard
-            .mock("GET", format!("{SCREEN_PATH}/{ADDRESS}").as_str())
+            .mock(
+                "GET",
+                format!("{SCREEN_PATH}/{ADDRESS}?chain=bitcoin").as_str(),
+            )
             .with_status(200)
             .with_header("content-type", "application/json")
             .with_body(&mock_json)
             .create_async()
             .await;
 
-        let can_accept = ctx.client.can_accept(ADDRESS).await;
+        let can_accept = ctx.client.can_accept(ADDRESS, Chain::Bitcoin).await;
         assert!(can_accept.is_ok());
         assert!(can_accept.unwrap());
 
         mock.assert_async().await;
     }
 
+    #[tokio::test]
+    async fn test_can_accept_stacks_principal() {
+        let ctx = setup().await;
+        let mut guard = ctx.server_guard.lock().await;
+        let principal = "SP2J6ZY48GV1EZ5V2V5RB9MP66SW86PYKKNRV9EJ7.contract";
+        let mock_json = json!({
+            "is_blocklisted": true,
+            "severity": "Severe",
+            "accept": false,
+            "reason": "local blocklist"
+        })
+        .to_string();
+
+        let mock = guard
+            .mock("GET", format!("{SCREEN_PATH}/{principal}").as_str())
+            .match_query(mockito::Matcher::UrlEncoded(
+                "chain".into(),
+                "stacks".into(),
+            ))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(&mock_json)
+            .create_async()
+            .await;
+
+        let can_accept = ctx.client.can_accept(principal, Chain::Stacks).await;
+        assert!(!can_accept.unwrap());
+
+        mock.assert_async().await;
+    }
+
     #[tokio::test]
     async fn test_check_address_http_error() {
         let ctx = setup().await;
         let mut guard = ctx.server_guard.lock().await;
 
         guard
-            .mock("GET", format!("{SCREEN_PATH}/{ADDRESS}").as_str())
+            .mock(
+                "GET",
+                format!("{SCREEN_PATH}/{ADDRESS}?chain=bitcoin").as_str(),
+            )
             .with_status(404)
             .with_header("content-type", "application/json")
             .with_body("Not found")
             .create_async()
             .await;
 
-        let result = ctx.client.can_accept(ADDRESS).await;
+        let result = ctx.client.can_accept(ADDRESS, Chain::Bitcoin).await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_can_accept_many() {
+        let ctx = setup().await;
+        let mut guard = ctx.server_guard.lock().await;
+        let mock_json = json!({
+            "results": [
+                {
+                    "address": ADDRESS,
+                    "status": {
+                        "is_blocklisted": false,
+                        "severity": "Low",
+                        "accept": true,
+                        "reason": null
+                    }
+                },
+                {
+                    "address": "bc1qblocked",
+                    "status": {
+                        "is_blocklisted": true,
+                        "severity": "Severe",
+                        "accept": false,
+                        "reason": "Fraud"
+                    }
+                }
+            ]
+        })
+        .to_string();
+
+        let mock = guard
+            .mock("POST", SCREEN_PATH)
+            .match_body(mockito::Matcher::Json(json!({
+                "addresses": [ADDRESS, "bc1qblocked"],
+                "chain": "bitcoin"
+            })))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(&mock_json)
+            .create_async()
+            .await;
+
+        let addresses = [ADDRESS.to_string(), "bc1qblocked".to_string()];
+        let results = ctx
+            .client
+            .can_accept_many(&addresses, Chain::Bitcoin)
+            .await
+            .unwrap();
+        assert_eq!(results, vec![true, false]);
+
+        mock.assert_async().await;
+    }
+
+    #[tokio::test]
+    async fn test_can_accept_many_unexpected_batch_size() {
+        let ctx = setup().await;
+        let mut guard = ctx.server_guard.lock().await;
+
+        guard
+            .mock("POST", SCREEN_PATH)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({ "results": [] }).to_string())
+            .create_async()
+            .await;
+
+        let result = ctx
+            .client
+            .can_accept_many(&[ADDRESS.to_string()], Chain::Bitcoin)
+            .await;
         assert!(result.is_err());
     }
 
+    #[tokio::test]
+    async fn test_circuit_breaker_opens_after_failures() {
+        let ctx = setup().await;
+        let mut guard = ctx.server_guard.lock().await;
+        let client = BlocklistClient::new(&BlocklistClientConfig {
+            endpoint: Url::parse(&guard.url()).unwrap(),
+            retry_delay: Duration::ZERO,
+            outage_policy: BlocklistOutagePolicy::Defer,
+            outage_defer_blocks: 6,
+            circuit_breaker_threshold: 2,
+            circuit_breaker_cooldown: Duration::from_secs(3600),
+        });
+
+        // Each failed call is retried once, so the two failed calls that
+        // open the breaker make four requests.
+        let mock = guard
+            .mock(
+                "GET",
+                format!("{SCREEN_PATH}/{ADDRESS}?chain=bitcoin").as_str(),
+            )
+            .with_status(500)
+            .expect(4)
+            .create_async()
+            .await;
+
+        for _ in 0..2 {
+            let result = client.can_accept(ADDRESS, Chain::Bitcoin).await;
+            assert!(!matches!(
+                result,
+                Err(Error::BlocklistClient(BlocklistClientError::CircuitOpen))
+            ));
+        }
+
+        // The breaker is now open, so the service is not called again.
+        let result = client.can_accept(ADDRESS, Chain::Bitcoin).await;
+        assert!(matches!(
+            result,
+            Err(Error::BlocklistClient(BlocklistClientError::CircuitOpen))
+        ));
+
+        mock.assert_async().await;
+    }
+
+    #[test]
+    fn circuit_breaker_closes_after_success() {
+        let breaker = CircuitBreaker::new(1, Duration::ZERO);
+        assert!(breaker.allows_call());
+
+        breaker.record_failure();
+        assert!(breaker.state.lock().unwrap().opened_at.is_some());
+        // The cooldown is zero, so calls are let through to probe the
+        // service.
+        assert!(breaker.allows_call());
+
+        breaker.record_success();
+        let state = breaker.state.lock().unwrap();
+        assert!(state.opened_at.is_none());
+        assert_eq!(state.consecutive_failures, 0);
+    }
+
+    #[test]
+    fn half_open_circuit_breaker_lets_a_single_probe_through() {
+        let cooldown = Duration::from_secs(30);
+        let breaker = CircuitBreaker::new(1, cooldown);
+        breaker.record_failure();
+        assert!(!breaker.allows_call());
+
+        // Pretend that the cooldown has elapsed.
+        let opened_at = Instant::now().checked_sub(cooldown).unwrap();
+        breaker.state.lock().unwrap().opened_at = Some(opened_at);
+
+        // Only the first call is let through as the probe.
+        assert!(breaker.allows_call());
+        assert!(!breaker.allows_call());
+
+        // The probe failed, so the breaker is open for another cooldown.
+        breaker.record_failure();
+        assert!(!breaker.allows_call());
+
+        breaker.state.lock().unwrap().opened_at = Some(opened_at);
+        assert!(breaker.allows_call());
+        assert!(!breaker.allows_call());
+
+        // The probe succeeded, so the breaker is closed.
+        breaker.record_success();
+        assert!(breaker.allows_call());
+        assert!(breaker.allows_call());
+    }
+
     #[test]
     fn try_from_url_with_slash() {
         let endpoint = Url::parse("http://localhost:8080/").unwrap();
@@ -200,6 +603,10 @@ mod tests {
         let client = BlocklistClient::new(&BlocklistClientConfig {
             endpoint,
             retry_delay: Duration::ZERO,
+            outage_policy: BlocklistOutagePolicy::Defer,
+            outage_defer_blocks: 6,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown: Duration::from_secs(30),
         });
 
         assert_eq!(client.config.base_path, "http://localhost:8080");
@@ -212,6 +619,10 @@ mod tests {
         let client = BlocklistClient::new(&BlocklistClientConfig {
             endpoint,
             retry_delay: Duration::ZERO,
+            outage_policy: BlocklistOutagePolicy::Defer,
+            outage_defer_blocks: 6,
+            circuit_breaker_threshold: 5,
+            circuit_breaker_cooldown: Duration::from_secs(30),
         });
 
         assert_eq!(client.config.base_path, "http://localhost:8080");
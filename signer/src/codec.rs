@@ -178,8 +178,10 @@ mod tests {
     use crate::keys::PublicKey;
     use crate::message::BitcoinPreSignAck;
     use crate::message::BitcoinPreSignRequest;
+    use crate::message::DecisionSyncRequest;
     use crate::message::SignerDepositDecision;
     use crate::message::SignerMessage;
+    use crate::message::SignerSetProposal;
     use crate::message::SignerWithdrawalDecision;
     use crate::message::StacksTransactionSignRequest;
     use crate::message::StacksTransactionSignature;
@@ -243,6 +245,8 @@ mod tests {
     #[test_case(PhantomData::<(Fees, proto::Fees)>; "Fees")]
     #[test_case(PhantomData::<(BitcoinPreSignRequest, proto::BitcoinPreSignRequest)>; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<(BitcoinPreSignAck, proto::BitcoinPreSignAck)>; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<(SignerSetProposal, proto::SignerSetProposal)>; "SignerSetProposal")]
+    #[test_case(PhantomData::<(DecisionSyncRequest, proto::DecisionSyncRequest)>; "DecisionSyncRequest")]
     fn sbtc_protobuf_message_codec_tag_order<T, U, E>(_: PhantomData<(T, U)>)
     where
         // `.unwrap()` requires that `E` implement `std::fmt::Debug` and
@@ -368,6 +372,8 @@ mod tests {
     #[test_case(PhantomData::<proto::Fees>; "Fees")]
     #[test_case(PhantomData::<proto::BitcoinPreSignRequest>; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<proto::BitcoinPreSignAck>; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<proto::SignerSetProposal>; "SignerSetProposal")]
+    #[test_case(PhantomData::<proto::DecisionSyncRequest>; "DecisionSyncRequest")]
     #[test_case(PhantomData::<proto::OutPoint>; "OutPoint")]
     #[test_case(PhantomData::<proto::RecoverableSignature>; "RecoverableSignature")]
     #[test_case(PhantomData::<proto::EcdsaSignature>; "EcdsaSignature")]
@@ -1,5 +1,7 @@
 use libp2p::Multiaddr;
 
+use crate::keys::PublicKey;
+
 /// Configuration error variants.
 #[derive(Debug, thiserror::Error)]
 pub enum SignerConfigError {
@@ -64,6 +66,23 @@ pub enum SignerConfigError {
     )]
     P2PPublicEndpointProtocolMismatch(Multiaddr),
 
+    /// A P2P relay signer must be part of the bootstrap signer set
+    #[error("P2P relay signer {0} is not in the bootstrap signer set")]
+    P2PRelayNotInSignerSet(PublicKey),
+
+    /// A pinned P2P peer must be given as `<public key>@<p2p uri>`
+    #[error("Invalid P2P pinned peer '{0}': expected '<public key>@<protocol>://<host>:<port>'")]
+    InvalidP2PPinnedPeer(String),
+
+    /// A pinned P2P peer must be part of the bootstrap signer set
+    #[error("P2P pinned peer {0} is not in the bootstrap signer set")]
+    P2PPinnedPeerNotInSignerSet(PublicKey),
+
+    /// A pinned P2P peer address must be an IP address, since inbound
+    /// connections are never from a hostname
+    #[error("P2P pinned peer address '{0}' must use an IP address rather than a hostname")]
+    P2PPinnedPeerNotIpAddress(Multiaddr),
+
     /// Unsupported database driver
     #[error("Unsupported database driver: {0}. Supported drivers are: 'postgresql'.")]
     UnsupportedDatabaseDriver(String),
@@ -6,7 +6,9 @@ use serde::{Deserialize as _, Deserializer};
 use url::Url;
 
 use crate::keys::PrivateKey;
+use crate::keys::PublicKey;
 
+use super::PinnedPeer;
 use super::error::SignerConfigError;
 
 /// A deserializer for the url::Url type. This will return an empty [`Vec`] if
@@ -75,6 +77,38 @@ where
     Ok(addrs)
 }
 
+/// A deserializer for a list of [`PinnedPeer`]s, each of which is given as
+/// `<public key>@<p2p uri>`, where the URI is in the same format as the
+/// other P2P addresses in the config.
+pub fn pinned_peers_deserializer<'de, D>(deserializer: D) -> Result<Vec<PinnedPeer>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let mut pinned_peers = Vec::new();
+
+    let items = Vec::<String>::deserialize(deserializer)?;
+
+    for s in items.iter().filter(|s| !s.is_empty()) {
+        let pinned_peer = try_parse_pinned_peer(s).map_err(serde::de::Error::custom)?;
+        pinned_peers.push(pinned_peer);
+    }
+
+    Ok(pinned_peers)
+}
+
+fn try_parse_pinned_peer(s: &str) -> Result<PinnedPeer, SignerConfigError> {
+    let (public_key, address) = s
+        .split_once('@')
+        .ok_or_else(|| SignerConfigError::InvalidP2PPinnedPeer(s.to_string()))?;
+
+    let public_key = secp256k1::PublicKey::from_str(public_key)
+        .map(PublicKey::from)
+        .map_err(|_| SignerConfigError::InvalidP2PPinnedPeer(s.to_string()))?;
+    let address = try_parse_p2p_multiaddr(address)?;
+
+    Ok(PinnedPeer { public_key, address })
+}
+
 /// A deserializer for the [`PrivateKey`] type. Returns an error if the private
 /// key is not valid hex or is not the correct length.
 pub fn private_key_deserializer<'de, D>(deserializer: D) -> Result<PrivateKey, D::Error>
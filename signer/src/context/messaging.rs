@@ -28,6 +28,9 @@ impl SignerSignal {
 pub enum SignerCommand {
     /// Signals to the application to publish a message to the P2P network.
     P2PPublish(Box<crate::network::Msg>),
+    /// Signals to the application to send a message directly to the signer
+    /// with the given public key over the P2P network.
+    P2PSendTo(crate::keys::PublicKey, Box<crate::network::Msg>),
     /// Signal to shut down the application
     Shutdown,
 }
@@ -1,19 +1,30 @@
 //! Module for signer state
 
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::sync::{
     RwLock,
     atomic::{AtomicBool, AtomicU64, Ordering},
 };
+use std::time::Duration;
+use std::time::Instant;
 
 use bitcoin::Amount;
 use hashbrown::HashSet;
+use libp2p::Multiaddr;
 use libp2p::PeerId;
+use libp2p::swarm::ConnectionId;
+use time::OffsetDateTime;
 
+use crate::PEER_EVENT_WRITE_INTERVAL;
 use crate::keys::PublicKey;
+use crate::message::SignerSetProposal;
+use crate::network::version::{PROTOCOL_VERSION, ProtocolVersions};
 use crate::stacks::api::SignerSetInfo;
+use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::BitcoinBlockHeight;
 use crate::storage::model::BitcoinBlockRef;
+use crate::storage::util::PeerEventThrottle;
 
 /// A struct for holding internal signer state. This struct is served by
 /// the [`SignerContext`] and can be used to cache global state instead of
@@ -21,14 +32,39 @@ use crate::storage::model::BitcoinBlockRef;
 #[derive(Debug)]
 pub struct SignerState {
     current_signer_set: SignerSet,
+    connected_peers: ConnectedPeers,
     current_limits: RwLock<SbtcLimits>,
     registry_signing_set_info: RwLock<Option<SignerSetInfo>>,
+    // The signer set change proposal that enough of the current signers
+    // have approved, if any. This gets updated by the block observer
+    // whenever it observes a new bitcoin block.
+    approved_signer_set: RwLock<Option<SignerSetProposal>>,
     sbtc_contracts_deployed: AtomicBool,
     sbtc_bitcoin_start_height: AtomicU64,
     is_sbtc_bitcoin_start_height_set: AtomicBool,
     // The current bitcoin chain tip. This gets updated at the end of the
     // block observer's duties when it observes a new bitcoin block.
     bitcoin_chain_tip: RwLock<Option<BitcoinBlockRef>>,
+    // The coordinator tenure of the current bitcoin chain tip. A new
+    // tenure starts whenever the bitcoin chain tip changes.
+    coordinator_tenure: RwLock<Option<CoordinatorTenure>>,
+    // Limits the writes of the peer events that peers can trigger at
+    // will.
+    peer_event_throttle: PeerEventThrottle,
+}
+
+/// The tenure of the coordinators of a bitcoin chain tip, as observed by
+/// this signer.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct CoordinatorTenure {
+    /// The bitcoin chain tip of the tenure.
+    pub chain_tip: BitcoinBlockHash,
+    /// When this signer observed the bitcoin chain tip.
+    pub started_at: Instant,
+    /// The coordinator that has been seen starting to sign during the
+    /// tenure, if any. Once set, it is the only coordinator accepted for
+    /// the rest of the tenure.
+    pub active_coordinator: Option<PublicKey>,
 }
 
 impl SignerState {
@@ -38,6 +74,17 @@ impl SignerState {
         &self.current_signer_set
     }
 
+    /// Get the peers that the p2p network is currently connected to.
+    pub fn connected_peers(&self) -> &ConnectedPeers {
+        &self.connected_peers
+    }
+
+    /// Get the throttle for writing the peer events that peers can trigger
+    /// at will.
+    pub fn peer_event_throttle(&self) -> &PeerEventThrottle {
+        &self.peer_event_throttle
+    }
+
     /// Set the set of signers that this signer is allow us to communicate
     /// with.
     #[cfg(any(test, feature = "testing"))]
@@ -64,6 +111,25 @@ impl SignerState {
             .cloned()
     }
 
+    /// Replace the approved signer set change proposal with the given
+    /// input.
+    pub fn set_approved_signer_set(&self, proposal: Option<SignerSetProposal>) {
+        *self
+            .approved_signer_set
+            .write()
+            .expect("BUG: Failed to acquire write lock of approved signer set") = proposal;
+    }
+
+    /// Return the signer set change proposal that enough of the current
+    /// signers have approved, as of the last observed bitcoin block.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn approved_signer_set(&self) -> Option<SignerSetProposal> {
+        self.approved_signer_set
+            .read()
+            .expect("BUG: Failed to acquire read lock of approved signer set")
+            .clone()
+    }
+
     /// Get the current bitcoin chain tip.
     #[allow(clippy::unwrap_in_result)]
     pub fn bitcoin_chain_tip(&self) -> Option<BitcoinBlockRef> {
@@ -73,14 +139,60 @@ impl SignerState {
             .to_owned()
     }
 
-    /// Set the current bitcoin chain tip.
+    /// Set the current bitcoin chain tip, starting a new coordinator
+    /// tenure if the chain tip changed.
     pub fn set_bitcoin_chain_tip(&self, chain_tip: BitcoinBlockRef) {
+        let mut tenure = self
+            .coordinator_tenure
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        if tenure.is_none_or(|tenure| tenure.chain_tip != chain_tip.block_hash) {
+            tenure.replace(CoordinatorTenure {
+                chain_tip: chain_tip.block_hash,
+                started_at: Instant::now(),
+                active_coordinator: None,
+            });
+        }
+
         self.bitcoin_chain_tip
             .write()
             .expect("BUG: Failed to acquire write lock")
             .replace(chain_tip);
     }
 
+    /// Get the coordinator tenure of the current bitcoin chain tip.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn coordinator_tenure(&self) -> Option<CoordinatorTenure> {
+        self.coordinator_tenure
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .to_owned()
+    }
+
+    /// Record that the given coordinator started signing during the tenure
+    /// of the given bitcoin chain tip. This does nothing if the tenure is
+    /// over or if a coordinator was already seen during it. Returns whether
+    /// the coordinator was recorded.
+    pub fn set_active_coordinator(
+        &self,
+        chain_tip: &BitcoinBlockHash,
+        coordinator: PublicKey,
+    ) -> bool {
+        let mut tenure = self
+            .coordinator_tenure
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        match tenure.as_mut() {
+            Some(tenure)
+                if &tenure.chain_tip == chain_tip && tenure.active_coordinator.is_none() =>
+            {
+                tenure.active_coordinator = Some(coordinator);
+                true
+            }
+            _ => false,
+        }
+    }
+
     /// Get the current sBTC limits.
     pub fn get_current_limits(&self) -> SbtcLimits {
         // We should never fail to acquire a lock from the RwLock so that it panics.
@@ -133,14 +245,18 @@ impl Default for SignerState {
     fn default() -> Self {
         Self {
             current_signer_set: Default::default(),
+            connected_peers: Default::default(),
             current_limits: RwLock::new(SbtcLimits::zero()),
             registry_signing_set_info: RwLock::new(None),
+            approved_signer_set: RwLock::new(None),
             sbtc_contracts_deployed: Default::default(),
             sbtc_bitcoin_start_height: Default::default(),
             is_sbtc_bitcoin_start_height_set: Default::default(),
             // The block hash here is often used as the parent block hash
             // of the genesis block on bitcoin.
             bitcoin_chain_tip: RwLock::new(None),
+            coordinator_tenure: RwLock::new(None),
+            peer_event_throttle: PeerEventThrottle::new(PEER_EVENT_WRITE_INTERVAL),
         }
     }
 }
@@ -543,6 +659,178 @@ impl SignerSet {
     }
 }
 
+/// The direction of a connection to a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ConnectionDirection {
+    /// The peer dialed us.
+    Inbound,
+    /// We dialed the peer.
+    Outbound,
+}
+
+/// An open connection to a peer.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PeerConnection {
+    /// The ID of the connection in the libp2p swarm.
+    pub connection_id: ConnectionId,
+    /// The address of the peer on the connection.
+    pub address: Multiaddr,
+    /// Whether the connection was dialed by us or by the peer.
+    pub direction: ConnectionDirection,
+    /// The security protocol of the connection, as inferred from its
+    /// address.
+    pub inferred_security: &'static str,
+}
+
+/// Diagnostic information about a peer that the p2p network is connected
+/// to.
+#[derive(Debug, Clone, PartialEq, Default)]
+pub struct ConnectedPeer {
+    /// The open connections to the peer.
+    pub connections: Vec<PeerConnection>,
+    /// The round-trip time of the last successful ping of the peer.
+    pub ping_rtt: Option<Duration>,
+    /// Whether the peer is in our gossipsub mesh for the signer topic.
+    pub in_gossipsub_mesh: bool,
+    /// When we last received a message from the peer.
+    pub last_message_received_at: Option<OffsetDateTime>,
+    /// The software that the peer runs, as it identified itself.
+    pub agent_version: Option<String>,
+    /// The signer protocol versions that the peer supports, once it has
+    /// identified itself.
+    pub protocol_versions: Option<ProtocolVersions>,
+    /// The signer protocol version negotiated with the peer, once it has
+    /// identified itself.
+    pub protocol_version: Option<u32>,
+}
+
+/// A struct for holding the peers that the p2p network is currently
+/// connected to. It is maintained by the libp2p event loop and is used for
+/// diagnostics, and for not sending peers messages that they cannot
+/// understand.
+#[derive(Debug, Default)]
+pub struct ConnectedPeers {
+    peers: RwLock<BTreeMap<PeerId, ConnectedPeer>>,
+}
+
+/// NOTE: We should never fail to acquire a lock from the RwLock so that it panics.
+/// If we do, then things have gone very wrong.
+#[allow(clippy::expect_used)]
+impl ConnectedPeers {
+    /// Record a newly established connection to the given peer.
+    pub fn add_connection(&self, peer_id: PeerId, connection: PeerConnection) {
+        self.peers
+            .write()
+            .expect("BUG: Failed to acquire write lock")
+            .entry(peer_id)
+            .or_default()
+            .connections
+            .push(connection);
+    }
+
+    /// Record that a connection to the given peer was closed. The peer is
+    /// forgotten once its last connection is closed.
+    pub fn remove_connection(&self, peer_id: &PeerId, connection_id: ConnectionId) {
+        let mut peers = self
+            .peers
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        if let Some(peer) = peers.get_mut(peer_id) {
+            peer.connections
+                .retain(|connection| connection.connection_id != connection_id);
+            if peer.connections.is_empty() {
+                peers.remove(peer_id);
+            }
+        }
+    }
+
+    /// Record the round-trip time of a successful ping of the given peer.
+    pub fn set_ping_rtt(&self, peer_id: &PeerId, rtt: Duration) {
+        let mut peers = self
+            .peers
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        if let Some(peer) = peers.get_mut(peer_id) {
+            peer.ping_rtt = Some(rtt);
+        }
+    }
+
+    /// Record that a message was just received from the given peer.
+    pub fn set_message_received(&self, peer_id: &PeerId) {
+        let mut peers = self
+            .peers
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        if let Some(peer) = peers.get_mut(peer_id) {
+            peer.last_message_received_at = Some(OffsetDateTime::now_utc());
+        }
+    }
+
+    /// Record the software and the signer protocol versions that the given
+    /// peer identified itself with, along with the version negotiated with
+    /// it.
+    pub fn set_protocol_versions(
+        &self,
+        peer_id: &PeerId,
+        agent_version: String,
+        versions: ProtocolVersions,
+        negotiated: u32,
+    ) {
+        let mut peers = self
+            .peers
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        if let Some(peer) = peers.get_mut(peer_id) {
+            peer.agent_version = Some(agent_version);
+            peer.protocol_versions = Some(versions);
+            peer.protocol_version = Some(negotiated);
+        }
+    }
+
+    /// Returns the highest signer protocol version that all connected peers
+    /// support, which is the version that messages sent to all signers must
+    /// be compatible with. Peers that have not identified themselves yet are
+    /// not taken into account.
+    pub fn protocol_version(&self) -> u32 {
+        self.peers
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .values()
+            .filter_map(|peer| peer.protocol_version)
+            .fold(PROTOCOL_VERSION, u32::min)
+    }
+
+    /// Returns the signer protocol version negotiated with the given peer,
+    /// if we are connected to it and it has identified itself.
+    pub fn peer_protocol_version(&self, peer_id: &PeerId) -> Option<u32> {
+        self.peers
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .get(peer_id)
+            .and_then(|peer| peer.protocol_version)
+    }
+
+    /// Replace the set of connected peers that are in our gossipsub mesh.
+    pub fn set_gossipsub_mesh(&self, mesh_peers: &BTreeSet<PeerId>) {
+        let mut peers = self
+            .peers
+            .write()
+            .expect("BUG: Failed to acquire write lock");
+        for (peer_id, peer) in peers.iter_mut() {
+            peer.in_gossipsub_mesh = mesh_peers.contains(peer_id);
+        }
+    }
+
+    /// Returns the peers that we are currently connected to.
+    pub fn get_peers(&self) -> BTreeMap<PeerId, ConnectedPeer> {
+        self.peers
+            .read()
+            .expect("BUG: Failed to acquire read lock")
+            .clone()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use rand::rngs::OsRng;
@@ -576,4 +864,83 @@ mod tests {
         signer_set.remove_signer(&public_key);
         assert!(!signer_set.is_allowed_peer(&public_key.into()));
     }
+
+    #[test]
+    fn test_coordinator_tenure() {
+        use super::*;
+        use fake::Fake as _;
+        use fake::Faker;
+
+        let state = SignerState::default();
+        let chain_tip: BitcoinBlockRef = Faker.fake_with_rng(&mut OsRng);
+        let coordinator = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+        let backup = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng));
+
+        assert!(state.coordinator_tenure().is_none());
+        state.set_bitcoin_chain_tip(chain_tip);
+        let tenure = state.coordinator_tenure().unwrap();
+        assert_eq!(tenure.chain_tip, chain_tip.block_hash);
+        assert!(tenure.active_coordinator.is_none());
+
+        // Only the first coordinator seen during the tenure is recorded,
+        // and setting the same chain tip again keeps the tenure going.
+        assert!(state.set_active_coordinator(&chain_tip.block_hash, coordinator));
+        assert!(!state.set_active_coordinator(&chain_tip.block_hash, backup));
+        state.set_bitcoin_chain_tip(chain_tip);
+        let same_tenure = state.coordinator_tenure().unwrap();
+        assert_eq!(same_tenure.started_at, tenure.started_at);
+        assert_eq!(same_tenure.active_coordinator, Some(coordinator));
+
+        // A new chain tip starts a new tenure.
+        let next_chain_tip: BitcoinBlockRef = Faker.fake_with_rng(&mut OsRng);
+        state.set_bitcoin_chain_tip(next_chain_tip);
+        assert!(!state.set_active_coordinator(&chain_tip.block_hash, coordinator));
+        let tenure = state.coordinator_tenure().unwrap();
+        assert_eq!(tenure.chain_tip, next_chain_tip.block_hash);
+        assert!(tenure.active_coordinator.is_none());
+    }
+
+    #[test]
+    fn test_connected_peers() {
+        use super::*;
+
+        let peers = ConnectedPeers::default();
+        let peer_id: PeerId = PublicKey::from_private_key(&PrivateKey::new(&mut OsRng)).into();
+        let connection = |id: usize, direction| PeerConnection {
+            connection_id: ConnectionId::new_unchecked(id),
+            address: Multiaddr::empty(),
+            direction,
+            inferred_security: "noise",
+        };
+
+        // Updates for peers that we are not connected to are ignored.
+        peers.set_ping_rtt(&peer_id, Duration::from_millis(10));
+        peers.set_message_received(&peer_id);
+        assert!(peers.get_peers().is_empty());
+
+        peers.add_connection(peer_id, connection(1, ConnectionDirection::Inbound));
+        peers.add_connection(peer_id, connection(2, ConnectionDirection::Outbound));
+        peers.set_ping_rtt(&peer_id, Duration::from_millis(10));
+        peers.set_message_received(&peer_id);
+        peers.set_gossipsub_mesh(&BTreeSet::from([peer_id]));
+        assert_eq!(peers.protocol_version(), PROTOCOL_VERSION);
+        assert_eq!(peers.peer_protocol_version(&peer_id), None);
+
+        let versions = ProtocolVersions { min: 0, max: 0 };
+        peers.set_protocol_versions(&peer_id, "sbtc-signer/0.0.0".to_string(), versions, 0);
+        assert_eq!(peers.protocol_version(), 0);
+        assert_eq!(peers.peer_protocol_version(&peer_id), Some(0));
+
+        let peer = peers.get_peers().remove(&peer_id).unwrap();
+        assert_eq!(peer.connections.len(), 2);
+        assert_eq!(peer.ping_rtt, Some(Duration::from_millis(10)));
+        assert!(peer.in_gossipsub_mesh);
+        assert!(peer.last_message_received_at.is_some());
+
+        // The peer is forgotten once its last connection is closed.
+        peers.remove_connection(&peer_id, ConnectionId::new_unchecked(1));
+        assert_eq!(peers.get_peers()[&peer_id].connections.len(), 1);
+        peers.remove_connection(&peer_id, ConnectionId::new_unchecked(2));
+        assert!(peers.get_peers().is_empty());
+    }
 }
@@ -287,6 +287,8 @@ mod tests {
     #[test_case(PhantomData::<message::WstsMessage> ; "WstsMessage")]
     #[test_case(PhantomData::<message::BitcoinPreSignRequest> ; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<message::BitcoinPreSignAck> ; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<message::SignerSetProposal> ; "SignerSetProposal")]
+    #[test_case(PhantomData::<message::DecisionSyncRequest> ; "DecisionSyncRequest")]
     fn payload_signing_recovery<T>(_: PhantomData<T>)
     where
         T: Into<message::Payload> + fake::Dummy<Faker>,
@@ -345,6 +347,8 @@ mod tests {
     #[test_case(PhantomData::<message::WstsMessage> ; "WstsMessage")]
     #[test_case(PhantomData::<message::BitcoinPreSignRequest> ; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<message::BitcoinPreSignAck> ; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<message::SignerSetProposal> ; "SignerSetProposal")]
+    #[test_case(PhantomData::<message::DecisionSyncRequest> ; "DecisionSyncRequest")]
     fn payload_signing_failing_validation<T>(_: PhantomData<T>)
     where
         T: Into<message::Payload> + fake::Dummy<Faker>,
@@ -431,6 +435,8 @@ mod tests {
     #[test_case(PhantomData::<message::WstsMessage> ; "WstsMessage")]
     #[test_case(PhantomData::<message::BitcoinPreSignRequest> ; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<message::BitcoinPreSignAck> ; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<message::SignerSetProposal> ; "SignerSetProposal")]
+    #[test_case(PhantomData::<message::DecisionSyncRequest> ; "DecisionSyncRequest")]
     fn backwards_compatible_updates<T>(_: PhantomData<T>)
     where
         T: Into<message::Payload> + fake::Dummy<Faker>,
@@ -142,6 +142,16 @@ pub enum Error {
     #[error("blocklist client error: {0}")]
     BlocklistClient(#[from] BlocklistClientError),
 
+    /// An error occurred while reading or writing the archive of the
+    /// `POST /new_block` webhook bodies.
+    #[error("new block event archive error for {1}: {0}")]
+    EventArchive(#[source] std::io::Error, std::path::PathBuf),
+
+    /// The `POST /new_block` handler failed on an archived webhook body
+    /// that was replayed to backfill the events of a stacks block.
+    #[error("the new block handler returned {1} when backfilling stacks block {0}")]
+    NewBlockBackfill(StacksBlockId, axum::http::StatusCode),
+
     /// Attempt to fetch a bitcoin blockhash ended in an unexpected error.
     /// This is not triggered if the block is missing.
     #[error("bitcoin-core getblock RPC error for hash {1}: {0}")]
@@ -60,6 +60,17 @@ pub const MAX_KEYS: u16 = 128;
 /// the deposit.
 pub const DEPOSIT_LOCKTIME_BLOCK_BUFFER: u16 = 3;
 
+/// The maximum age, in bitcoin blocks, of an approval of a signer set
+/// change proposal for it to count towards the proposal. Signers resend
+/// their proposal on every bitcoin block, so older approvals are from
+/// signers that stopped proposing the change.
+pub const SIGNER_SET_APPROVAL_MAX_AGE: u64 = 6;
+
+/// The minimum time between two writes of the statistics of a peer for
+/// the same throttled event, such as an invalid message. Occurrences in
+/// between are counted and written together.
+pub const PEER_EVENT_WRITE_INTERVAL: std::time::Duration = std::time::Duration::from_secs(30);
+
 /// This is the capacity of the channel used for messages sent within the
 /// signer.
 pub const SIGNER_CHANNEL_CAPACITY: usize = 1024;
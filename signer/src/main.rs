@@ -1,5 +1,6 @@
 //! The main entrypoint for the sBTC signer binary.
 
+use std::net::SocketAddr;
 use std::path::PathBuf;
 use std::sync::Arc;
 use std::sync::atomic::AtomicU64;
@@ -11,6 +12,7 @@ use axum::http::Response;
 use cfg_if::cfg_if;
 use clap::Parser;
 use clap::ValueEnum;
+use signer::PEER_EVENT_WRITE_INTERVAL;
 use signer::api;
 use signer::api::ApiState;
 use signer::bitcoin::poller::BitcoinChainTipPoller;
@@ -29,6 +31,7 @@ use signer::request_decider::RequestDeciderEventLoop;
 use signer::stacks::api::StacksClient;
 use signer::storage::DbRead as _;
 use signer::storage::postgres::PgStore;
+use signer::storage::util::flush_throttled_peer_events;
 use signer::transaction_coordinator;
 use signer::transaction_signer;
 use signer::util::ApiFallbackClient;
@@ -57,6 +60,10 @@ const KNOWN_PEER_WINDOW: Duration = Duration::from_secs(60 * 60 * 24 * 30); // 3
 /// addition to the seed peers.
 const MAX_KNOWN_PEERS: usize = 6;
 
+/// The interval at which the stacks nodes pushing events to the event
+/// observer are checked for having stopped pushing them.
+const EVENT_SOURCES_CHECK_INTERVAL: Duration = Duration::from_secs(30);
+
 #[derive(Debug, Clone, Copy, ValueEnum)]
 enum LogOutputFormat {
     Json,
@@ -167,6 +174,9 @@ async fn main() -> Result<(), Box<dyn std::error::Error>> {
         // Signer info logger intentionally runned in unchecked mode,
         // since it is not necessary for signer to be operational.
         run_signer_info_logger(context.clone()),
+        // Losing the pending peer events only makes the peer statistics
+        // less accurate, so this is not needed for the signer either.
+        run_peer_event_flusher(context.clone()),
     );
 
     Ok(())
@@ -320,14 +330,36 @@ async fn run_libp2p_swarm(ctx: impl Context) -> Result<(), Error> {
             .collect::<Vec<_>>()
     };
 
+    // Signers pinned to addresses are only reachable on those addresses,
+    // so we dial them there as well.
+    let pinned_peers = config
+        .signer
+        .p2p
+        .pinned_peers
+        .iter()
+        .map(|pinned_peer| (pinned_peer.peer_id(), pinned_peer.address.clone()))
+        .collect::<Vec<_>>();
+
+    // Signers designated as relays relay connections for the other
+    // signers, which use them to be reachable from behind a NAT.
+    let relay_signers = &config.signer.p2p.relay_signers;
+    let is_relay = relay_signers.contains(&config.signer.public_key());
+    let relay_peers = relay_signers
+        .iter()
+        .map(|public_key| libp2p::PeerId::from(*public_key))
+        .collect::<Vec<_>>();
+
     // Build the swarm.
     let mut swarm = SignerSwarmBuilder::new(&config.signer.private_key)
         .add_listen_endpoints(&ctx.config().signer.p2p.listen_on)
         .add_seed_addrs(&ctx.config().signer.p2p.seeds)
         .add_known_peers(&known_peers)
+        .add_known_peers(&pinned_peers)
         .add_external_addresses(&ctx.config().signer.p2p.public_endpoints)
         .enable_mdns(config.signer.p2p.enable_mdns)
         .enable_quic_transport(enable_quic)
+        .enable_relay_server(is_relay)
+        .add_relay_peers(&relay_peers)
         .with_initial_bootstrap_delay(Duration::from_secs(INITIAL_BOOTSTRAP_DELAY_SECS))
         .with_num_signers(num_signers)
         .build()?;
@@ -348,7 +380,31 @@ async fn run_api(ctx: impl Context + 'static) -> Result<(), Error> {
     let socket_addr = ctx.config().signer.event_observer.bind;
     tracing::info!(%socket_addr, "initializing the signer API server");
 
-    let state = ApiState { ctx: ctx.clone() };
+    let archive = match ctx.config().signer.event_observer.archive.clone() {
+        Some(config) => {
+            tracing::info!(path = %config.path.display(), "archiving new block events");
+            Some(Arc::new(api::EventArchive::new(config)?))
+        }
+        None => None,
+    };
+
+    let event_sources = api::EventSources::new(&ctx.config().signer.event_observer).map(Arc::new);
+    let monitor = event_sources.clone().map(|event_sources| {
+        tracing::info!("monitoring the stacks nodes pushing events");
+        tokio::spawn(async move {
+            let mut interval = tokio::time::interval(EVENT_SOURCES_CHECK_INTERVAL);
+            loop {
+                interval.tick().await;
+                event_sources.check_stale();
+            }
+        })
+    });
+
+    let state = ApiState {
+        ctx: ctx.clone(),
+        archive,
+        event_sources,
+    };
 
     let request_id = Arc::new(AtomicU64::new(0));
 
@@ -381,20 +437,29 @@ async fn run_api(ctx: impl Context + 'static) -> Result<(), Error> {
     // Get the termination signal handle.
     let mut term = ctx.get_termination_handle();
 
-    // Run our app with hyper
-    axum::serve(listener, app)
-        .with_graceful_shutdown(async move {
-            // Listen for an application shutdown signal. We need to loop here
-            // because we may receive other signals (which we will ignore here).
-            term.wait_for_shutdown().await;
-            tracing::info!("stopping the signer API server");
-        })
-        .await
-        .map_err(|error| {
-            tracing::error!(%error, "error running the signer API server");
-            ctx.get_termination_handle().signal_shutdown();
-            error.into()
-        })
+    // Run our app with hyper. The address of the peer is needed to tell
+    // apart the stacks nodes pushing events.
+    let result: Result<(), Error> = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(async move {
+        // Listen for an application shutdown signal. We need to loop here
+        // because we may receive other signals (which we will ignore here).
+        term.wait_for_shutdown().await;
+        tracing::info!("stopping the signer API server");
+    })
+    .await
+    .map_err(|error| {
+        tracing::error!(%error, "error running the signer API server");
+        ctx.get_termination_handle().signal_shutdown();
+        error.into()
+    });
+
+    if let Some(monitor) = monitor {
+        monitor.abort();
+    }
+    result
 }
 
 /// Run the block observer event-loop.
@@ -425,6 +490,25 @@ async fn run_signer_info_logger(ctx: impl Context) {
         .await
 }
 
+/// Periodically write the throttled peer events that have been counted
+/// but not written yet, and write the remaining ones on shutdown.
+async fn run_peer_event_flusher(ctx: impl Context) {
+    let mut term = ctx.get_termination_handle();
+    let db = ctx.get_storage_mut();
+
+    loop {
+        let shutdown = tokio::select! {
+            _ = term.wait_for_shutdown() => true,
+            _ = tokio::time::sleep(PEER_EVENT_WRITE_INTERVAL) => false,
+        };
+        flush_throttled_peer_events(&db, ctx.state().peer_event_throttle()).await;
+        if shutdown {
+            break;
+        }
+    }
+    tracing::info!("peer event flusher has stopped");
+}
+
 /// Run the transaction signer event-loop.
 async fn run_transaction_signer(ctx: impl Context) -> Result<(), Error> {
     let network = P2PNetwork::new(&ctx);
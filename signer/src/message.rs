@@ -1,10 +1,13 @@
 //! Signer message definition for network communication
 
+use std::collections::BTreeSet;
+
 use secp256k1::ecdsa::RecoverableSignature;
 
 use crate::bitcoin::utxo::Fees;
 use crate::bitcoin::validation::TxRequestIds;
 use crate::keys::PublicKey;
+use crate::stacks::api::SignerSetInfo;
 use crate::stacks::contracts::ContractCall;
 use crate::stacks::contracts::StacksTx;
 use crate::storage::model;
@@ -38,6 +41,10 @@ pub enum Payload {
     BitcoinPreSignRequest(BitcoinPreSignRequest),
     /// An acknowledgment of a BitconPreSignRequest
     BitcoinPreSignAck(BitcoinPreSignAck),
+    /// A proposal to change the signing set, approved by its sender
+    SignerSetProposal(SignerSetProposal),
+    /// A request for the decisions of the recipient on the given requests
+    DecisionSyncRequest(DecisionSyncRequest),
 }
 
 impl std::fmt::Display for Payload {
@@ -69,6 +76,8 @@ impl std::fmt::Display for Payload {
             }
             Self::BitcoinPreSignRequest(_) => write!(f, "BitcoinPreSignRequest(..)"),
             Self::BitcoinPreSignAck(_) => write!(f, "BitcoinPreSignAck(..)"),
+            Self::SignerSetProposal(_) => write!(f, "SignerSetProposal(..)"),
+            Self::DecisionSyncRequest(_) => write!(f, "DecisionSyncRequest(..)"),
         }
     }
 }
@@ -81,6 +90,16 @@ impl Payload {
             payload: self,
         }
     }
+
+    /// Returns the oldest signer protocol version that can carry this
+    /// payload. Signers that negotiated an older version with us cannot
+    /// decode the payload, so it must not be sent to them.
+    pub fn protocol_version(&self) -> u32 {
+        match self {
+            Self::SignerSetProposal(_) | Self::DecisionSyncRequest(_) => 2,
+            _ => 1,
+        }
+    }
 }
 
 impl From<SignerDepositDecision> for Payload {
@@ -125,6 +144,18 @@ impl From<BitcoinPreSignAck> for Payload {
     }
 }
 
+impl From<SignerSetProposal> for Payload {
+    fn from(value: SignerSetProposal) -> Self {
+        Self::SignerSetProposal(value)
+    }
+}
+
+impl From<DecisionSyncRequest> for Payload {
+    fn from(value: DecisionSyncRequest) -> Self {
+        Self::DecisionSyncRequest(value)
+    }
+}
+
 /// Represents a decision related to signer deposit
 #[derive(Debug, Clone, PartialEq)]
 pub struct SignerDepositDecision {
@@ -254,6 +285,71 @@ impl std::fmt::Display for BitcoinPreSignRequest {
 #[derive(Debug, Clone, PartialEq, serde::Serialize, serde::Deserialize)]
 pub struct BitcoinPreSignAck;
 
+/// A proposal to change the signing set.
+///
+/// A signer sending a proposal approves it, and the signers start DKG for
+/// the proposed signing set once enough of the current signers have sent
+/// the identical proposal.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct SignerSetProposal {
+    /// The public keys of the proposed signing set.
+    pub signer_set: BTreeSet<PublicKey>,
+    /// The proposed number of signatures required.
+    pub signatures_required: u16,
+}
+
+impl SignerSetProposal {
+    /// Return whether the proposal is well formed, meaning that it
+    /// requires at least one signature and no more signatures than there
+    /// are signers.
+    pub fn is_valid(&self) -> bool {
+        self.signatures_required > 0
+            && usize::from(self.signatures_required) <= self.signer_set.len()
+    }
+}
+
+impl From<&SignerSetInfo> for SignerSetProposal {
+    fn from(info: &SignerSetInfo) -> Self {
+        Self {
+            signer_set: info.signer_set.clone(),
+            signatures_required: info.signatures_required,
+        }
+    }
+}
+
+impl From<model::SignerSetApproval> for SignerSetProposal {
+    fn from(approval: model::SignerSetApproval) -> Self {
+        Self {
+            signer_set: approval.signer_set_public_keys(),
+            signatures_required: approval.signatures_required,
+        }
+    }
+}
+
+/// A request for the decisions that the recipient made on the given
+/// deposit and withdrawal requests.
+///
+/// Signers send this request to a peer when they connect to it, asking
+/// for the decisions on the requests in their context window that they
+/// have no vote from the peer on, so that decisions that were broadcast
+/// while the signers were disconnected are not lost. The peer answers
+/// with a decision message for each request that it has decided on.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DecisionSyncRequest {
+    /// The deposit requests that the sender wants the decisions for.
+    pub deposits: Vec<bitcoin::OutPoint>,
+    /// The withdrawal requests that the sender wants the decisions for.
+    pub withdrawals: Vec<model::QualifiedRequestId>,
+}
+
+impl DecisionSyncRequest {
+    /// Return whether the request asks for any decisions at all.
+    pub fn is_empty(&self) -> bool {
+        self.deposits.is_empty() && self.withdrawals.is_empty()
+    }
+}
+
 /// The identifier for a WSTS message.
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum WstsMessageId {
@@ -44,6 +44,9 @@ pub enum Metrics {
     /// The total number of tenures that this signer has served as
     /// coordinator.
     CoordinatorTenuresTotal,
+    /// The total number of tenures in which this signer took over as a
+    /// backup coordinator.
+    CoordinatorFailoversTotal,
     /// The total number of sign requests received from the signer.
     SignRequestsTotal,
     /// The amount of time it took to complete a signing round in seconds.
@@ -55,6 +58,11 @@ pub enum Metrics {
     ValidationDurationSeconds,
     /// The number of peers connected in the p2p network.
     PeersConnected,
+    /// The total number of messages from peers in the p2p network that
+    /// exceeded the size or rate limits for their payload type, each of
+    /// which temporarily banned the peer. We use labels to distinguish
+    /// between payload types and the exceeded limit.
+    P2pLimitsExceededTotal,
     /// The amount of time, in seconds, it took for a call-read request to
     /// return from the stacks node.
     CallReadOnlyDurationSeconds,
@@ -73,6 +81,26 @@ pub enum Metrics {
     /// The total number of times that a request to read a map entry in a
     /// smart contract has been made to the stacks node.
     ReadMapEntryRequestsTotal,
+    /// The total number of calls to the blocklist client that failed,
+    /// including calls rejected by the open circuit breaker. We use a
+    /// label to distinguish between the two.
+    BlocklistClientFailuresTotal,
+    /// Whether the circuit breaker around the blocklist client is open,
+    /// one if it is and zero otherwise.
+    BlocklistCircuitBreakerOpen,
+    /// The total number of votes on deposit and withdrawal requests that
+    /// were decided by the blocklist outage policy, or deferred by it,
+    /// because the blocklist client was unavailable.
+    BlocklistOutageDecisionsTotal,
+    /// The total number of times two stacks nodes pushing events to the
+    /// event observer disagreed about the block at a stacks block height.
+    StacksEventSourceTipDisagreementsTotal,
+    /// Whether a configured stacks node has stopped pushing events to the
+    /// event observer, one if it has and zero otherwise.
+    StacksEventSourceStale,
+    /// The total number of pushes of a stacks block whose events were not
+    /// handled because they were already handled for another stacks node.
+    StacksEventsDeduplicatedTotal,
 }
 
 impl From<Metrics> for metrics::KeyName {
@@ -120,6 +148,18 @@ impl Metrics {
         metrics::gauge!(Metrics::PeersConnected).decrement(1.0);
     }
 
+    /// Increment the number of messages from peers that exceeded their
+    /// limits. The kind is the payload type of the message and the limit
+    /// is either `size` or `rate`.
+    pub fn increment_p2p_limits_exceeded(kind: &'static str, limit: &'static str) {
+        metrics::counter!(
+            Metrics::P2pLimitsExceededTotal,
+            "kind" => kind,
+            "limit" => limit,
+        )
+        .increment(1);
+    }
+
     /// Increment number of presign requests that were processed noting
     /// whether the presign validation finished successfully. Also record
     /// the amount of time that it took to run the validation.
@@ -275,6 +315,58 @@ impl Metrics {
         )
         .increment(1);
     }
+
+    /// Increment the number of failed calls to the blocklist client. The
+    /// reason is either `error`, when the blocklist client returned an
+    /// error, or `circuit-open`, when the call was rejected by the open
+    /// circuit breaker.
+    pub fn increment_blocklist_client_failures(reason: &'static str) {
+        metrics::counter!(
+            Metrics::BlocklistClientFailuresTotal,
+            "reason" => reason,
+        )
+        .increment(1);
+    }
+
+    /// Record whether the circuit breaker around the blocklist client is
+    /// open.
+    pub fn set_blocklist_circuit_breaker_open(is_open: bool) {
+        metrics::gauge!(Metrics::BlocklistCircuitBreakerOpen).set(if is_open { 1.0 } else { 0.0 });
+    }
+
+    /// Increment the number of votes on requests decided by the blocklist
+    /// outage policy. The kind is either `deposit` or `withdrawal` and the
+    /// decision one of `accept`, `reject` or `defer`.
+    pub fn increment_blocklist_outage_decisions(kind: &'static str, decision: &'static str) {
+        metrics::counter!(
+            Metrics::BlocklistOutageDecisionsTotal,
+            "kind" => kind,
+            "decision" => decision,
+        )
+        .increment(1);
+    }
+
+    /// Increment the number of disagreements between stacks nodes about
+    /// the stacks chain tip.
+    pub fn increment_stacks_event_source_tip_disagreements() {
+        metrics::counter!(Metrics::StacksEventSourceTipDisagreementsTotal).increment(1);
+    }
+
+    /// Record whether the stacks node at the given address has stopped
+    /// pushing events.
+    pub fn set_stacks_event_source_stale(source: std::net::IpAddr, is_stale: bool) {
+        metrics::gauge!(
+            Metrics::StacksEventSourceStale,
+            "source" => source.to_string(),
+        )
+        .set(if is_stale { 1.0 } else { 0.0 });
+    }
+
+    /// Increment the number of pushes of stacks blocks whose events were
+    /// already handled.
+    pub fn increment_stacks_events_deduplicated() {
+        metrics::counter!(Metrics::StacksEventsDeduplicatedTotal).increment(1);
+    }
 }
 
 /// Label for bitcoin blockchain based metrics
@@ -15,6 +15,7 @@ use tokio::sync::broadcast;
 use crate::{
     codec::{Decode as _, Encode as _},
     error::Error,
+    keys::PublicKey,
 };
 
 const BROADCAST_CHANNEL_CAPACITY: usize = 10_000;
@@ -98,6 +99,12 @@ impl super::MessageTransfer for MpmcBroadcaster {
         Ok(())
     }
 
+    /// The in-memory network has no notion of signer identities, so
+    /// messages sent to a specific signer are broadcast to everyone.
+    async fn send_to(&mut self, _: PublicKey, msg: super::Msg) -> Result<(), Error> {
+        self.broadcast(msg).await
+    }
+
     async fn receive(&mut self) -> Result<super::Msg, Error> {
         let mut encoded_msg = self.receiver.recv().await.map_err(Error::ChannelReceive)?;
         let mut msg = super::Msg::decode(encoded_msg.as_slice())?;
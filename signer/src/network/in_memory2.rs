@@ -13,17 +13,23 @@ use crate::context::P2PEvent;
 use crate::context::SignerEvent;
 use crate::context::SignerSignal;
 use crate::error::Error;
+use crate::keys::PublicKey;
 
 use super::MessageTransfer;
 use super::Msg;
 
 const DEFAULT_WAN_CAPACITY: usize = 10_000;
 
+/// A message sent over the in-memory WAN network. It holds the ID of the
+/// signer that sent it, the public key of the intended recipient if the
+/// message is not meant for everyone, and the encoded message.
+type WanMessage = (u8, Option<PublicKey>, Vec<u8>);
+
 /// In-memory representation of a WAN network between different signers.
 pub struct WanNetwork {
     /// A sender that passes the message along with the ID of the signer
     /// that sent it.
-    tx: Sender<(u8, Vec<u8>)>,
+    tx: Sender<WanMessage>,
     /// A variable with the last ID of the signers.
     id: AtomicU8,
 }
@@ -58,8 +64,9 @@ impl Default for WanNetwork {
 /// same network.
 #[derive(Debug, Clone)]
 pub struct SignerNetwork {
-    wan_tx: Sender<(u8, Vec<u8>)>,
+    wan_tx: Sender<WanMessage>,
     signer_tx: Sender<SignerSignal>,
+    public_key: PublicKey,
     id: u8,
 }
 
@@ -73,14 +80,18 @@ impl SignerNetwork {
 
         // We spawn a task that listens to the WAN network and forwards messages
         // to the signer network, but only if this signer instance isn't the
-        // sender.
+        // sender and the message is meant for us.
         let my_id = self.id;
+        let my_public_key = self.public_key;
         tokio::spawn(async move {
             while let Some(item) = rx.next().await {
                 match item {
                     // We do not send messages where the ID is the same as
-                    // ours, since those originated with us.
-                    Ok((id, msg)) if id != my_id => {
+                    // ours, since those originated with us, nor messages
+                    // that were sent directly to another signer.
+                    Ok((id, recipient, msg))
+                        if id != my_id && recipient.is_none_or(|key| key == my_public_key) =>
+                    {
                         let msg = match Msg::decode_with_digest(msg.as_slice()) {
                             Ok((msg, digest)) if msg.verify_digest(digest).is_ok() => msg,
                             Ok(_) => {
@@ -112,19 +123,26 @@ impl SignerNetwork {
     }
 
     /// Create a new in-memory signer network.
-    fn new<C: Context>(ctx: &C, wan_tx: Sender<(u8, Vec<u8>)>, id: u8) -> Self {
+    fn new<C: Context>(ctx: &C, wan_tx: Sender<WanMessage>, id: u8) -> Self {
         // We create a new broadcast channel for this signer's network.
         let signer_tx = ctx.get_signal_sender();
+        let public_key = ctx.config().signer.public_key();
 
-        Self { wan_tx, signer_tx, id }
+        Self {
+            wan_tx,
+            signer_tx,
+            public_key,
+            id,
+        }
     }
 
-    /// Sends a message to the WAN network.
-    fn send(&self, msg: Msg) -> Result<(), Error> {
+    /// Sends a message to the WAN network. If a recipient is given then
+    /// only the signer with that public key will receive the message.
+    fn send(&self, recipient: Option<PublicKey>, msg: Msg) -> Result<(), Error> {
         let encoded_msg = msg.encode_to_vec();
         // Send the message out to the WAN.
         self.wan_tx
-            .send((self.id, encoded_msg))
+            .send((self.id, recipient, encoded_msg))
             .inspect_err(|error| tracing::error!(%error, "could not send over the network"))
             .map(|_| ())
             .map_err(|_| Error::SendMessage)
@@ -159,7 +177,11 @@ impl Clone for SignerNetworkInstance {
 
 impl MessageTransfer for SignerNetworkInstance {
     async fn broadcast(&mut self, msg: Msg) -> Result<(), Error> {
-        self.signer_network.send(msg)
+        self.signer_network.send(None, msg)
+    }
+
+    async fn send_to(&mut self, peer: PublicKey, msg: Msg) -> Result<(), Error> {
+        self.signer_network.send(Some(peer), msg)
     }
 
     async fn receive(&mut self) -> Result<Msg, Error> {
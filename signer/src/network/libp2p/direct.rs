@@ -0,0 +1,146 @@
+//! Point-to-point messaging between signers using the libp2p
+//! request-response protocol.
+//!
+//! Gossipsub floods every message to the whole signer set, which is
+//! wasteful for messages that only have a single interested recipient,
+//! like the responses that a signer sends back to the coordinator. This
+//! module defines the protocol used for sending such messages directly to
+//! a single peer. A request carries an encoded [`Msg`](crate::network::Msg)
+//! and the response is an empty acknowledgement.
+
+use std::io;
+use std::time::Duration;
+
+use libp2p::StreamProtocol;
+use libp2p::futures::{AsyncRead, AsyncReadExt as _, AsyncWrite, AsyncWriteExt as _};
+use libp2p::request_response::{self, ProtocolSupport};
+
+/// The protocol name used for direct messages between signers.
+pub const PROTOCOL: StreamProtocol = StreamProtocol::new("/sbtc-signer/direct/1.0.0");
+
+/// The oldest signer protocol version that supports direct messages.
+/// Messages for peers that negotiated an older version are published
+/// instead.
+pub const MIN_PROTOCOL_VERSION: u32 = 2;
+
+/// The maximum size, in bytes, of an encoded direct message. This matches
+/// the maximum size of messages that we accept over gossipsub.
+pub const MAX_MESSAGE_SIZE: usize = 65_536;
+
+/// The maximum amount of time to wait for a peer to acknowledge a direct
+/// message before the request is considered failed.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// The request-response behavior used for direct messages.
+pub type Behavior = request_response::Behaviour<Codec>;
+
+/// Create a new request-response behavior for direct messages.
+pub fn behavior() -> Behavior {
+    let config = request_response::Config::default().with_request_timeout(REQUEST_TIMEOUT);
+    request_response::Behaviour::with_codec(Codec, [(PROTOCOL, ProtocolSupport::Full)], config)
+}
+
+/// The codec for direct messages. Requests are written as a big-endian
+/// `u32` length prefix followed by the encoded message, while responses
+/// carry no data at all.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Codec;
+
+#[async_trait::async_trait]
+impl request_response::Codec for Codec {
+    type Protocol = StreamProtocol;
+    type Request = Vec<u8>;
+    type Response = ();
+
+    async fn read_request<T>(&mut self, _: &Self::Protocol, io: &mut T) -> io::Result<Vec<u8>>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        let mut len = [0u8; 4];
+        io.read_exact(&mut len).await?;
+        let len = u32::from_be_bytes(len) as usize;
+
+        if len > MAX_MESSAGE_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("direct message of {len} bytes exceeds the maximum size"),
+            ));
+        }
+
+        let mut data = vec![0u8; len];
+        io.read_exact(&mut data).await?;
+        Ok(data)
+    }
+
+    async fn read_response<T>(&mut self, _: &Self::Protocol, _: &mut T) -> io::Result<()>
+    where
+        T: AsyncRead + Unpin + Send,
+    {
+        Ok(())
+    }
+
+    async fn write_request<T>(
+        &mut self,
+        _: &Self::Protocol,
+        io: &mut T,
+        data: Vec<u8>,
+    ) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        if data.len() > MAX_MESSAGE_SIZE {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidInput,
+                format!(
+                    "direct message of {} bytes exceeds the maximum size",
+                    data.len()
+                ),
+            ));
+        }
+
+        io.write_all(&(data.len() as u32).to_be_bytes()).await?;
+        io.write_all(&data).await?;
+        io.close().await
+    }
+
+    async fn write_response<T>(&mut self, _: &Self::Protocol, io: &mut T, _: ()) -> io::Result<()>
+    where
+        T: AsyncWrite + Unpin + Send,
+    {
+        io.close().await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use libp2p::futures::io::Cursor;
+    use request_response::Codec as _;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn codec_round_trips_requests() {
+        let data = vec![1, 2, 3, 4, 5];
+        let mut buffer = Cursor::new(Vec::new());
+        Codec
+            .write_request(&PROTOCOL, &mut buffer, data.clone())
+            .await
+            .unwrap();
+
+        buffer.set_position(0);
+        let decoded = Codec.read_request(&PROTOCOL, &mut buffer).await.unwrap();
+        assert_eq!(decoded, data);
+    }
+
+    #[tokio::test]
+    async fn codec_rejects_oversized_requests() {
+        let data = vec![0; MAX_MESSAGE_SIZE + 1];
+        let mut buffer = Cursor::new(Vec::new());
+        let result = Codec.write_request(&PROTOCOL, &mut buffer, data).await;
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidInput);
+
+        let mut buffer = Cursor::new(((MAX_MESSAGE_SIZE + 1) as u32).to_be_bytes().to_vec());
+        let result = Codec.read_request(&PROTOCOL, &mut buffer).await;
+        assert_eq!(result.unwrap_err().kind(), io::ErrorKind::InvalidData);
+    }
+}
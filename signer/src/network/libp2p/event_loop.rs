@@ -1,55 +1,89 @@
+use std::collections::BTreeSet;
+use std::collections::HashMap;
 use std::sync::Arc;
 use std::time::Duration;
+use std::time::Instant;
 
 use futures::StreamExt as _;
 use libp2p::core::ConnectedPoint;
+use libp2p::gossipsub::MessageAcceptance;
 use libp2p::kad::RoutingUpdate;
+use libp2p::multiaddr::Protocol;
+use libp2p::request_response::{self, OutboundRequestId};
 use libp2p::swarm::SwarmEvent;
-use libp2p::{Swarm, gossipsub, identify, kad, mdns};
+use libp2p::swarm::dial_opts::{DialOpts, PeerCondition};
+use libp2p::{Multiaddr, PeerId, Swarm, dcutr, gossipsub, identify, kad, mdns, ping, relay};
 use tokio::sync::Mutex;
 
 use crate::codec::Encode as _;
-use crate::context::{Context, P2PEvent, SignerCommand, SignerSignal};
-use crate::error::Error;
+use crate::context::{
+    ConnectionDirection, Context, P2PEvent, PeerConnection, SignerCommand, SignerSignal,
+};
+use crate::keys::PublicKey;
 use crate::network::Msg;
 use crate::network::libp2p::MultiaddrExt as _;
+use crate::network::version::ProtocolVersions;
 use crate::storage::DbWrite as _;
 
-use super::TOPIC;
-use super::swarm::{SignerBehavior, SignerBehaviorEvent};
+use super::direct;
+use super::rate_limit::RateLimiter;
+use super::swarm::{SignerBehavior, SignerBehaviorEvent, inferred_security_protocol};
+use super::{TOPIC, TOPIC_V2};
 
 #[tracing::instrument(skip_all, name = "swarm")]
-pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
-    // Subscribe to the gossipsub topic.
-    let topic = TOPIC.clone();
-    swarm
-        .lock()
-        .await
-        .behaviour_mut()
-        .gossipsub
-        .subscribe(&TOPIC)
+pub async fn run(
+    ctx: &impl Context,
+    swarm: Arc<Mutex<Swarm<SignerBehavior>>>,
+    relay_peers: &[PeerId],
+    protocol_versions: ProtocolVersions,
+) {
+    // Subscribe to the gossipsub topics of the protocol versions that we
+    // support.
+    {
+        let mut swarm = swarm.lock().await;
+        let gossipsub = &mut swarm.behaviour_mut().gossipsub;
         // If this doesn't succeed then nothing will work. It should never fail.
-        .expect("failed to subscribe to topic");
+        gossipsub
+            .subscribe(&TOPIC)
+            .expect("failed to subscribe to topic");
+        if protocol_versions.max >= 2 {
+            gossipsub
+                .subscribe(&TOPIC_V2)
+                .expect("failed to subscribe to topic");
+        }
+    }
+
+    // The addresses that the operator pinned each signer to, if any.
+    let mut pinned_addresses = HashMap::<PeerId, Vec<Multiaddr>>::new();
+    for pinned_peer in &ctx.config().signer.p2p.pinned_peers {
+        pinned_addresses
+            .entry(pinned_peer.peer_id())
+            .or_default()
+            .push(pinned_peer.address.clone());
+    }
 
     let mut term = ctx.get_termination_handle();
     let mut signal_rx = ctx.get_signal_receiver();
     let signal_tx = ctx.get_signal_sender();
 
-    // Here we create a future that listens for `P2PPublish` commands from the
-    // app signalling channel and pushes them into the outbound message queue.
+    // Here we create a future that listens for `P2PPublish` and `P2PSendTo`
+    // commands from the app signalling channel and pushes them into the
+    // outbound message queue, along with the recipient for direct messages.
     // This queue is then polled by the `poll_swarm` event loop to publish the
     // messages to the network.
-    let outbox = Mutex::new(Vec::<Msg>::new());
+    let outbox = Mutex::new(Vec::<(Option<PublicKey>, Msg)>::new());
     let poll_outbound = async {
         tracing::debug!("p2p outbound message polling started");
         loop {
-            let Ok(SignerSignal::Command(SignerCommand::P2PPublish(payload))) =
-                signal_rx.recv().await
-            else {
-                continue;
-            };
-
-            outbox.lock().await.push(*payload);
+            match signal_rx.recv().await {
+                Ok(SignerSignal::Command(SignerCommand::P2PPublish(payload))) => {
+                    outbox.lock().await.push((None, *payload));
+                }
+                Ok(SignerSignal::Command(SignerCommand::P2PSendTo(peer, payload))) => {
+                    outbox.lock().await.push((Some(peer), *payload));
+                }
+                _ => continue,
+            }
         }
     };
 
@@ -61,6 +95,15 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
             .inspect_err(|error| tracing::error!(%error, "error signalling event loop start"));
         tracing::debug!("p2p network polling started");
 
+        // Direct messages that are awaiting an acknowledgement from their
+        // recipient. We keep the messages around so that they can be
+        // published to everyone if the direct delivery fails.
+        let mut pending_direct = HashMap::<OutboundRequestId, Msg>::new();
+
+        // Tracks the messages received from each peer, banning peers that
+        // exceed their limits.
+        let mut rate_limiter = RateLimiter::new(ctx.config().signer.p2p.peer_ban_duration);
+
         loop {
             // Poll the libp2p swarm for events, waiting for a maximum of 5ms
             // so that we don't starve the outbox.
@@ -81,11 +124,22 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                     // Identify protocol events. These are used by the relay to
                     // help determine/verify its own address.
                     SwarmEvent::Behaviour(SignerBehaviorEvent::Identify(event)) => {
-                        handle_identify_event(&mut swarm, ctx, event)
+                        handle_identify_event(&mut swarm, ctx, event, protocol_versions)
                     }
                     // Gossipsub protocol events.
                     SwarmEvent::Behaviour(SignerBehaviorEvent::Gossipsub(event)) => {
-                        handle_gossipsub_event(&mut swarm, ctx, event)
+                        handle_gossipsub_event(&mut swarm, ctx, event, &mut rate_limiter)
+                    }
+                    // Request-response events for messages sent directly
+                    // between two signers.
+                    SwarmEvent::Behaviour(SignerBehaviorEvent::Direct(event)) => {
+                        handle_direct_event(
+                            &mut swarm,
+                            ctx,
+                            event,
+                            &mut pending_direct,
+                            &mut rate_limiter,
+                        )
                     }
                     SwarmEvent::NewListenAddr { address, .. } => {
                         tracing::info!(%address, "listener started");
@@ -106,13 +160,54 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                         connection_id,
                         endpoint,
                         peer_id,
+                        num_established,
                         ..
                     } => {
                         if !ctx.state().current_signer_set().is_allowed_peer(&peer_id) {
                             tracing::warn!(%connection_id, %peer_id, ?endpoint, "connected to peer, however it is not a known signer; disconnecting");
                             let _ = swarm.disconnect_peer_id(peer_id);
+                        } else if rate_limiter.is_banned(&peer_id, Instant::now()) {
+                            tracing::debug!(%connection_id, %peer_id, ?endpoint, "connected to peer, however it is temporarily banned; disconnecting");
+                            let _ = swarm.disconnect_peer_id(peer_id);
+                        } else if pinned_addresses.get(&peer_id).is_some_and(|pinned| {
+                            let address = endpoint.get_remote_address();
+                            !pinned.iter().any(|pin| pin.is_same_host(address))
+                        }) {
+                            tracing::warn!(%connection_id, %peer_id, ?endpoint, "connected to peer on an address that it is not pinned to; closing the connection");
+                            swarm.close_connection(connection_id);
                         } else {
-                            tracing::debug!(%peer_id, ?endpoint, "connected to peer");
+                            tracing::info!(
+                                %peer_id,
+                                %connection_id,
+                                ?endpoint,
+                                inferred_security =
+                                    inferred_security_protocol(endpoint.get_remote_address()),
+                                "connected to peer"
+                            );
+
+                            let direction = if endpoint.is_dialer() {
+                                ConnectionDirection::Outbound
+                            } else {
+                                ConnectionDirection::Inbound
+                            };
+                            ctx.state().connected_peers().add_connection(
+                                peer_id,
+                                PeerConnection {
+                                    connection_id,
+                                    address: endpoint.get_remote_address().clone(),
+                                    direction,
+                                    inferred_security: inferred_security_protocol(
+                                        endpoint.get_remote_address(),
+                                    ),
+                                },
+                            );
+
+                            // Let the application know when we (re)connect to
+                            // a peer, so that it can catch up on the messages
+                            // that it missed while it was disconnected.
+                            if num_established.get() == 1 {
+                                let _ = ctx.signal(P2PEvent::PeerConnected(peer_id).into());
+                            }
 
                             // Perform operations that are only needed/possible when we are the
                             // dialer and have peer's confirmed dialable address.
@@ -153,17 +248,44 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                                 } else {
                                     tracing::warn!(%peer_id, "BUG: peer was allowed, but we couldn't map it to a public key");
                                 }
+
+                                // If the peer is one of our relays, reserve a slot
+                                // with it so that other signers can reach us
+                                // through it.
+                                if swarm.behaviour().relay_client.is_enabled()
+                                    && relay_peers.contains(&peer_id)
+                                    && num_established.get() == 1
+                                    && !address.is_relayed()
+                                {
+                                    let circuit = address
+                                        .without_p2p_protocol()
+                                        .with(Protocol::P2p(peer_id))
+                                        .with(Protocol::P2pCircuit);
+                                    tracing::debug!(%peer_id, %circuit, "listening through relay");
+                                    let _ = swarm.listen_on(circuit).inspect_err(|error| {
+                                        tracing::warn!(%peer_id, %error, "failed to listen through relay");
+                                    });
+                                }
                             }
                         }
                     }
-                    SwarmEvent::ConnectionClosed { peer_id, cause, endpoint, .. } => {
+                    SwarmEvent::ConnectionClosed {
+                        peer_id,
+                        connection_id,
+                        cause,
+                        endpoint,
+                        ..
+                    } => {
                         tracing::trace!(%peer_id, ?cause, ?endpoint, "connection closed");
+                        ctx.state()
+                            .connected_peers()
+                            .remove_connection(&peer_id, connection_id);
                     }
                     SwarmEvent::IncomingConnection { local_addr, send_back_addr, .. } => {
                         tracing::trace!(%local_addr, %send_back_addr, "incoming connection");
                     }
-                    SwarmEvent::Behaviour(SignerBehaviorEvent::Ping(ping)) => {
-                        tracing::trace!("ping received: {:?}", ping);
+                    SwarmEvent::Behaviour(SignerBehaviorEvent::Ping(event)) => {
+                        handle_ping_event(&mut swarm, ctx, event)
                     }
                     SwarmEvent::OutgoingConnectionError { connection_id, error, peer_id } => {
                         tracing::trace!(%connection_id, %error, ?peer_id, "outgoing connection error");
@@ -232,6 +354,20 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
                             "autonat server event"
                         );
                     }
+                    // Circuit relay events, for connections that we relay
+                    // for other signers and for connections that other
+                    // signers relay for us.
+                    SwarmEvent::Behaviour(SignerBehaviorEvent::RelayServer(event)) => {
+                        tracing::debug!(?event, "relay server event");
+                    }
+                    SwarmEvent::Behaviour(SignerBehaviorEvent::RelayClient(event)) => {
+                        handle_relay_client_event(&mut swarm, ctx, event)
+                    }
+                    // Hole punching events, for upgrading relayed connections
+                    // to direct connections.
+                    SwarmEvent::Behaviour(SignerBehaviorEvent::Dcutr(event)) => {
+                        handle_dcutr_event(event)
+                    }
                     SwarmEvent::Behaviour(SignerBehaviorEvent::Bootstrap(_)) => {}
                     // The derived `SwarmEvent` is marked as #[non_exhaustive], so we must have a
                     // catch-all.
@@ -241,37 +377,41 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
 
             // Drain the outbox and publish the messages to the network.
             let outbox = outbox.lock().await.drain(..).collect::<Vec<_>>();
-            for payload in outbox {
-                let msg_id = payload.id();
+            for (recipient, payload) in outbox {
+                let mut swarm = swarm.lock().await;
+                let Some(recipient) = recipient else {
+                    publish_message(&mut swarm, &signal_tx, payload);
+                    continue;
+                };
+
+                // Peers that negotiated a protocol version without direct
+                // messages get the message the same way as everyone else.
+                let peer_id = PeerId::from(recipient);
+                let peer_version = ctx
+                    .state()
+                    .connected_peers()
+                    .peer_protocol_version(&peer_id);
+                if peer_version.is_some_and(|version| version < direct::MIN_PROTOCOL_VERSION) {
+                    publish_message(&mut swarm, &signal_tx, payload);
+                    continue;
+                }
+
                 tracing::trace!(
-                    message_id = hex::encode(msg_id),
+                    message_id = hex::encode(payload.id()),
                     msg = %payload,
-                    "publishing message"
+                    %recipient,
+                    %peer_id,
+                    "sending message directly to peer"
                 );
 
-                // Encode the message payload into bytes using the signer codec.
-                let encoded_msg = payload.encode_to_vec();
-
-                let _ = swarm
-                    .lock()
-                    .await
+                // The success or failure of the delivery is signalled once
+                // the recipient acknowledges the message, or the request
+                // fails.
+                let request_id = swarm
                     .behaviour_mut()
-                    .gossipsub
-                    .publish(topic.clone(), encoded_msg)
-                    .inspect_err(|error| {
-                        // An error occurred while attempting to publish.
-                        // Log the error and send a failure signal to the application
-                        // so that it can handle the failure as needed.
-                        tracing::warn!(%error, ?msg_id, "failed to publish message");
-                        let _ = signal_tx.send(P2PEvent::PublishFailure(msg_id).into());
-                    })
-                    .inspect(|_| {
-                        // The message was published successfully. Log the success
-                        // and send a success signal to the application so that it can
-                        // handle the success as needed.
-                        tracing::trace!(?msg_id, "message published successfully");
-                        let _ = signal_tx.send(P2PEvent::PublishSuccess(msg_id).into());
-                    });
+                    .direct
+                    .send_request(&peer_id, payload.encode_to_vec());
+                pending_direct.insert(request_id, payload);
             }
         }
     };
@@ -297,6 +437,284 @@ pub async fn run(ctx: &impl Context, swarm: Arc<Mutex<Swarm<SignerBehavior>>>) {
     tracing::info!("libp2p event loop terminated");
 }
 
+/// Publish the given message to all signers using gossipsub, signalling
+/// the outcome to the application.
+///
+/// Messages that require a newer signer protocol version are published on
+/// the topic of that version, so that they never reach signers that only
+/// support older versions.
+fn publish_message(
+    swarm: &mut Swarm<SignerBehavior>,
+    signal_tx: &tokio::sync::broadcast::Sender<SignerSignal>,
+    payload: Msg,
+) {
+    let msg_id = payload.id();
+    tracing::trace!(
+        message_id = hex::encode(msg_id),
+        msg = %payload,
+        "publishing message"
+    );
+
+    // Encode the message payload into bytes using the signer codec.
+    let encoded_msg = payload.encode_to_vec();
+
+    let topic = match payload.inner.payload.protocol_version() {
+        1 => TOPIC.clone(),
+        _ => TOPIC_V2.clone(),
+    };
+
+    let _ = swarm
+        .behaviour_mut()
+        .gossipsub
+        .publish(topic, encoded_msg)
+        .inspect_err(|error| {
+            // An error occurred while attempting to publish.
+            // Log the error and send a failure signal to the application
+            // so that it can handle the failure as needed.
+            tracing::warn!(%error, ?msg_id, "failed to publish message");
+            let _ = signal_tx.send(P2PEvent::PublishFailure(msg_id).into());
+        })
+        .inspect(|_| {
+            // The message was published successfully. Log the success
+            // and send a success signal to the application so that it can
+            // handle the success as needed.
+            tracing::trace!(?msg_id, "message published successfully");
+            let _ = signal_tx.send(P2PEvent::PublishSuccess(msg_id).into());
+        });
+}
+
+/// Decode a message received from the given origin peer, verify its
+/// signature and check it against the limits of the origin peer before
+/// handing it over to the application.
+///
+/// The returned acceptance tells gossipsub whether the message should be
+/// propagated further, and whether the peer should be penalized for it.
+/// Messages that fail to decode or verify are rejected, while messages
+/// from peers that are, or that just got, banned are ignored, since they
+/// may have been relayed to us by an honest peer.
+fn forward_received_message(
+    swarm: &mut Swarm<SignerBehavior>,
+    ctx: &impl Context,
+    rate_limiter: &mut RateLimiter,
+    peer_id: PeerId,
+    origin_peer_id: PeerId,
+    data: &[u8],
+) -> MessageAcceptance {
+    let now = Instant::now();
+    if rate_limiter.is_banned(&origin_peer_id, now) {
+        tracing::trace!(%peer_id, %origin_peer_id, "ignoring message from banned peer");
+        return MessageAcceptance::Ignore;
+    }
+
+    let (msg, digest) = match Msg::decode_with_digest(data) {
+        Ok(decoded) => decoded,
+        Err(error) => {
+            tracing::warn!(%peer_id, %origin_peer_id, %error, "Failed to decode message");
+            return MessageAcceptance::Reject;
+        }
+    };
+    tracing::trace!(
+        local_peer_id = %swarm.local_peer_id(),
+        %peer_id,
+        message_id = hex::encode(msg.id()),
+        %msg,
+        "received message",
+    );
+
+    if origin_peer_id != msg.signer_public_key.into() {
+        tracing::error!(%origin_peer_id, "connected peer sent an invalid message");
+        return MessageAcceptance::Reject;
+    }
+
+    if let Err(error) = msg.verify_digest(digest) {
+        tracing::error!(%origin_peer_id, %error, "connected peer sent an invalid signature");
+        return MessageAcceptance::Reject;
+    }
+
+    if let Err(exceeded) = rate_limiter.check(origin_peer_id, &msg.payload, data.len(), now) {
+        tracing::warn!(
+            %origin_peer_id,
+            limit = %exceeded,
+            size = data.len(),
+            message_id = hex::encode(msg.id()),
+            "peer exceeded its message limits; banning and disconnecting"
+        );
+        let _ = swarm.disconnect_peer_id(origin_peer_id);
+        return MessageAcceptance::Ignore;
+    }
+
+    ctx.state().connected_peers().set_message_received(&peer_id);
+
+    let _ = ctx
+        .get_signal_sender()
+        .send(P2PEvent::MessageReceived(Box::new(msg)).into())
+        .inspect_err(|error| {
+            tracing::debug!(%error, "Failed to send message to application; we are likely shutting down.");
+        });
+
+    MessageAcceptance::Accept
+}
+
+#[tracing::instrument(skip_all, name = "direct")]
+fn handle_direct_event(
+    swarm: &mut Swarm<SignerBehavior>,
+    ctx: &impl Context,
+    event: request_response::Event<Vec<u8>, ()>,
+    pending: &mut HashMap<OutboundRequestId, Msg>,
+    rate_limiter: &mut RateLimiter,
+) {
+    use request_response::{Event, Message};
+
+    match event {
+        Event::Message {
+            peer: peer_id,
+            message: Message::Request { request, channel, .. },
+            ..
+        } => {
+            // Direct messages are never relayed, so the peer that sent
+            // the request must also be the origin of the message.
+            if !ctx.state().current_signer_set().is_allowed_peer(&peer_id) {
+                tracing::warn!(%peer_id, "ignoring direct message from unknown peer");
+                return;
+            }
+
+            // We only acknowledge messages that we accepted, so that the
+            // sender falls back to publishing the message otherwise.
+            let acceptance =
+                forward_received_message(swarm, ctx, rate_limiter, peer_id, peer_id, &request);
+            if !matches!(acceptance, MessageAcceptance::Accept) {
+                return;
+            }
+
+            let _ = swarm
+                .behaviour_mut()
+                .direct
+                .send_response(channel, ())
+                .inspect_err(|_| {
+                    tracing::debug!(%peer_id, "failed to acknowledge direct message");
+                });
+        }
+        Event::Message {
+            peer: peer_id,
+            message: Message::Response { request_id, .. },
+            ..
+        } => {
+            if let Some(msg) = pending.remove(&request_id) {
+                let msg_id = msg.id();
+                tracing::trace!(%peer_id, ?msg_id, "direct message acknowledged by peer");
+                let _ = ctx.signal(P2PEvent::PublishSuccess(msg_id).into());
+            }
+        }
+        Event::OutboundFailure {
+            peer: peer_id,
+            request_id,
+            error,
+            ..
+        } => {
+            // We could not deliver the message directly, most likely
+            // because we are not connected to the peer. Rather than
+            // dropping it we fall back to publishing it to all signers,
+            // which reaches the peer if it is connected to any of them.
+            if let Some(msg) = pending.remove(&request_id) {
+                tracing::debug!(%peer_id, %error, "failed to send direct message; publishing it instead");
+                publish_message(swarm, &ctx.get_signal_sender(), msg);
+            }
+        }
+        Event::InboundFailure { peer: peer_id, error, .. } => {
+            tracing::debug!(%peer_id, %error, "failed to receive direct message");
+        }
+        Event::ResponseSent { peer: peer_id, .. } => {
+            tracing::trace!(%peer_id, "acknowledged direct message");
+        }
+    }
+}
+
+#[tracing::instrument(skip_all, name = "relay-client")]
+fn handle_relay_client_event(
+    swarm: &mut Swarm<SignerBehavior>,
+    ctx: &impl Context,
+    event: relay::client::Event,
+) {
+    match event {
+        relay::client::Event::ReservationReqAccepted { relay_peer_id, renewal, .. } => {
+            if renewal {
+                tracing::trace!(%relay_peer_id, "renewed reservation with relay");
+                return;
+            }
+            tracing::info!(%relay_peer_id, "reserved a slot with relay");
+
+            // Now that other signers can reach us through the relay, try
+            // to reach the signers that we are not connected to through
+            // it as well. Signers that are behind a NAT themselves can only
+            // be reached this way, and only once they have a reservation
+            // with the relay, so whichever of two such signers reserves
+            // its slot last connects them.
+            let local_peer_id = *swarm.local_peer_id();
+            for signer in ctx.state().current_signer_set().get_signers() {
+                let peer_id = *signer.peer_id();
+                if [local_peer_id, relay_peer_id].contains(&peer_id) || swarm.is_connected(&peer_id)
+                {
+                    continue;
+                }
+
+                let address = Multiaddr::empty()
+                    .with(Protocol::P2p(relay_peer_id))
+                    .with(Protocol::P2pCircuit)
+                    .with(Protocol::P2p(peer_id));
+                let opts = DialOpts::peer_id(peer_id)
+                    .condition(PeerCondition::DisconnectedAndNotDialing)
+                    .addresses(vec![address])
+                    .build();
+
+                tracing::debug!(%peer_id, %relay_peer_id, "dialing peer through relay");
+                let _ = swarm.dial(opts).inspect_err(|error| {
+                    tracing::debug!(%peer_id, %error, "failed to dial peer through relay");
+                });
+            }
+        }
+        event => tracing::debug!(?event, "relay client event"),
+    }
+}
+
+#[tracing::instrument(skip_all, name = "ping")]
+fn handle_ping_event(swarm: &mut Swarm<SignerBehavior>, ctx: &impl Context, event: ping::Event) {
+    let peer_id = event.peer;
+    match event.result {
+        Ok(rtt) => {
+            tracing::trace!(%peer_id, ?rtt, "ping succeeded");
+            ctx.state().connected_peers().set_ping_rtt(&peer_id, rtt);
+        }
+        Err(error) => {
+            tracing::trace!(%peer_id, %error, "ping failed");
+        }
+    }
+
+    // Gossipsub does not report changes to its mesh, so we take the
+    // periodic pings as the opportunity to refresh our view of it.
+    let mesh_peers = swarm
+        .behaviour()
+        .gossipsub
+        .mesh_peers(&TOPIC.hash())
+        .copied()
+        .collect::<BTreeSet<_>>();
+    ctx.state()
+        .connected_peers()
+        .set_gossipsub_mesh(&mesh_peers);
+}
+
+#[tracing::instrument(skip_all, name = "dcutr")]
+fn handle_dcutr_event(event: dcutr::Event) {
+    let remote_peer_id = event.remote_peer_id;
+    match event.result {
+        Ok(connection_id) => {
+            tracing::info!(%remote_peer_id, %connection_id, "upgraded relayed connection to a direct connection");
+        }
+        Err(error) => {
+            tracing::debug!(%remote_peer_id, %error, "failed to upgrade relayed connection to a direct connection");
+        }
+    }
+}
+
 #[tracing::instrument(skip_all, name = "kademlia")]
 fn handle_kademlia_event(event: kad::Event) {
     match event {
@@ -362,15 +780,51 @@ fn handle_mdns_event(swarm: &mut Swarm<SignerBehavior>, ctx: &impl Context, even
 
 #[tracing::instrument(skip_all, name = "identify")]
 fn handle_identify_event(
-    _swarm: &mut Swarm<SignerBehavior>,
-    _: &impl Context,
+    swarm: &mut Swarm<SignerBehavior>,
+    ctx: &impl Context,
     event: identify::Event,
+    protocol_versions: ProtocolVersions,
 ) {
     use identify::Event;
 
     match event {
         Event::Received { peer_id, info, .. } => {
             tracing::debug!(%peer_id, ?info, "received identify message from peer");
+
+            // Negotiate the signer protocol version to use with the peer,
+            // refusing to talk to peers that we cannot understand.
+            let versions = ProtocolVersions::from_advertised(&info.protocol_version);
+            let negotiated = versions.and_then(|versions| protocol_versions.negotiate(&versions));
+
+            match (versions, negotiated) {
+                (Some(versions), Some(version)) => {
+                    if version < protocol_versions.max {
+                        tracing::info!(
+                            %peer_id,
+                            agent_version = %info.agent_version,
+                            peer_versions = %versions,
+                            %version,
+                            "peer runs an older signer protocol version; downgrading"
+                        );
+                    }
+                    ctx.state().connected_peers().set_protocol_versions(
+                        &peer_id,
+                        info.agent_version,
+                        versions,
+                        version,
+                    );
+                }
+                _ => {
+                    tracing::warn!(
+                        %peer_id,
+                        agent_version = %info.agent_version,
+                        peer_versions = %info.protocol_version,
+                        supported_versions = %protocol_versions,
+                        "peer does not support any signer protocol version that we support; disconnecting"
+                    );
+                    let _ = swarm.disconnect_peer_id(peer_id);
+                }
+            }
         }
         Event::Pushed { connection_id, peer_id, info } => {
             tracing::debug!(%connection_id, %peer_id, ?info, "pushed identify message to peer");
@@ -389,70 +843,59 @@ fn handle_gossipsub_event(
     swarm: &mut Swarm<SignerBehavior>,
     ctx: &impl Context,
     event: gossipsub::Event,
+    rate_limiter: &mut RateLimiter,
 ) {
     use gossipsub::Event;
 
     match event {
         Event::Message {
             propagation_source: peer_id,
+            message_id,
             message,
-            ..
         } => {
+            // Since messages are only propagated once we report them as
+            // valid, every message must end with a validation result.
             let current_signer_set = ctx.state().current_signer_set();
             // The following check should be unnecessary. In order to
             // receive a message the peer needs to establish a connection,
             // and in order to do that the peer needs to be in the current
             // signer set. When we implement the signing set changing code,
             // we should re-evaluate whether we should remove this check.
-            if !current_signer_set.is_allowed_peer(&peer_id) {
+            let acceptance = if !current_signer_set.is_allowed_peer(&peer_id) {
                 tracing::warn!(%peer_id, "ignoring message from unknown peer");
-                return;
-            }
-
-            // The message may have originated from someone else, let's
-            // check that peer ID too. If we haven't been told the source
-            // then we distrust the message and ignore it.
-            let Some(origin_peer_id) = message.source else {
-                tracing::warn!(%peer_id, "origin peer id unknown, ignoring message");
-                return;
-            };
-
-            if !current_signer_set.is_allowed_peer(&origin_peer_id) {
-                tracing::warn!(%origin_peer_id, "ignoring message from unknown origin peer");
-                return;
-            }
-
-            Msg::decode_with_digest(&message.data)
-                .and_then(|(msg, digest)| {
-                    tracing::trace!(
-                        local_peer_id = %swarm.local_peer_id(),
-                        %peer_id,
-                        message_id = hex::encode(msg.id()),
-                        %msg,
-                        "received message",
-                    );
-
-                    if origin_peer_id != msg.signer_public_key.into() {
-                        tracing::error!(%origin_peer_id, "connected peer sent an invalid message");
-                        return Err(Error::InvalidSignature)
+                MessageAcceptance::Reject
+            } else {
+                // The message may have originated from someone else,
+                // let's check that peer ID too. If we haven't been told
+                // the source then we distrust the message and reject it.
+                match message.source {
+                    None => {
+                        tracing::warn!(%peer_id, "origin peer id unknown, ignoring message");
+                        MessageAcceptance::Reject
                     }
-
-                    if let Err(error) = msg.verify_digest(digest) {
-                        tracing::error!(%origin_peer_id, "connected peer sent an invalid signature");
-                        return Err(error)
+                    Some(origin_peer_id)
+                        if !current_signer_set.is_allowed_peer(&origin_peer_id) =>
+                    {
+                        tracing::warn!(%origin_peer_id, "ignoring message from unknown origin peer");
+                        MessageAcceptance::Reject
                     }
+                    Some(origin_peer_id) => forward_received_message(
+                        swarm,
+                        ctx,
+                        rate_limiter,
+                        peer_id,
+                        origin_peer_id,
+                        &message.data,
+                    ),
+                }
+            };
 
-                    let _ = ctx.get_signal_sender()
-                        .send(P2PEvent::MessageReceived(Box::new(msg)).into())
-                        .inspect_err(|error| {
-                            tracing::debug!(%error, "Failed to send message to application; we are likely shutting down.");
-                        });
-
-                    Ok(())
-                })
-                .unwrap_or_else(|error| {
-                    tracing::warn!(%peer_id, %error, "Failed to decode message");
-                });
+            // Rejected messages count against the score of the peer that
+            // sent them to us.
+            let _ = swarm
+                .behaviour_mut()
+                .gossipsub
+                .report_message_validation_result(&message_id, &peer_id, acceptance);
         }
         Event::Subscribed { peer_id, topic } => {
             tracing::debug!(%peer_id, %topic, "subscribed to topic");
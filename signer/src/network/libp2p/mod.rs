@@ -5,10 +5,12 @@ use std::sync::LazyLock;
 use libp2p::gossipsub::IdentTopic;
 
 mod bootstrap;
+mod direct;
 mod errors;
 mod event_loop;
 mod multiaddr;
 mod network;
+mod rate_limit;
 mod swarm;
 
 pub use self::errors::SignerSwarmError;
@@ -22,3 +24,8 @@ pub const DEFAULT_P2P_PORT: u16 = 4122;
 /// The topic used for signer gossipsub messages
 // NOTE: Using LazyLock (static) instead of LazyCell (const) as IdentTopic is interior mutable.
 pub static TOPIC: LazyLock<IdentTopic> = LazyLock::new(|| IdentTopic::new("sbtc-signer"));
+
+/// The topic used for signer gossipsub messages that require signer
+/// protocol version 2. Signers that only support version 1 never subscribe
+/// to it, so these messages are never sent to them.
+pub static TOPIC_V2: LazyLock<IdentTopic> = LazyLock::new(|| IdentTopic::new("sbtc-signer/2"));
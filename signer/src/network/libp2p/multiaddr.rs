@@ -11,6 +11,13 @@ pub trait MultiaddrExt {
     fn is_quic(&self) -> bool;
     /// Returns `true` if the multiaddress uses the in-memory transport protocol.
     fn is_memory(&self) -> bool;
+    /// Returns `true` if the multiaddress goes through a circuit relay.
+    fn is_relayed(&self) -> bool;
+    /// Returns `true` if both multiaddresses are direct addresses on the
+    /// same host, i.e. if they start with the same IP address or in-memory
+    /// port. Ports and transport protocols are not compared, and DNS names
+    /// never match, since they may resolve to any address.
+    fn is_same_host(&self, other: &Multiaddr) -> bool;
     /// Returns the transport protocol used by the multiaddress, or `None` if no
     /// supported transport protocol was found.
     fn get_transport_protocol(&self) -> Option<Protocol<'_>>;
@@ -60,6 +67,24 @@ impl MultiaddrExt for Multiaddr {
         matches!(parts.next(), Some(Protocol::Memory(_)))
     }
 
+    fn is_relayed(&self) -> bool {
+        self.iter().any(|part| matches!(part, Protocol::P2pCircuit))
+    }
+
+    fn is_same_host(&self, other: &Multiaddr) -> bool {
+        if self.is_relayed() || other.is_relayed() {
+            return false;
+        }
+
+        match (self.iter().next(), other.iter().next()) {
+            (
+                Some(host @ (Protocol::Ip4(_) | Protocol::Ip6(_) | Protocol::Memory(_))),
+                Some(other_host),
+            ) => host == other_host,
+            _ => false,
+        }
+    }
+
     fn get_transport_protocol(&self) -> Option<Protocol<'_>> {
         let mut parts = self.iter();
         parts.find(|part| {
@@ -126,6 +151,50 @@ mod tests {
         assert!(!multiaddr.is_tcp(), "memory");
     }
 
+    #[test]
+    fn test_is_relayed() {
+        let relay = Multiaddr::empty()
+            .with(Protocol::Ip4(IP4_LOOPBACK))
+            .with(Protocol::Tcp(8080))
+            .with(Protocol::P2p(PeerId::random()));
+        assert!(!relay.is_relayed(), "relay address");
+
+        let multiaddr = relay
+            .with(Protocol::P2pCircuit)
+            .with(Protocol::P2p(PeerId::random()));
+        assert!(multiaddr.is_relayed(), "relay address + circuit");
+    }
+
+    #[test]
+    fn test_is_same_host() {
+        let tcp = Multiaddr::empty()
+            .with(Protocol::Ip4(IP4_LOOPBACK))
+            .with(Protocol::Tcp(8080));
+        let quic = Multiaddr::empty()
+            .with(Protocol::Ip4(IP4_LOOPBACK))
+            .with(Protocol::Udp(9090))
+            .with(Protocol::QuicV1);
+        assert!(tcp.is_same_host(&quic), "same ip4, different transports");
+
+        let ip6 = Multiaddr::empty()
+            .with(Protocol::Ip6(IP6_LOOPBACK))
+            .with(Protocol::Tcp(8080));
+        assert!(!tcp.is_same_host(&ip6), "ip4 + ip6");
+
+        let dns = Multiaddr::empty()
+            .with(Protocol::Dns("localhost".into()))
+            .with(Protocol::Tcp(8080));
+        assert!(!dns.is_same_host(&dns), "same dns");
+        assert!(!tcp.is_same_host(&dns), "ip4 + dns");
+
+        let relayed = tcp
+            .clone()
+            .with(Protocol::P2p(PeerId::random()))
+            .with(Protocol::P2pCircuit);
+        assert!(!tcp.is_same_host(&relayed), "direct + relayed");
+        assert!(!relayed.is_same_host(&relayed), "relayed + relayed");
+    }
+
     #[test]
     fn test_is_quic() {
         let multiaddr = Multiaddr::empty()
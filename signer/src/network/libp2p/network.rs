@@ -11,6 +11,7 @@ use crate::context::SignerEvent;
 use crate::context::SignerSignal;
 use crate::context::TerminationHandle;
 use crate::error::Error;
+use crate::keys::PublicKey;
 use crate::network::MessageTransfer;
 use crate::network::Msg;
 
@@ -65,6 +66,24 @@ impl MessageTransfer for P2PNetwork {
             .map(|_| ())
     }
 
+    /// This will send the message to the application signalling channel
+    /// using a [`SignerCommand::P2PSendTo`] command, signalling to the active
+    /// network implementation to deliver the message directly to the given
+    /// peer instead of publishing it to all signers.
+    ///
+    /// Like [`Self::broadcast`], this is not a blocking operation and the
+    /// same success/failure events are emitted once the message has been
+    /// delivered, or has failed to be delivered.
+    async fn send_to(&mut self, peer: PublicKey, msg: Msg) -> Result<(), Error> {
+        let boxed_msg = Box::new(msg);
+        self.signal_tx
+            .send(SignerSignal::Command(SignerCommand::P2PSendTo(
+                peer, boxed_msg,
+            )))
+            .map_err(|_| Error::SignerShutdown)
+            .map(|_| ())
+    }
+
     /// This will listen for incoming messages on the application signalling
     /// channel, and return the message once it has been received.
     ///
@@ -109,15 +128,17 @@ mod tests {
     use std::{str::FromStr as _, time::Duration};
 
     use futures::StreamExt as _;
-    use libp2p::Multiaddr;
+    use libp2p::{Multiaddr, PeerId};
     use test_log::test;
     use tokio_stream::wrappers::BroadcastStream;
 
     use super::*;
 
     use crate::{
+        ecdsa::SignEcdsa as _,
         keys::{PrivateKey, PublicKey},
-        network::libp2p::SignerSwarmBuilder,
+        message::{SignerDepositDecision, SignerMessage, SignerSetProposal},
+        network::{libp2p::SignerSwarmBuilder, version::ProtocolVersions},
         testing::{self, clear_env, context::*, get_rng, network::MultiaddrExt as _},
     };
 
@@ -537,6 +558,190 @@ mod tests {
         assert_eq!(gossiped_messages, signed_messages);
     }
 
+    #[test(tokio::test)]
+    async fn direct_messages_are_only_received_by_the_recipient() {
+        clear_env();
+
+        let rng = &mut get_rng();
+        let keys = [
+            PrivateKey::new(rng),
+            PrivateKey::new(rng),
+            PrivateKey::new(rng),
+        ];
+        let addrs = [
+            Multiaddr::random_memory(rng),
+            Multiaddr::random_memory(rng),
+            Multiaddr::random_memory(rng),
+        ];
+
+        let mut networks = Vec::new();
+        for (key, addr) in keys.iter().zip(addrs.iter()) {
+            let context = TestContext::builder()
+                .with_in_memory_storage()
+                .with_mocked_clients()
+                .modify_settings(|settings| {
+                    settings.signer.private_key = *key;
+                    settings.signer.p2p.enable_mdns = false;
+                })
+                .build();
+
+            let signer_set = context.state().current_signer_set();
+            for key in keys.iter() {
+                signer_set.add_signer(PublicKey::from_private_key(key));
+            }
+
+            let seeds: Vec<Multiaddr> = addrs.iter().filter(|a| *a != addr).cloned().collect();
+            let mut swarm = SignerSwarmBuilder::new(key)
+                .enable_memory_transport(true)
+                .add_listen_endpoint(addr.clone())
+                .add_seed_addrs(&seeds)
+                .build()
+                .expect("Failed to build swarm");
+
+            networks.push(P2PNetwork::new(&context));
+            tokio::spawn(async move {
+                swarm.start(&context).await.unwrap();
+            });
+        }
+
+        // Give the swarms a bit of time to connect.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let mut network3 = networks.pop().unwrap();
+        let mut network2 = networks.pop().unwrap();
+        let mut network1 = networks.pop().unwrap();
+
+        let msg_to_2 = tokio::spawn(async move {
+            tokio::time::timeout(Duration::from_secs(2), network2.receive()).await
+        });
+        let msg_to_3 = tokio::spawn(async move {
+            tokio::time::timeout(Duration::from_secs(2), network3.receive()).await
+        });
+
+        let msg = Msg::random_with_private_key(rng, &keys[0]);
+        network1
+            .send_to(PublicKey::from_private_key(&keys[1]), msg.clone())
+            .await
+            .unwrap();
+
+        let received = msg_to_2
+            .await
+            .unwrap()
+            .expect("Recipient did not receive the direct message")
+            .unwrap();
+        assert_eq!(received, msg);
+
+        // The third signer is connected to the other two, but the message
+        // was not meant for it.
+        assert!(msg_to_3.await.unwrap().is_err());
+    }
+
+    #[test(tokio::test)]
+    async fn v1_peers_do_not_receive_v2_messages() {
+        clear_env();
+
+        let rng = &mut get_rng();
+        let keys = [
+            PrivateKey::new(rng),
+            PrivateKey::new(rng),
+            PrivateKey::new(rng),
+        ];
+        let addrs = [
+            Multiaddr::random_memory(rng),
+            Multiaddr::random_memory(rng),
+            Multiaddr::random_memory(rng),
+        ];
+
+        // The third signer only supports protocol version 1.
+        let versions = [
+            ProtocolVersions::SUPPORTED,
+            ProtocolVersions::SUPPORTED,
+            ProtocolVersions::LEGACY,
+        ];
+
+        let mut networks = Vec::new();
+        let mut contexts = Vec::new();
+        for ((key, addr), versions) in keys.iter().zip(addrs.iter()).zip(versions) {
+            let context = TestContext::builder()
+                .with_in_memory_storage()
+                .with_mocked_clients()
+                .modify_settings(|settings| {
+                    settings.signer.private_key = *key;
+                    settings.signer.p2p.enable_mdns = false;
+                })
+                .build();
+
+            let signer_set = context.state().current_signer_set();
+            for key in keys.iter() {
+                signer_set.add_signer(PublicKey::from_private_key(key));
+            }
+
+            let seeds: Vec<Multiaddr> = addrs.iter().filter(|a| *a != addr).cloned().collect();
+            let mut swarm = SignerSwarmBuilder::new(key)
+                .enable_memory_transport(true)
+                .add_listen_endpoint(addr.clone())
+                .add_seed_addrs(&seeds)
+                .with_protocol_versions(versions)
+                .build()
+                .expect("Failed to build swarm");
+
+            networks.push(P2PNetwork::new(&context));
+            contexts.push(context.clone());
+            tokio::spawn(async move {
+                swarm.start(&context).await.unwrap();
+            });
+        }
+
+        // Give the swarms a bit of time to connect and identify each other.
+        tokio::time::sleep(Duration::from_secs(1)).await;
+
+        let legacy_peer_id = PeerId::from(PublicKey::from_private_key(&keys[2]));
+        let negotiated = contexts[0]
+            .state()
+            .connected_peers()
+            .peer_protocol_version(&legacy_peer_id);
+        assert_eq!(negotiated, Some(1));
+
+        let mut network3 = networks.pop().unwrap();
+        let mut network2 = networks.pop().unwrap();
+        let mut network1 = networks.pop().unwrap();
+
+        // Signer set proposals require version 2, so they only reach the
+        // second signer.
+        let proposal = SignerMessage::random_with_payload_type::<SignerSetProposal, _>(rng)
+            .sign_ecdsa(&keys[0]);
+        network1.broadcast(proposal.clone()).await.unwrap();
+
+        let received = tokio::time::timeout(Duration::from_secs(2), network2.receive())
+            .await
+            .expect("The v2 signer did not receive the proposal")
+            .unwrap();
+        assert_eq!(received, proposal);
+        let received = tokio::time::timeout(Duration::from_secs(2), network3.receive()).await;
+        assert!(received.is_err());
+
+        // Direct messages require version 2 as well, so a message for the
+        // third signer is published instead, which the second signer also
+        // receives.
+        let msg = SignerMessage::random_with_payload_type::<SignerDepositDecision, _>(rng)
+            .sign_ecdsa(&keys[0]);
+        network1
+            .send_to(PublicKey::from_private_key(&keys[2]), msg.clone())
+            .await
+            .unwrap();
+
+        let received = tokio::time::timeout(Duration::from_secs(2), network3.receive())
+            .await
+            .expect("The v1 signer did not receive the message")
+            .unwrap();
+        assert_eq!(received, msg);
+        let received = tokio::time::timeout(Duration::from_secs(2), network2.receive())
+            .await
+            .expect("The message for the v1 signer was not published")
+            .unwrap();
+        assert_eq!(received, msg);
+    }
+
     #[tokio::test]
     async fn swarm_rejects_connections_from_unknown_peers() {
         let rng = &mut get_rng();
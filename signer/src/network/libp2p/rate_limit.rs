@@ -0,0 +1,242 @@
+//! Per-peer limits on the messages that signers send to each other.
+//!
+//! Signers only accept connections from other members of the signer set,
+//! but a buggy or compromised signer could still flood the network with
+//! messages. The [`RateLimiter`] counts, for each peer, the messages of
+//! each payload type that it sent during the current window, and bans
+//! peers that send messages that are too large or too frequent for a
+//! while.
+
+use std::collections::HashMap;
+use std::time::Duration;
+use std::time::Instant;
+
+use libp2p::PeerId;
+
+use crate::message::Payload;
+use crate::metrics::Metrics;
+
+use super::direct::MAX_MESSAGE_SIZE;
+
+/// The length of the window over which the messages of a peer are
+/// counted.
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(10);
+
+/// The limits on messages of a single payload type sent by a peer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PayloadLimits {
+    /// A label for the payload type, used in logs and metrics.
+    pub kind: &'static str,
+    /// The maximum size, in bytes, of an encoded message.
+    pub max_size: usize,
+    /// The maximum number of messages within a rate limit window.
+    pub max_messages: u32,
+}
+
+impl PayloadLimits {
+    /// Return the limits for messages with the given payload.
+    ///
+    /// The limits are generous compared to what honest signers send, even
+    /// while sweeping a large number of requests, since their purpose is
+    /// to protect against floods rather than to shape traffic.
+    pub fn for_payload(payload: &Payload) -> Self {
+        let (kind, max_size, max_messages) = match payload {
+            Payload::SignerDepositDecision(_) => ("signer_deposit_decision", 4 * 1024, 2_000),
+            Payload::SignerWithdrawalDecision(_) => ("signer_withdrawal_decision", 4 * 1024, 2_000),
+            Payload::StacksTransactionSignRequest(_) => {
+                ("stacks_transaction_sign_request", MAX_MESSAGE_SIZE, 500)
+            }
+            Payload::StacksTransactionSignature(_) => ("stacks_transaction_signature", 1024, 500),
+            Payload::WstsMessage(_) => ("wsts_message", MAX_MESSAGE_SIZE, 5_000),
+            Payload::BitcoinPreSignRequest(_) => ("bitcoin_pre_sign_request", MAX_MESSAGE_SIZE, 20),
+            Payload::BitcoinPreSignAck(_) => ("bitcoin_pre_sign_ack", 1024, 20),
+            Payload::SignerSetProposal(_) => ("signer_set_proposal", 8 * 1024, 20),
+            Payload::DecisionSyncRequest(_) => ("decision_sync_request", MAX_MESSAGE_SIZE, 20),
+        };
+
+        Self { kind, max_size, max_messages }
+    }
+}
+
+/// The limit that a peer exceeded with a message.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LimitExceeded {
+    /// The message was larger than allowed for its payload type.
+    Size,
+    /// The peer sent too many messages of the payload type within the
+    /// current window.
+    Rate,
+}
+
+impl LimitExceeded {
+    /// A label for the exceeded limit, used in logs and metrics.
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Size => "size",
+            Self::Rate => "rate",
+        }
+    }
+}
+
+impl std::fmt::Display for LimitExceeded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+/// Tracks the messages received from each peer and the peers that are
+/// temporarily banned for exceeding their limits.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// How long a peer stays banned after exceeding a limit.
+    ban_duration: Duration,
+    /// The start of the current window and the number of messages
+    /// received in it, for each peer and payload type.
+    windows: HashMap<(PeerId, &'static str), (Instant, u32)>,
+    /// The time until which each banned peer is banned.
+    banned: HashMap<PeerId, Instant>,
+}
+
+impl RateLimiter {
+    /// Create a new rate limiter that bans peers for the given duration.
+    pub fn new(ban_duration: Duration) -> Self {
+        Self {
+            ban_duration,
+            windows: HashMap::new(),
+            banned: HashMap::new(),
+        }
+    }
+
+    /// Return whether the peer is currently banned, lifting the ban if it
+    /// has expired.
+    pub fn is_banned(&mut self, peer_id: &PeerId, now: Instant) -> bool {
+        match self.banned.get(peer_id) {
+            Some(until) if *until > now => true,
+            Some(_) => {
+                self.banned.remove(peer_id);
+                false
+            }
+            None => false,
+        }
+    }
+
+    /// Count a message of the given encoded size received from the peer,
+    /// and check that it is within the limits for its payload type. If it
+    /// is not then the peer is banned.
+    pub fn check(
+        &mut self,
+        peer_id: PeerId,
+        payload: &Payload,
+        size: usize,
+        now: Instant,
+    ) -> Result<(), LimitExceeded> {
+        let limits = PayloadLimits::for_payload(payload);
+
+        let (window_start, count) = self
+            .windows
+            .entry((peer_id, limits.kind))
+            .or_insert((now, 0));
+
+        if now.saturating_duration_since(*window_start) >= RATE_LIMIT_WINDOW {
+            *window_start = now;
+            *count = 0;
+        }
+        *count += 1;
+
+        let result = if size > limits.max_size {
+            Err(LimitExceeded::Size)
+        } else if *count > limits.max_messages {
+            Err(LimitExceeded::Rate)
+        } else {
+            Ok(())
+        };
+
+        if let Err(exceeded) = result {
+            Metrics::increment_p2p_limits_exceeded(limits.kind, exceeded.as_str());
+            self.windows.retain(|(peer, _), _| *peer != peer_id);
+            self.banned.insert(peer_id, now + self.ban_duration);
+        }
+
+        result
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::keys::PublicKey;
+    use crate::message::BitcoinPreSignAck;
+    use crate::message::SignerDepositDecision;
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    #[test]
+    fn peers_exceeding_the_message_rate_are_banned() {
+        let rng = &mut get_rng();
+        let mut limiter = RateLimiter::new(Duration::from_secs(60));
+        let peer_id = Faker.fake_with_rng::<PublicKey, _>(rng).into();
+        let other_peer_id = Faker.fake_with_rng::<PublicKey, _>(rng).into();
+        let payload = Payload::BitcoinPreSignAck(BitcoinPreSignAck);
+        let max_messages = PayloadLimits::for_payload(&payload).max_messages;
+        let now = Instant::now();
+
+        for _ in 0..max_messages {
+            assert!(limiter.check(peer_id, &payload, 100, now).is_ok());
+        }
+        // Limits are tracked separately for each payload type and peer.
+        let decision: SignerDepositDecision = Faker.fake_with_rng(rng);
+        let decision = Payload::SignerDepositDecision(decision);
+        assert!(limiter.check(peer_id, &decision, 100, now).is_ok());
+        assert!(limiter.check(other_peer_id, &payload, 100, now).is_ok());
+        assert!(!limiter.is_banned(&peer_id, now));
+
+        let result = limiter.check(peer_id, &payload, 100, now);
+        assert_eq!(result, Err(LimitExceeded::Rate));
+        assert!(limiter.is_banned(&peer_id, now));
+        assert!(!limiter.is_banned(&other_peer_id, now));
+
+        // The ban is lifted once it expires.
+        let later = now + Duration::from_secs(61);
+        assert!(!limiter.is_banned(&peer_id, later));
+        assert!(limiter.check(peer_id, &payload, 100, later).is_ok());
+    }
+
+    #[test]
+    fn message_counts_reset_with_each_window() {
+        let rng = &mut get_rng();
+        let mut limiter = RateLimiter::new(Duration::from_secs(60));
+        let peer_id = Faker.fake_with_rng::<PublicKey, _>(rng).into();
+        let payload = Payload::BitcoinPreSignAck(BitcoinPreSignAck);
+        let max_messages = PayloadLimits::for_payload(&payload).max_messages;
+        let now = Instant::now();
+
+        for _ in 0..max_messages {
+            assert!(limiter.check(peer_id, &payload, 100, now).is_ok());
+        }
+
+        let next_window = now + RATE_LIMIT_WINDOW;
+        for _ in 0..max_messages {
+            assert!(limiter.check(peer_id, &payload, 100, next_window).is_ok());
+        }
+        assert!(!limiter.is_banned(&peer_id, next_window));
+    }
+
+    #[test]
+    fn peers_sending_oversized_messages_are_banned() {
+        let rng = &mut get_rng();
+        let mut limiter = RateLimiter::new(Duration::from_secs(60));
+        let peer_id = Faker.fake_with_rng::<PublicKey, _>(rng).into();
+        let payload = Payload::BitcoinPreSignAck(BitcoinPreSignAck);
+        let max_size = PayloadLimits::for_payload(&payload).max_size;
+        let now = Instant::now();
+
+        assert!(limiter.check(peer_id, &payload, max_size, now).is_ok());
+
+        let result = limiter.check(peer_id, &payload, max_size + 1, now);
+        assert_eq!(result, Err(LimitExceeded::Size));
+        assert!(limiter.is_banned(&peer_id, now));
+    }
+}
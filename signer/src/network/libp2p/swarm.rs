@@ -4,6 +4,7 @@ use std::time::Duration;
 
 use crate::context::Context;
 use crate::keys::PrivateKey;
+use crate::network::version::ProtocolVersions;
 use libp2p::core::muxing::StreamMuxerBox;
 use libp2p::core::upgrade::Version;
 use libp2p::identity::Keypair;
@@ -13,15 +14,16 @@ use libp2p::swarm::NetworkBehaviour;
 use libp2p::swarm::behaviour::toggle::Toggle;
 use libp2p::swarm::dial_opts::DialOpts;
 use libp2p::{
-    Multiaddr, PeerId, Swarm, Transport as _, autonat, connection_limits, gossipsub, identify, kad,
-    mdns, noise, ping, quic, tcp, yamux,
+    Multiaddr, PeerId, Swarm, Transport as _, autonat, connection_limits, dcutr, gossipsub,
+    identify, kad, mdns, noise, ping, quic, relay, tcp, yamux,
 };
 use rand::SeedableRng as _;
 use rand::rngs::StdRng;
 use tokio::sync::Mutex;
 
+use super::MultiaddrExt as _;
 use super::errors::SignerSwarmError;
-use super::{bootstrap, event_loop};
+use super::{TOPIC, TOPIC_V2, bootstrap, direct, event_loop};
 
 /// The maximum number of substreams _per connection_. This is used to limit
 /// the number of concurrent substreams that can be opened on a single
@@ -31,6 +33,9 @@ use super::{bootstrap, event_loop};
 /// * AutoNAT: 2 streams (one for client, one for server operations)
 /// * Identify: 1 stream for peer identification
 /// * Ping: 1 stream for keepalive pings
+/// * Direct messages: 1 short-lived stream per in-flight request
+/// * Relay: 1 stream per reservation, plus 1 stream per relayed connection
+/// * DCUtR: 1 short-lived stream per hole punching attempt
 const MAX_SUBSTREAMS_PER_CONNECTION: usize = 20;
 
 /// The maximum time to wait for a connection negotiation to complete. This is
@@ -42,10 +47,19 @@ const MAX_SUBSTREAMS_PER_CONNECTION: usize = 20;
 /// timeout is applied to both inbound and outbound connections.
 const NEGOTIATION_TIMEOUT_SECS: u64 = 10;
 
+/// The maximum lifetime of a connection relayed for another signer. Signers
+/// re-establish relayed connections once they are closed.
+const RELAY_MAX_CIRCUIT_DURATION_SECS: u64 = 60 * 60;
+
+/// The maximum number of bytes that a connection relayed for another signer
+/// may carry in each direction.
+const RELAY_MAX_CIRCUIT_BYTES: u64 = 256 * 1024 * 1024;
+
 /// Define the behaviors of the [`SignerSwarm`] libp2p network.
 #[derive(NetworkBehaviour)]
 pub struct SignerBehavior {
     pub gossipsub: gossipsub::Behaviour,
+    pub direct: direct::Behavior,
     mdns: Toggle<mdns::tokio::Behaviour>,
     pub kademlia: Toggle<kad::Behaviour<MemoryStore>>,
     ping: ping::Behaviour,
@@ -54,16 +68,25 @@ pub struct SignerBehavior {
     pub autonat_server: Toggle<autonat::v2::server::Behaviour<StdRng>>,
     pub bootstrap: bootstrap::Behavior,
     pub connection_limits: connection_limits::Behaviour,
+    pub relay_server: Toggle<relay::Behaviour>,
+    pub relay_client: Toggle<relay::client::Behaviour>,
+    dcutr: Toggle<dcutr::Behaviour>,
 }
 
 pub struct SignerSwarmConfig {
     pub enable_mdns: bool,
     pub enable_kademlia: bool,
     pub enable_autonat: bool,
+    pub enable_relay_server: bool,
+    /// The relay client behavior, which must be created together with the
+    /// relay transport. Hole punching is enabled along with the client.
+    pub relay_client: Option<relay::client::Behaviour>,
     pub initial_bootstrap_delay: Duration,
     pub seed_addresses: Vec<Multiaddr>,
     pub known_peers: Vec<(PeerId, Multiaddr)>,
     pub num_signers: u16,
+    /// The signer protocol versions that we advertise to our peers.
+    pub protocol_versions: ProtocolVersions,
 }
 
 impl SignerBehavior {
@@ -97,10 +120,12 @@ impl SignerBehavior {
             (None.into(), None.into())
         };
 
-        let identify = identify::Behaviour::new(identify::Config::new(
-            identify::PUSH_PROTOCOL_NAME.to_string(),
-            keypair.public(),
-        ));
+        // Peers learn the signer protocol versions that we support from
+        // our identify messages.
+        let identify = identify::Behaviour::new(
+            identify::Config::new(config.protocol_versions.to_string(), keypair.public())
+                .with_agent_version(format!("sbtc-signer/{}", env!("CARGO_PKG_VERSION"))),
+        );
 
         let bootstrap_config = bootstrap::Config::new(local_peer_id)
             .with_initial_delay(config.initial_bootstrap_delay)
@@ -108,8 +133,20 @@ impl SignerBehavior {
             .add_known_peers(config.known_peers);
         let bootstrap = bootstrap::Behavior::new(bootstrap_config);
 
+        let relay_server = config
+            .enable_relay_server
+            .then(|| Self::relay_server(local_peer_id, config.num_signers))
+            .into();
+
+        let dcutr = config
+            .relay_client
+            .is_some()
+            .then(|| dcutr::Behaviour::new(local_peer_id))
+            .into();
+
         Ok(Self {
             gossipsub: Self::gossipsub(&keypair)?,
+            direct: direct::behavior(),
             mdns,
             kademlia,
             ping: Default::default(),
@@ -118,9 +155,35 @@ impl SignerBehavior {
             autonat_server,
             bootstrap,
             connection_limits: Self::connection_limits(config.num_signers),
+            relay_server,
+            relay_client: config.relay_client.into(),
+            dcutr,
         })
     }
 
+    /// Create a new relay server behavior.
+    ///
+    /// The default limits are meant for public relays, which only relay
+    /// connections long enough for the peers to upgrade them to direct
+    /// connections. Signers behind symmetric NATs may never manage to
+    /// upgrade, so we allow circuits to live much longer and carry much
+    /// more data, while limiting the number of reservations and circuits
+    /// to what the signer set needs.
+    fn relay_server(peer_id: PeerId, num_signers: u16) -> relay::Behaviour {
+        let num_signers = num_signers.max(2) as usize;
+        let config = relay::Config {
+            max_reservations: num_signers,
+            max_reservations_per_peer: 2,
+            max_circuits: num_signers.saturating_mul(num_signers),
+            max_circuits_per_peer: num_signers,
+            max_circuit_duration: Duration::from_secs(RELAY_MAX_CIRCUIT_DURATION_SECS),
+            max_circuit_bytes: RELAY_MAX_CIRCUIT_BYTES,
+            ..Default::default()
+        };
+
+        relay::Behaviour::new(peer_id, config)
+    }
+
     fn connection_limits(num_signers_in: u16) -> connection_limits::Behaviour {
         // The number of signers is the number of signers in the set, minus one
         // for the local signer. This is used to calculate the connection limits.
@@ -176,15 +239,63 @@ impl SignerBehavior {
         let gossipsub_config = gossipsub::ConfigBuilder::default()
             .heartbeat_interval(Duration::from_secs(1)) // Default is 1 second
             .validation_mode(gossipsub::ValidationMode::Strict)
+            // Messages are only forwarded to other peers once the event
+            // loop has validated them, and the validation results feed
+            // into the peer scores.
+            .validate_messages()
             .message_id_fn(message_id_fn)
             .build()
             .map_err(|e| SignerSwarmError::LibP2P(Box::new(e)))?;
 
-        gossipsub::Behaviour::new(
+        let mut gossipsub = gossipsub::Behaviour::new(
             gossipsub::MessageAuthenticity::Signed(keypair.clone()),
             gossipsub_config,
         )
-        .map_err(SignerSwarmError::LibP2PMessage)
+        .map_err(SignerSwarmError::LibP2PMessage)?;
+
+        gossipsub
+            .with_peer_score(
+                Self::peer_score_params(),
+                gossipsub::PeerScoreThresholds::default(),
+            )
+            .map_err(|e| SignerSwarmError::LibP2P(e.into()))?;
+
+        Ok(gossipsub)
+    }
+
+    /// The gossipsub peer scoring parameters.
+    ///
+    /// The defaults are tuned for large open networks, where peers are
+    /// expected to constantly deliver messages and where many peers on the
+    /// same IP address suggest a sybil attack. The signer set is small and
+    /// closed, signers can be quiet for long stretches, and several of them
+    /// may share a host, so we only penalize peers for delivering invalid
+    /// messages and for misbehaving in the gossipsub protocol itself.
+    fn peer_score_params() -> gossipsub::PeerScoreParams {
+        let topic_params = gossipsub::TopicScoreParams {
+            // Don't penalize peers for not delivering enough messages in
+            // the mesh, since there may not be any messages to deliver.
+            mesh_message_deliveries_weight: 0.0,
+            mesh_failure_penalty_weight: 0.0,
+            // The penalty is the square of the number of invalid messages,
+            // so a couple of invalid messages push a peer below the gossip
+            // and publish thresholds and a few more get it graylisted. The
+            // count decays by 10% each second.
+            invalid_message_deliveries_weight: -10.0,
+            invalid_message_deliveries_decay: 0.9,
+            ..Default::default()
+        };
+
+        gossipsub::PeerScoreParams {
+            topics: [
+                (TOPIC.hash(), topic_params.clone()),
+                (TOPIC_V2.hash(), topic_params),
+            ]
+            .into_iter()
+            .collect(),
+            ip_colocation_factor_weight: 0.0,
+            ..Default::default()
+        }
     }
 
     /// Create a new kademlia behavior.
@@ -211,8 +322,11 @@ pub struct SignerSwarmBuilder<'a> {
     enable_autonat: bool,
     enable_quic_transport: bool,
     enable_memory_transport: bool,
+    enable_relay_server: bool,
+    relay_peers: Vec<PeerId>,
     initial_bootstrap_delay: Duration,
     num_signers: u16,
+    protocol_versions: ProtocolVersions,
 }
 
 impl<'a> SignerSwarmBuilder<'a> {
@@ -229,8 +343,11 @@ impl<'a> SignerSwarmBuilder<'a> {
             enable_autonat: true,
             enable_quic_transport: false,
             enable_memory_transport: false,
+            enable_relay_server: false,
+            relay_peers: Vec::new(),
             initial_bootstrap_delay: Duration::ZERO,
             num_signers: crate::MAX_KEYS,
+            protocol_versions: ProtocolVersions::SUPPORTED,
         }
     }
 
@@ -267,12 +384,42 @@ impl<'a> SignerSwarmBuilder<'a> {
         self
     }
 
+    /// Sets the signer protocol versions that this swarm supports. This is
+    /// only useful for testing compatibility with older signers, since the
+    /// swarm does not actually speak older versions of the protocol.
+    #[cfg(any(test, feature = "testing"))]
+    pub fn with_protocol_versions(mut self, protocol_versions: ProtocolVersions) -> Self {
+        self.protocol_versions = protocol_versions;
+        self
+    }
+
     /// Sets whether or not this swarm should use the memory transport.
     pub fn enable_memory_transport(mut self, enable: bool) -> Self {
         self.enable_memory_transport = enable;
         self
     }
 
+    /// Sets whether or not this swarm should relay connections for other
+    /// signers.
+    pub fn enable_relay_server(mut self, enable: bool) -> Self {
+        self.enable_relay_server = enable;
+        self
+    }
+
+    /// Add the peers that relay connections for this swarm. When any relay
+    /// peers are given, the swarm reserves a slot with each relay that it
+    /// connects to, so that it can be reached through the relay, and
+    /// upgrades relayed connections to direct connections using hole
+    /// punching (DCUtR) where possible.
+    pub fn add_relay_peers(mut self, peers: &[PeerId]) -> Self {
+        for peer_id in peers {
+            if !self.relay_peers.contains(peer_id) {
+                self.relay_peers.push(*peer_id);
+            }
+        }
+        self
+    }
+
     /// Add a listen endpoint to the builder.
     pub fn add_listen_endpoint(mut self, addr: Multiaddr) -> Self {
         if !self.listen_on.contains(&addr) {
@@ -346,14 +493,35 @@ impl<'a> SignerSwarmBuilder<'a> {
     /// Build the [`SignerSwarm`], consuming the builder.
     pub fn build(self) -> Result<SignerSwarm, SignerSwarmError> {
         let keypair: Keypair = (*self.private_key).into();
+        let local_peer_id = keypair.public().to_peer_id();
+
+        // The relay client is made up of a transport, which dials and
+        // listens through relays, and a behavior, which manages the
+        // reservations and relayed connections of the transport. We never
+        // use ourselves as a relay.
+        let relay_peers: Vec<PeerId> = self
+            .relay_peers
+            .into_iter()
+            .filter(|peer_id| *peer_id != local_peer_id)
+            .collect();
+        let (relay_transport, relay_client) = if relay_peers.is_empty() {
+            (None, None)
+        } else {
+            let (transport, behavior) = relay::client::new(local_peer_id);
+            (Some(transport), Some(behavior))
+        };
+
         let behavior_config = SignerSwarmConfig {
             enable_mdns: self.enable_mdns,
             enable_kademlia: self.enable_kademlia,
             enable_autonat: self.enable_autonat,
+            enable_relay_server: self.enable_relay_server,
+            relay_client,
             initial_bootstrap_delay: self.initial_bootstrap_delay,
             seed_addresses: self.seed_addrs,
             known_peers: self.known_peers,
             num_signers: self.num_signers,
+            protocol_versions: self.protocol_versions,
         };
         let behavior = SignerBehavior::new(keypair.clone(), behavior_config)?;
 
@@ -408,34 +576,62 @@ impl<'a> SignerSwarmBuilder<'a> {
                 .boxed();
         }
 
+        // If the relay client is enabled, add the relay transport to the
+        // transport. Relayed connections are secured and multiplexed just
+        // like direct ones, on top of the connection to the relay.
+        if let Some(relay_transport) = relay_transport {
+            let relay_transport = relay_transport
+                .upgrade(Version::V1)
+                .authenticate(noise.clone())
+                .multiplex(yamux.clone())
+                .boxed();
+            transport = transport
+                .or_transport(relay_transport)
+                .map(|either, _| either.into_inner())
+                .boxed();
+        }
+
         // Add the DNS transport to the transport.
         transport = libp2p::dns::tokio::Transport::system(transport)
             .map_err(|e| SignerSwarmError::LibP2P(Box::new(e)))?
             .boxed();
 
         // Create the swarm.
-        let swarm = Swarm::new(
-            transport,
-            behavior,
-            keypair.public().to_peer_id(),
-            swarm_config,
-        );
+        let swarm = Swarm::new(transport, behavior, local_peer_id, swarm_config);
 
         Ok(SignerSwarm {
             keypair,
             swarm: Arc::new(Mutex::new(swarm)),
             listen_addrs: self.listen_on,
             external_addresses: self.external_addresses,
+            relay_peers,
+            protocol_versions: self.protocol_versions,
         })
     }
 }
 
+/// Returns the security protocol that connections on the given address are
+/// expected to be secured with. The connection upgrade does not report the
+/// protocol that it negotiated, so it is inferred from the transports of
+/// the [`SignerSwarm`]: QUIC connections are secured by the TLS 1.3
+/// handshake built into QUIC, while all other connections, including
+/// relayed ones, are upgraded with the Noise protocol.
+pub fn inferred_security_protocol(address: &Multiaddr) -> &'static str {
+    if !address.is_relayed() && address.is_quic() {
+        "tls-1.3"
+    } else {
+        "noise"
+    }
+}
+
 #[derive(Clone)]
 pub struct SignerSwarm {
     keypair: Keypair,
     swarm: Arc<Mutex<Swarm<SignerBehavior>>>,
     listen_addrs: Vec<Multiaddr>,
     external_addresses: Vec<Multiaddr>,
+    relay_peers: Vec<PeerId>,
+    protocol_versions: ProtocolVersions,
 }
 
 impl SignerSwarm {
@@ -480,7 +676,13 @@ impl SignerSwarm {
         }
 
         // Run the event loop, blocking until its completion.
-        event_loop::run(ctx, Arc::clone(&self.swarm)).await;
+        event_loop::run(
+            ctx,
+            Arc::clone(&self.swarm),
+            &self.relay_peers,
+            self.protocol_versions,
+        )
+        .await;
 
         Ok(())
     }
@@ -501,7 +703,9 @@ mod tests {
     use rand::RngCore as _;
 
     use crate::{
+        config::PinnedPeer,
         keys::PublicKey,
+        network::libp2p::MultiaddrExt as _,
         storage::DbRead as _,
         testing::{context::*, get_rng, network::MultiaddrExt as _},
     };
@@ -890,4 +1094,160 @@ mod tests {
         let p2p_peers_2a = context2.get_storage().get_p2p_peers().await.unwrap();
         assert!(p2p_peers_2a.is_empty());
     }
+
+    #[test_log::test(tokio::test)]
+    async fn signers_behind_nat_connect_through_a_relay() {
+        let rng = &mut get_rng();
+        let relay_key = PrivateKey::new(rng);
+        let nat_keys = [PrivateKey::new(rng), PrivateKey::new(rng)];
+        let relay_peer_id: PeerId = PublicKey::from_private_key(&relay_key).into();
+        let relay_addr = Multiaddr::random_memory(rng);
+
+        // All three signers are in each other's signer set.
+        let signer_keys = [relay_key, nat_keys[0], nat_keys[1]];
+        let new_context = |private_key: PrivateKey| {
+            let context = TestContext::builder()
+                .with_in_memory_storage()
+                .with_mocked_clients()
+                .with_private_key(private_key)
+                .build();
+            for key in signer_keys.iter() {
+                let public_key = PublicKey::from_private_key(key);
+                context.state().current_signer_set().add_signer(public_key);
+            }
+            context
+        };
+
+        // The relay is the only signer that listens on an address that the
+        // others can dial.
+        let mut relay = SignerSwarmBuilder::new(&relay_key)
+            .enable_mdns(false)
+            .enable_memory_transport(true)
+            .enable_relay_server(true)
+            .add_listen_endpoint(relay_addr.clone())
+            .build()
+            .expect("Failed to build relay swarm");
+        let relay_context = new_context(relay_key);
+        tokio::spawn(async move {
+            relay.start(&relay_context).await.unwrap();
+        });
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        // The other two signers are "behind a NAT": they do not listen on
+        // anything, so they can only dial out to the relay.
+        let mut nat_swarms = Vec::new();
+        for key in nat_keys.iter() {
+            let swarm = SignerSwarmBuilder::new(key)
+                .enable_mdns(false)
+                .enable_memory_transport(true)
+                .with_initial_bootstrap_delay(Duration::ZERO)
+                .add_seed_addr(relay_addr.clone())
+                .add_relay_peers(&[relay_peer_id])
+                .build()
+                .expect("Failed to build NATed swarm");
+            let context = new_context(*key);
+            let mut swarm_clone = swarm.clone();
+            tokio::spawn(async move {
+                swarm_clone.start(&context).await.unwrap();
+            });
+            nat_swarms.push(swarm);
+        }
+
+        let peer_id1 = nat_swarms[0].local_peer_id();
+        let peer_id2 = nat_swarms[1].local_peer_id();
+        let connected = tokio::time::timeout(Duration::from_secs(10), async {
+            loop {
+                let connected1 = nat_swarms[0].swarm.lock().await.is_connected(&peer_id2);
+                let connected2 = nat_swarms[1].swarm.lock().await.is_connected(&peer_id1);
+                if connected1 && connected2 {
+                    break;
+                }
+                tokio::time::sleep(Duration::from_millis(100)).await;
+            }
+        })
+        .await;
+        assert!(connected.is_ok(), "NATed signers did not connect");
+
+        // Both NATed signers are reachable through a reservation on the
+        // relay.
+        for swarm in nat_swarms.iter() {
+            let listen_addrs = swarm.listen_addrs().await;
+            assert!(listen_addrs.iter().any(|addr| addr.is_relayed()));
+        }
+    }
+
+    #[test_log::test(tokio::test)]
+    async fn connections_on_unpinned_addresses_are_closed() {
+        let rng = &mut get_rng();
+        let keys = [
+            PrivateKey::new(rng),
+            PrivateKey::new(rng),
+            PrivateKey::new(rng),
+        ];
+        let public_keys = keys.map(|key| PublicKey::from_private_key(&key));
+        let addrs = [
+            Multiaddr::random_memory(rng),
+            Multiaddr::random_memory(rng),
+            Multiaddr::random_memory(rng),
+        ];
+
+        // The first signer pins the second signer to an address that it
+        // does not listen on, and the third signer to its actual address.
+        let pinned_peers = vec![
+            PinnedPeer {
+                public_key: public_keys[1],
+                address: Multiaddr::random_memory(rng),
+            },
+            PinnedPeer {
+                public_key: public_keys[2],
+                address: addrs[2].clone(),
+            },
+        ];
+
+        let mut swarms = Vec::new();
+        for (index, key) in keys.iter().enumerate() {
+            let pinned_peers = if index == 0 {
+                pinned_peers.clone()
+            } else {
+                Vec::new()
+            };
+            let context = TestContext::builder()
+                .with_in_memory_storage()
+                .with_mocked_clients()
+                .with_private_key(*key)
+                .modify_settings(|settings| settings.signer.p2p.pinned_peers = pinned_peers)
+                .build();
+            for public_key in public_keys {
+                context.state().current_signer_set().add_signer(public_key);
+            }
+
+            let swarm = SignerSwarmBuilder::new(key)
+                .enable_mdns(false)
+                .enable_kademlia(false)
+                .enable_autonat(false)
+                .enable_memory_transport(true)
+                .with_initial_bootstrap_delay(Duration::MAX) // We manually dial below
+                .add_listen_endpoint(addrs[index].clone())
+                .build()
+                .expect("Failed to build swarm");
+
+            let mut swarm_clone = swarm.clone();
+            tokio::spawn(async move {
+                swarm_clone.start(&context).await.unwrap();
+            });
+            swarms.push(swarm);
+        }
+
+        // Wait for the swarms to start.
+        tokio::time::sleep(Duration::from_millis(100)).await;
+
+        swarms[0].dial(addrs[1].clone()).await.unwrap();
+        swarms[0].dial(addrs[2].clone()).await.unwrap();
+
+        tokio::time::sleep(Duration::from_millis(200)).await;
+
+        let swarm = swarms[0].swarm.lock().await;
+        assert!(!swarm.is_connected(&public_keys[1].into()));
+        assert!(swarm.is_connected(&public_keys[2].into()));
+    }
 }
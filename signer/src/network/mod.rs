@@ -11,11 +11,13 @@ pub mod in_memory;
 pub mod in_memory2;
 
 pub mod libp2p;
+pub mod version;
 
 use std::future::Future;
 
 use crate::ecdsa;
 use crate::error::Error;
+use crate::keys::PublicKey;
 use crate::message;
 
 #[cfg(any(test, feature = "testing"))]
@@ -32,6 +34,13 @@ pub type MsgId = [u8; 32];
 pub trait MessageTransfer: Clone {
     /// Send `msg` to all other signers
     fn broadcast(&mut self, msg: Msg) -> impl Future<Output = Result<(), Error>> + Send;
+    /// Send `msg` only to the signer with the given public key, instead of
+    /// flooding it to all other signers
+    fn send_to(
+        &mut self,
+        peer: PublicKey,
+        msg: Msg,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
     /// Receive a message from the network
     fn receive(&mut self) -> impl Future<Output = Result<Msg, Error>> + Send;
 }
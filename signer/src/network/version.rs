@@ -0,0 +1,123 @@
+//! Versioning of the protocol that signers use to exchange messages.
+//!
+//! Signers advertise the range of protocol versions that they support when
+//! they connect to each other, and each pair of signers uses the highest
+//! version that they both support. Signers that do not support any version
+//! in common cannot understand each other, so they refuse to talk. Signers
+//! that predate version negotiation do not advertise a range, and are
+//! treated as supporting version 1 only.
+//!
+//! The protocol version must be bumped whenever a change to the messages
+//! cannot be handled by signers running the previous version, and the
+//! minimum version raised once no signer needs the older one anymore.
+//!
+//! Version 2 added signer set proposals, decision sync requests and direct
+//! messages between signers. None of these are sent to peers that
+//! negotiated version 1.
+
+use std::str::FromStr;
+
+use serde::Serialize;
+
+/// The latest version of the signer protocol supported by this signer.
+pub const PROTOCOL_VERSION: u32 = 2;
+
+/// The oldest version of the signer protocol supported by this signer.
+pub const MIN_PROTOCOL_VERSION: u32 = 1;
+
+/// The prefix of an advertised range of protocol versions.
+const PREFIX: &str = "/sbtc-signer/";
+
+/// A range of supported protocol versions, as advertised by a signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct ProtocolVersions {
+    /// The oldest supported version.
+    pub min: u32,
+    /// The latest supported version.
+    pub max: u32,
+}
+
+impl ProtocolVersions {
+    /// The protocol versions supported by this signer.
+    pub const SUPPORTED: Self = Self {
+        min: MIN_PROTOCOL_VERSION,
+        max: PROTOCOL_VERSION,
+    };
+
+    /// The protocol versions supported by signers that predate version
+    /// negotiation.
+    pub const LEGACY: Self = Self { min: 1, max: 1 };
+
+    /// Parse the protocol versions advertised by a peer. Peers that do not
+    /// advertise a range of signer protocol versions at all predate
+    /// version negotiation, while `None` is returned for malformed ranges.
+    pub fn from_advertised(advertised: &str) -> Option<Self> {
+        if advertised.starts_with(PREFIX) {
+            advertised.parse().ok()
+        } else {
+            Some(Self::LEGACY)
+        }
+    }
+
+    /// Returns the highest protocol version supported by both ranges, or
+    /// `None` if they have no version in common.
+    pub fn negotiate(&self, other: &Self) -> Option<u32> {
+        let version = self.max.min(other.max);
+        (version >= self.min.max(other.min)).then_some(version)
+    }
+}
+
+impl std::fmt::Display for ProtocolVersions {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{PREFIX}{}-{}", self.min, self.max)
+    }
+}
+
+impl FromStr for ProtocolVersions {
+    type Err = std::num::ParseIntError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let range = s.strip_prefix(PREFIX).unwrap_or(s);
+        let (min, max) = range.split_once('-').unwrap_or((range, range));
+        Ok(Self {
+            min: min.parse()?,
+            max: max.parse()?,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use test_case::test_case;
+
+    use super::*;
+
+    #[test]
+    fn advertised_versions_round_trip() {
+        let versions = ProtocolVersions { min: 2, max: 5 };
+        let advertised = versions.to_string();
+        assert_eq!(advertised, "/sbtc-signer/2-5");
+        assert_eq!(
+            ProtocolVersions::from_advertised(&advertised),
+            Some(versions)
+        );
+    }
+
+    #[test_case("/ipfs/id/push/1.0.0", Some(ProtocolVersions::LEGACY); "legacy")]
+    #[test_case("/sbtc-signer/3", Some(ProtocolVersions { min: 3, max: 3 }); "single version")]
+    #[test_case("/sbtc-signer/one-2", None; "malformed")]
+    fn parse_advertised_versions(advertised: &str, expected: Option<ProtocolVersions>) {
+        assert_eq!(ProtocolVersions::from_advertised(advertised), expected);
+    }
+
+    #[test_case(1, 3, 2, 5, Some(3); "overlapping")]
+    #[test_case(2, 5, 1, 3, Some(3); "overlapping, reversed")]
+    #[test_case(1, 5, 2, 3, Some(3); "contained")]
+    #[test_case(1, 1, 1, 1, Some(1); "equal")]
+    #[test_case(1, 2, 3, 4, None; "disjoint")]
+    fn negotiate_versions(min1: u32, max1: u32, min2: u32, max2: u32, expected: Option<u32>) {
+        let versions1 = ProtocolVersions { min: min1, max: max1 };
+        let versions2 = ProtocolVersions { min: min2, max: max2 };
+        assert_eq!(versions1.negotiate(&versions2), expected);
+    }
+}
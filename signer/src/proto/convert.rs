@@ -46,9 +46,11 @@ use crate::error::Error;
 use crate::keys::PublicKey;
 use crate::message::BitcoinPreSignAck;
 use crate::message::BitcoinPreSignRequest;
+use crate::message::DecisionSyncRequest;
 use crate::message::Payload;
 use crate::message::SignerDepositDecision;
 use crate::message::SignerMessage;
+use crate::message::SignerSetProposal;
 use crate::message::SignerWithdrawalDecision;
 use crate::message::StacksTransactionSignRequest;
 use crate::message::StacksTransactionSignature;
@@ -1263,6 +1265,63 @@ impl From<proto::BitcoinPreSignAck> for BitcoinPreSignAck {
     }
 }
 
+impl From<SignerSetProposal> for proto::SignerSetProposal {
+    fn from(value: SignerSetProposal) -> Self {
+        proto::SignerSetProposal {
+            signer_set: value.signer_set.into_iter().map(|v| v.into()).collect(),
+            signatures_required: value.signatures_required.into(),
+        }
+    }
+}
+
+impl TryFrom<proto::SignerSetProposal> for SignerSetProposal {
+    type Error = Error;
+    fn try_from(value: proto::SignerSetProposal) -> Result<Self, Self::Error> {
+        Ok(SignerSetProposal {
+            signer_set: value
+                .signer_set
+                .into_iter()
+                .map(|v| v.try_into())
+                .collect::<Result<BTreeSet<_>, Error>>()?,
+            signatures_required: value
+                .signatures_required
+                .try_into()
+                .map_err(|_| Error::TypeConversion)?,
+        })
+    }
+}
+
+impl From<DecisionSyncRequest> for proto::DecisionSyncRequest {
+    fn from(value: DecisionSyncRequest) -> Self {
+        proto::DecisionSyncRequest {
+            deposits: value
+                .deposits
+                .into_iter()
+                .map(proto::OutPoint::from)
+                .collect(),
+            withdrawals: value.withdrawals.into_iter().map(|v| v.into()).collect(),
+        }
+    }
+}
+
+impl TryFrom<proto::DecisionSyncRequest> for DecisionSyncRequest {
+    type Error = Error;
+    fn try_from(value: proto::DecisionSyncRequest) -> Result<Self, Self::Error> {
+        Ok(DecisionSyncRequest {
+            deposits: value
+                .deposits
+                .into_iter()
+                .map(OutPoint::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+            withdrawals: value
+                .withdrawals
+                .into_iter()
+                .map(QualifiedRequestId::try_from)
+                .collect::<Result<Vec<_>, _>>()?,
+        })
+    }
+}
+
 impl From<SignerMessage> for proto::SignerMessage {
     fn from(value: SignerMessage) -> Self {
         proto::SignerMessage {
@@ -1306,6 +1365,12 @@ impl From<Payload> for proto::Payload {
             Payload::BitcoinPreSignAck(inner) => {
                 proto::signer_message::Payload::BitcoinPreSignAck(inner.into())
             }
+            Payload::SignerSetProposal(inner) => {
+                proto::signer_message::Payload::SignerSetProposal(inner.into())
+            }
+            Payload::DecisionSyncRequest(inner) => {
+                proto::signer_message::Payload::DecisionSyncRequest(inner.into())
+            }
         }
     }
 }
@@ -1335,6 +1400,12 @@ impl TryFrom<proto::Payload> for Payload {
             proto::signer_message::Payload::BitcoinPreSignAck(inner) => {
                 Payload::BitcoinPreSignAck(inner.into())
             }
+            proto::signer_message::Payload::SignerSetProposal(inner) => {
+                Payload::SignerSetProposal(inner.try_into()?)
+            }
+            proto::signer_message::Payload::DecisionSyncRequest(inner) => {
+                Payload::DecisionSyncRequest(inner.try_into()?)
+            }
         };
         Ok(payload)
     }
@@ -1643,6 +1714,8 @@ impl codec::ProtoSerializable for SignerMessage {
             Payload::WstsMessage(_) => "SBTC_WSTS_MESSAGE",
             Payload::BitcoinPreSignRequest(_) => "SBTC_BITCOIN_PRE_SIGN_REQUEST",
             Payload::BitcoinPreSignAck(_) => "SBTC_BITCOIN_PRE_SIGN_ACK",
+            Payload::SignerSetProposal(_) => "SBTC_SIGNER_SET_PROPOSAL",
+            Payload::DecisionSyncRequest(_) => "SBTC_DECISION_SYNC_REQUEST",
         }
     }
 }
@@ -1724,6 +1797,8 @@ mod tests {
     #[test_case(PhantomData::<(Fees, proto::Fees)>; "Fees")]
     #[test_case(PhantomData::<(BitcoinPreSignRequest, proto::BitcoinPreSignRequest)>; "BitcoinPreSignRequest")]
     #[test_case(PhantomData::<(BitcoinPreSignAck, proto::BitcoinPreSignAck)>; "BitcoinPreSignAck")]
+    #[test_case(PhantomData::<(SignerSetProposal, proto::SignerSetProposal)>; "SignerSetProposal")]
+    #[test_case(PhantomData::<(DecisionSyncRequest, proto::DecisionSyncRequest)>; "DecisionSyncRequest")]
     fn convert_protobuf_type<T, U, E>(_: PhantomData<(T, U)>)
     where
         // `.unwrap()` requires that `E` implement `std::fmt::Debug` and
@@ -284,7 +284,7 @@ pub struct SignerMessage {
         super::super::super::bitcoin::BitcoinBlockHash,
     >,
     /// The message payload
-    #[prost(oneof = "signer_message::Payload", tags = "2, 3, 4, 5, 8, 10, 11")]
+    #[prost(oneof = "signer_message::Payload", tags = "2, 3, 4, 5, 8, 10, 11, 12, 13")]
     pub payload: ::core::option::Option<signer_message::Payload>,
 }
 /// Nested message and enum types in `SignerMessage`.
@@ -313,6 +313,12 @@ pub mod signer_message {
         /// Represents an acknowledgment of a BitcoinPreSignRequest
         #[prost(message, tag = "11")]
         BitcoinPreSignAck(super::BitcoinPreSignAck),
+        /// A proposal to change the signing set, approved by its sender
+        #[prost(message, tag = "12")]
+        SignerSetProposal(super::SignerSetProposal),
+        /// A request for the decisions of the recipient on the given requests
+        #[prost(message, tag = "13")]
+        DecisionSyncRequest(super::DecisionSyncRequest),
     }
 }
 /// A wsts message.
@@ -419,6 +425,30 @@ pub struct BitcoinPreSignRequest {
 /// Represents an acknowledgment of a BitcoinPreSignRequest.
 #[derive(Clone, Copy, PartialEq, ::prost::Message)]
 pub struct BitcoinPreSignAck {}
+/// A proposal to change the signing set. A signer sending a proposal
+/// approves it.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SignerSetProposal {
+    /// The public keys of the proposed signing set.
+    #[prost(message, repeated, tag = "1")]
+    pub signer_set: ::prost::alloc::vec::Vec<super::super::super::crypto::PublicKey>,
+    /// The proposed number of signatures required.
+    #[prost(uint32, tag = "2")]
+    pub signatures_required: u32,
+}
+/// A request for the decisions that the recipient made on the given
+/// deposit and withdrawal requests. The recipient answers with a
+/// SignerDepositDecision or SignerWithdrawalDecision message for each
+/// request that it has decided on.
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct DecisionSyncRequest {
+    /// The deposit requests that the sender wants the decisions for.
+    #[prost(message, repeated, tag = "1")]
+    pub deposits: ::prost::alloc::vec::Vec<super::super::super::bitcoin::OutPoint>,
+    /// The withdrawal requests that the sender wants the decisions for.
+    #[prost(message, repeated, tag = "2")]
+    pub withdrawals: ::prost::alloc::vec::Vec<QualifiedRequestId>,
+}
 /// This type is a container for all deposits and withdrawals that are part
 /// of a transaction package.
 #[derive(Clone, PartialEq, ::prost::Message)]
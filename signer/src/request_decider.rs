@@ -5,10 +5,15 @@
 //!
 //! For more details, see the [`RequestDeciderEventLoop`] documentation.
 
+use std::collections::HashMap;
 use std::time::Duration;
 
+use crate::bitcoin::validation::DepositConfirmationStatus;
 use crate::block_observer::BlockObserver;
 use crate::blocklist_client::BlocklistChecker;
+use crate::blocklist_client::BlocklistClientError;
+use crate::blocklist_client::Chain;
+use crate::config::BlocklistOutagePolicy;
 use crate::context::Context;
 use crate::context::P2PEvent;
 use crate::context::RequestDeciderEvent;
@@ -21,21 +26,39 @@ use crate::emily_client::EmilyInteract as _;
 use crate::error::Error;
 use crate::keys::PrivateKey;
 use crate::keys::PublicKey;
+use crate::message::DecisionSyncRequest;
 use crate::message::Payload;
 use crate::message::SignerDepositDecision;
 use crate::message::SignerMessage;
+use crate::message::SignerSetProposal;
 use crate::message::SignerWithdrawalDecision;
+use crate::metrics::Metrics;
 use crate::network::MessageTransfer;
 use crate::storage::DbRead as _;
 use crate::storage::DbWrite as _;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockHash;
+use crate::storage::model::BitcoinBlockHeight;
 use crate::storage::model::BitcoinBlockRef;
 use crate::storage::model::DepositSigner;
 use crate::storage::model::WithdrawalSigner;
+use crate::transaction_coordinator::is_recent_signer_set_approval;
 
 use futures::StreamExt as _;
 use futures::TryStreamExt as _;
+use libp2p::PeerId;
+
+/// The metrics label for decisions on deposit requests.
+const DEPOSIT_KIND: &str = "deposit";
+
+/// The metrics label for decisions on withdrawal requests.
+const WITHDRAWAL_KIND: &str = "withdrawal";
+
+/// The maximum number of deposit requests, and separately of withdrawal
+/// requests, that we ask a peer for its decisions on in a single decision
+/// sync request. This keeps the request well within the maximum message
+/// size.
+const MAX_DECISION_SYNC_REQUESTS: usize = 250;
 
 /// This struct is responsible for deciding whether to accept or reject
 /// requests and persisting requests from other signers.
@@ -66,6 +89,7 @@ fn run_loop_message_filter(signal: &SignerSignal) -> bool {
         signal,
         SignerSignal::Command(SignerCommand::Shutdown)
             | SignerSignal::Event(SignerEvent::P2P(P2PEvent::MessageReceived(_)))
+            | SignerSignal::Event(SignerEvent::P2P(P2PEvent::PeerConnected(_)))
             | SignerSignal::Event(SignerEvent::BitcoinBlockObserved(_))
     )
 }
@@ -94,13 +118,19 @@ where
         while let Some(message) = signal_stream.next().await {
             match message {
                 SignerSignal::Command(SignerCommand::Shutdown) => break,
-                SignerSignal::Command(SignerCommand::P2PPublish(_)) => {}
+                SignerSignal::Command(SignerCommand::P2PPublish(_))
+                | SignerSignal::Command(SignerCommand::P2PSendTo(..)) => {}
                 SignerSignal::Event(event) => match event {
                     SignerEvent::P2P(P2PEvent::MessageReceived(msg)) => {
                         if let Err(error) = self.handle_signer_message(&msg).await {
                             tracing::error!(%error, "error handling signer message");
                         }
                     }
+                    SignerEvent::P2P(P2PEvent::PeerConnected(peer_id)) => {
+                        if let Err(error) = self.handle_peer_connected(peer_id).await {
+                            tracing::warn!(%error, %peer_id, "error requesting decisions from peer");
+                        }
+                    }
                     SignerEvent::BitcoinBlockObserved(chain_tip) => {
                         if let Err(error) = self.handle_new_requests(chain_tip).await {
                             tracing::warn!(%error, "error handling new requests; skipping this round");
@@ -140,6 +170,12 @@ where
         let chain_tip = block_ref.block_hash;
         let signer_public_key = self.signer_public_key();
         let db = self.context.get_storage();
+
+        let _ = self
+            .propose_signer_set(&block_ref)
+            .await
+            .inspect_err(|error| tracing::warn!(%error, "error proposing a signer set change"));
+
         // We retry the deposit decisions because some signers' bitcoin nodes might have
         // been running behind and ignored the previous messages.
         let deposit_decisions_to_retry = db
@@ -161,10 +197,21 @@ where
             .get_pending_deposit_requests(&chain_tip, self.context_window, &signer_public_key)
             .await?;
 
+        let withdraw_requests = db
+            .get_pending_withdrawal_requests(&chain_tip, self.context_window, &signer_public_key)
+            .await?;
+
+        // We screen the addresses of all pending requests in one go, so
+        // that the blocklist client is called once per batch rather than
+        // once per address.
+        let screened = self
+            .screen_addresses(&deposit_requests, &withdraw_requests)
+            .await;
+
         for deposit_request in deposit_requests {
             let outpoint = deposit_request.outpoint();
             let _ = self
-                .handle_pending_deposit_request(deposit_request, &chain_tip)
+                .decide_deposit_request(deposit_request, &chain_tip, &screened)
                 .await
                 .inspect_err(|error| {
                     tracing::warn!(
@@ -190,14 +237,10 @@ where
                 |error| tracing::warn!(%error, "error handling withdrawal decisions to retry"),
             );
 
-        let withdraw_requests = db
-            .get_pending_withdrawal_requests(&chain_tip, self.context_window, &signer_public_key)
-            .await?;
-
         for withdraw_request in withdraw_requests {
             let request_id = withdraw_request.request_id;
             let _ = self
-                .handle_pending_withdrawal_request(withdraw_request, &chain_tip)
+                .handle_pending_withdrawal_request(withdraw_request, &chain_tip, &screened)
                 .await
                 .inspect_err(|error| {
                     tracing::warn!(
@@ -223,6 +266,18 @@ where
                 self.persist_received_withdraw_decision(decision, msg.signer_public_key)
                     .await?;
             }
+            Payload::SignerSetProposal(proposal) => {
+                self.persist_received_signer_set_proposal(
+                    proposal,
+                    msg.signer_public_key,
+                    msg.bitcoin_chain_tip,
+                )
+                .await?;
+            }
+            Payload::DecisionSyncRequest(request) => {
+                self.handle_decision_sync_request(request, msg.signer_public_key)
+                    .await?;
+            }
             Payload::StacksTransactionSignRequest(_)
             | Payload::BitcoinPreSignRequest(_)
             | Payload::BitcoinPreSignAck(_)
@@ -238,7 +293,7 @@ where
     ///
     /// 1. Reach out to the blocklist client and find out whether we can
     ///    accept the deposit given all the input `scriptPubKey`s of the
-    ///    transaction.
+    ///    transaction and the Stacks principal of the recipient.
     /// 2. Check if we are a part of the signing set associated with the
     ///    public key locking the funds.
     ///
@@ -249,6 +304,20 @@ where
         &mut self,
         request: model::DepositRequest,
         chain_tip: &BitcoinBlockHash,
+    ) -> Result<(), Error> {
+        self.decide_deposit_request(request, chain_tip, &HashMap::new())
+            .await
+    }
+
+    /// Decide on the deposit request and send the decision to the other
+    /// signers. The `screened` map holds the blocklist results of
+    /// addresses that have already been screened in a batch, addresses
+    /// missing from it are screened individually.
+    async fn decide_deposit_request(
+        &mut self,
+        request: model::DepositRequest,
+        chain_tip: &BitcoinBlockHash,
+        screened: &HashMap<String, bool>,
     ) -> Result<(), Error> {
         let db = self.context.get_storage_mut();
 
@@ -264,7 +333,25 @@ where
             .await?
             .unwrap_or(false);
 
-        let can_accept = self.can_accept_deposit_request(&request).await?;
+        let can_accept = match self.can_accept_deposit_request(&request, screened).await {
+            Err(Error::BlocklistClient(error)) => {
+                let confirmed_at = db
+                    .get_deposit_request_report(
+                        chain_tip,
+                        &request.txid,
+                        request.output_index,
+                        &signer_public_key,
+                    )
+                    .await?
+                    .and_then(|report| match report.status {
+                        DepositConfirmationStatus::Confirmed(height, _) => Some(height),
+                        _ => None,
+                    });
+                self.outage_decision(error, DEPOSIT_KIND, confirmed_at, chain_tip)
+                    .await?
+            }
+            result => result?,
+        };
 
         let msg = SignerDepositDecision {
             txid: request.txid.into(),
@@ -332,12 +419,19 @@ where
         &mut self,
         withdrawal_request: model::WithdrawalRequest,
         chain_tip: &BitcoinBlockHash,
+        screened: &HashMap<String, bool>,
     ) -> Result<(), Error> {
-        // TODO: Do we want to do this on the sender address or the
-        // recipient address?
-        let is_accepted = self
-            .can_accept_withdrawal_request(&withdrawal_request)
-            .await?;
+        let is_accepted = match self
+            .can_accept_withdrawal_request(&withdrawal_request, screened)
+            .await
+        {
+            Err(Error::BlocklistClient(error)) => {
+                let confirmed_at = Some(withdrawal_request.bitcoin_block_height);
+                self.outage_decision(error, WITHDRAWAL_KIND, confirmed_at, chain_tip)
+                    .await?
+            }
+            result => result?,
+        };
 
         let msg = SignerWithdrawalDecision {
             request_id: withdrawal_request.request_id,
@@ -367,16 +461,159 @@ where
         Ok(())
     }
 
-    async fn can_accept_withdrawal_request(
+    /// Decide on a request whose addresses could not be screened because
+    /// the blocklist client is unavailable, according to the configured
+    /// outage policy. The `confirmed_at` height is the height of the
+    /// bitcoin block confirming the request, if it is known.
+    ///
+    /// Returns the vote on the request, or the blocklist client error if
+    /// the vote is deferred to a later bitcoin block.
+    async fn outage_decision(
         &self,
-        req: &model::WithdrawalRequest,
+        error: BlocklistClientError,
+        kind: &'static str,
+        confirmed_at: Option<BitcoinBlockHeight>,
+        chain_tip: &BitcoinBlockHash,
     ) -> Result<bool, Error> {
-        // If we have not configured a blocklist checker, then we can
-        // return early.
+        let Some(config) = self.context.config().blocklist_client.as_ref() else {
+            return Err(Error::BlocklistClient(error));
+        };
+
+        let decision = match config.outage_policy {
+            BlocklistOutagePolicy::Accept => Some(true),
+            BlocklistOutagePolicy::Reject => Some(false),
+            BlocklistOutagePolicy::Defer => {
+                let chain_tip_height = self
+                    .context
+                    .get_storage()
+                    .get_bitcoin_block(chain_tip)
+                    .await?
+                    .map(|block| block.block_height);
+                // If we do not know how old the request is then we keep
+                // deferring, the request eventually leaves the context
+                // window.
+                let deferral_expired = confirmed_at.zip(chain_tip_height).is_some_and(
+                    |(confirmed_at, chain_tip_height)| {
+                        *chain_tip_height.saturating_sub(confirmed_at)
+                            >= u64::from(config.outage_defer_blocks)
+                    },
+                );
+                deferral_expired.then_some(false)
+            }
+        };
+
+        let policy = config.outage_policy;
+        match decision {
+            Some(can_accept) => {
+                tracing::warn!(
+                    %error,
+                    %policy,
+                    %kind,
+                    can_accept,
+                    "blocklist client unavailable, voting according to the outage policy"
+                );
+                let label = if can_accept { "accept" } else { "reject" };
+                Metrics::increment_blocklist_outage_decisions(kind, label);
+                Ok(can_accept)
+            }
+            None => {
+                tracing::warn!(
+                    %error,
+                    %policy,
+                    %kind,
+                    "blocklist client unavailable, deferring the vote"
+                );
+                Metrics::increment_blocklist_outage_decisions(kind, "defer");
+                Err(Error::BlocklistClient(error))
+            }
+        }
+    }
+
+    /// Screen the addresses of the given requests with one batched call
+    /// to the blocklist checker per chain.
+    ///
+    /// Returns whether each address can be accepted. If a batch cannot be
+    /// screened then its addresses are missing from the returned map and
+    /// each request falls back to screening them individually.
+    async fn screen_addresses(
+        &self,
+        deposits: &[model::DepositRequest],
+        withdrawals: &[model::WithdrawalRequest],
+    ) -> HashMap<String, bool> {
+        let mut screened = HashMap::new();
         let Some(client) = self.blocklist_checker.as_ref() else {
-            return Ok(true);
+            return screened;
         };
 
+        // Requests whose addresses cannot be derived are skipped here,
+        // they fail with the appropriate error when they are handled.
+        let addresses = deposits
+            .iter()
+            .filter_map(|req| self.deposit_addresses(req).ok())
+            .chain(
+                withdrawals
+                    .iter()
+                    .filter_map(|req| self.withdrawal_addresses(req).ok()),
+            )
+            .flatten()
+            .collect::<Vec<_>>();
+
+        for chain in [Chain::Bitcoin, Chain::Stacks] {
+            let mut batch = addresses
+                .iter()
+                .filter(|(_, address_chain)| *address_chain == chain)
+                .map(|(address, _)| address.clone())
+                .collect::<Vec<_>>();
+            batch.sort();
+            batch.dedup();
+
+            if batch.is_empty() {
+                continue;
+            }
+
+            match client.can_accept_many(&batch, chain).await {
+                Ok(results) => screened.extend(batch.into_iter().zip(results)),
+                Err(error) => tracing::warn!(
+                    %error,
+                    %chain,
+                    "could not screen addresses in a batch, screening them individually"
+                ),
+            }
+        }
+
+        screened
+    }
+
+    /// Check the addresses with the blocklist checker, skipping those that
+    /// have already been screened. Returns whether all of the addresses
+    /// can be accepted.
+    async fn can_accept_addresses(
+        client: &B,
+        addresses: &[(String, Chain)],
+        screened: &HashMap<String, bool>,
+    ) -> Result<bool, Error> {
+        let responses = futures::stream::iter(addresses)
+            .then(|(address, chain)| async move {
+                match screened.get(address) {
+                    Some(can_accept) => Ok(*can_accept),
+                    None => client.can_accept(address, *chain).await,
+                }
+            })
+            .inspect_err(|error| tracing::error!(%error, "blocklist client issue"))
+            .collect::<Vec<_>>()
+            .await
+            .into_iter()
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(responses.into_iter().all(|res| res))
+    }
+
+    /// The addresses screened for the withdrawal request: the bitcoin
+    /// address of the recipient and the Stacks principal of the sender.
+    fn withdrawal_addresses(
+        &self,
+        req: &model::WithdrawalRequest,
+    ) -> Result<Vec<(String, Chain)>, Error> {
         let network = bitcoin::Network::from(self.context.config().signer.network);
         let receiver_address = bitcoin::Address::from_script(&req.recipient, network.params())
             .map_err(|err| {
@@ -387,44 +624,70 @@ where
                 )
             })?;
 
-        let can_accept = client
-            .can_accept(&receiver_address.to_string())
-            .await
-            .inspect_err(|error| tracing::error!(%error, "blocklist client issue"))?;
+        Ok(vec![
+            (receiver_address.to_string(), Chain::Bitcoin),
+            (req.sender_address.to_string(), Chain::Stacks),
+        ])
+    }
 
-        Ok(can_accept)
+    /// The addresses screened for the deposit request: the bitcoin
+    /// addresses of all the input `scriptPubKey`s of the deposit
+    /// transaction and the Stacks principal of the recipient.
+    fn deposit_addresses(
+        &self,
+        req: &model::DepositRequest,
+    ) -> Result<Vec<(String, Chain)>, Error> {
+        let bitcoin_network = bitcoin::Network::from(self.context.config().signer.network);
+        let params = bitcoin_network.params();
+        let mut addresses = req
+            .sender_script_pub_keys
+            .iter()
+            .map(|script_pubkey| {
+                bitcoin::Address::from_script(script_pubkey, params)
+                    .map(|address| (address.to_string(), Chain::Bitcoin))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|err| Error::DepositBitcoinAddressFromScript(err, req.outpoint()))?;
+
+        addresses.push((req.recipient.to_string(), Chain::Stacks));
+        Ok(addresses)
     }
 
-    async fn can_accept_deposit_request(&self, req: &model::DepositRequest) -> Result<bool, Error> {
+    async fn can_accept_withdrawal_request(
+        &self,
+        req: &model::WithdrawalRequest,
+        screened: &HashMap<String, bool>,
+    ) -> Result<bool, Error> {
         // If we have not configured a blocklist checker, then we can
         // return early.
         let Some(client) = self.blocklist_checker.as_ref() else {
             return Ok(true);
         };
 
-        // We turn all the input scriptPubKeys into addresses and check
-        // those with the blocklist client.
-        let bitcoin_network = bitcoin::Network::from(self.context.config().signer.network);
-        let params = bitcoin_network.params();
-        let addresses = req
-            .sender_script_pub_keys
-            .iter()
-            .map(|script_pubkey| bitcoin::Address::from_script(script_pubkey, params))
-            .collect::<Result<Vec<bitcoin::Address>, _>>()
-            .map_err(|err| Error::DepositBitcoinAddressFromScript(err, req.outpoint()))?;
+        // We check both the recipient and the sender of the withdrawal
+        // with the blocklist client.
+        let addresses = self.withdrawal_addresses(req)?;
 
-        let responses = futures::stream::iter(&addresses)
-            .then(|address| async { client.can_accept(&address.to_string()).await })
-            .inspect_err(|error| tracing::error!(%error, "blocklist client issue"))
-            .collect::<Vec<_>>()
-            .await
-            .into_iter()
-            .collect::<Result<Vec<_>, _>>()?;
+        Self::can_accept_addresses(client, &addresses, screened).await
+    }
 
-        // If all of the inputs addresses are fine then we pass the deposit
-        // request.
-        let can_accept = responses.into_iter().all(|res| res);
-        Ok(can_accept)
+    async fn can_accept_deposit_request(
+        &self,
+        req: &model::DepositRequest,
+        screened: &HashMap<String, bool>,
+    ) -> Result<bool, Error> {
+        // If we have not configured a blocklist checker, then we can
+        // return early.
+        let Some(client) = self.blocklist_checker.as_ref() else {
+            return Ok(true);
+        };
+
+        // We turn all the input scriptPubKeys into addresses and check
+        // those, along with the recipient, with the blocklist client. If
+        // all of them are fine then we pass the deposit request.
+        let addresses = self.deposit_addresses(req)?;
+
+        Self::can_accept_addresses(client, &addresses, screened).await
     }
 
     /// Save the given decision into the database
@@ -517,6 +780,203 @@ where
         Ok(())
     }
 
+    /// Ask a signer that we just connected to for its decisions on the
+    /// requests in our context window that we have no vote from it on.
+    ///
+    /// Signers broadcast their decision on a request once, when they
+    /// first decide on it, so we miss the decisions of signers that we
+    /// were disconnected from at the time. Pulling these decisions once we
+    /// are connected again means that we do not have to rely on the
+    /// periodic rebroadcast of recent decisions to catch up.
+    #[tracing::instrument(skip_all, fields(%peer_id))]
+    pub async fn handle_peer_connected(&mut self, peer_id: PeerId) -> Result<(), Error> {
+        let state = self.context.state();
+        let Some(peer_public_key) = state.current_signer_set().get_pubkey_for_peer(&peer_id) else {
+            tracing::debug!("connected peer is not a known signer; skipping decision sync");
+            return Ok(());
+        };
+        let Some(chain_tip) = state.bitcoin_chain_tip() else {
+            tracing::debug!("no bitcoin chain tip yet; skipping decision sync");
+            return Ok(());
+        };
+
+        let chain_tip = chain_tip.block_hash;
+        let db = self.context.get_storage();
+
+        // The pending requests "of" the peer are the requests that we
+        // have no decision from the peer on.
+        let deposits = db
+            .get_pending_deposit_requests(&chain_tip, self.context_window, &peer_public_key)
+            .await?
+            .iter()
+            .take(MAX_DECISION_SYNC_REQUESTS)
+            .map(model::DepositRequest::outpoint)
+            .collect();
+
+        let withdrawals = db
+            .get_pending_withdrawal_requests(&chain_tip, self.context_window, &peer_public_key)
+            .await?
+            .iter()
+            .take(MAX_DECISION_SYNC_REQUESTS)
+            .map(model::WithdrawalRequest::qualified_id)
+            .collect();
+
+        let request = DecisionSyncRequest { deposits, withdrawals };
+        if request.is_empty() {
+            return Ok(());
+        }
+
+        tracing::debug!(
+            peer_public_key = %peer_public_key,
+            num_deposits = %request.deposits.len(),
+            num_withdrawals = %request.withdrawals.len(),
+            "requesting missing decisions from peer"
+        );
+        self.send_message_to(request, &chain_tip, peer_public_key)
+            .await
+    }
+
+    /// Answer a decision sync request from the given signer with our
+    /// decisions on the requested deposit and withdrawal requests. The
+    /// decisions are read from the database and sent only to the signer
+    /// that asked for them.
+    #[tracing::instrument(skip_all, fields(sender = %sender_public_key))]
+    pub async fn handle_decision_sync_request(
+        &mut self,
+        request: &DecisionSyncRequest,
+        sender_public_key: PublicKey,
+    ) -> Result<(), Error> {
+        let Some(chain_tip) = self.context.state().bitcoin_chain_tip() else {
+            return Ok(());
+        };
+
+        let chain_tip = chain_tip.block_hash;
+        let signer_public_key = self.signer_public_key();
+        let db = self.context.get_storage();
+
+        let mut deposit_decisions = Vec::new();
+        for outpoint in request.deposits.iter().take(MAX_DECISION_SYNC_REQUESTS) {
+            let decision = db
+                .get_deposit_signers(&outpoint.txid.into(), outpoint.vout)
+                .await?
+                .into_iter()
+                .find(|decision| decision.signer_pub_key == signer_public_key);
+            deposit_decisions.extend(decision.map(SignerDepositDecision::from));
+        }
+
+        let mut withdrawal_decisions = Vec::new();
+        for id in request.withdrawals.iter().take(MAX_DECISION_SYNC_REQUESTS) {
+            let decision = db
+                .get_withdrawal_signers(id.request_id, &id.block_hash)
+                .await?
+                .into_iter()
+                .find(|decision| {
+                    decision.signer_pub_key == signer_public_key && decision.txid == id.txid
+                });
+            withdrawal_decisions.extend(decision.map(SignerWithdrawalDecision::from));
+        }
+
+        tracing::debug!(
+            num_deposits = %deposit_decisions.len(),
+            num_withdrawals = %withdrawal_decisions.len(),
+            "answering decision sync request"
+        );
+
+        for decision in deposit_decisions {
+            self.send_message_to(decision, &chain_tip, sender_public_key)
+                .await?;
+        }
+        for decision in withdrawal_decisions {
+            self.send_message_to(decision, &chain_tip, sender_public_key)
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    /// Resend the signer set change proposal that the operator of this
+    /// signer approved through the API, if any, to the other signers.
+    ///
+    /// The proposal is resent on every new bitcoin block, so that signers
+    /// that missed it, or that restarted, eventually learn about it, and
+    /// our approval is refreshed so that it does not expire while the
+    /// operator keeps it. Once the operator withdraws it, the approval of
+    /// the registry signer set that replaced it is resent, without being
+    /// refreshed, until it expires.
+    #[tracing::instrument(skip_all)]
+    async fn propose_signer_set(&mut self, chain_tip: &BitcoinBlockRef) -> Result<(), Error> {
+        let Some(registry_signer_info) = self.context.state().registry_signer_set_info() else {
+            return Ok(());
+        };
+
+        let signer_public_key = self.signer_public_key();
+        let db = self.context.get_storage();
+        let approval = db
+            .get_signer_set_approvals()
+            .await?
+            .into_iter()
+            .find(|approval| approval.approver_public_key == signer_public_key);
+        let Some(approval) = approval else {
+            return Ok(());
+        };
+
+        let is_recent = is_recent_signer_set_approval(&db, &approval, chain_tip).await?;
+        let proposal = SignerSetProposal::from(approval);
+        if proposal == SignerSetProposal::from(&registry_signer_info) {
+            if is_recent {
+                tracing::debug!("resending the withdrawal of our signer set change approval");
+                self.send_message(proposal, &chain_tip.block_hash).await?;
+            }
+            return Ok(());
+        }
+
+        tracing::info!(
+            signatures_required = %proposal.signatures_required,
+            num_signers = %proposal.signer_set.len(),
+            "proposing a signer set change"
+        );
+        self.persist_received_signer_set_proposal(
+            &proposal,
+            signer_public_key,
+            chain_tip.block_hash,
+        )
+        .await?;
+
+        self.send_message(proposal, &chain_tip.block_hash).await
+    }
+
+    /// Persist the approval of a signer set change proposal sent by the
+    /// given signer, replacing any proposal that it approved before.
+    #[tracing::instrument(skip_all)]
+    pub async fn persist_received_signer_set_proposal(
+        &mut self,
+        proposal: &SignerSetProposal,
+        approver_public_key: PublicKey,
+        bitcoin_chain_tip: BitcoinBlockHash,
+    ) -> Result<(), Error> {
+        if !proposal.is_valid() {
+            tracing::warn!(
+                sender = %approver_public_key,
+                signatures_required = %proposal.signatures_required,
+                num_signers = %proposal.signer_set.len(),
+                "ignoring invalid signer set change proposal"
+            );
+            return Ok(());
+        }
+
+        let approval = model::SignerSetApproval {
+            approver_public_key,
+            signer_set_public_keys: proposal.signer_set.iter().copied().collect(),
+            signatures_required: proposal.signatures_required,
+            bitcoin_chain_tip,
+        };
+
+        self.context
+            .get_storage_mut()
+            .write_signer_set_approval(&approval)
+            .await
+    }
+
     #[tracing::instrument(skip_all)]
     async fn send_message(
         &mut self,
@@ -533,6 +993,20 @@ where
         Ok(())
     }
 
+    async fn send_message_to(
+        &mut self,
+        msg: impl Into<Payload>,
+        chain_tip: &BitcoinBlockHash,
+        recipient: PublicKey,
+    ) -> Result<(), Error> {
+        let payload: Payload = msg.into();
+        let msg = payload
+            .to_message(*chain_tip)
+            .sign_ecdsa(&self.signer_private_key);
+
+        self.network.send_to(recipient, msg).await
+    }
+
     fn signer_public_key(&self) -> PublicKey {
         PublicKey::from_private_key(&self.signer_private_key)
     }
@@ -602,4 +1076,11 @@ mod tests {
             .assert_should_store_decisions_received_from_other_signers()
             .await;
     }
+
+    #[tokio::test]
+    async fn should_sync_missing_decisions_from_connected_peers() {
+        test_environment()
+            .assert_should_sync_missing_decisions_from_connected_peers()
+            .await;
+    }
 }
@@ -790,6 +790,20 @@ impl DbRead for SharedStore {
         let peers = store.p2p_peers.values().cloned().collect();
         Ok(peers)
     }
+
+    async fn get_peer_statistics(&self) -> Result<Vec<model::PeerStatistics>, Error> {
+        let store = self.lock().await;
+        let mut statistics: Vec<_> = store.peer_statistics.values().cloned().collect();
+        statistics.sort_by_key(|stats| stats.public_key);
+        Ok(statistics)
+    }
+
+    async fn get_signer_set_approvals(&self) -> Result<Vec<model::SignerSetApproval>, Error> {
+        let store = self.lock().await;
+        let mut approvals: Vec<_> = store.signer_set_approvals.values().cloned().collect();
+        approvals.sort_by_key(|approval| approval.approver_public_key);
+        Ok(approvals)
+    }
 }
 
 impl DbRead for InMemoryTransaction {
@@ -1158,4 +1172,12 @@ impl DbRead for InMemoryTransaction {
     async fn get_p2p_peers(&self) -> Result<Vec<model::P2PPeer>, Error> {
         self.store.get_p2p_peers().await
     }
+
+    async fn get_peer_statistics(&self) -> Result<Vec<model::PeerStatistics>, Error> {
+        self.store.get_peer_statistics().await
+    }
+
+    async fn get_signer_set_approvals(&self) -> Result<Vec<model::SignerSetApproval>, Error> {
+        self.store.get_signer_set_approvals().await
+    }
 }
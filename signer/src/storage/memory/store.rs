@@ -119,6 +119,12 @@ pub struct Store {
 
     /// Stored P2P peers
     pub p2p_peers: HashMap<(PeerId, PublicKey), model::P2PPeer>,
+
+    /// Statistics about the peers
+    pub peer_statistics: HashMap<PublicKey, model::PeerStatistics>,
+
+    /// The latest signer set change approval of each signer
+    pub signer_set_approvals: HashMap<PublicKey, model::SignerSetApproval>,
 }
 
 impl Store {
@@ -387,6 +387,34 @@ impl DbWrite for SharedStore {
 
         Ok(())
     }
+
+    async fn record_peer_event(
+        &self,
+        pub_key: &PublicKey,
+        event: model::PeerEvent,
+        count: u32,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store
+            .peer_statistics
+            .entry(*pub_key)
+            .or_insert_with(|| model::PeerStatistics::new(*pub_key))
+            .record(event, count);
+
+        Ok(())
+    }
+
+    async fn write_signer_set_approval(
+        &self,
+        approval: &model::SignerSetApproval,
+    ) -> Result<(), Error> {
+        let mut store = self.lock().await;
+        store
+            .signer_set_approvals
+            .insert(approval.approver_public_key, approval.clone());
+
+        Ok(())
+    }
 }
 
 impl DbWrite for InMemoryTransaction {
@@ -543,4 +571,20 @@ impl DbWrite for InMemoryTransaction {
             .update_peer_connection(pub_key, peer_id, address)
             .await
     }
+
+    async fn record_peer_event(
+        &self,
+        pub_key: &PublicKey,
+        event: model::PeerEvent,
+        count: u32,
+    ) -> Result<(), Error> {
+        self.store.record_peer_event(pub_key, event, count).await
+    }
+
+    async fn write_signer_set_approval(
+        &self,
+        approval: &model::SignerSetApproval,
+    ) -> Result<(), Error> {
+        self.store.write_signer_set_approval(approval).await
+    }
 }
@@ -464,6 +464,17 @@ pub trait DbRead {
 
     /// Returns the list of stored peers.
     fn get_p2p_peers(&self) -> impl Future<Output = Result<Vec<model::P2PPeer>, Error>> + Send;
+
+    /// Returns the statistics of all peers that have any recorded events.
+    fn get_peer_statistics(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::PeerStatistics>, Error>> + Send;
+
+    /// Returns the latest approval of a signer set change proposal of
+    /// every signer that has sent one.
+    fn get_signer_set_approvals(
+        &self,
+    ) -> impl Future<Output = Result<Vec<model::SignerSetApproval>, Error>> + Send;
 }
 
 /// Represents the ability to write data to the signer storage.
@@ -618,4 +629,20 @@ pub trait DbWrite {
         peer_id: &PeerId,
         address: Multiaddr,
     ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Counts the given number of occurrences of the event in the
+    /// statistics of the given peer.
+    fn record_peer_event(
+        &self,
+        pub_key: &PublicKey,
+        event: model::PeerEvent,
+        count: u32,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
+
+    /// Write the approval of a signer set change proposal, replacing any
+    /// previous approval of the same signer.
+    fn write_signer_set_approval(
+        &self,
+        approval: &model::SignerSetApproval,
+    ) -> impl Future<Output = Result<(), Error>> + Send;
 }
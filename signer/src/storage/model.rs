@@ -40,6 +40,114 @@ pub struct P2PPeer {
     pub last_dialed_at: Timestamp,
 }
 
+/// Statistics about how reliably a signer has taken part in coordinating
+/// and signing, as observed by this signer.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
+pub struct PeerStatistics {
+    /// The public key of the peer.
+    pub public_key: PublicKey,
+    /// The number of bitcoin tenures that the peer started coordinating.
+    #[sqlx(try_from = "i64")]
+    pub tenures_coordinated: u64,
+    /// The number of bitcoin tenures that the peer was due to coordinate
+    /// but did not, so that a backup coordinator took over.
+    #[sqlx(try_from = "i64")]
+    pub tenures_missed: u64,
+    /// The number of WSTS signing rounds that the peer contributed a
+    /// signature share to.
+    #[sqlx(try_from = "i64")]
+    pub rounds_completed: u64,
+    /// The number of WSTS signing rounds that timed out without the peer
+    /// sending its nonce.
+    #[sqlx(try_from = "i64")]
+    pub nonce_timeouts: u64,
+    /// The number of WSTS signing rounds that timed out after the peer
+    /// sent its nonce but without it sending its signature share.
+    #[sqlx(try_from = "i64")]
+    pub signature_share_timeouts: u64,
+    /// The number of messages from the peer that were rejected as invalid.
+    #[sqlx(try_from = "i64")]
+    pub invalid_messages: u64,
+    /// The timestamp of the last update to the statistics.
+    pub updated_at: Timestamp,
+}
+
+impl PeerStatistics {
+    /// Create empty statistics for the given peer.
+    pub fn new(public_key: PublicKey) -> Self {
+        Self {
+            public_key,
+            tenures_coordinated: 0,
+            tenures_missed: 0,
+            rounds_completed: 0,
+            nonce_timeouts: 0,
+            signature_share_timeouts: 0,
+            invalid_messages: 0,
+            updated_at: time::OffsetDateTime::now_utc().into(),
+        }
+    }
+
+    /// Count the given number of occurrences of the event in the
+    /// statistics.
+    pub fn record(&mut self, event: PeerEvent, count: u32) {
+        let counter = match event {
+            PeerEvent::TenureCoordinated => &mut self.tenures_coordinated,
+            PeerEvent::TenureMissed => &mut self.tenures_missed,
+            PeerEvent::RoundCompleted => &mut self.rounds_completed,
+            PeerEvent::NonceTimeout => &mut self.nonce_timeouts,
+            PeerEvent::SignatureShareTimeout => &mut self.signature_share_timeouts,
+            PeerEvent::InvalidMessage => &mut self.invalid_messages,
+        };
+        *counter += u64::from(count);
+        self.updated_at = time::OffsetDateTime::now_utc().into();
+    }
+}
+
+/// An event that is counted in the [`PeerStatistics`] of a signer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, strum::Display)]
+#[strum(serialize_all = "snake_case")]
+pub enum PeerEvent {
+    /// The peer started coordinating a bitcoin tenure.
+    TenureCoordinated,
+    /// The peer was due to coordinate a bitcoin tenure but a backup
+    /// coordinator took over.
+    TenureMissed,
+    /// The peer contributed a signature share to a WSTS signing round.
+    RoundCompleted,
+    /// A WSTS signing round timed out without the peer sending its nonce.
+    NonceTimeout,
+    /// A WSTS signing round timed out after the peer sent its nonce but
+    /// without it sending its signature share.
+    SignatureShareTimeout,
+    /// A message from the peer was rejected as invalid.
+    InvalidMessage,
+}
+
+/// The approval, by a signer, of a proposal to change the signing set.
+///
+/// Each signer approves at most one proposal at a time, so a new approval
+/// from a signer replaces its previous one.
+#[derive(Debug, Clone, Hash, PartialEq, Eq, PartialOrd, Ord, sqlx::FromRow)]
+#[cfg_attr(feature = "testing", derive(fake::Dummy))]
+pub struct SignerSetApproval {
+    /// The public key of the signer approving the proposal.
+    pub approver_public_key: PublicKey,
+    /// The public keys of the proposed signing set, in ascending order.
+    pub signer_set_public_keys: Vec<PublicKey>,
+    /// The proposed number of signatures required.
+    #[sqlx(try_from = "i32")]
+    pub signatures_required: u16,
+    /// The bitcoin chain tip of the signer when it sent the approval.
+    pub bitcoin_chain_tip: BitcoinBlockHash,
+}
+
+impl SignerSetApproval {
+    /// Return the public keys of the proposed signing set.
+    pub fn signer_set_public_keys(&self) -> BTreeSet<PublicKey> {
+        self.signer_set_public_keys.iter().copied().collect()
+    }
+}
+
 /// A bitcoin transaction output (TXO) relevant for the sBTC signers.
 ///
 /// This object can have a few different meanings, all of them identified
@@ -2563,6 +2563,54 @@ impl PgRead {
         .await
         .map_err(Error::SqlxQuery)
     }
+
+    async fn get_peer_statistics<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<Vec<model::PeerStatistics>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::PeerStatistics>(
+            r#"
+            SELECT
+                public_key
+              , tenures_coordinated
+              , tenures_missed
+              , rounds_completed
+              , nonce_timeouts
+              , signature_share_timeouts
+              , invalid_messages
+              , updated_at
+            FROM sbtc_signer.peer_statistics
+            ORDER BY public_key
+            "#,
+        )
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
+
+    async fn get_signer_set_approvals<'e, E>(
+        executor: &'e mut E,
+    ) -> Result<Vec<model::SignerSetApproval>, Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query_as::<_, model::SignerSetApproval>(
+            r#"
+            SELECT
+                approver_public_key
+              , signer_set_public_keys
+              , signatures_required
+              , bitcoin_chain_tip
+            FROM sbtc_signer.signer_set_approvals
+            ORDER BY approver_public_key
+            "#,
+        )
+        .fetch_all(executor)
+        .await
+        .map_err(Error::SqlxQuery)
+    }
 }
 
 impl DbRead for PgStore {
@@ -2998,6 +3046,14 @@ impl DbRead for PgStore {
     async fn get_p2p_peers(&self) -> Result<Vec<model::P2PPeer>, Error> {
         PgRead::get_p2p_peers(self.get_connection().await?.as_mut()).await
     }
+
+    async fn get_peer_statistics(&self) -> Result<Vec<model::PeerStatistics>, Error> {
+        PgRead::get_peer_statistics(self.get_connection().await?.as_mut()).await
+    }
+
+    async fn get_signer_set_approvals(&self) -> Result<Vec<model::SignerSetApproval>, Error> {
+        PgRead::get_signer_set_approvals(self.get_connection().await?.as_mut()).await
+    }
 }
 
 impl DbRead for PgTransaction<'_> {
@@ -3433,4 +3489,14 @@ impl DbRead for PgTransaction<'_> {
         let mut tx = self.tx.lock().await;
         PgRead::get_p2p_peers(tx.as_mut()).await
     }
+
+    async fn get_peer_statistics(&self) -> Result<Vec<model::PeerStatistics>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_peer_statistics(tx.as_mut()).await
+    }
+
+    async fn get_signer_set_approvals(&self) -> Result<Vec<model::SignerSetApproval>, Error> {
+        let mut tx = self.tx.lock().await;
+        PgRead::get_signer_set_approvals(tx.as_mut()).await
+    }
 }
@@ -1002,6 +1002,95 @@ impl PgWrite {
 
         Ok(())
     }
+
+    async fn record_peer_event<'e, E>(
+        executor: &'e mut E,
+        pub_key: &PublicKey,
+        event: model::PeerEvent,
+        count: u32,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        // Exactly one of the counters is incremented, depending on the
+        // event.
+        let increment = |counted: model::PeerEvent| {
+            if event == counted {
+                i64::from(count)
+            } else {
+                0
+            }
+        };
+
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.peer_statistics (
+                public_key
+              , tenures_coordinated
+              , tenures_missed
+              , rounds_completed
+              , nonce_timeouts
+              , signature_share_timeouts
+              , invalid_messages
+            )
+            VALUES ($1, $2, $3, $4, $5, $6, $7)
+            ON CONFLICT (public_key) DO UPDATE SET
+                tenures_coordinated = peer_statistics.tenures_coordinated + EXCLUDED.tenures_coordinated
+              , tenures_missed = peer_statistics.tenures_missed + EXCLUDED.tenures_missed
+              , rounds_completed = peer_statistics.rounds_completed + EXCLUDED.rounds_completed
+              , nonce_timeouts = peer_statistics.nonce_timeouts + EXCLUDED.nonce_timeouts
+              , signature_share_timeouts = peer_statistics.signature_share_timeouts + EXCLUDED.signature_share_timeouts
+              , invalid_messages = peer_statistics.invalid_messages + EXCLUDED.invalid_messages
+              , updated_at = NOW()
+            "#,
+        )
+        .bind(pub_key)
+        .bind(increment(model::PeerEvent::TenureCoordinated))
+        .bind(increment(model::PeerEvent::TenureMissed))
+        .bind(increment(model::PeerEvent::RoundCompleted))
+        .bind(increment(model::PeerEvent::NonceTimeout))
+        .bind(increment(model::PeerEvent::SignatureShareTimeout))
+        .bind(increment(model::PeerEvent::InvalidMessage))
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
+
+    async fn write_signer_set_approval<'e, E>(
+        executor: &'e mut E,
+        approval: &model::SignerSetApproval,
+    ) -> Result<(), Error>
+    where
+        &'e mut E: sqlx::PgExecutor<'e>,
+    {
+        sqlx::query(
+            r#"
+            INSERT INTO sbtc_signer.signer_set_approvals (
+                approver_public_key
+              , signer_set_public_keys
+              , signatures_required
+              , bitcoin_chain_tip
+            )
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (approver_public_key) DO UPDATE SET
+                signer_set_public_keys = EXCLUDED.signer_set_public_keys
+              , signatures_required = EXCLUDED.signatures_required
+              , bitcoin_chain_tip = EXCLUDED.bitcoin_chain_tip
+              , updated_at = NOW()
+            "#,
+        )
+        .bind(approval.approver_public_key)
+        .bind(&approval.signer_set_public_keys)
+        .bind(i32::from(approval.signatures_required))
+        .bind(approval.bitcoin_chain_tip)
+        .execute(executor)
+        .await
+        .map_err(Error::SqlxQuery)?;
+
+        Ok(())
+    }
 }
 
 impl DbWrite for PgStore {
@@ -1164,6 +1253,23 @@ impl DbWrite for PgStore {
         )
         .await
     }
+
+    async fn record_peer_event(
+        &self,
+        pub_key: &PublicKey,
+        event: model::PeerEvent,
+        count: u32,
+    ) -> Result<(), Error> {
+        PgWrite::record_peer_event(self.get_connection().await?.as_mut(), pub_key, event, count)
+            .await
+    }
+
+    async fn write_signer_set_approval(
+        &self,
+        approval: &model::SignerSetApproval,
+    ) -> Result<(), Error> {
+        PgWrite::write_signer_set_approval(self.get_connection().await?.as_mut(), approval).await
+    }
 }
 
 impl DbWrite for PgTransaction<'_> {
@@ -1337,4 +1443,22 @@ impl DbWrite for PgTransaction<'_> {
         let mut tx = self.tx.lock().await;
         PgWrite::update_peer_connection(tx.as_mut(), pub_key, peer_id, address).await
     }
+
+    async fn record_peer_event(
+        &self,
+        pub_key: &PublicKey,
+        event: model::PeerEvent,
+        count: u32,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::record_peer_event(tx.as_mut(), pub_key, event, count).await
+    }
+
+    async fn write_signer_set_approval(
+        &self,
+        approval: &model::SignerSetApproval,
+    ) -> Result<(), Error> {
+        let mut tx = self.tx.lock().await;
+        PgWrite::write_signer_set_approval(tx.as_mut(), approval).await
+    }
 }
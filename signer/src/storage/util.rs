@@ -1,11 +1,18 @@
 //! General utilities for the storage.
 
+use std::collections::HashMap;
 use std::collections::HashSet;
+use std::collections::hash_map::Entry;
+use std::sync::Mutex;
+use std::time::Duration;
+use std::time::Instant;
 
 use crate::bitcoin::utxo::SignerUtxo;
 use crate::error::Error;
 use crate::keys::PublicKey;
 use crate::keys::SignerScriptPubKey as _;
+use crate::storage::DbWrite;
+use crate::storage::model::PeerEvent;
 
 /// Given the sbtc txs in a block, returns the `aggregate_key` utxo (if there's exactly one)
 pub fn get_utxo(
@@ -44,3 +51,168 @@ pub fn get_utxo(
         _ => Err(Error::TooManySignerUtxos),
     }
 }
+
+/// Count occurrences of an event in the statistics of the given signer.
+/// The statistics are informational, so failing to write them is only
+/// logged.
+pub async fn record_peer_event(
+    db: &impl DbWrite,
+    public_key: &PublicKey,
+    event: PeerEvent,
+    count: u32,
+) {
+    if let Err(error) = db.record_peer_event(public_key, event, count).await {
+        tracing::warn!(%error, %public_key, %event, count, "could not record a peer event");
+    }
+}
+
+/// Count an occurrence of an event that a peer can trigger at will, such
+/// as sending an invalid message, in the statistics of the given signer.
+/// The occurrences are written at most once per interval of the throttle,
+/// so that a misbehaving peer does not cause a database write per event.
+pub async fn record_throttled_peer_event(
+    db: &impl DbWrite,
+    throttle: &PeerEventThrottle,
+    public_key: &PublicKey,
+    event: PeerEvent,
+) {
+    if let Some(count) = throttle.add(*public_key, event) {
+        record_peer_event(db, public_key, event, count).await;
+    }
+}
+
+/// Write the occurrences of throttled peer events that have been counted
+/// but not written yet. This is done periodically and on shutdown, so that
+/// the occurrences that no later event would write are not lost.
+pub async fn flush_throttled_peer_events(db: &impl DbWrite, throttle: &PeerEventThrottle) {
+    for (public_key, event, count) in throttle.take_pending() {
+        record_peer_event(db, &public_key, event, count).await;
+    }
+}
+
+/// Limits how often occurrences of the events of a peer are written to the
+/// database. The first occurrence of an event is written right away. Later
+/// occurrences are counted, and written together with the first occurrence
+/// at least one interval after the last write. The counted occurrences
+/// that no later occurrence writes are written by
+/// [`flush_throttled_peer_events`].
+#[derive(Debug)]
+pub struct PeerEventThrottle {
+    interval: Duration,
+    pending: Mutex<HashMap<(PublicKey, PeerEvent), PendingPeerEvents>>,
+}
+
+/// The occurrences of an event that have not been written yet.
+#[derive(Debug)]
+struct PendingPeerEvents {
+    count: u32,
+    written_at: Instant,
+}
+
+impl PeerEventThrottle {
+    /// Create a throttle that writes the occurrences of an event at most
+    /// once per the given interval.
+    pub fn new(interval: Duration) -> Self {
+        Self {
+            interval,
+            pending: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Count an occurrence of the event. Returns the number of occurrences
+    /// to write now, if it is time to write them.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn add(&self, public_key: PublicKey, event: PeerEvent) -> Option<u32> {
+        let now = Instant::now();
+        let mut pending = self
+            .pending
+            .lock()
+            .expect("BUG: Failed to acquire peer event throttle lock");
+        match pending.entry((public_key, event)) {
+            Entry::Vacant(entry) => {
+                entry.insert(PendingPeerEvents { count: 0, written_at: now });
+                Some(1)
+            }
+            Entry::Occupied(mut entry) => {
+                let pending = entry.get_mut();
+                pending.count = pending.count.saturating_add(1);
+                if now.duration_since(pending.written_at) < self.interval {
+                    return None;
+                }
+                pending.written_at = now;
+                Some(std::mem::take(&mut pending.count))
+            }
+        }
+    }
+
+    /// Take the occurrences that have been counted but not written yet,
+    /// as if they were written now.
+    #[allow(clippy::unwrap_in_result)]
+    pub fn take_pending(&self) -> Vec<(PublicKey, PeerEvent, u32)> {
+        let now = Instant::now();
+        let mut pending = self
+            .pending
+            .lock()
+            .expect("BUG: Failed to acquire peer event throttle lock");
+        pending
+            .iter_mut()
+            .filter(|(_, pending)| pending.count > 0)
+            .map(|(&(public_key, event), pending)| {
+                pending.written_at = now;
+                (public_key, event, std::mem::take(&mut pending.count))
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use fake::Fake as _;
+    use fake::Faker;
+
+    use crate::testing::get_rng;
+
+    use super::*;
+
+    #[test]
+    fn peer_event_throttle_batches_occurrences() {
+        let rng = &mut get_rng();
+        let throttle = PeerEventThrottle::new(Duration::from_millis(50));
+        let public_key: PublicKey = Faker.fake_with_rng(rng);
+        let event = PeerEvent::InvalidMessage;
+
+        // The first occurrence is written right away, the next ones wait
+        // for the interval to pass.
+        assert_eq!(throttle.add(public_key, event), Some(1));
+        assert_eq!(throttle.add(public_key, event), None);
+        assert_eq!(throttle.add(public_key, event), None);
+
+        // Other peers and events are throttled separately.
+        let other_public_key: PublicKey = Faker.fake_with_rng(rng);
+        assert_eq!(throttle.add(other_public_key, event), Some(1));
+        assert_eq!(throttle.add(public_key, PeerEvent::NonceTimeout), Some(1));
+
+        std::thread::sleep(Duration::from_millis(60));
+        assert_eq!(throttle.add(public_key, event), Some(3));
+        assert_eq!(throttle.add(public_key, event), None);
+    }
+
+    #[test]
+    fn peer_event_throttle_hands_over_pending_occurrences() {
+        let rng = &mut get_rng();
+        let throttle = PeerEventThrottle::new(Duration::from_secs(60));
+        let public_key: PublicKey = Faker.fake_with_rng(rng);
+        let event = PeerEvent::InvalidMessage;
+
+        assert_eq!(throttle.add(public_key, event), Some(1));
+        assert_eq!(throttle.add(public_key, event), None);
+        assert_eq!(throttle.add(public_key, event), None);
+        assert_eq!(throttle.add(public_key, PeerEvent::NonceTimeout), Some(1));
+
+        // Only the occurrences that were held back are pending, and they
+        // are only handed over once.
+        assert_eq!(throttle.take_pending(), vec![(public_key, event, 2)]);
+        assert!(throttle.take_pending().is_empty());
+        assert_eq!(throttle.add(public_key, event), None);
+    }
+}
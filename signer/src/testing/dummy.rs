@@ -65,6 +65,7 @@ use crate::keys::PublicKeyXOnly;
 use crate::keys::SignerScriptPubKey as _;
 use crate::message::BitcoinPreSignAck;
 use crate::message::BitcoinPreSignRequest;
+use crate::message::DecisionSyncRequest;
 use crate::message::SignerMessage;
 use crate::stacks::contracts::AcceptWithdrawalV1;
 use crate::stacks::contracts::CompleteDepositV1;
@@ -784,6 +785,13 @@ impl fake::Dummy<fake::Faker> for BitcoinPreSignAck {
     }
 }
 
+impl fake::Dummy<fake::Faker> for DecisionSyncRequest {
+    fn dummy_with_rng<R: rand::RngCore + ?Sized>(config: &fake::Faker, rng: &mut R) -> Self {
+        let TxRequestIds { deposits, withdrawals } = config.fake_with_rng(rng);
+        DecisionSyncRequest { deposits, withdrawals }
+    }
+}
+
 impl fake::Dummy<fake::Faker> for model::Timestamp {
     fn dummy_with_rng<R: rand::RngCore + ?Sized>(_: &fake::Faker, rng: &mut R) -> Self {
         // The PostgreSQL epoch is 2000-01-01 00:00:00 UTC
@@ -50,6 +50,8 @@ impl fake::Dummy<fake::Faker> for message::Payload {
             dummy_payload::<message::StacksTransactionSignature, _>,
             dummy_payload::<message::WstsMessage, _>,
             dummy_payload::<message::BitcoinPreSignRequest, _>,
+            dummy_payload::<message::SignerSetProposal, _>,
+            dummy_payload::<message::DecisionSyncRequest, _>,
         ];
         variants.choose(rng).unwrap()(config, rng)
     }
@@ -12,6 +12,7 @@ pub mod message;
 pub mod network;
 pub mod request_decider;
 pub mod stacks;
+pub mod stacks_node;
 pub mod storage;
 pub mod transaction_coordinator;
 pub mod transaction_signer;
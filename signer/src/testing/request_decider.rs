@@ -4,6 +4,7 @@ use std::collections::BTreeSet;
 use std::time::Duration;
 
 use crate::context::Context;
+use crate::context::P2PEvent;
 use crate::context::RequestDeciderEvent;
 use crate::context::SignerEvent;
 use crate::context::SignerSignal;
@@ -440,6 +441,115 @@ where
         }
     }
 
+    /// Assert that the request decider asks a peer that it connects to for
+    /// the decisions that it is missing, and that it stores the decisions
+    /// that the peer sends back.
+    pub async fn assert_should_sync_missing_decisions_from_connected_peers(self) {
+        let mut rng = get_rng();
+        let network = WanNetwork::default();
+        let signer_info = testing::wsts::generate_signer_info(&mut rng, 2);
+        let signer_set = &signer_info.first().unwrap().signer_public_keys;
+        let test_data = self.generate_test_data(&mut rng, signer_set);
+
+        // Each signer needs its own private key in its config, since that
+        // is how the network knows who direct messages are for.
+        let mut handles = Vec::new();
+        for info in signer_info.iter() {
+            let private_key = info.signer_private_key;
+            let ctx = TestContext::builder()
+                .with_in_memory_storage()
+                .with_mocked_clients()
+                .modify_settings(|settings| settings.signer.private_key = private_key)
+                .build();
+
+            test_data.write_to(&ctx.get_storage_mut()).await;
+            let chain_tip_ref = ctx
+                .get_storage()
+                .get_bitcoin_canonical_chain_tip_ref()
+                .await
+                .unwrap()
+                .unwrap();
+            ctx.state().set_bitcoin_chain_tip(chain_tip_ref);
+            ctx.state().update_current_signer_set(signer_set.clone());
+
+            let net = network.connect(&ctx);
+            let event_loop_harness = RequestDeciderEventLoopHarness::create(
+                ctx,
+                net,
+                self.context_window,
+                self.deposit_decisions_retry_window,
+                self.withdrawal_decisions_retry_window,
+                private_key,
+            );
+
+            let mut handle = event_loop_harness.start();
+            handle
+                .wait_for_events(
+                    RequestDeciderEvent::EventLoopStarted,
+                    1,
+                    Duration::from_secs(1),
+                )
+                .await
+                .expect("timed out waiting for the event loop to start");
+            handles.push(handle);
+        }
+
+        let peer = handles.pop().unwrap();
+        let mut requester = handles.pop().unwrap();
+        let peer_public_key = PublicKey::from_private_key(&signer_info[1].signer_private_key);
+
+        // The peer decided on all the deposit requests in the context
+        // window while the requester was not around to hear about it.
+        let peer_db = peer.context.get_storage_mut();
+        let chain_tip = peer.context.state().bitcoin_chain_tip().unwrap().block_hash;
+        let deposit_requests = peer_db
+            .get_pending_deposit_requests(&chain_tip, self.context_window, &peer_public_key)
+            .await
+            .unwrap();
+        assert!(!deposit_requests.is_empty());
+
+        for request in deposit_requests.iter() {
+            let decision = model::DepositSigner {
+                txid: request.txid,
+                output_index: request.output_index,
+                signer_pub_key: peer_public_key,
+                can_accept: true,
+                can_sign: true,
+            };
+            peer_db
+                .write_deposit_signer_decision(&decision)
+                .await
+                .unwrap();
+        }
+
+        requester
+            .context
+            .signal(P2PEvent::PeerConnected(peer_public_key.into()).into())
+            .expect("failed to send signal");
+
+        let msg = RequestDeciderEvent::ReceivedDepositDecision;
+        requester
+            .wait_for_events(msg, deposit_requests.len() as u16, Duration::from_secs(10))
+            .await
+            .expect("timed out waiting for the synced decisions");
+
+        let requester_db = requester.context.get_storage();
+        for request in deposit_requests.iter() {
+            let decisions = requester_db
+                .get_deposit_signers(&request.txid, request.output_index)
+                .await
+                .unwrap();
+            assert!(
+                decisions
+                    .iter()
+                    .any(|decision| decision.signer_pub_key == peer_public_key)
+            );
+        }
+
+        requester.abort();
+        peer.abort();
+    }
+
     async fn write_test_data<S>(storage: &S, test_data: &TestData)
     where
         S: DbWrite,
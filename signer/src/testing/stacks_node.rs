@@ -0,0 +1,866 @@
+//! A lightweight mock of the RPC API of a stacks node.
+//!
+//! The [`MockStacksNode`] is an HTTP server serving the endpoints that
+//! the [`StacksClient`] uses from an in-memory [`MockStacksNodeState`].
+//! Contract state lives in a [`ClarityVm`], which runs the sBTC contracts
+//! on an in-memory Clarity database. Read-only function calls, data
+//! variables and map entries are read from the VM, and submitted contract
+//! calls and contract deployments are executed in it. Accounts, blocks
+//! and the chain info are whatever the test stored in the state. Like a
+//! stacks node, the mock can also push `POST /new_block` events to event
+//! observers.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::sync::Mutex;
+use std::sync::MutexGuard;
+
+use axum::Json;
+use axum::Router;
+use axum::body::Bytes;
+use axum::extract::Path;
+use axum::extract::State;
+use axum::http::StatusCode;
+use axum::response::IntoResponse;
+use axum::response::Response;
+use axum::routing::get;
+use axum::routing::post;
+use blockstack_lib::chainstate::burn::ConsensusHash;
+use blockstack_lib::chainstate::nakamoto::NakamotoBlock;
+use blockstack_lib::chainstate::stacks::StacksTransaction;
+use blockstack_lib::chainstate::stacks::TransactionPayload;
+use blockstack_lib::codec::StacksMessageCodec as _;
+use blockstack_lib::net::api::getinfo::RPCPeerInfoData;
+use blockstack_lib::net::api::getpoxinfo::RPCPoxInfoData;
+use blockstack_lib::net::api::getsortition::SortitionInfo;
+use blockstack_lib::net::api::gettenureinfo::RPCGetTenureInfo;
+use blockstack_lib::net::api::postfeerate::RPCFeeEstimateResponse;
+use blockstack_lib::types::chainstate::StacksAddress;
+use blockstack_lib::types::chainstate::StacksBlockId;
+use clarity::consts::CHAIN_ID_TESTNET;
+use clarity::types::StacksEpochId;
+use clarity::vm::ClarityVersion;
+use clarity::vm::SymbolicExpression;
+use clarity::vm::Value;
+use clarity::vm::ast::ASTRules;
+use clarity::vm::contexts::OwnedEnvironment;
+use clarity::vm::database::MemoryBackingStore;
+use clarity::vm::errors::Error as ClarityError;
+use clarity::vm::events::StacksTransactionEvent;
+use clarity::vm::types::PrincipalData;
+use clarity::vm::types::QualifiedContractIdentifier;
+use tokio::task::JoinHandle;
+use url::Url;
+
+use crate::stacks::api::RejectionReason;
+use crate::stacks::api::StacksClient;
+use crate::stacks::api::TxRejection;
+use crate::stacks::contracts::SMART_CONTRACTS;
+
+/// The epoch that the Clarity VM of the mock stacks node runs in.
+const EPOCH: StacksEpochId = StacksEpochId::Epoch30;
+
+/// An account known to the mock stacks node.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MockAccount {
+    /// The balance of the account in micro-STX.
+    pub balance: u128,
+    /// The next nonce of the account.
+    pub nonce: u64,
+}
+
+/// A Clarity VM backed by an in-memory database.
+///
+/// Every call gets a fresh [`OwnedEnvironment`] over the database, and
+/// the changes made by a call are committed to the database when it
+/// succeeds. The database has no headers or burnchain state, so
+/// `get-burn-block-info?` and `burn-block-height` do not know about any
+/// bitcoin block. This means that the `complete-deposit-wrapper` and
+/// `accept-withdrawal-request` calls fail their burn hash check, and the
+/// `initiate-withdrawal-request` call fails, while calls like
+/// `rotate-keys-wrapper` and `reject-withdrawal-request` work as they do
+/// on a stacks node.
+pub struct ClarityVm {
+    /// The database holding the state of the deployed contracts.
+    store: MemoryBackingStore,
+}
+
+impl std::fmt::Debug for ClarityVm {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ClarityVm").finish_non_exhaustive()
+    }
+}
+
+impl Default for ClarityVm {
+    fn default() -> Self {
+        let mut store = MemoryBackingStore::new();
+
+        let mut db = store.as_clarity_db();
+        db.begin();
+        db.set_clarity_epoch_version(EPOCH).unwrap();
+        db.commit().unwrap();
+
+        Self { store }
+    }
+}
+
+impl ClarityVm {
+    /// An environment for executing code against the database of the VM.
+    /// Execution is free, so no cost contract needs to be deployed.
+    fn env(&mut self) -> OwnedEnvironment<'_, '_> {
+        OwnedEnvironment::new_free(false, CHAIN_ID_TESTNET, self.store.as_clarity_db(), EPOCH)
+    }
+
+    /// Deploy the contract with the given identifier and source.
+    pub fn deploy(
+        &mut self,
+        contract_id: QualifiedContractIdentifier,
+        version: ClarityVersion,
+        source: &str,
+    ) -> Result<Vec<StacksTransactionEvent>, ClarityError> {
+        let (_, _, events) = self.env().initialize_versioned_contract(
+            contract_id,
+            version,
+            source,
+            None,
+            ASTRules::PrecheckSize,
+        )?;
+        Ok(events)
+    }
+
+    /// Call the public or read-only function of the given contract as the
+    /// given sender. The changes made by the call are kept if the function
+    /// returns an `ok` response and rolled back otherwise.
+    pub fn call(
+        &mut self,
+        sender: PrincipalData,
+        sponsor: Option<PrincipalData>,
+        contract_id: QualifiedContractIdentifier,
+        fn_name: &str,
+        args: &[Value],
+    ) -> Result<(Value, Vec<StacksTransactionEvent>), ClarityError> {
+        let args: Vec<SymbolicExpression> = args
+            .iter()
+            .cloned()
+            .map(SymbolicExpression::atom_value)
+            .collect();
+
+        let (value, _, events) =
+            self.env()
+                .execute_transaction(sender, sponsor, contract_id, fn_name, &args)?;
+        Ok((value, events))
+    }
+
+    /// The value of the given data variable of the given contract.
+    pub fn data_var(
+        &mut self,
+        contract_id: &QualifiedContractIdentifier,
+        var_name: &str,
+    ) -> Result<Value, ClarityError> {
+        let program = format!("(var-get {var_name})");
+        let (value, _, _) = self.env().eval_read_only(contract_id, &program)?;
+        Ok(value)
+    }
+
+    /// The entry of the given map of the given contract at the given key,
+    /// as an optional value.
+    pub fn map_entry(
+        &mut self,
+        contract_id: &QualifiedContractIdentifier,
+        map_name: &str,
+        key: &Value,
+    ) -> Result<Value, ClarityError> {
+        let sender = PrincipalData::from(contract_id.issuer.clone());
+        let (value, _, _) = self.env().execute_in_env(sender, None, None, |env| {
+            let epoch = env.global_context.epoch_id;
+            env.global_context.database.fetch_entry_unknown_descriptor(
+                contract_id,
+                map_name,
+                key,
+                &epoch,
+            )
+        })?;
+        Ok(value)
+    }
+}
+
+/// A transaction accepted by the mock stacks node, along with the outcome
+/// of executing it.
+#[derive(Debug, Clone)]
+pub struct MockTxReceipt {
+    /// The submitted transaction.
+    pub tx: StacksTransaction,
+    /// The value returned by the contract call, or `(ok true)` for other
+    /// transactions. The error is the reason the VM failed to execute the
+    /// transaction, in which case a stacks node would have mined it as
+    /// aborted.
+    pub result: Result<Value, String>,
+    /// The events emitted while executing the transaction.
+    pub events: Vec<StacksTransactionEvent>,
+}
+
+/// The in-memory state backing the mock stacks node.
+#[derive(Debug, Default)]
+pub struct MockStacksNodeState {
+    /// The accounts, keyed by their address. Unknown accounts are
+    /// reported with a zero balance and nonce, like a stacks node does.
+    pub accounts: HashMap<String, MockAccount>,
+    /// The Clarity VM holding the state of the deployed contracts.
+    pub vm: ClarityVm,
+    /// The source of the deployed contracts, keyed by deployer address
+    /// and contract name.
+    pub contract_sources: HashMap<(String, String), String>,
+    /// The Nakamoto blocks known to the node.
+    pub blocks: HashMap<StacksBlockId, NakamotoBlock>,
+    /// The sortitions known to the node, keyed by consensus hash.
+    pub sortitions: HashMap<ConsensusHash, SortitionInfo>,
+    /// The response to `GET /v3/tenures/info`.
+    pub tenure_info: Option<RPCGetTenureInfo>,
+    /// The response to `GET /v2/pox`.
+    pub pox_info: Option<RPCPoxInfoData>,
+    /// The response to `GET /v2/info`.
+    pub node_info: Option<RPCPeerInfoData>,
+    /// The response to `POST /v2/fees/transaction`. The node fails to
+    /// estimate fees if this is not set.
+    pub fee_estimate: Option<RPCFeeEstimateResponse>,
+    /// The transactions accepted through `POST /v2/transactions`, in the
+    /// order in which they were submitted.
+    pub transactions: Vec<MockTxReceipt>,
+    /// The endpoints of the event observers that `POST /new_block`
+    /// events are pushed to.
+    pub observers: Vec<Url>,
+}
+
+impl MockStacksNodeState {
+    /// Deploy the contract with the given name and source from the given
+    /// deployer.
+    pub fn deploy_contract(
+        &mut self,
+        deployer: &StacksAddress,
+        contract_name: &str,
+        version: ClarityVersion,
+        source: &str,
+    ) -> Result<Vec<StacksTransactionEvent>, ClarityError> {
+        let contract_id =
+            QualifiedContractIdentifier::parse(&format!("{deployer}.{contract_name}"))?;
+        let events = self.vm.deploy(contract_id, version, source)?;
+
+        let key = (deployer.to_string(), contract_name.to_string());
+        self.contract_sources.insert(key, source.to_string());
+        Ok(events)
+    }
+
+    /// Deploy the sBTC contracts from the given deployer, the same way
+    /// that the signers do.
+    pub fn deploy_sbtc_contracts(&mut self, deployer: &StacksAddress) {
+        for contract in SMART_CONTRACTS {
+            self.deploy_contract(
+                deployer,
+                contract.contract_name(),
+                ClarityVersion::Clarity3,
+                contract.contract_body(),
+            )
+            .unwrap();
+        }
+    }
+
+    /// Execute the given transaction in the VM.
+    fn execute_tx(
+        &mut self,
+        tx: &StacksTransaction,
+    ) -> Result<(Value, Vec<StacksTransactionEvent>), ClarityError> {
+        let origin = tx.origin_address();
+
+        match &tx.payload {
+            TransactionPayload::ContractCall(call) => {
+                let contract_id = QualifiedContractIdentifier::new(
+                    call.address.into(),
+                    call.contract_name.clone(),
+                );
+                let sender = PrincipalData::from(origin);
+                let sponsor = tx.sponsor_address().map(PrincipalData::from);
+                self.vm.call(
+                    sender,
+                    sponsor,
+                    contract_id,
+                    &call.function_name,
+                    &call.function_args,
+                )
+            }
+            TransactionPayload::SmartContract(contract, version) => {
+                let version = version.unwrap_or(ClarityVersion::Clarity3);
+                let source = contract.code_body.to_string();
+                let events = self.deploy_contract(&origin, &contract.name, version, &source)?;
+                Ok((Value::okay_true(), events))
+            }
+            _ => Ok((Value::okay_true(), Vec::new())),
+        }
+    }
+
+    /// Add a Nakamoto block to the node.
+    pub fn add_block(&mut self, block: NakamotoBlock) {
+        self.blocks.insert(block.block_id(), block);
+    }
+
+    /// The blocks of the tenure of the given block, starting with that
+    /// block and followed by its ancestors within the same tenure.
+    fn tenure_blocks(&self, block_id: &StacksBlockId) -> Vec<&NakamotoBlock> {
+        let mut blocks = Vec::new();
+        let mut next = self.blocks.get(block_id);
+
+        while let Some(block) = next {
+            blocks.push(block);
+            next = self
+                .blocks
+                .get(&block.header.parent_block_id)
+                .filter(|parent| parent.header.consensus_hash == block.header.consensus_hash);
+        }
+
+        blocks
+    }
+}
+
+/// The shared state of the mock stacks node.
+type SharedState = Arc<Mutex<MockStacksNodeState>>;
+
+/// A mock stacks node serving its RPC API on a local port. The server is
+/// stopped when this is dropped.
+#[derive(Debug)]
+pub struct MockStacksNode {
+    /// The base URL of the RPC API of the node.
+    url: Url,
+    /// The state backing the node.
+    state: SharedState,
+    /// The task running the server.
+    handle: JoinHandle<()>,
+}
+
+impl MockStacksNode {
+    /// Start a mock stacks node with an empty state on a random local
+    /// port.
+    pub async fn start() -> Self {
+        Self::start_with_state(MockStacksNodeState::default()).await
+    }
+
+    /// Start a mock stacks node with the given state on a random local
+    /// port.
+    pub async fn start_with_state(state: MockStacksNodeState) -> Self {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = Url::parse(&format!("http://{}", listener.local_addr().unwrap())).unwrap();
+
+        let state = Arc::new(Mutex::new(state));
+        let app = router().with_state(state.clone());
+        let handle = tokio::spawn(async move {
+            axum::serve(listener, app).await.unwrap();
+        });
+
+        Self { url, state, handle }
+    }
+
+    /// The base URL of the RPC API of the node.
+    pub fn url(&self) -> &Url {
+        &self.url
+    }
+
+    /// A client for the RPC API of the node.
+    pub fn client(&self) -> StacksClient {
+        StacksClient::new(self.url.clone()).unwrap()
+    }
+
+    /// Lock the state backing the node. The guard must not be held across
+    /// requests to the node.
+    pub fn state(&self) -> MutexGuard<'_, MockStacksNodeState> {
+        self.state.lock().unwrap()
+    }
+
+    /// Push the given `POST /new_block` event body to every event
+    /// observer of the node, returning the status code of each of them.
+    pub async fn push_new_block(&self, body: &str) -> Vec<StatusCode> {
+        let observers = self.state().observers.clone();
+        let client = reqwest::Client::new();
+
+        let mut statuses = Vec::with_capacity(observers.len());
+        for observer in observers {
+            let response = client
+                .post(observer.join("new_block").unwrap())
+                .header("content-type", "application/json")
+                .body(body.to_string())
+                .send()
+                .await
+                .unwrap();
+            statuses.push(StatusCode::from_u16(response.status().as_u16()).unwrap());
+        }
+
+        statuses
+    }
+}
+
+impl Drop for MockStacksNode {
+    fn drop(&mut self) {
+        self.handle.abort();
+    }
+}
+
+/// The routes of the RPC API of the mock stacks node.
+fn router() -> Router<SharedState> {
+    Router::new()
+        .route("/v2/info", get(get_node_info))
+        .route("/v2/pox", get(get_pox_info))
+        .route("/v2/accounts/{principal}", get(get_account))
+        .route(
+            "/v2/contracts/call-read/{principal}/{contract}/{function}",
+            post(call_read),
+        )
+        .route(
+            "/v2/contracts/source/{principal}/{contract}",
+            get(get_contract_source),
+        )
+        .route(
+            "/v2/data_var/{principal}/{contract}/{var}",
+            get(get_data_var),
+        )
+        .route(
+            "/v2/map_entry/{principal}/{contract}/{map}",
+            post(get_map_entry),
+        )
+        .route("/v2/transactions", post(submit_tx))
+        .route("/v2/fees/transaction", post(get_fee_estimate))
+        .route("/v3/blocks/{block_id}", get(get_block))
+        .route("/v3/tenures/info", get(get_tenure_info))
+        .route("/v3/tenures/{block_id}", get(get_tenure))
+        .route(
+            "/v3/sortitions/consensus/{consensus_hash}",
+            get(get_sortition_info),
+        )
+}
+
+/// Respond with the given value as JSON, or with a 404 if there is none.
+fn json_or_not_found<T: serde::Serialize>(value: Option<T>) -> Response {
+    match value {
+        Some(value) => Json(value).into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+/// Hex encode a Clarity value the way the stacks node does.
+fn clarity_hex(value: &Value) -> String {
+    format!("0x{}", value.serialize_to_hex().unwrap())
+}
+
+async fn get_node_info(State(state): State<SharedState>) -> Response {
+    json_or_not_found(state.lock().unwrap().node_info.clone())
+}
+
+async fn get_pox_info(State(state): State<SharedState>) -> Response {
+    json_or_not_found(state.lock().unwrap().pox_info.clone())
+}
+
+async fn get_tenure_info(State(state): State<SharedState>) -> Response {
+    json_or_not_found(state.lock().unwrap().tenure_info.clone())
+}
+
+async fn get_account(
+    State(state): State<SharedState>,
+    Path(principal): Path<String>,
+) -> Json<serde_json::Value> {
+    let account = state
+        .lock()
+        .unwrap()
+        .accounts
+        .get(&principal)
+        .copied()
+        .unwrap_or_default();
+
+    Json(serde_json::json!({
+        "balance": format!("0x{:032x}", account.balance),
+        "locked": format!("0x{:032x}", 0),
+        "unlock_height": 0,
+        "nonce": account.nonce,
+    }))
+}
+
+/// The body of a `POST /v2/contracts/call-read` request.
+#[derive(Debug, serde::Deserialize)]
+struct CallReadBody {
+    /// The address of the sender of the call.
+    sender: String,
+    /// The hex encoded arguments of the function.
+    arguments: Vec<String>,
+}
+
+/// The identifier of the contract in the path of a request, or a 400
+/// response if it is invalid.
+fn contract_id(principal: &str, contract: &str) -> Result<QualifiedContractIdentifier, Response> {
+    QualifiedContractIdentifier::parse(&format!("{principal}.{contract}"))
+        .map_err(|_| StatusCode::BAD_REQUEST.into_response())
+}
+
+/// Respond with the hex encoded value as the data of the response, like
+/// the data variable and map entry endpoints of a stacks node do, or with
+/// a 404 if the VM could not produce it.
+fn data_or_not_found(value: Result<Value, ClarityError>) -> Response {
+    json_or_not_found(
+        value
+            .ok()
+            .map(|value| serde_json::json!({ "data": clarity_hex(&value) })),
+    )
+}
+
+async fn call_read(
+    State(state): State<SharedState>,
+    Path((principal, contract, function)): Path<(String, String, String)>,
+    Json(body): Json<CallReadBody>,
+) -> Response {
+    let contract_id = match contract_id(&principal, &contract) {
+        Ok(contract_id) => contract_id,
+        Err(response) => return response,
+    };
+    let Ok(sender) = PrincipalData::parse(&body.sender) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+    let Ok(args) = body
+        .arguments
+        .iter()
+        .map(|arg| Value::try_deserialize_hex_untyped(arg))
+        .collect::<Result<Vec<Value>, _>>()
+    else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    let mut state = state.lock().unwrap();
+    match state.vm.call(sender, None, contract_id, &function, &args) {
+        Ok((result, _)) => Json(serde_json::json!({
+            "okay": true,
+            "result": clarity_hex(&result),
+        }))
+        .into_response(),
+        Err(error) => Json(serde_json::json!({
+            "okay": false,
+            "cause": error.to_string(),
+        }))
+        .into_response(),
+    }
+}
+
+async fn get_contract_source(
+    State(state): State<SharedState>,
+    Path((principal, contract)): Path<(String, String)>,
+) -> Response {
+    let state = state.lock().unwrap();
+    let source = state.contract_sources.get(&(principal, contract));
+    json_or_not_found(source.map(|source| {
+        serde_json::json!({
+            "source": source,
+            "publish_height": 0,
+        })
+    }))
+}
+
+async fn get_data_var(
+    State(state): State<SharedState>,
+    Path((principal, contract, var)): Path<(String, String, String)>,
+) -> Response {
+    let contract_id = match contract_id(&principal, &contract) {
+        Ok(contract_id) => contract_id,
+        Err(response) => return response,
+    };
+
+    let mut state = state.lock().unwrap();
+    data_or_not_found(state.vm.data_var(&contract_id, &var))
+}
+
+async fn get_map_entry(
+    State(state): State<SharedState>,
+    Path((principal, contract, map)): Path<(String, String, String)>,
+    Json(map_key): Json<String>,
+) -> Response {
+    let contract_id = match contract_id(&principal, &contract) {
+        Ok(contract_id) => contract_id,
+        Err(response) => return response,
+    };
+    let Ok(map_key) = Value::try_deserialize_hex_untyped(&map_key) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    let mut state = state.lock().unwrap();
+    data_or_not_found(state.vm.map_entry(&contract_id, &map, &map_key))
+}
+
+async fn submit_tx(State(state): State<SharedState>, body: Bytes) -> Response {
+    let Ok(tx) = StacksTransaction::consensus_deserialize(&mut body.as_ref()) else {
+        return (StatusCode::BAD_REQUEST, "Failed to deserialize transaction").into_response();
+    };
+
+    let mut state = state.lock().unwrap();
+    let account = state
+        .accounts
+        .entry(tx.origin_address().to_string())
+        .or_default();
+
+    if tx.get_origin_nonce() != account.nonce {
+        let rejection = TxRejection {
+            error: "transaction rejection".to_string(),
+            reason: RejectionReason::BadNonce,
+            reason_data: Some(serde_json::json!({
+                "expected": account.nonce,
+                "actual": tx.get_origin_nonce(),
+            })),
+            txid: tx.txid(),
+        };
+        return (StatusCode::BAD_REQUEST, Json(rejection)).into_response();
+    }
+    account.nonce += 1;
+
+    // The mock mines the transaction right away. A transaction that the
+    // VM fails to execute is still mined, like a stacks node does with
+    // transactions that abort at runtime.
+    let txid = tx.txid();
+    let (result, events) = match state.execute_tx(&tx) {
+        Ok((value, events)) => (Ok(value), events),
+        Err(error) => (Err(error.to_string()), Vec::new()),
+    };
+    state
+        .transactions
+        .push(MockTxReceipt { tx, result, events });
+    Json(txid).into_response()
+}
+
+async fn get_fee_estimate(State(state): State<SharedState>) -> Response {
+    match state.lock().unwrap().fee_estimate.clone() {
+        Some(estimate) => Json(estimate).into_response(),
+        None => (
+            StatusCode::BAD_REQUEST,
+            "Estimator RPC endpoint failed to estimate fees for tx.",
+        )
+            .into_response(),
+    }
+}
+
+async fn get_block(State(state): State<SharedState>, Path(block_id): Path<String>) -> Response {
+    let Ok(block_id) = StacksBlockId::from_hex(&block_id) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    match state.lock().unwrap().blocks.get(&block_id) {
+        Some(block) => block.serialize_to_vec().into_response(),
+        None => StatusCode::NOT_FOUND.into_response(),
+    }
+}
+
+async fn get_tenure(State(state): State<SharedState>, Path(block_id): Path<String>) -> Response {
+    let Ok(block_id) = StacksBlockId::from_hex(&block_id) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    let state = state.lock().unwrap();
+    let blocks = state.tenure_blocks(&block_id);
+    if blocks.is_empty() {
+        return StatusCode::NOT_FOUND.into_response();
+    }
+
+    // The response is the concatenation of the consensus serialized
+    // blocks, starting with the requested one.
+    blocks
+        .into_iter()
+        .flat_map(|block| block.serialize_to_vec())
+        .collect::<Vec<u8>>()
+        .into_response()
+}
+
+async fn get_sortition_info(
+    State(state): State<SharedState>,
+    Path(consensus_hash): Path<String>,
+) -> Response {
+    let Ok(consensus_hash) = ConsensusHash::from_hex(&consensus_hash) else {
+        return StatusCode::BAD_REQUEST.into_response();
+    };
+
+    let state = state.lock().unwrap();
+    let sortition = state.sortitions.get(&consensus_hash).cloned();
+    json_or_not_found(sortition.map(|sortition| vec![sortition]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use blockstack_lib::chainstate::nakamoto::NakamotoBlockHeader;
+    use blockstack_lib::chainstate::stacks::SinglesigHashMode;
+    use blockstack_lib::chainstate::stacks::SinglesigSpendingCondition;
+    use blockstack_lib::chainstate::stacks::TokenTransferMemo;
+    use blockstack_lib::chainstate::stacks::TransactionAnchorMode;
+    use blockstack_lib::chainstate::stacks::TransactionAuth;
+    use blockstack_lib::chainstate::stacks::TransactionPostConditionMode;
+    use blockstack_lib::chainstate::stacks::TransactionPublicKeyEncoding;
+    use blockstack_lib::chainstate::stacks::TransactionSpendingCondition;
+    use blockstack_lib::chainstate::stacks::TransactionVersion;
+    use clarity::util::secp256k1::MessageSignature;
+
+    use crate::keys::PublicKey;
+    use crate::stacks::api::StacksInteract as _;
+    use crate::stacks::api::SubmitTxResponse;
+    use crate::stacks::contracts::RotateKeysV1;
+    use crate::stacks::contracts::SmartContract;
+    use crate::stacks::wallet::MultisigTx;
+    use crate::testing::stacks::DUMMY_SORTITION_INFO;
+    use crate::testing::wallet::ContractCallWrapper;
+    use crate::testing::wallet::WALLET;
+
+    fn stx_transfer(sender: &StacksAddress, nonce: u64) -> StacksTransaction {
+        let auth = SinglesigSpendingCondition {
+            signer: *sender.bytes(),
+            nonce,
+            tx_fee: 1000,
+            hash_mode: SinglesigHashMode::P2PKH,
+            key_encoding: TransactionPublicKeyEncoding::Compressed,
+            signature: MessageSignature::empty(),
+        };
+        let recipient = PrincipalData::from(StacksAddress::burn_address(false));
+
+        StacksTransaction {
+            version: TransactionVersion::Testnet,
+            chain_id: CHAIN_ID_TESTNET,
+            auth: TransactionAuth::Standard(TransactionSpendingCondition::Singlesig(auth)),
+            anchor_mode: TransactionAnchorMode::Any,
+            post_condition_mode: TransactionPostConditionMode::Allow,
+            post_conditions: Vec::new(),
+            payload: TransactionPayload::TokenTransfer(recipient, 1, TokenTransferMemo([0; 34])),
+        }
+    }
+
+    fn block(parent: Option<&NakamotoBlock>, consensus_hash: ConsensusHash) -> NakamotoBlock {
+        let mut header = NakamotoBlockHeader::empty();
+        header.consensus_hash = consensus_hash;
+        if let Some(parent) = parent {
+            header.parent_block_id = parent.block_id();
+            header.chain_length = parent.header.chain_length + 1;
+        }
+        NakamotoBlock { header, txs: Vec::new() }
+    }
+
+    /// A transaction from the test wallet rotating the keys to those of
+    /// the wallet.
+    fn rotate_keys(deployer: StacksAddress, aggregate_key: &PublicKey) -> StacksTransaction {
+        let rotate_keys = RotateKeysV1::new(&WALLET.0, deployer, aggregate_key);
+        MultisigTx::new_tx(&ContractCallWrapper(rotate_keys), &WALLET.0, 1000)
+            .tx()
+            .clone()
+    }
+
+    #[tokio::test]
+    async fn executes_contract_calls_in_the_clarity_vm() {
+        let node = MockStacksNode::start().await;
+        let client = node.client();
+        let deployer = *WALLET.0.address();
+        let aggregate_key = *WALLET.0.stacks_aggregate_key();
+
+        node.state().deploy_sbtc_contracts(&deployer);
+
+        let source = client
+            .get_contract_source(&deployer, "sbtc-registry")
+            .await
+            .unwrap();
+        assert_eq!(source.source, SmartContract::SbtcRegistry.contract_body());
+        // The registry starts out without a signer set.
+        let signer_set = client.get_current_signer_set_info(&deployer).await.unwrap();
+        assert!(signer_set.is_none());
+
+        let tx = rotate_keys(deployer, &aggregate_key);
+        let response = client.submit_tx(&tx).await.unwrap();
+        assert!(matches!(response, SubmitTxResponse::Acceptance(txid) if txid == tx.txid()));
+        assert_eq!(node.state().transactions[0].result, Ok(Value::okay_true()));
+
+        // The rotate-keys-wrapper call updated the state of the registry,
+        // which we read through a read-only call, a data variable and a
+        // map entry.
+        let signer_set = client
+            .get_current_signer_set_info(&deployer)
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(signer_set.aggregate_key, aggregate_key);
+        assert_eq!(&signer_set.signer_set, WALLET.0.public_keys());
+
+        let current_key = client
+            .get_current_signers_aggregate_key(&deployer)
+            .await
+            .unwrap();
+        assert_eq!(current_key, Some(aggregate_key));
+
+        let completed = client.is_withdrawal_completed(&deployer, 1).await.unwrap();
+        assert!(!completed);
+    }
+
+    #[tokio::test]
+    async fn failed_contract_calls_do_not_change_the_state() {
+        let node = MockStacksNode::start().await;
+        let client = node.client();
+        // The test wallet is not the current signer principal of contracts
+        // deployed by someone else.
+        let deployer = StacksAddress::burn_address(false);
+        let aggregate_key = *WALLET.0.stacks_aggregate_key();
+
+        node.state().deploy_sbtc_contracts(&deployer);
+
+        let tx = rotate_keys(deployer, &aggregate_key);
+        let response = client.submit_tx(&tx).await.unwrap();
+        assert!(matches!(response, SubmitTxResponse::Acceptance(_)));
+
+        let result = node.state().transactions[0].result.clone().unwrap();
+        assert!(matches!(result, Value::Response(ref data) if !data.committed));
+
+        let signer_set = client.get_current_signer_set_info(&deployer).await.unwrap();
+        assert!(signer_set.is_none());
+    }
+
+    #[tokio::test]
+    async fn serves_blocks_and_tenures() {
+        let node = MockStacksNode::start().await;
+        let client = node.client();
+
+        let consensus_hash = DUMMY_SORTITION_INFO.consensus_hash;
+        let first = block(None, ConsensusHash([1; 20]));
+        let second = block(Some(&first), consensus_hash);
+        let third = block(Some(&second), consensus_hash);
+        {
+            let mut state = node.state();
+            state.add_block(first.clone());
+            state.add_block(second.clone());
+            state.add_block(third.clone());
+            state
+                .sortitions
+                .insert(consensus_hash, DUMMY_SORTITION_INFO);
+        }
+
+        let fetched = client.get_block(second.block_id()).await.unwrap();
+        assert_eq!(fetched.block_id(), second.block_id());
+
+        // The tenure stops at the first block, which is from another
+        // tenure.
+        let tenure = client.get_tenure(third.block_id()).await.unwrap();
+        let block_ids: Vec<_> = tenure.blocks().iter().map(|b| b.block_id()).collect();
+        assert_eq!(block_ids, [third.block_id(), second.block_id()]);
+    }
+
+    #[tokio::test]
+    async fn checks_the_nonce_of_submitted_transactions() {
+        let node = MockStacksNode::start().await;
+        let client = node.client();
+
+        let tx = stx_transfer(WALLET.0.address(), 0);
+
+        let response = client.submit_tx(&tx).await.unwrap();
+        assert!(matches!(response, SubmitTxResponse::Acceptance(txid) if txid == tx.txid()));
+
+        // The nonce was used up by the first submission.
+        let response = client.submit_tx(&tx).await.unwrap();
+        assert!(matches!(
+            response,
+            SubmitTxResponse::Rejection(TxRejection {
+                reason: RejectionReason::BadNonce,
+                ..
+            })
+        ));
+        assert_eq!(node.state().transactions.len(), 1);
+    }
+}
@@ -121,9 +121,21 @@ where
 type EventLoop<Context, M, Rng> = transaction_signer::TxSignerEventLoop<Context, M, Rng>;
 
 impl blocklist_client::BlocklistChecker for () {
-    async fn can_accept(&self, _address: &str) -> Result<bool, Error> {
+    async fn can_accept(
+        &self,
+        _address: &str,
+        _chain: blocklist_client::Chain,
+    ) -> Result<bool, Error> {
         Ok(true)
     }
+
+    async fn can_accept_many(
+        &self,
+        addresses: &[String],
+        _chain: blocklist_client::Chain,
+    ) -> Result<Vec<bool>, Error> {
+        Ok(vec![true; addresses.len()])
+    }
 }
 
 /// Test environment.
@@ -5,6 +5,7 @@
 //!
 //! For more details, see the [`TxCoordinatorEventLoop`] documentation.
 
+use std::collections::BTreeMap;
 use std::collections::BTreeSet;
 use std::collections::HashSet;
 use std::time::Duration;
@@ -15,6 +16,7 @@ use futures::StreamExt as _;
 use futures::future::try_join_all;
 use sha2::Digest as _;
 
+use crate::SIGNER_SET_APPROVAL_MAX_AGE;
 use crate::WITHDRAWAL_BLOCKS_EXPIRY;
 use crate::WITHDRAWAL_DUST_LIMIT;
 use crate::WITHDRAWAL_EXPIRY_BUFFER;
@@ -25,6 +27,7 @@ use crate::bitcoin::utxo;
 use crate::bitcoin::utxo::Fees;
 use crate::bitcoin::utxo::UnsignedMockTransaction;
 use crate::context::Context;
+use crate::context::CoordinatorTenure;
 use crate::context::P2PEvent;
 use crate::context::RequestDeciderEvent;
 use crate::context::SbtcLimits;
@@ -43,6 +46,7 @@ use crate::message;
 use crate::message::BitcoinPreSignRequest;
 use crate::message::Payload;
 use crate::message::SignerMessage;
+use crate::message::SignerSetProposal;
 use crate::message::StacksTransactionSignRequest;
 use crate::message::WstsMessageId;
 use crate::metrics::BITCOIN_BLOCKCHAIN;
@@ -67,9 +71,12 @@ use crate::stacks::contracts::SmartContract;
 use crate::stacks::wallet::MultisigTx;
 use crate::stacks::wallet::SignerWallet;
 use crate::storage::DbRead;
+use crate::storage::DbWrite as _;
 use crate::storage::model;
 use crate::storage::model::BitcoinBlockRef;
 use crate::storage::model::StacksTxId;
+use crate::storage::util::record_peer_event;
+use crate::storage::util::record_throttled_peer_event;
 use crate::wsts_state_machine::FireCoordinator;
 use crate::wsts_state_machine::FrostCoordinator;
 use crate::wsts_state_machine::WstsCoordinator;
@@ -80,6 +87,10 @@ use wsts::state_machine::OperationResult as WstsOperationResult;
 use wsts::state_machine::StateMachine as _;
 use wsts::state_machine::coordinator::State as WstsCoordinatorState;
 
+/// How often a backup coordinator checks whether another coordinator has
+/// started signing while it waits for its turn.
+const COORDINATOR_FAILOVER_POLL_INTERVAL: Duration = Duration::from_secs(1);
+
 #[cfg_attr(doc, aquamarine::aquamarine)]
 /// # Transaction coordinator event loop
 ///
@@ -92,7 +103,10 @@ use wsts::state_machine::coordinator::State as WstsCoordinatorState;
 /// database upon receiving a [`TxSignerEvent::NewRequestsHandled`] event from
 /// the transaction signer. This tip is used to decide whether this particular
 /// signer is selected as the signers' coordinator or if it should be passive in
-/// favor of another signer as the coordinator in the signer network.
+/// favor of another signer as the coordinator in the signer network. If
+/// coordinator failover is enabled, the other signers are ordered as backup
+/// coordinators, and each one takes over in turn if no coordinator before it
+/// has started signing within the failover timeout.
 ///
 /// When the coordinator is selected, that coordinator will begin by looking up
 /// the signer UTXO, and do a fee rate estimation for both Bitcoin and Stacks.
@@ -196,6 +210,15 @@ pub struct GetPendingRequestsParams<'a> {
     pub signature_threshold: u16,
 }
 
+/// The signers that responded during a WSTS round.
+#[derive(Debug, Default)]
+struct WstsResponders {
+    /// The signers that sent their nonces.
+    nonces: HashSet<PublicKey>,
+    /// The signers that sent their signature shares.
+    signature_shares: HashSet<PublicKey>,
+}
+
 /// This function defines which messages this event loop is interested
 /// in.
 fn run_loop_message_filter(signal: &SignerSignal) -> bool {
@@ -231,7 +254,8 @@ where
         while let Some(message) = signal_stream.next().await {
             match message {
                 SignerSignal::Command(SignerCommand::Shutdown) => break,
-                SignerSignal::Command(SignerCommand::P2PPublish(_)) => {}
+                SignerSignal::Command(SignerCommand::P2PPublish(_))
+                | SignerSignal::Command(SignerCommand::P2PSendTo(..)) => {}
                 SignerSignal::Event(SignerEvent::RequestDecider(
                     RequestDeciderEvent::NewRequestsHandled(chain_tip),
                 )) => {
@@ -299,7 +323,11 @@ where
         // If we are not the coordinator, then we have no business
         // coordinating DKG or constructing bitcoin and stacks
         // transactions, might as well return early.
-        if !self.is_coordinator(bitcoin_chain_tip.as_ref()) {
+        if !self.is_coordinator(bitcoin_chain_tip.as_ref())
+            && !self
+                .wait_for_coordinator_failover(&bitcoin_chain_tip)
+                .await?
+        {
             tracing::debug!("we are not the coordinator, so nothing to do");
             return Ok(());
         }
@@ -1620,12 +1648,21 @@ where
         self.send_message(msg, bitcoin_chain_tip).await?;
 
         let max_duration = self.signing_round_max_duration;
-        let run_signing_round =
-            self.drive_wsts_state_machine(signal_stream, bitcoin_chain_tip, coordinator, id);
+        let mut responders = WstsResponders::default();
+        let run_signing_round = self.drive_wsts_state_machine(
+            signal_stream,
+            bitcoin_chain_tip,
+            coordinator,
+            id,
+            &mut responders,
+        );
 
-        let operation_result = tokio::time::timeout(max_duration, run_signing_round)
-            .await
-            .map_err(|_| Error::CoordinatorTimeout(max_duration.as_secs()))??;
+        let round_result = tokio::time::timeout(max_duration, run_signing_round).await;
+        self.record_signing_round(&responders, round_result.is_err())
+            .await;
+
+        let operation_result =
+            round_result.map_err(|_| Error::CoordinatorTimeout(max_duration.as_secs()))??;
 
         match operation_result {
             WstsOperationResult::SignTaproot(sig) | WstsOperationResult::SignSchnorr(sig) => {
@@ -1644,12 +1681,19 @@ where
     ) -> Result<PublicKey, Error> {
         tracing::info!("Coordinating DKG");
         let block_hash = chain_tip.block_hash;
-        // Get the current signer set for running DKG.
-        let signer_set = self.context.config().signer.bootstrap_signing_set.clone();
+        // Get the signer set for running DKG.
+        let SignerSetProposal {
+            signer_set,
+            signatures_required,
+        } = dkg_signer_set(&self.context).await?;
 
         let block_height = chain_tip.block_height;
-        let mut state_machine =
-            FireCoordinator::new(signer_set, self.threshold, self.private_key, block_height);
+        let mut state_machine = FireCoordinator::new(
+            signer_set,
+            signatures_required,
+            self.private_key,
+            block_height,
+        );
 
         // Okay let's move the coordinator state machine to the beginning
         // of the DKG phase.
@@ -1679,8 +1723,14 @@ where
 
         // Now that DKG has "begun" we need to drive it to completion.
         let max_duration = self.dkg_max_duration;
-        let dkg_fut =
-            self.drive_wsts_state_machine(signal_stream, &block_hash, &mut state_machine, id);
+        let mut responders = WstsResponders::default();
+        let dkg_fut = self.drive_wsts_state_machine(
+            signal_stream,
+            &block_hash,
+            &mut state_machine,
+            id,
+            &mut responders,
+        );
 
         let operation_result = tokio::time::timeout(max_duration, dkg_fut)
             .await
@@ -1699,12 +1749,13 @@ where
         bitcoin_chain_tip: &model::BitcoinBlockHash,
         coordinator: &mut Coordinator,
         id: WstsMessageId,
+        responders: &mut WstsResponders,
     ) -> Result<WstsOperationResult, Error>
     where
         S: Stream<Item = Signed<SignerMessage>>,
         Coordinator: WstsCoordinator,
     {
-        let signer_set = self.context.config().signer.bootstrap_signing_set.clone();
+        let signer_set = coordinator_signer_set(&self.context);
         tokio::pin!(signal_stream);
 
         // Let's get the next message from the network or the
@@ -1726,8 +1777,13 @@ where
 
             let msg_public_key = msg.signer_public_key;
 
-            let sender_is_coordinator =
-                given_key_is_coordinator(msg_public_key, bitcoin_chain_tip, &signer_set);
+            let sender_is_coordinator = given_key_is_active_coordinator(
+                msg_public_key,
+                bitcoin_chain_tip,
+                &signer_set,
+                self.context.state().coordinator_tenure().as_ref(),
+                self.context.config().signer.coordinator_failover_timeout,
+            );
 
             let public_keys = &coordinator.get_config().signer_public_keys;
             let public_key_point = p256k1::point::Point::from(msg_public_key);
@@ -1743,6 +1799,10 @@ where
             );
 
             if !is_authenticated {
+                let db = self.context.get_storage_mut();
+                let throttle = self.context.state().peer_event_throttle();
+                let event = model::PeerEvent::InvalidMessage;
+                record_throttled_peer_event(&db, throttle, &msg_public_key, event).await;
                 continue;
             }
 
@@ -1754,6 +1814,16 @@ where
                 }
             };
 
+            match &msg {
+                wsts::net::Message::NonceResponse(_) => {
+                    responders.nonces.insert(msg_public_key);
+                }
+                wsts::net::Message::SignatureShareResponse(_) => {
+                    responders.signature_shares.insert(msg_public_key);
+                }
+                _ => {}
+            }
+
             if let Some(packet) = outbound_packet {
                 let msg = message::WstsMessage { id, inner: packet.msg };
                 self.send_message(msg, bitcoin_chain_tip).await?;
@@ -1770,6 +1840,30 @@ where
         Err(Error::SignerShutdown)
     }
 
+    /// Count the outcome of a WSTS signing round in the statistics of the
+    /// signers. If the round completed, every signer that sent its
+    /// signature share completed it. If it timed out, every signer that
+    /// did not send its nonce or its signature share timed out.
+    async fn record_signing_round(&self, responders: &WstsResponders, timed_out: bool) {
+        let db = self.context.get_storage_mut();
+        if !timed_out {
+            for public_key in &responders.signature_shares {
+                record_peer_event(&db, public_key, model::PeerEvent::RoundCompleted, 1).await;
+            }
+            return;
+        }
+
+        let signer_set = current_signer_set(&self.context).signer_set;
+        for public_key in &signer_set {
+            if !responders.nonces.contains(public_key) {
+                record_peer_event(&db, public_key, model::PeerEvent::NonceTimeout, 1).await;
+            } else if !responders.signature_shares.contains(public_key) {
+                let event = model::PeerEvent::SignatureShareTimeout;
+                record_peer_event(&db, public_key, event, 1).await;
+            }
+        }
+    }
+
     fn authenticate_message(
         msg: &wsts::net::Message,
         public_keys: &hashbrown::HashMap<u32, p256k1::point::Point>,
@@ -1840,10 +1934,150 @@ where
         given_key_is_coordinator(
             self.signer_public_key(),
             bitcoin_chain_tip,
-            &self.context.config().signer.bootstrap_signing_set,
+            &coordinator_signer_set(&self.context),
         )
     }
 
+    /// Wait for our turn as a backup coordinator for the tenure of the
+    /// given bitcoin chain tip. Returns `true` if our turn has come without
+    /// any coordinator starting to sign, in which case we take over as the
+    /// coordinator, and `false` if another coordinator started signing, if
+    /// the bitcoin chain tip changed while waiting, if there is no work
+    /// that a coordinator would start signing for, if failover is
+    /// disabled, or if the signer shuts down or sees a new bitcoin chain
+    /// tip while waiting.
+    #[tracing::instrument(skip_all)]
+    pub async fn wait_for_coordinator_failover(
+        &self,
+        bitcoin_chain_tip: &BitcoinBlockRef,
+    ) -> Result<bool, Error> {
+        let failover_timeout = self.context.config().signer.coordinator_failover_timeout;
+        if failover_timeout.is_zero() {
+            return Ok(false);
+        }
+
+        let signer_set = coordinator_signer_set(&self.context);
+        let ordering = coordinator_ordering(&bitcoin_chain_tip.block_hash, &signer_set);
+        let public_key = self.signer_public_key();
+        let Some(position) = ordering.iter().position(|key| key == &public_key) else {
+            return Ok(false);
+        };
+
+        let current_tenure = || {
+            self.context
+                .state()
+                .coordinator_tenure()
+                .filter(|tenure| tenure.chain_tip == bitcoin_chain_tip.block_hash)
+        };
+        let Some(tenure) = current_tenure() else {
+            return Ok(false);
+        };
+
+        // A coordinator with nothing to do never starts signing, so its
+        // silence says nothing about whether it is alive.
+        if !self.has_coordinator_work(bitcoin_chain_tip).await? {
+            tracing::debug!("no coordinator work to do in this tenure; not waiting to take over");
+            return Ok(false);
+        }
+
+        let turn_starts_at = tenure.started_at + failover_timeout.saturating_mul(position as u32);
+        // A shutdown or a new bitcoin chain tip ends the wait, so that
+        // the run loop can handle it right away.
+        let mut interrupts = self.context.as_signal_stream(run_loop_message_filter);
+
+        tracing::debug!(%position, "waiting for our turn as a backup coordinator");
+        loop {
+            match current_tenure() {
+                Some(tenure) if tenure.active_coordinator.is_none() => {}
+                // Either another coordinator started signing or the
+                // bitcoin chain tip changed, so it is not our turn.
+                _ => return Ok(false),
+            }
+
+            let now = std::time::Instant::now();
+            if now >= turn_starts_at {
+                break;
+            }
+            let remaining = turn_starts_at.saturating_duration_since(now);
+            tokio::select! {
+                _ = tokio::time::sleep(remaining.min(COORDINATOR_FAILOVER_POLL_INTERVAL)) => {}
+                // The run loop picks up the signal once we return.
+                Some(signal) = interrupts.next() => {
+                    tracing::debug!(?signal, "stopped waiting for our turn as a backup coordinator");
+                    return Ok(false);
+                }
+            }
+        }
+
+        tracing::warn!(
+            %position,
+            coordinator = ?ordering.first(),
+            "no coordinator started signing in time; taking over as a backup coordinator"
+        );
+        metrics::counter!(Metrics::CoordinatorFailoversTotal).increment(1);
+        Ok(true)
+    }
+
+    /// Returns whether the coordinator of the tenure of the given bitcoin
+    /// chain tip has work to do that it needs the other signers to sign
+    /// for: deploying the smart contracts, running DKG, sweeping pending
+    /// requests, or finalizing swept or rejected requests on Stacks.
+    async fn has_coordinator_work(
+        &self,
+        bitcoin_chain_tip: &BitcoinBlockRef,
+    ) -> Result<bool, Error> {
+        if !self.context.state().sbtc_contracts_deployed()
+            || should_coordinate_dkg(&self.context, bitcoin_chain_tip).await?
+        {
+            return Ok(true);
+        }
+
+        let db = self.context.get_storage();
+        let chain_tip = &bitcoin_chain_tip.block_hash;
+
+        let deposits = db
+            .get_pending_accepted_deposit_requests(
+                bitcoin_chain_tip,
+                self.context_window,
+                self.threshold,
+            )
+            .await?;
+        if !deposits.is_empty() {
+            return Ok(true);
+        }
+
+        if let Some(stacks_chain_tip) = db.get_stacks_chain_tip(chain_tip).await? {
+            let min_bitcoin_height = bitcoin_chain_tip
+                .block_height
+                .saturating_sub(WITHDRAWAL_BLOCKS_EXPIRY);
+            let withdrawals = db
+                .get_pending_accepted_withdrawal_requests(
+                    chain_tip,
+                    &stacks_chain_tip.block_hash,
+                    min_bitcoin_height,
+                    self.threshold,
+                )
+                .await?;
+            if !withdrawals.is_empty() {
+                return Ok(true);
+            }
+        }
+
+        let swept_deposits = db
+            .get_swept_deposit_requests(chain_tip, self.context_window)
+            .await?;
+        let swept_withdrawals = db
+            .get_swept_withdrawal_requests(chain_tip, self.context_window)
+            .await?;
+        let rejected_withdrawals = db
+            .get_pending_rejected_withdrawal_requests(bitcoin_chain_tip, self.context_window)
+            .await?;
+
+        Ok(!swept_deposits.is_empty()
+            || !swept_withdrawals.is_empty()
+            || !rejected_withdrawals.is_empty())
+    }
+
     /// Constructs a new [`utxo::SignerBtcState`] based on the current market
     /// fee rate, the signer's UTXO, and the last sweep package.
     #[tracing::instrument(skip_all)]
@@ -2481,6 +2715,19 @@ pub fn coordinator_public_key(
     bitcoin_chain_tip: &model::BitcoinBlockHash,
     signer_public_keys: &BTreeSet<PublicKey>,
 ) -> Option<PublicKey> {
+    coordinator_ordering(bitcoin_chain_tip, signer_public_keys)
+        .first()
+        .copied()
+}
+
+/// Return the signers in the order in which they coordinate during the
+/// tenure of the provided chain tip. The first one is the coordinator and
+/// the rest are the backup coordinators, in the order in which they take
+/// over if no coordinator before them starts signing.
+pub fn coordinator_ordering(
+    bitcoin_chain_tip: &model::BitcoinBlockHash,
+    signer_public_keys: &BTreeSet<PublicKey>,
+) -> Vec<PublicKey> {
     // Create a hash of the bitcoin chain tip. SHA256 will always result in
     // a 32 byte digest.
     let mut hasher = sha2::Sha256::new();
@@ -2498,11 +2745,60 @@ pub fn coordinator_public_key(
     let index = u32::from_be_bytes(u32_bytes);
 
     let num_signers = signer_public_keys.len();
+    if num_signers == 0 {
+        return Vec::new();
+    }
 
+    // The backups follow the coordinator in the order of their public
+    // keys, wrapping around at the end.
     signer_public_keys
         .iter()
-        .nth((index as usize) % num_signers)
+        .cycle()
+        .skip((index as usize) % num_signers)
+        .take(num_signers)
         .copied()
+        .collect()
+}
+
+/// Check if the provided public key is the active coordinator for the
+/// tenure of the provided chain tip.
+///
+/// Once a coordinator has been seen starting to sign during the tenure, it
+/// is the only active coordinator for the rest of it. Before that, the
+/// coordinator is active, and so is each backup coordinator once its turn
+/// has come. The n-th backup's turn comes n failover timeouts into the
+/// tenure, but since signers observe the chain tip at slightly different
+/// times, a backup is accepted half a timeout early. Failover is disabled
+/// if the timeout is zero.
+pub fn given_key_is_active_coordinator(
+    pub_key: PublicKey,
+    bitcoin_chain_tip: &model::BitcoinBlockHash,
+    signer_public_keys: &BTreeSet<PublicKey>,
+    tenure: Option<&CoordinatorTenure>,
+    failover_timeout: Duration,
+) -> bool {
+    let tenure = tenure.filter(|tenure| &tenure.chain_tip == bitcoin_chain_tip);
+    if let Some(active_coordinator) = tenure.and_then(|tenure| tenure.active_coordinator) {
+        return active_coordinator == pub_key;
+    }
+
+    let ordering = coordinator_ordering(bitcoin_chain_tip, signer_public_keys);
+    let Some(position) = ordering.iter().position(|key| key == &pub_key) else {
+        return false;
+    };
+    if position == 0 {
+        return true;
+    }
+    let Some(tenure) = tenure else {
+        return false;
+    };
+    if failover_timeout.is_zero() {
+        return false;
+    }
+
+    let elapsed = tenure.started_at.elapsed();
+    let turn_starts_at = failover_timeout.saturating_mul(position as u32);
+    elapsed + failover_timeout / 2 >= turn_starts_at
 }
 
 /// Determine, according to the current state of the signer and configuration,
@@ -2528,26 +2824,21 @@ pub async fn should_coordinate_dkg(
         return Ok(false);
     }
 
-    // If the registry has signer set info, we may need to run DKG based on it
-    if let Some(registry_signer_info) = context.state().registry_signer_set_info() {
-        // If the registry differs from the config we may need to run DKG
-        if registry_signer_info.signatures_required != config.signer.bootstrap_signatures_required
-            || registry_signer_info.signer_set != config.signer.bootstrap_signing_set
+    // If enough of the current signers approved a change of the signer
+    // set, we may need to run DKG for the proposed set.
+    if let Some(proposal) = approved_signer_set_proposal(context).await? {
+        // If we don't have new shares for the proposal already, we need DKG
+        if latest_dkg_shares.signature_share_threshold != proposal.signatures_required
+            || latest_dkg_shares.signer_set_public_keys() != proposal.signer_set
         {
-            // If we don't have new shares for the config already, we need DKG
-            if latest_dkg_shares.signature_share_threshold
-                != config.signer.bootstrap_signatures_required
-                || latest_dkg_shares.signer_set_public_keys() != config.signer.bootstrap_signing_set
-            {
-                tracing::info!(
-                    "signer set config differs from registry and latest DKG shares; proceeding with DKG"
-                );
-                return Ok(true);
-            } else {
-                tracing::debug!(
-                    "signer set config differs from registry, but we already have verified shares for it; checking other conditions"
-                );
-            }
+            tracing::info!(
+                "a signer set change was approved and differs from the latest DKG shares; proceeding with DKG"
+            );
+            return Ok(true);
+        } else {
+            tracing::debug!(
+                "a signer set change was approved, but we already have verified shares for it; checking other conditions"
+            );
         }
     }
 
@@ -2581,6 +2872,113 @@ pub async fn should_coordinate_dkg(
     }
 }
 
+/// Return whether the given signer set change approval was sent within
+/// the last [`SIGNER_SET_APPROVAL_MAX_AGE`] bitcoin blocks of the given
+/// chain tip. Approvals sent on a chain tip that we don't know about are
+/// treated as expired.
+pub async fn is_recent_signer_set_approval(
+    db: &impl DbRead,
+    approval: &model::SignerSetApproval,
+    chain_tip: &BitcoinBlockRef,
+) -> Result<bool, Error> {
+    let is_recent = db
+        .get_bitcoin_block(&approval.bitcoin_chain_tip)
+        .await?
+        .is_some_and(|block| {
+            *block.block_height + SIGNER_SET_APPROVAL_MAX_AGE >= *chain_tip.block_height
+        });
+    Ok(is_recent)
+}
+
+/// Return the signer set change proposal that enough of the current
+/// signers have approved, if any.
+///
+/// The current signers are those in the registry contract, and a proposal
+/// is approved once the number of signatures required by the registry of
+/// them have sent the identical proposal within the last
+/// [`SIGNER_SET_APPROVAL_MAX_AGE`] bitcoin blocks. Proposals of the current
+/// signer set are never approved, since there is nothing to change.
+pub async fn approved_signer_set_proposal(
+    context: &impl Context,
+) -> Result<Option<SignerSetProposal>, Error> {
+    let Some(registry_signer_info) = context.state().registry_signer_set_info() else {
+        return Ok(None);
+    };
+    let Some(chain_tip) = context.state().bitcoin_chain_tip() else {
+        return Ok(None);
+    };
+
+    let db = context.get_storage();
+    let approvals = db.get_signer_set_approvals().await?;
+
+    let mut approval_counts: BTreeMap<SignerSetProposal, u16> = BTreeMap::new();
+    for approval in approvals {
+        if !registry_signer_info
+            .signer_set
+            .contains(&approval.approver_public_key)
+        {
+            continue;
+        }
+        if is_recent_signer_set_approval(&db, &approval, &chain_tip).await? {
+            *approval_counts.entry(approval.into()).or_default() += 1;
+        }
+    }
+
+    let registry_proposal = SignerSetProposal::from(&registry_signer_info);
+    let approved = approval_counts
+        .into_iter()
+        .filter(|(proposal, _)| proposal != &registry_proposal)
+        .find(|(_, count)| *count >= registry_signer_info.signatures_required)
+        .map(|(proposal, _)| proposal);
+
+    Ok(approved)
+}
+
+/// Return the current signer set, along with the number of signatures
+/// required.
+///
+/// This is the signer set in the registry contract if it is known, and
+/// the bootstrap signer set of the config otherwise, which is the case
+/// before the first key rotation.
+pub fn current_signer_set(context: &impl Context) -> SignerSetProposal {
+    if let Some(registry_signer_info) = context.state().registry_signer_set_info() {
+        return SignerSetProposal::from(&registry_signer_info);
+    }
+
+    let config = &context.config().signer;
+    SignerSetProposal {
+        signer_set: config.bootstrap_signing_set.clone(),
+        signatures_required: config.bootstrap_signatures_required,
+    }
+}
+
+/// Return the signer set that the coordinator of a bitcoin chain tip is
+/// picked from.
+///
+/// This is the signer set of the signer set change proposal approved as
+/// of the last observed bitcoin block if there is one, so that the
+/// coordinator can run DKG for it, and the current signer set otherwise.
+pub fn coordinator_signer_set(context: &impl Context) -> BTreeSet<PublicKey> {
+    context
+        .state()
+        .approved_signer_set()
+        .unwrap_or_else(|| current_signer_set(context))
+        .signer_set
+}
+
+/// Return the signer set, along with the number of signatures required,
+/// that DKG is run for.
+///
+/// This is the signer set of the approved signer set change proposal if
+/// there is one, and the current signer set otherwise.
+pub async fn dkg_signer_set(context: &impl Context) -> Result<SignerSetProposal, Error> {
+    if let Some(proposal) = approved_signer_set_proposal(context).await? {
+        return Ok(proposal);
+    }
+
+    Ok(current_signer_set(context))
+}
+
 /// Assert, given the last dkg and smart contract current aggregate key, if we
 /// need to verify the shares and/or issue a rotate key call.
 pub fn assert_rotate_key_action<C>(
@@ -2844,6 +3242,262 @@ mod tests {
             .unwrap();
     }
 
+    #[test]
+    fn coordinator_ordering_starts_with_the_coordinator() {
+        let mut rng = testing::get_rng();
+        let signer_set: BTreeSet<PublicKey> = (0..7)
+            .map(|_| PublicKey::from_private_key(&PrivateKey::new(&mut rng)))
+            .collect();
+
+        for _ in 0..20 {
+            let chain_tip: model::BitcoinBlockHash = Faker.fake_with_rng(&mut rng);
+            let ordering = coordinator_ordering(&chain_tip, &signer_set);
+
+            assert_eq!(
+                ordering.first().copied(),
+                coordinator_public_key(&chain_tip, &signer_set)
+            );
+            assert_eq!(
+                ordering.iter().copied().collect::<BTreeSet<_>>(),
+                signer_set
+            );
+        }
+    }
+
+    #[test_case(0, None, 0, 10, true; "coordinator")]
+    #[test_case(1, None, 0, 10, false; "backup before its turn")]
+    #[test_case(1, None, 5, 10, true; "backup within the grace period")]
+    #[test_case(1, None, 10, 10, true; "backup on its turn")]
+    #[test_case(1, None, 10, 0, false; "backup with failover disabled")]
+    #[test_case(2, None, 10, 10, false; "second backup on the first backup's turn")]
+    #[test_case(2, None, 20, 10, true; "second backup on its turn")]
+    #[test_case(0, Some(1), 20, 10, false; "coordinator after the backup started signing")]
+    #[test_case(1, Some(1), 0, 10, true; "backup that started signing")]
+    #[test_case(2, Some(0), 20, 10, false; "backup after the coordinator started signing")]
+    fn test_given_key_is_active_coordinator(
+        position: usize,
+        active_position: Option<usize>,
+        elapsed_secs: u64,
+        failover_timeout_secs: u64,
+        is_active: bool,
+    ) {
+        let mut rng = testing::get_rng();
+        let signer_set: BTreeSet<PublicKey> = (0..7)
+            .map(|_| PublicKey::from_private_key(&PrivateKey::new(&mut rng)))
+            .collect();
+        let chain_tip: model::BitcoinBlockHash = Faker.fake_with_rng(&mut rng);
+        let ordering = coordinator_ordering(&chain_tip, &signer_set);
+
+        let tenure = CoordinatorTenure {
+            chain_tip,
+            started_at: std::time::Instant::now() - Duration::from_secs(elapsed_secs),
+            active_coordinator: active_position.map(|position| ordering[position]),
+        };
+
+        let result = given_key_is_active_coordinator(
+            ordering[position],
+            &chain_tip,
+            &signer_set,
+            Some(&tenure),
+            Duration::from_secs(failover_timeout_secs),
+        );
+        assert_eq!(result, is_active);
+    }
+
+    /// Check that a backup coordinator takes over once its turn has come,
+    /// unless another coordinator started signing before then.
+    #[tokio::test]
+    async fn should_take_over_as_backup_coordinator() {
+        let mut rng = testing::get_rng();
+        let private_keys: Vec<PrivateKey> = (0..3).map(|_| PrivateKey::new(&mut rng)).collect();
+        let signer_set: BTreeSet<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.bootstrap_signing_set = signer_set.clone();
+                settings.signer.coordinator_failover_timeout = Duration::from_millis(100);
+            })
+            .build();
+
+        // Find a chain tip for which the first signer is the first backup
+        // coordinator.
+        let public_key = PublicKey::from_private_key(&private_keys[0]);
+        let chain_tip =
+            std::iter::repeat_with(|| Faker.fake_with_rng::<BitcoinBlockRef, _>(&mut rng))
+                .find(|chain_tip| {
+                    coordinator_ordering(&chain_tip.block_hash, &signer_set)[1] == public_key
+                })
+                .unwrap();
+
+        let network = WanNetwork::default();
+        let net = network.connect(&ctx);
+
+        let ev = TxCoordinatorEventLoop {
+            network: net.spawn(),
+            context: ctx.clone(),
+            context_window: 10000,
+            private_key: private_keys[0],
+            signing_round_max_duration: Duration::from_secs(10),
+            bitcoin_presign_request_max_duration: Duration::from_secs(10),
+            threshold: ctx.config().signer.bootstrap_signatures_required,
+            dkg_max_duration: Duration::from_secs(10),
+            is_epoch3: true,
+        };
+        assert!(!ev.is_coordinator(&chain_tip.block_hash));
+
+        ctx.state().set_bitcoin_chain_tip(chain_tip);
+        let took_over = tokio::time::timeout(
+            Duration::from_secs(1),
+            ev.wait_for_coordinator_failover(&chain_tip),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(took_over);
+
+        // Once the coordinator has started signing, the backup stands
+        // down.
+        let coordinator = coordinator_public_key(&chain_tip.block_hash, &signer_set).unwrap();
+        ctx.state()
+            .set_active_coordinator(&chain_tip.block_hash, coordinator);
+        assert!(!ev.wait_for_coordinator_failover(&chain_tip).await.unwrap());
+    }
+
+    /// Check that a backup coordinator stops waiting for its turn when
+    /// the signer sees a new bitcoin chain tip.
+    #[tokio::test]
+    async fn should_stop_waiting_for_failover_on_new_chain_tip() {
+        let mut rng = testing::get_rng();
+        let private_keys: Vec<PrivateKey> = (0..3).map(|_| PrivateKey::new(&mut rng)).collect();
+        let signer_set: BTreeSet<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.bootstrap_signing_set = signer_set.clone();
+                settings.signer.coordinator_failover_timeout = Duration::from_secs(30);
+            })
+            .build();
+
+        // Find a chain tip for which the first signer is the first backup
+        // coordinator.
+        let public_key = PublicKey::from_private_key(&private_keys[0]);
+        let chain_tip =
+            std::iter::repeat_with(|| Faker.fake_with_rng::<BitcoinBlockRef, _>(&mut rng))
+                .find(|chain_tip| {
+                    coordinator_ordering(&chain_tip.block_hash, &signer_set)[1] == public_key
+                })
+                .unwrap();
+
+        let network = WanNetwork::default();
+        let net = network.connect(&ctx);
+
+        let ev = TxCoordinatorEventLoop {
+            network: net.spawn(),
+            context: ctx.clone(),
+            context_window: 10000,
+            private_key: private_keys[0],
+            signing_round_max_duration: Duration::from_secs(10),
+            bitcoin_presign_request_max_duration: Duration::from_secs(10),
+            threshold: ctx.config().signer.bootstrap_signatures_required,
+            dkg_max_duration: Duration::from_secs(10),
+            is_epoch3: true,
+        };
+
+        // Our turn would only come after the failover timeout, but the
+        // new chain tip ends the wait long before that.
+        ctx.state().set_bitcoin_chain_tip(chain_tip);
+        let new_chain_tip: BitcoinBlockRef = Faker.fake_with_rng(&mut rng);
+        let new_tip_signal = async {
+            tokio::time::sleep(Duration::from_millis(50)).await;
+            ctx.signal(RequestDeciderEvent::NewRequestsHandled(new_chain_tip).into())
+                .unwrap();
+        };
+        let (took_over, _) = tokio::time::timeout(
+            Duration::from_secs(1),
+            futures::future::join(ev.wait_for_coordinator_failover(&chain_tip), new_tip_signal),
+        )
+        .await
+        .unwrap();
+        assert!(!took_over.unwrap());
+    }
+
+    #[tokio::test]
+    async fn should_not_take_over_from_idle_coordinator() {
+        let mut rng = testing::get_rng();
+        let private_keys: Vec<PrivateKey> = (0..3).map(|_| PrivateKey::new(&mut rng)).collect();
+        let signer_set: BTreeSet<PublicKey> = private_keys
+            .iter()
+            .map(PublicKey::from_private_key)
+            .collect();
+
+        let ctx = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .modify_settings(|settings| {
+                settings.signer.bootstrap_signing_set = signer_set.clone();
+                settings.signer.coordinator_failover_timeout = Duration::from_millis(100);
+            })
+            .build();
+
+        // The contracts are deployed, DKG has been run and there are no
+        // requests, so the coordinator has nothing to sign.
+        ctx.state().set_sbtc_contracts_deployed();
+        let dkg_shares = model::EncryptedDkgShares {
+            dkg_shares_status: model::DkgSharesStatus::Verified,
+            ..Faker.fake_with_rng(&mut rng)
+        };
+        ctx.get_storage_mut()
+            .write_encrypted_dkg_shares(&dkg_shares)
+            .await
+            .unwrap();
+
+        // Find a chain tip for which the first signer is the first backup
+        // coordinator.
+        let public_key = PublicKey::from_private_key(&private_keys[0]);
+        let chain_tip =
+            std::iter::repeat_with(|| Faker.fake_with_rng::<BitcoinBlockRef, _>(&mut rng))
+                .find(|chain_tip| {
+                    coordinator_ordering(&chain_tip.block_hash, &signer_set)[1] == public_key
+                })
+                .unwrap();
+
+        let network = WanNetwork::default();
+        let net = network.connect(&ctx);
+
+        let ev = TxCoordinatorEventLoop {
+            network: net.spawn(),
+            context: ctx.clone(),
+            context_window: 10000,
+            private_key: private_keys[0],
+            signing_round_max_duration: Duration::from_secs(10),
+            bitcoin_presign_request_max_duration: Duration::from_secs(10),
+            threshold: ctx.config().signer.bootstrap_signatures_required,
+            dkg_max_duration: Duration::from_secs(10),
+            is_epoch3: true,
+        };
+
+        // The backup returns right away instead of waiting for its turn.
+        ctx.state().set_bitcoin_chain_tip(chain_tip);
+        let took_over = tokio::time::timeout(
+            Duration::from_millis(50),
+            ev.wait_for_coordinator_failover(&chain_tip),
+        )
+        .await
+        .unwrap()
+        .unwrap();
+        assert!(!took_over);
+    }
+
     #[tokio::test]
     async fn should_get_signer_utxo_simple() {
         test_environment().assert_get_signer_utxo_simple().await;
@@ -2943,6 +3597,118 @@ mod tests {
         assert_eq!(result, should_allow);
     }
 
+    #[tokio::test]
+    async fn signer_set_proposal_is_approved_by_a_threshold_of_signers() {
+        let context = TestContext::builder()
+            .with_in_memory_storage()
+            .with_mocked_clients()
+            .build();
+        let storage = context.get_storage_mut();
+
+        let aggregate_key = Faker.fake();
+        prevent_dkg_on_changed_signer_set_info(&context, aggregate_key);
+        let registry_signer_info = context.state().registry_signer_set_info().unwrap();
+        let signers: Vec<PublicKey> = registry_signer_info.signer_set.iter().copied().collect();
+        let threshold = registry_signer_info.signatures_required as usize;
+
+        // Approvals are only counted while they are recent, so we need
+        // the bitcoin blocks that they were sent on.
+        let stale_block = model::BitcoinBlock {
+            block_hash: Faker.fake(),
+            block_height: 100u64.into(),
+            parent_hash: Faker.fake(),
+        };
+        let chain_tip = model::BitcoinBlock {
+            block_hash: Faker.fake(),
+            block_height: stale_block.block_height + SIGNER_SET_APPROVAL_MAX_AGE + 1,
+            parent_hash: Faker.fake(),
+        };
+        storage.write_bitcoin_block(&stale_block).await.unwrap();
+        storage.write_bitcoin_block(&chain_tip).await.unwrap();
+        context.state().set_bitcoin_chain_tip((&chain_tip).into());
+
+        // The proposal drops one of the current signers.
+        let proposal = SignerSetProposal {
+            signer_set: signers[1..].iter().copied().collect(),
+            signatures_required: registry_signer_info.signatures_required - 1,
+        };
+        let approval = |approver_public_key: PublicKey, proposal: &SignerSetProposal| {
+            model::SignerSetApproval {
+                approver_public_key,
+                signer_set_public_keys: proposal.signer_set.iter().copied().collect(),
+                signatures_required: proposal.signatures_required,
+                bitcoin_chain_tip: chain_tip.block_hash,
+            }
+        };
+
+        // Approvals from signers outside of the current signer set are
+        // not counted.
+        for _ in 0..threshold {
+            let outsider: PublicKey = Faker.fake();
+            let outsider_approval = approval(outsider, &proposal);
+            storage
+                .write_signer_set_approval(&outsider_approval)
+                .await
+                .unwrap();
+        }
+        // Neither are approvals of a different proposal.
+        let other_proposal = SignerSetProposal {
+            signer_set: proposal.signer_set.clone(),
+            signatures_required: proposal.signatures_required + 1,
+        };
+        let other_approval = approval(signers[0], &other_proposal);
+        storage
+            .write_signer_set_approval(&other_approval)
+            .await
+            .unwrap();
+
+        for approver in &signers[1..threshold] {
+            let signer_approval = approval(*approver, &proposal);
+            storage
+                .write_signer_set_approval(&signer_approval)
+                .await
+                .unwrap();
+        }
+        let approved = approved_signer_set_proposal(&context).await.unwrap();
+        assert!(approved.is_none());
+
+        // Nor are approvals that are too old.
+        let stale_approval = model::SignerSetApproval {
+            bitcoin_chain_tip: stale_block.block_hash,
+            ..approval(signers[threshold], &proposal)
+        };
+        storage
+            .write_signer_set_approval(&stale_approval)
+            .await
+            .unwrap();
+        let approved = approved_signer_set_proposal(&context).await.unwrap();
+        assert!(approved.is_none());
+
+        // The threshold number of current signers now approve the
+        // identical proposal.
+        let signer_approval = approval(signers[threshold], &proposal);
+        storage
+            .write_signer_set_approval(&signer_approval)
+            .await
+            .unwrap();
+        let approved = approved_signer_set_proposal(&context).await.unwrap();
+        assert_eq!(approved.as_ref(), Some(&proposal));
+
+        // DKG is run for the approved signer set.
+        let signer_set = dkg_signer_set(&context).await.unwrap();
+        assert_eq!(signer_set, proposal);
+
+        // The coordinator is picked from the registry signer set until
+        // the approved proposal is observed with a new bitcoin block,
+        // and from the approved signer set afterwards.
+        let coordinator_set = coordinator_signer_set(&context);
+        assert_eq!(coordinator_set, registry_signer_info.signer_set);
+
+        context.state().set_approved_signer_set(approved);
+        let coordinator_set = coordinator_signer_set(&context);
+        assert_eq!(coordinator_set, proposal.signer_set);
+    }
+
     fn public_key_from_seed(seed: u64) -> PublicKey {
         let mut rng = rand::rngs::StdRng::seed_from_u64(seed);
         PublicKey::from_private_key(&PrivateKey::new(&mut rng))
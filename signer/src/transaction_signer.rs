@@ -28,6 +28,7 @@ use crate::keys::PublicKeyXOnly;
 use crate::message;
 use crate::message::BitcoinPreSignAck;
 use crate::message::Payload;
+use crate::message::SignerSetProposal;
 use crate::message::StacksTransactionSignRequest;
 use crate::message::WstsMessageId;
 use crate::metrics::Metrics;
@@ -46,6 +47,11 @@ use crate::storage::model;
 use crate::storage::model::BitcoinBlockHash;
 use crate::storage::model::DkgSharesStatus;
 use crate::storage::model::SigHash;
+use crate::storage::util::record_peer_event;
+use crate::storage::util::record_throttled_peer_event;
+use crate::transaction_coordinator::approved_signer_set_proposal;
+use crate::transaction_coordinator::coordinator_signer_set;
+use crate::transaction_coordinator::dkg_signer_set;
 use crate::wsts_state_machine::FrostCoordinator;
 use crate::wsts_state_machine::SignerStateMachine;
 use crate::wsts_state_machine::StateMachineId;
@@ -233,6 +239,8 @@ fn run_loop_message_filter(signal: &SignerSignal) -> bool {
                 | message::Payload::SignerWithdrawalDecision(_)
                 | message::Payload::StacksTransactionSignature(_)
                 | message::Payload::BitcoinPreSignAck(_)
+                | message::Payload::SignerSetProposal(_)
+                | message::Payload::DecisionSyncRequest(_)
         ),
         SignerSignal::Command(SignerCommand::Shutdown)
         | SignerSignal::Event(SignerEvent::TxCoordinator(TxCoordinatorEvent::MessageGenerated(
@@ -298,7 +306,8 @@ where
         while let Some(message) = signal_stream.next().await {
             match message {
                 SignerSignal::Command(SignerCommand::Shutdown) => break,
-                SignerSignal::Command(SignerCommand::P2PPublish(_)) => {}
+                SignerSignal::Command(SignerCommand::P2PPublish(_))
+                | SignerSignal::Command(SignerCommand::P2PSendTo(..)) => {}
                 SignerSignal::Event(event) => match event {
                     SignerEvent::TxCoordinator(TxCoordinatorEvent::MessageGenerated(msg))
                     | SignerEvent::P2P(P2PEvent::MessageReceived(msg)) => {
@@ -343,8 +352,20 @@ where
         );
 
         let payload = &msg.inner.payload;
+        if chain_tip_status == ChainTipStatus::Canonical
+            && !sender_is_coordinator
+            && is_coordinator_request(payload)
+        {
+            let db = self.context.get_storage_mut();
+            let throttle = self.context.state().peer_event_throttle();
+            let event = model::PeerEvent::InvalidMessage;
+            record_throttled_peer_event(&db, throttle, &msg.signer_public_key, event).await;
+        }
+
         match (payload, sender_is_coordinator, chain_tip_status) {
             (Payload::StacksTransactionSignRequest(request), true, ChainTipStatus::Canonical) => {
+                self.record_active_coordinator(&chain_tip.block_hash, msg.signer_public_key)
+                    .await;
                 self.handle_stacks_transaction_sign_request(
                     request,
                     &chain_tip,
@@ -359,9 +380,13 @@ where
             }
 
             (Payload::BitcoinPreSignRequest(requests), true, ChainTipStatus::Canonical) => {
+                // The coordinator started signing, so it is the only one
+                // that we accept for the rest of the tenure.
+                self.record_active_coordinator(&chain_tip.block_hash, msg.signer_public_key)
+                    .await;
                 let instant = std::time::Instant::now();
                 let presign_result = self
-                    .handle_bitcoin_pre_sign_request(requests, &chain_tip)
+                    .handle_bitcoin_pre_sign_request(requests, &chain_tip, &msg.signer_public_key)
                     .await;
 
                 Metrics::increment_presign_validation(instant.elapsed(), &presign_result);
@@ -370,7 +395,9 @@ where
             // Message types ignored by the transaction signer
             (Payload::StacksTransactionSignature(_), _, _)
             | (Payload::SignerDepositDecision(_), _, _)
-            | (Payload::SignerWithdrawalDecision(_), _, _) => (),
+            | (Payload::SignerWithdrawalDecision(_), _, _)
+            | (Payload::SignerSetProposal(_), _, _)
+            | (Payload::DecisionSyncRequest(_), _, _) => (),
 
             // Any other combination should be logged
             _ => {
@@ -381,6 +408,34 @@ where
         Ok(())
     }
 
+    /// Record that the given coordinator started signing during the tenure
+    /// of the given chain tip. The first time this happens in a tenure,
+    /// the coordinator is counted as having coordinated the tenure, and
+    /// every coordinator before it in the coordinator ordering as having
+    /// missed it.
+    async fn record_active_coordinator(
+        &self,
+        chain_tip: &model::BitcoinBlockHash,
+        coordinator: PublicKey,
+    ) {
+        if !self
+            .context
+            .state()
+            .set_active_coordinator(chain_tip, coordinator)
+        {
+            return;
+        }
+
+        let db = self.context.get_storage_mut();
+        record_peer_event(&db, &coordinator, model::PeerEvent::TenureCoordinated, 1).await;
+
+        let signer_set = coordinator_signer_set(&self.context);
+        let ordering = crate::transaction_coordinator::coordinator_ordering(chain_tip, &signer_set);
+        for missed in ordering.iter().take_while(|key| **key != coordinator) {
+            record_peer_event(&db, missed, model::PeerEvent::TenureMissed, 1).await;
+        }
+    }
+
     /// Find out the status of the given chain tip
     #[tracing::instrument(skip_all)]
     async fn inspect_msg_chain_tip(
@@ -402,11 +457,13 @@ where
             .is_some();
         let is_canonical = msg_bitcoin_chain_tip == &chain_tip.block_hash;
 
-        let signer_set = &self.context.config().signer.bootstrap_signing_set;
-        let sender_is_coordinator = crate::transaction_coordinator::given_key_is_coordinator(
+        let config = self.context.config();
+        let sender_is_coordinator = crate::transaction_coordinator::given_key_is_active_coordinator(
             msg_sender,
             &chain_tip.block_hash,
-            signer_set,
+            &coordinator_signer_set(&self.context),
+            self.context.state().coordinator_tenure().as_ref(),
+            config.signer.coordinator_failover_timeout,
         );
 
         let chain_tip_status = match (is_known, is_canonical) {
@@ -433,6 +490,7 @@ where
         &mut self,
         request: &message::BitcoinPreSignRequest,
         chain_tip: &model::BitcoinBlockRef,
+        origin_public_key: &PublicKey,
     ) -> Result<(), Error> {
         let db = self.context.get_storage_mut();
 
@@ -485,7 +543,7 @@ where
         db.write_bitcoin_withdrawals_outputs(&withdrawals_outputs)
             .await?;
 
-        self.send_message(BitcoinPreSignAck, &chain_tip.block_hash)
+        self.send_message_to(BitcoinPreSignAck, &chain_tip.block_hash, origin_public_key)
             .await?;
 
         Ok(())
@@ -524,7 +582,8 @@ where
 
         let msg = message::StacksTransactionSignature { txid, signature };
 
-        self.send_message(msg, &chain_tip.block_hash).await?;
+        self.send_message_to(msg, &chain_tip.block_hash, origin_public_key)
+            .await?;
 
         // Mark the sign request as signed for this tenure
         let request_id = StacksSignRequestId::from_sign_request(request);
@@ -681,14 +740,14 @@ where
                 assert_allow_dkg_begin(&self.context, chain_tip).await?;
 
                 tracing::debug!("processing message");
-                let signer_public_keys = self.context.config().signer.bootstrap_signing_set.clone();
-                // The as _ cast is okay because we are going from a u16 to
-                // a u32, which is always okay.
-                let threshold = self.context.config().signer.bootstrap_signatures_required as u32;
+                let SignerSetProposal {
+                    signer_set,
+                    signatures_required,
+                } = dkg_signer_set(&self.context).await?;
 
                 let state_machine = SignerStateMachine::new(
-                    signer_public_keys,
-                    threshold,
+                    signer_set,
+                    signatures_required.into(),
                     *chain_tip,
                     self.signer_private_key,
                 )?;
@@ -1552,9 +1611,22 @@ where
                 self.wsts_state_machines.pop(state_machine_id);
             }
 
-            // Publish the message to the network.
+            // Nonces and signature shares are only of interest to the
+            // coordinator that requested them, which is the sender of the
+            // request. During DKG verification every signer follows the
+            // signing round, so they are published to the network like
+            // every other message.
+            let is_coordinator_response = matches!(
+                outbound,
+                WstsNetMessage::NonceResponse(_) | WstsNetMessage::SignatureShareResponse(_)
+            );
             let msg = message::WstsMessage { id: wsts_id, inner: outbound };
-            self.send_message(msg, bitcoin_chain_tip).await?;
+            if is_coordinator_response && !is_dkg_verification {
+                self.send_message_to(msg, bitcoin_chain_tip, &sender)
+                    .await?;
+            } else {
+                self.send_message(msg, bitcoin_chain_tip).await?;
+            }
         }
 
         Ok(())
@@ -1580,6 +1652,34 @@ where
         Ok(())
     }
 
+    /// Sign and send the message only to the given signer. This is used
+    /// for responses that are only of interest to the coordinator that
+    /// made the request, so they need not be broadcast to everyone.
+    #[tracing::instrument(skip_all)]
+    async fn send_message_to(
+        &mut self,
+        msg: impl Into<message::Payload>,
+        bitcoin_chain_tip: &model::BitcoinBlockHash,
+        recipient: &PublicKey,
+    ) -> Result<(), Error> {
+        let payload: message::Payload = msg.into();
+        tracing::trace!(%payload, %recipient, "sending message");
+
+        let msg = payload
+            .to_message(*bitcoin_chain_tip)
+            .sign_ecdsa(&self.signer_private_key);
+
+        // Our own coordinator receives the message through the signal
+        // channel below, so there is nothing to send over the network.
+        if *recipient != self.signer_public_key() {
+            self.network.send_to(*recipient, msg.clone()).await?;
+        }
+        self.context
+            .signal(TxSignerEvent::MessageGenerated(Box::new(msg)).into())?;
+
+        Ok(())
+    }
+
     fn signer_public_key(&self) -> PublicKey {
         PublicKey::from_private_key(&self.signer_private_key)
     }
@@ -1608,26 +1708,21 @@ pub async fn assert_allow_dkg_begin(
         return Err(Error::DkgHasAlreadyRun);
     }
 
-    // If the registry has signer set info, we may need to run DKG based on it
-    if let Some(registry_signer_info) = context.state().registry_signer_set_info() {
-        // If the registry differs from the config we may need to run DKG
-        if registry_signer_info.signatures_required != config.signer.bootstrap_signatures_required
-            || registry_signer_info.signer_set != config.signer.bootstrap_signing_set
+    // If enough of the current signers approved a change of the signer
+    // set, we may need to run DKG for the proposed set.
+    if let Some(proposal) = approved_signer_set_proposal(context).await? {
+        // If we don't have new shares for the proposal already, we need DKG
+        if latest_dkg_shares.signature_share_threshold != proposal.signatures_required
+            || latest_dkg_shares.signer_set_public_keys() != proposal.signer_set
         {
-            // If we don't have new shares for the config already, we need DKG
-            if latest_dkg_shares.signature_share_threshold
-                != config.signer.bootstrap_signatures_required
-                || latest_dkg_shares.signer_set_public_keys() != config.signer.bootstrap_signing_set
-            {
-                tracing::info!(
-                    "signer set config differs from registry and latest DKG shares; proceeding with DKG"
-                );
-                return Ok(());
-            } else {
-                tracing::debug!(
-                    "signer set config differs from registry, but we already have verified shares for it; checking other conditions"
-                );
-            }
+            tracing::info!(
+                "a signer set change was approved and differs from the latest DKG shares; proceeding with DKG"
+            );
+            return Ok(());
+        } else {
+            tracing::debug!(
+                "a signer set change was approved, but we already have verified shares for it; checking other conditions"
+            );
         }
     }
 
@@ -1688,11 +1783,30 @@ pub async fn assert_allow_dkg_begin(
     Ok(())
 }
 
+/// Whether the given payload is a request that only the coordinator may
+/// send.
+fn is_coordinator_request(payload: &Payload) -> bool {
+    match payload {
+        Payload::StacksTransactionSignRequest(_) | Payload::BitcoinPreSignRequest(_) => true,
+        Payload::WstsMessage(wsts_msg) => matches!(
+            wsts_msg.inner,
+            WstsNetMessage::DkgBegin(_)
+                | WstsNetMessage::DkgPrivateBegin(_)
+                | WstsNetMessage::DkgEndBegin(_)
+                | WstsNetMessage::NonceRequest(_)
+                | WstsNetMessage::SignatureShareRequest(_)
+        ),
+        _ => false,
+    }
+}
+
 /// Relevant information for validating incoming messages
 /// relating to a particular chain tip.
 #[derive(Debug, Clone, Copy)]
 pub struct MsgChainTipReport {
-    /// Whether the sender of the incoming message is the coordinator for this chain tip.
+    /// Whether the sender of the incoming message is the active coordinator
+    /// for this chain tip, which is either the coordinator or a backup
+    /// coordinator that took over.
     pub sender_is_coordinator: bool,
     /// The status of the chain tip relative to the signers' perspective.
     pub chain_tip_status: ChainTipStatus,
@@ -2071,4 +2185,110 @@ mod tests {
             .await
             .expect("expected success");
     }
+    /// Return the next message that the signer of the given signal
+    /// receiver received over the network.
+    async fn next_received_message(
+        signal_rx: &mut tokio::sync::broadcast::Receiver<SignerSignal>,
+    ) -> network::Msg {
+        loop {
+            if let Ok(SignerSignal::Event(SignerEvent::P2P(P2PEvent::MessageReceived(msg)))) =
+                signal_rx.recv().await
+            {
+                return *msg;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn nonce_responses_are_only_sent_to_the_coordinator() {
+        let mut rng = testing::get_rng();
+        let signer_private_key = PrivateKey::new(&mut rng);
+        let coordinator_private_key = PrivateKey::new(&mut rng);
+        let bystander_private_key = PrivateKey::new(&mut rng);
+        let context_with_key = |private_key: PrivateKey| {
+            TestContext::builder()
+                .with_in_memory_storage()
+                .with_mocked_clients()
+                .modify_settings(|settings| settings.signer.private_key = private_key)
+                .build()
+        };
+        let signer_ctx = context_with_key(signer_private_key);
+        let coordinator_ctx = context_with_key(coordinator_private_key);
+        let bystander_ctx = context_with_key(bystander_private_key);
+
+        let network = network::in_memory2::WanNetwork::default();
+        let signer_network = network.connect(&signer_ctx);
+        let _coordinator_network = network.connect(&coordinator_ctx);
+        let _bystander_network = network.connect(&bystander_ctx);
+        let mut coordinator_rx = coordinator_ctx.get_signal_receiver();
+        let mut bystander_rx = bystander_ctx.get_signal_receiver();
+
+        let coordinator_public_key = PublicKey::from_private_key(&coordinator_private_key);
+        let signer_set = [
+            coordinator_public_key,
+            PublicKey::from_private_key(&signer_private_key),
+            PublicKey::from_private_key(&bystander_private_key),
+        ];
+        let chain_tip: model::BitcoinBlockRef = Faker.fake_with_rng(&mut rng);
+        let state_machine =
+            SignerStateMachine::new(signer_set, 2, chain_tip, signer_private_key).unwrap();
+
+        let sighash = SigHash::from(TapSighash::all_zeros());
+        let state_machine_id = StateMachineId::BitcoinSign(sighash);
+        let mut wsts_state_machines = LruCache::new(NonZeroUsize::new(100).unwrap());
+        wsts_state_machines.put(state_machine_id, state_machine);
+
+        let mut signer = TxSignerEventLoop {
+            context: signer_ctx,
+            network: signer_network.spawn(),
+            signer_private_key,
+            context_window: 1,
+            wsts_state_machines,
+            threshold: 2,
+            last_presign_block: None,
+            rng: rand::rngs::OsRng,
+            dkg_begin_pause: None,
+            dkg_verification_state_machines: LruCache::new(NonZeroUsize::new(5).unwrap()),
+            stacks_sign_request: LruCache::new(STACKS_SIGN_REQUEST_LRU_SIZE),
+        };
+
+        let request = WstsNetMessage::NonceRequest(wsts::net::NonceRequest {
+            dkg_id: 0,
+            sign_id: 0,
+            sign_iter_id: 0,
+            message: sighash.to_byte_array().to_vec(),
+            signature_type: wsts::net::SignatureType::Schnorr,
+        });
+        signer
+            .relay_message(
+                &state_machine_id,
+                WstsMessageId::Sweep(Txid::all_zeros()),
+                coordinator_public_key,
+                None,
+                &request,
+                &chain_tip.block_hash,
+            )
+            .await
+            .unwrap();
+
+        // The coordinator receives the nonce response.
+        let msg = tokio::time::timeout(
+            Duration::from_secs(1),
+            next_received_message(&mut coordinator_rx),
+        )
+        .await
+        .expect("the coordinator did not receive the nonce response");
+        let Payload::WstsMessage(wsts_msg) = msg.inner.payload else {
+            panic!("expected a WSTS message");
+        };
+        assert!(matches!(wsts_msg.inner, WstsNetMessage::NonceResponse(_)));
+
+        // The other signers do not.
+        let received = tokio::time::timeout(
+            Duration::from_millis(100),
+            next_received_message(&mut bystander_rx),
+        )
+        .await;
+        assert!(received.is_err());
+    }
 }
@@ -6252,6 +6252,91 @@ mod p2p_peers {
     }
 }
 
+mod peer_statistics {
+    use signer::storage::model::PeerEvent;
+
+    use super::*;
+
+    #[tokio::test]
+    async fn record_and_read_peer_statistics() {
+        let db = testing::storage::new_test_database().await;
+        let rng = &mut get_rng();
+
+        let pub_key: PublicKey = Faker.fake_with_rng(rng);
+        let other_pub_key: PublicKey = Faker.fake_with_rng(rng);
+
+        let events = [
+            PeerEvent::TenureCoordinated,
+            PeerEvent::RoundCompleted,
+            PeerEvent::RoundCompleted,
+            PeerEvent::NonceTimeout,
+            PeerEvent::SignatureShareTimeout,
+        ];
+        for event in events {
+            db.record_peer_event(&pub_key, event, 1).await.unwrap();
+        }
+        db.record_peer_event(&pub_key, PeerEvent::InvalidMessage, 3)
+            .await
+            .unwrap();
+        db.record_peer_event(&other_pub_key, PeerEvent::TenureMissed, 1)
+            .await
+            .unwrap();
+
+        let statistics = db.get_peer_statistics().await.unwrap();
+        assert_eq!(statistics.len(), 2);
+
+        let stats = statistics
+            .iter()
+            .find(|stats| stats.public_key == pub_key)
+            .unwrap();
+        assert_eq!(stats.tenures_coordinated, 1);
+        assert_eq!(stats.tenures_missed, 0);
+        assert_eq!(stats.rounds_completed, 2);
+        assert_eq!(stats.nonce_timeouts, 1);
+        assert_eq!(stats.signature_share_timeouts, 1);
+        assert_eq!(stats.invalid_messages, 3);
+
+        let other_stats = statistics
+            .iter()
+            .find(|stats| stats.public_key == other_pub_key)
+            .unwrap();
+        let mut expected = model::PeerStatistics::new(other_pub_key);
+        expected.tenures_missed = 1;
+        expected.updated_at = other_stats.updated_at;
+        assert_eq!(other_stats, &expected);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
+mod signer_set_approvals {
+    use super::*;
+
+    #[tokio::test]
+    async fn latest_signer_set_approval_replaces_previous_one() {
+        let db = testing::storage::new_test_database().await;
+        let rng = &mut get_rng();
+
+        let mut approval: model::SignerSetApproval = Faker.fake_with_rng(rng);
+        let other_approval: model::SignerSetApproval = Faker.fake_with_rng(rng);
+        db.write_signer_set_approval(&approval).await.unwrap();
+        db.write_signer_set_approval(&other_approval).await.unwrap();
+
+        // The signer changes its mind and approves another proposal.
+        approval.signer_set_public_keys = other_approval.signer_set_public_keys.clone();
+        approval.signatures_required = other_approval.signatures_required;
+        db.write_signer_set_approval(&approval).await.unwrap();
+
+        let mut expected = vec![approval, other_approval];
+        expected.sort_by_key(|approval| approval.approver_public_key);
+
+        let approvals = db.get_signer_set_approvals().await.unwrap();
+        assert_eq!(approvals, expected);
+
+        testing::storage::drop_db(db).await;
+    }
+}
+
 /// Module containing a test suite and helpers specific to
 /// `DbRead::get_pending_accepted_withdrawal_requests`.
 mod get_pending_accepted_withdrawal_requests {
@@ -14,6 +14,8 @@ use emily_client::apis::deposit_api;
 use emily_client::models::CreateDepositRequestBody;
 use signer::bitcoin::MockBitcoinInteract;
 use signer::blocklist_client::BlocklistClient;
+use signer::config::BlocklistClientConfig;
+use signer::config::BlocklistOutagePolicy;
 use signer::context::Context as _;
 use signer::emily_client::EmilyClient;
 use signer::emily_client::MockEmilyInteract;
@@ -499,13 +501,27 @@ async fn blocklist_client_retry(num_failures: u8, failing_iters: u8) {
 
     let counter = Arc::new(AtomicU8::new(0));
     blocklist_server
-        .mock("GET", format!("/screen/{sender_address}").as_str())
+        .mock(
+            "GET",
+            format!("/screen/{sender_address}?chain=bitcoin").as_str(),
+        )
         .match_request(move |_| counter.fetch_add(1, Ordering::SeqCst) >= num_failures)
         .with_status(200)
         .with_header("content-type", "application/json")
         .with_body(&mock_json)
         .create_async()
         .await;
+    // The Stacks recipient of the deposit is screened too.
+    blocklist_server
+        .mock(
+            "GET",
+            format!("/screen/{}?chain=stacks", request.recipient).as_str(),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(&mock_json)
+        .create_async()
+        .await;
 
     let blocklist_client = BlocklistClient::with_base_url(blocklist_server.url());
 
@@ -565,6 +581,92 @@ async fn blocklist_client_retry(num_failures: u8, failing_iters: u8) {
     testing::storage::drop_db(db).await;
 }
 
+/// Test that the request decider votes on a deposit request according to
+/// the configured outage policy when the blocklist client is unavailable.
+#[test_case::test_case(BlocklistOutagePolicy::Accept, 1000, Some(true); "accept")]
+#[test_case::test_case(BlocklistOutagePolicy::Reject, 1000, Some(false); "reject")]
+#[test_case::test_case(BlocklistOutagePolicy::Defer, 1000, None; "defer")]
+#[test_case::test_case(BlocklistOutagePolicy::Defer, 0, Some(false); "defer expired")]
+#[tokio::test]
+async fn blocklist_client_outage_policy(
+    outage_policy: BlocklistOutagePolicy,
+    outage_defer_blocks: u16,
+    expected_vote: Option<bool>,
+) {
+    let db = testing::storage::new_test_database().await;
+    let network = InMemoryNetwork::new();
+
+    let mut rng = get_rng();
+
+    // The blocklist server has no mocks, so every call to it fails.
+    let blocklist_server = Server::new_async().await;
+    let blocklist_config = BlocklistClientConfig {
+        endpoint: Url::parse(&blocklist_server.url()).unwrap(),
+        retry_delay: Duration::ZERO,
+        outage_policy,
+        outage_defer_blocks,
+        circuit_breaker_threshold: 1,
+        circuit_breaker_cooldown: Duration::from_secs(3600),
+    };
+
+    let ctx = TestContext::builder()
+        .with_storage(db.clone())
+        .with_mocked_clients()
+        .modify_settings(|settings| settings.blocklist_client = Some(blocklist_config.clone()))
+        .build();
+
+    let (rpc, faucet) = sbtc::testing::regtest::initialize_blockchain();
+
+    // This confirms a deposit transaction, and has a nice helper function
+    // for storing a real deposit.
+    let setup = TestSweepSetup::new_setup(rpc, faucet, 10000, &mut rng);
+
+    // Let's get the blockchain data into the database.
+    let chain_tip: BitcoinBlockHash = setup.sweep_block_hash.into();
+    backfill_bitcoin_blocks(&db, rpc, &chain_tip).await;
+
+    let chain_tip_ref = db
+        .get_bitcoin_canonical_chain_tip_ref()
+        .await
+        .unwrap()
+        .unwrap();
+    ctx.state().set_bitcoin_chain_tip(chain_tip_ref);
+
+    setup.store_deposit_request(&db).await;
+    setup.store_deposit_tx(&db).await;
+    setup.store_dkg_shares(&db).await;
+
+    let outpoint = setup.deposit_request.outpoint;
+
+    let mut request_decider = RequestDeciderEventLoop {
+        network: network.connect(),
+        context: ctx.clone(),
+        context_window: 10000,
+        blocklist_checker: Some(BlocklistClient::new(&blocklist_config)),
+        signer_private_key: setup.aggregated_signer.keypair.secret_key().into(),
+        deposit_decisions_retry_window: 1,
+        withdrawal_decisions_retry_window: 1,
+    };
+
+    // We need this so that there is a live "network". Otherwise we will error
+    // when trying to send a message at the end.
+    let _rec = ctx.get_signal_receiver();
+
+    request_decider
+        .handle_new_requests(chain_tip_ref)
+        .await
+        .unwrap();
+
+    let votes = db
+        .get_deposit_signers(&outpoint.txid.into(), outpoint.vout)
+        .await
+        .unwrap();
+    let vote = votes.first().map(|vote| vote.can_accept);
+    assert_eq!(vote, expected_vote);
+
+    testing::storage::drop_db(db).await;
+}
+
 #[test_case::test_case(true, false; "withdrawal not blocklisted")]
 #[test_case::test_case(true, true; "withdrawal blocklisted")]
 #[test_case::test_case(false, false; "deposit not blocklisted")]
@@ -655,13 +757,46 @@ async fn do_not_procceed_with_blocked_addresses(is_withdrawal: bool, is_blocked:
     .to_string();
 
     blocklist_server
-        .mock("GET", format!("/screen/{address_to_check}").as_str())
+        .mock(
+            "GET",
+            format!("/screen/{address_to_check}?chain=bitcoin").as_str(),
+        )
         .with_status(200)
         .with_header("content-type", "application/json")
         .with_body(&mock_json)
         .create_async()
         .await;
 
+    // The Stacks principals of the requests are screened too, and are not
+    // blocklisted.
+    let principal_to_check = if is_withdrawal {
+        withdrawal_requests
+            .first()
+            .unwrap()
+            .sender_address
+            .to_string()
+    } else {
+        deposit_requests.first().unwrap().recipient.to_string()
+    };
+    blocklist_server
+        .mock(
+            "GET",
+            format!("/screen/{principal_to_check}?chain=stacks").as_str(),
+        )
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(
+            json!({
+                "is_blocklisted": false,
+                "severity": "Low",
+                "accept": true,
+                "reason": null,
+            })
+            .to_string(),
+        )
+        .create_async()
+        .await;
+
     let blocklist_client = BlocklistClient::with_base_url(blocklist_server.url());
 
     let mut request_decider = RequestDeciderEventLoop {
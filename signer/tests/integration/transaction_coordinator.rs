@@ -1084,7 +1084,8 @@ struct RunDkgSignerSetScenario {
     pub threshold_changed: bool,
     pub latest_shares_matches: bool,
 }
-/// Tests that dkg will be triggered if signer set changes
+/// Tests that dkg will be triggered if the signers approve a signer set
+/// change
 #[test_case(RunDkgSignerSetScenario {
     signer_set_changed: false,
     threshold_changed: false,
@@ -1175,6 +1176,32 @@ async fn run_dkg_if_signer_set_changes(scenario: RunDkgSignerSetScenario, expect
     if scenario.threshold_changed {
         signer_set_info.signatures_required -= 1;
     }
+
+    // The signers in the registry approve the signer set of the config,
+    // which is only a change if the registry was altered. Approvals only
+    // count while the block that they were sent on is recent.
+    let chaintip_block = model::BitcoinBlock {
+        block_hash: chaintip.block_hash,
+        block_height: chaintip.block_height,
+        parent_hash: Faker.fake_with_rng(&mut rng),
+    };
+    db.write_bitcoin_block(&chaintip_block).await.unwrap();
+    ctx.state().set_bitcoin_chain_tip(chaintip);
+    for approver_public_key in &signer_set_info.signer_set {
+        let approval = model::SignerSetApproval {
+            approver_public_key: *approver_public_key,
+            signer_set_public_keys: ctx
+                .config()
+                .signer
+                .bootstrap_signing_set
+                .iter()
+                .copied()
+                .collect(),
+            signatures_required: ctx.config().signer.bootstrap_signatures_required,
+            bitcoin_chain_tip: chaintip.block_hash,
+        };
+        db.write_signer_set_approval(&approval).await.unwrap();
+    }
     ctx.state().update_registry_signer_set_info(signer_set_info);
 
     if scenario.latest_shares_matches {
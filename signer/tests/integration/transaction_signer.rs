@@ -584,9 +584,11 @@ async fn assert_should_be_able_to_handle_sbtc_requests() {
     let deposit_digest = deposit_digest[0];
 
     let mut handle = network.connect(&ctx).spawn();
+    // The ack is sent directly to the coordinator, so we act as one.
+    let coordinator_public_key = ctx.config().signer.public_key();
 
     tx_signer
-        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip)
+        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip, &coordinator_public_key)
         .await
         .unwrap();
 
@@ -703,9 +705,10 @@ async fn presign_requests_with_dkg_shares_status(status: DkgSharesStatus, is_ok:
         fee_rate: 2.0,
         last_fees: None,
     };
+    let coordinator_public_key = ctx.config().signer.public_key();
 
     let result = tx_signer
-        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip)
+        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip, &coordinator_public_key)
         .await;
 
     match result {
@@ -800,9 +803,10 @@ pub async fn presign_request_ignore_request_if_already_processed_this_block() {
         fee_rate: 2.0,
         last_fees: None,
     };
+    let coordinator_public_key = ctx.config().signer.public_key();
 
     let result = tx_signer
-        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip)
+        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip, &coordinator_public_key)
         .await;
 
     // We are processing this block with verified shares and thus it should be ok.
@@ -814,7 +818,7 @@ pub async fn presign_request_ignore_request_if_already_processed_this_block() {
     // Now we will try to process the same block again, but since we already
     // processed it, we should get an error.
     let result = tx_signer
-        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip)
+        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip, &coordinator_public_key)
         .await;
 
     let err = result.unwrap_err();
@@ -829,7 +833,7 @@ pub async fn presign_request_ignore_request_if_already_processed_this_block() {
 
     tx_signer.last_presign_block = None;
     let result = tx_signer
-        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip)
+        .handle_bitcoin_pre_sign_request(&sbtc_context, &chain_tip, &coordinator_public_key)
         .await;
 
     assert!(result.is_ok());